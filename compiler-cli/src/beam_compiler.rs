@@ -1,6 +1,6 @@
 use gleam_core::{
     error::Error,
-    io::{FileSystemWriter, Stdio},
+    io::{ErlangCompilerDiagnostic, FileSystemWriter, Stdio},
     paths, Result,
 };
 
@@ -35,7 +35,7 @@ impl BeamCompiler {
         lib: &Utf8Path,
         modules: &HashSet<Utf8PathBuf>,
         stdio: Stdio,
-    ) -> Result<(), Error> {
+    ) -> Result<Vec<ErlangCompilerDiagnostic>, Error> {
         let inner = match self.inner {
             Some(ref mut inner) => {
                 if let Ok(None) = inner.process.try_wait() {
@@ -65,16 +65,42 @@ impl BeamCompiler {
             err: Some(e.kind()),
         })?;
 
+        let mut warnings = vec![];
+        let mut errors = vec![];
         let mut buf = String::new();
         while let (Ok(_), Ok(None)) = (inner.stdout.read_line(&mut buf), inner.process.try_wait()) {
+            let line = buf.trim_end_matches(['\r', '\n']);
+            if let Some(warning) = line
+                .strip_prefix("gleam-compile-warning\t")
+                .and_then(ErlangCompilerDiagnostic::parse)
+            {
+                warnings.push(warning);
+                buf.clear();
+                continue;
+            }
+            if let Some(error) = line
+                .strip_prefix("gleam-compile-error\t")
+                .and_then(ErlangCompilerDiagnostic::parse)
+            {
+                errors.push(error);
+                buf.clear();
+                continue;
+            }
+
             match buf.trim() {
-                "gleam-compile-result-ok" => return Ok(()),
-                "gleam-compile-result-error" => {
+                "gleam-compile-result-ok" => return Ok(warnings),
+                // Elixir modules report their own errors, so if there are no
+                // errors from the Erlang compiler we can only report that
+                // compilation failed.
+                "gleam-compile-result-error" if errors.is_empty() => {
                     return Err(Error::ShellCommand {
                         program: "escript".into(),
                         err: None,
                     })
                 }
+                "gleam-compile-result-error" => {
+                    return Err(Error::ErlangCompilation { errors });
+                }
                 _ => match stdio {
                     Stdio::Inherit => print!("{}", buf),
                     Stdio::Null => {}
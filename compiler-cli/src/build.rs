@@ -20,6 +20,23 @@ pub fn download_dependencies(telemetry: impl Telemetry) -> Result<Manifest> {
     crate::dependencies::download(&paths, telemetry, None, Vec::new(), UseManifest::Yes)
 }
 
+/// Prints the public functions of the root package that are not used by any
+/// of its `main` functions or tests.
+pub fn print_unused_public_functions(built: &Built) -> Result<()> {
+    let root = get_project_root(get_current_directory()?)?;
+    for function in built.unused_public_functions() {
+        let path = function
+            .src_path
+            .strip_prefix(&root)
+            .unwrap_or(&function.src_path);
+        cli::print_unused(&format!(
+            "{}.{} ({path}:{})",
+            function.module, function.name, function.line
+        ));
+    }
+    Ok(())
+}
+
 pub fn main(options: Options, manifest: Manifest) -> Result<Built> {
     main_with_warnings(options, manifest, Rc::new(ConsoleWarningEmitter))
 }
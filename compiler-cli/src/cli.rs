@@ -108,6 +108,10 @@ pub fn print_unretired(package: &str, version: &str) {
     print_colourful_prefix("Unretired", &format!("{package} {version}"))
 }
 
+pub fn print_unused(text: &str) {
+    print_colourful_prefix("Unused", text)
+}
+
 pub fn print_publishing_documentation() {
     print_colourful_prefix("Publishing", "documentation");
 }
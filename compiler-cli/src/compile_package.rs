@@ -33,6 +33,12 @@ pub fn command(options: CompilePackage) -> Result<()> {
             prelude_location: options
                 .javascript_prelude
                 .ok_or_else(|| Error::JavaScriptPreludeRequired)?,
+            module_format: config.javascript.module_format,
+            int_representation: config.javascript.int_representation,
+            ffi_int_representation: config.javascript.int_representation,
+            // The prelude is provided by the caller, so it cannot be made to
+            // freeze values.
+            freeze_values: false,
         },
     };
 
@@ -360,7 +360,7 @@ fn remove_extra_packages<Telem: Telemetry>(
     Ok(())
 }
 
-fn read_manifest_from_disc(paths: &ProjectPaths) -> Result<Manifest> {
+pub(crate) fn read_manifest_from_disc(paths: &ProjectPaths) -> Result<Manifest> {
     tracing::debug!("reading_manifest_toml");
     let manifest_path = paths.manifest();
     let toml = fs::read(&manifest_path)?;
@@ -6,8 +6,11 @@ use hexpm::version::Version;
 use pretty_assertions::assert_eq;
 
 use gleam_core::{
-    build::Runtime,
-    config::{DenoConfig, DenoFlag, Docs, ErlangConfig, JavaScriptConfig, Repository},
+    build::{IntRepresentation, ModuleFormat, Runtime},
+    config::{
+        DenoConfig, DenoFlag, Docs, ErlangConfig, FormatConfig, JavaScriptConfig, Repository,
+        WarningsConfig,
+    },
     manifest::{Base16Checksum, Manifest, ManifestPackage, ManifestPackageSource},
     requirement::Requirement,
     Error,
@@ -933,7 +936,13 @@ fn package_config(
         gleam_version: None,
         licences: vec![],
         description: "".into(),
-        documentation: Docs { pages: vec![] },
+        documentation: Docs {
+            pages: vec![],
+            doctests: false,
+            logo: None,
+            favicon: None,
+            assets: vec![],
+        },
         dependencies,
         dev_dependencies,
         repository: Repository::None,
@@ -941,10 +950,15 @@ fn package_config(
         erlang: ErlangConfig {
             application_start_module: None,
             extra_applications: vec![],
+            minimum_otp_version: None,
         },
         javascript: JavaScriptConfig {
             typescript_declarations: false,
             runtime: Runtime::NodeJs,
+            module_format: ModuleFormat::Esm,
+            int_representation: IntRepresentation::Number,
+            freeze_values: false,
+            package_json: false,
             deno: DenoConfig {
                 allow_env: DenoFlag::AllowAll,
                 allow_sys: true,
@@ -960,6 +974,8 @@ fn package_config(
             },
         },
         target: Target::Erlang,
+        warnings: WarningsConfig::default(),
+        format: FormatConfig::default(),
         internal_modules: None,
     }
 }
@@ -0,0 +1,189 @@
+//! Running Dialyzer over the Erlang generated for the root package.
+//!
+//! The generated Erlang uses `-file` attributes pointing back to the Gleam
+//! source, so the problems Dialyzer finds are reported in the Gleam modules
+//! they come from.
+
+use std::process::{Command, Stdio};
+
+use camino::Utf8PathBuf;
+use gleam_core::{
+    build::{package_compiler::locate_in_gleam_modules, Built, Mode, Target},
+    error::Error,
+    io::ErlangCompilerDiagnostic,
+    paths::ProjectPaths,
+    warning::{Warning, WarningEmitterIO},
+    Result,
+};
+
+use crate::fs::{get_os, ConsoleWarningEmitter};
+
+const PLT_FILE_NAME: &str = "gleam.plt";
+
+/// Runs Dialyzer over the Erlang target of the root package, printing what it
+/// finds as warnings.
+///
+/// A PLT for the Erlang standard library and the dependencies is built the
+/// first time this is run, and again whenever the manifest changes.
+pub fn check(paths: &ProjectPaths, built: &Built) -> Result<()> {
+    let mode = Mode::Dev;
+    let target = Target::Erlang;
+    let root_package = built.root_package.config.name.as_str();
+    let build = paths.build_directory_for_target(mode, target);
+    let plt = build.join(PLT_FILE_NAME);
+
+    if plt_is_stale(paths, &plt) {
+        let dependencies = crate::fs::read_dir(&build)?
+            .filter_map(Result::ok)
+            .filter(|entry| entry.file_name() != root_package)
+            .map(|entry| entry.into_path().join("ebin"))
+            .filter(|ebin| ebin.is_dir())
+            .map(String::from);
+        let mut args = vec![
+            "--build_plt".into(),
+            "--output_plt".into(),
+            plt.to_string(),
+            "--apps".into(),
+            "erts".into(),
+            "kernel".into(),
+            "stdlib".into(),
+            "-r".into(),
+        ];
+        args.extend(dependencies);
+        // Dialyzer reports an exit code of 2 when the dependencies themselves
+        // have warnings, which is not something the user can act on.
+        let _ = dialyzer(&args)?;
+    }
+
+    let ebin = paths
+        .build_directory_for_package(mode, target, root_package)
+        .join("ebin");
+    let args = vec![
+        "--plt".into(),
+        plt.to_string(),
+        "--fullpath".into(),
+        "--quiet".into(),
+        "-r".into(),
+        ebin.to_string(),
+    ];
+    let output = dialyzer(&args)?;
+
+    for mut warning in parse_dialyzer_output(&output) {
+        locate_in_gleam_modules(&mut warning, &built.root_package.modules);
+        ConsoleWarningEmitter.emit_warning(Warning::Dialyzer { warning });
+    }
+    Ok(())
+}
+
+fn plt_is_stale(paths: &ProjectPaths, plt: &Utf8PathBuf) -> bool {
+    let modified = |path: &Utf8PathBuf| path.metadata().and_then(|metadata| metadata.modified());
+    match (modified(plt), modified(&paths.manifest())) {
+        (Ok(plt), Ok(manifest)) => plt < manifest,
+        (Ok(_), Err(_)) => false,
+        (Err(_), _) => true,
+    }
+}
+
+/// Runs Dialyzer, returning what it printed. Dialyzer exits with 0 when there
+/// are no warnings, 2 when there are warnings, and 1 when it failed to run.
+fn dialyzer(args: &[String]) -> Result<String> {
+    tracing::trace!(args=?args.join(" "), "dialyzer");
+    let output = Command::new("dialyzer")
+        .args(args)
+        .stdin(Stdio::null())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|error| match error.kind() {
+            std::io::ErrorKind::NotFound => Error::ShellProgramNotFound {
+                program: "dialyzer".into(),
+                os: get_os(),
+            },
+            other => Error::ShellCommand {
+                program: "dialyzer".into(),
+                err: Some(other),
+            },
+        })?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout).into_owned();
+    match output.status.code() {
+        Some(0 | 2) => Ok(stdout),
+        _ => {
+            print!("{stdout}");
+            Err(Error::ShellCommand {
+                program: "dialyzer".into(),
+                err: None,
+            })
+        }
+    }
+}
+
+/// Parses the warnings Dialyzer prints, each of which starts with a
+/// `path:line: ` or `path:line:column: ` location. A warning can continue on
+/// the following indented lines. Anything else Dialyzer prints, such as its
+/// progress, is ignored.
+fn parse_dialyzer_output(output: &str) -> Vec<ErlangCompilerDiagnostic> {
+    let mut warnings: Vec<ErlangCompilerDiagnostic> = vec![];
+    let mut continues_warning = false;
+    for line in output.lines() {
+        if line.starts_with(char::is_whitespace) && !line.trim().is_empty() {
+            if let Some(warning) = warnings.last_mut().filter(|_| continues_warning) {
+                warning.message.push('\n');
+                warning.message.push_str(line.trim());
+            }
+            continue;
+        }
+        match parse_dialyzer_warning(line) {
+            Some(warning) => {
+                warnings.push(warning);
+                continues_warning = true;
+            }
+            None => continues_warning = false,
+        }
+    }
+    warnings
+}
+
+fn parse_dialyzer_warning(line: &str) -> Option<ErlangCompilerDiagnostic> {
+    let (location, message) = line.split_once(": ")?;
+    let (rest, last) = location.rsplit_once(':')?;
+    let last: u32 = last.parse().ok()?;
+    let (path, line) = match rest.rsplit_once(':') {
+        Some((path, line)) if line.parse::<u32>().is_ok() => (path, line.parse().ok()),
+        _ => (rest, Some(last)),
+    };
+    Some(ErlangCompilerDiagnostic {
+        path: Utf8PathBuf::from(path),
+        line,
+        message: message.trim().into(),
+        source: None,
+    })
+}
+
+#[test]
+fn parse_dialyzer_output_test() {
+    let output = "  Checking whether the PLT build/dev/erlang/gleam.plt is up-to-date... yes
+  Proceeding with analysis... done in 0m0.52s
+/app/src/app.gleam:12:5: Function main/0 has no local return
+/app/src/app.gleam:20: The pattern
+          'error' can never match the type
+          'ok'
+done (warnings were emitted)
+";
+    assert_eq!(
+        parse_dialyzer_output(output),
+        vec![
+            ErlangCompilerDiagnostic {
+                path: Utf8PathBuf::from("/app/src/app.gleam"),
+                line: Some(12),
+                message: "Function main/0 has no local return".into(),
+                source: None,
+            },
+            ErlangCompilerDiagnostic {
+                path: Utf8PathBuf::from("/app/src/app.gleam"),
+                line: Some(20),
+                message: "The pattern\n'error' can never match the type\n'ok'".into(),
+                source: None,
+            },
+        ]
+    );
+}
@@ -1,6 +1,9 @@
+mod serve;
+
 use std::time::{Instant, SystemTime};
 
 use camino::{Utf8Path, Utf8PathBuf};
+use clap::ValueEnum;
 
 use crate::{cli, fs::ProjectIO, http::HttpClient};
 use gleam_core::{
@@ -11,9 +14,12 @@ use gleam_core::{
     error::Error,
     hex,
     io::HttpClient as _,
+    manifest::ManifestPackageSource,
     Result,
 };
 
+pub use serve::{serve, ServeOptions};
+
 pub fn remove(package: String, version: String) -> Result<()> {
     let runtime = tokio::runtime::Runtime::new().expect("Unable to start Tokio async runtime");
     let hex_config = hexpm::Config::new();
@@ -37,6 +43,16 @@ pub struct BuildOptions {
     /// Whether to open the docs after building.
     pub open: bool,
     pub target: Option<Target>,
+    pub format: DocsFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[clap(rename_all = "lower")]
+pub enum DocsFormat {
+    #[default]
+    Html,
+    /// The documentation of every module in a single `docs.json` file.
+    Json,
 }
 
 pub fn build(options: BuildOptions) -> Result<()> {
@@ -56,25 +72,38 @@ pub fn build(options: BuildOptions) -> Result<()> {
             warnings_as_errors: false,
             root_target_support: TargetSupport::Enforced,
             no_print_progress: false,
+            doctests: false,
         },
         crate::build::download_dependencies(cli::Reporter::new())?,
     )?;
-    let outputs = build_documentation(&config, &mut built.root_package, DocContext::Build)?;
+    let (outputs, index) = match options.format {
+        DocsFormat::Html => (
+            build_documentation(&config, &mut built.root_package, DocContext::Build)?,
+            out.join("index.html"),
+        ),
+        DocsFormat::Json => {
+            built.root_package.attach_doc_and_module_comments();
+            let output = gleam_core::docs::generate_json(
+                &paths,
+                &config,
+                built.root_package.modules.as_slice(),
+            );
+            let index = out.join(&output.path);
+            (vec![output], index)
+        }
+    };
 
     // Write
     crate::fs::delete_directory(&out)?;
     crate::fs::write_outputs_under(&outputs, &out)?;
 
-    let index_html = out.join("index.html");
-
     println!(
-        "\nThe documentation for {package} has been rendered to \n{index_html}",
+        "\nThe documentation for {package} has been rendered to \n{index}",
         package = config.name,
-        index_html = index_html
     );
 
     if options.open {
-        open_docs(&index_html)?;
+        open_docs(&index)?;
     }
 
     // We're done!
@@ -108,9 +137,16 @@ pub(crate) fn build_documentation(
         source: paths.readme(), // TODO: support non markdown READMEs. Or a default if there is none.
     }];
     pages.extend(config.documentation.pages.iter().cloned());
+    let hex_packages = crate::dependencies::read_manifest_from_disc(&paths)?
+        .packages
+        .into_iter()
+        .filter(|package| matches!(package.source, ManifestPackageSource::Hex { .. }))
+        .map(|package| package.name)
+        .collect();
     let mut outputs = gleam_core::docs::generate_html(
         &paths,
         config,
+        &hex_packages,
         compiled.modules.as_slice(),
         &pages,
         ProjectIO::new(),
@@ -118,6 +154,12 @@ pub(crate) fn build_documentation(
         is_hex_publish,
     );
 
+    outputs.extend(gleam_core::docs::static_assets(
+        &paths,
+        config,
+        &ProjectIO::new(),
+    )?);
+
     outputs.push(gleam_core::docs::generate_json_package_interface(
         Utf8PathBuf::from("package-interface.json"),
         compiled,
@@ -146,6 +188,7 @@ pub fn publish() -> Result<()> {
             mode: Mode::Prod,
             target: None,
             no_print_progress: false,
+            doctests: false,
         },
         crate::build::download_dependencies(cli::Reporter::new())?,
     )?;
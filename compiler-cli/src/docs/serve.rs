@@ -0,0 +1,318 @@
+//! A web server for previewing documentation while writing it. The docs are
+//! built again whenever the package changes, and the pages being viewed
+//! reload themselves once they have been.
+
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use gleam_core::{build::Target, error::Error, paths::ProjectPaths, Result};
+
+use super::{BuildOptions, DocsFormat};
+
+/// The path pages request to find out which build of the docs is the latest.
+const VERSION_PATH: &str = "/__gleam/docs-version";
+
+/// How often the package is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+#[derive(Debug)]
+pub struct ServeOptions {
+    /// Whether to open the docs in a browser once the server has started.
+    pub open: bool,
+    pub target: Option<Target>,
+    pub port: u16,
+}
+
+pub fn serve(options: ServeOptions) -> Result<()> {
+    let paths = crate::find_project_paths()?;
+    let config = crate::config::root_config()?;
+    let out = paths.build_documentation_directory(&config.name);
+
+    build(&options)?;
+
+    let address = format!("127.0.0.1:{}", options.port);
+    let listener = TcpListener::bind(&address).map_err(|error| Error::FailedToStartDocsServer {
+        address: address.clone(),
+        error: error.to_string(),
+    })?;
+    let url = format!("http://{address}/");
+    println!("\nServing the documentation at {url}");
+
+    let version = Arc::new(AtomicU64::new(0));
+    let server_version = version.clone();
+    let _ = thread::spawn(move || {
+        for stream in listener.incoming().filter_map(Result::ok) {
+            let out = out.clone();
+            let version = server_version.clone();
+            let _ = thread::spawn(move || respond(stream, &out, version.load(Ordering::SeqCst)));
+        }
+    });
+
+    if options.open {
+        opener::open(&url).map_err(|error| Error::FailedToOpenDocs {
+            path: Utf8PathBuf::from(url),
+            error: error.to_string(),
+        })?;
+    }
+
+    let mut sources = watched_files(&paths);
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let latest = watched_files(&paths);
+        if latest == sources {
+            continue;
+        }
+        sources = latest;
+        crate::cli::print_colourful_prefix("Rebuilding", "documentation");
+        // A mistake in the code shouldn't stop the server, so the error is
+        // shown and the previous build of the docs is served until the next
+        // change.
+        // The pages are only reloaded once there are new docs to show.
+        match build(&options) {
+            Ok(()) => {
+                let _ = version.fetch_add(1, Ordering::SeqCst);
+            }
+            Err(error) => {
+                let stderr = crate::cli::stderr_buffer_writer();
+                let mut buffer = stderr.buffer();
+                error.pretty(&mut buffer);
+                stderr.print(&buffer).expect("Writing error");
+            }
+        }
+    }
+}
+
+fn build(options: &ServeOptions) -> Result<()> {
+    super::build(BuildOptions {
+        open: false,
+        target: options.target,
+        format: DocsFormat::Html,
+    })
+}
+
+/// The files the documentation is built from, with their modification times,
+/// in a consistent order so they can be compared to spot any changes.
+fn watched_files(paths: &ProjectPaths) -> Vec<(Utf8PathBuf, SystemTime)> {
+    let mut roots = vec![paths.src_directory(), paths.root_config(), paths.readme()];
+    if let Ok(config) = crate::config::root_config() {
+        let documentation = config.documentation;
+        roots.extend(
+            documentation
+                .pages
+                .into_iter()
+                .map(|page| paths.root().join(page.source)),
+        );
+        roots.extend(
+            documentation
+                .logo
+                .into_iter()
+                .chain(documentation.favicon)
+                .chain(documentation.assets)
+                .map(|asset| paths.root().join(asset)),
+        );
+    }
+
+    let mut files = roots
+        .iter()
+        .flat_map(|root| walkdir::WalkDir::new(root).follow_links(true))
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter_map(|entry| {
+            let path = Utf8PathBuf::from_path_buf(entry.path().to_path_buf()).ok()?;
+            let modified = entry.metadata().ok()?.modified().ok()?;
+            Some((path, modified))
+        })
+        .collect::<Vec<_>>();
+    files.sort();
+    files
+}
+
+fn respond(mut stream: TcpStream, out: &Utf8Path, version: u64) {
+    let mut request_line = String::new();
+    let mut reader = BufReader::new(&stream);
+    if reader.read_line(&mut request_line).is_err() {
+        return;
+    }
+    // The headers aren't needed, but are read so the client isn't cut off
+    // while still sending them.
+    let mut header = String::new();
+    while reader.read_line(&mut header).is_ok_and(|read| read > 2) {
+        header.clear();
+    }
+
+    let response = match request_line.split_whitespace().collect::<Vec<_>>()[..] {
+        ["GET", VERSION_PATH, _] => Response::ok("text/plain", version.to_string().into_bytes()),
+        ["GET", path, _] => match file_path(out, path) {
+            Some(path) => match std::fs::read(&path) {
+                Ok(content) if path.extension() == Some("html") => Response::ok(
+                    "text/html; charset=utf-8",
+                    with_reload_script(content, version),
+                ),
+                Ok(content) => Response::ok(content_type(&path), content),
+                Err(_) => Response::not_found(),
+            },
+            None => Response::not_found(),
+        },
+        _ => Response {
+            status: "405 Method Not Allowed",
+            content_type: "text/plain",
+            body: b"Method not allowed".to_vec(),
+        },
+    };
+    let _ = response.write(&mut stream);
+}
+
+struct Response {
+    status: &'static str,
+    content_type: &'static str,
+    body: Vec<u8>,
+}
+
+impl Response {
+    fn ok(content_type: &'static str, body: Vec<u8>) -> Self {
+        Self {
+            status: "200 OK",
+            content_type,
+            body,
+        }
+    }
+
+    fn not_found() -> Self {
+        Self {
+            status: "404 Not Found",
+            content_type: "text/plain",
+            body: b"Not found".to_vec(),
+        }
+    }
+
+    fn write(&self, stream: &mut TcpStream) -> std::io::Result<()> {
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nCache-Control: no-cache\r\nConnection: close\r\n\r\n",
+            self.status,
+            self.content_type,
+            self.body.len()
+        )?;
+        stream.write_all(&self.body)?;
+        stream.flush()
+    }
+}
+
+/// The file in the documentation a request is for. Directories are served
+/// by their `index.html`, as they would be by HexDocs.
+fn file_path(out: &Utf8Path, request_path: &str) -> Option<Utf8PathBuf> {
+    let request_path = request_path
+        .split(['?', '#'])
+        .next()
+        .unwrap_or(request_path);
+    let mut path = out.to_path_buf();
+    for segment in request_path
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+    {
+        let segment = percent_decode(segment)?;
+        if segment == ".." || segment == "." || segment.contains(['/', '\\']) {
+            return None;
+        }
+        path.push(segment);
+    }
+    if path.is_dir() {
+        path.push("index.html");
+    }
+    Some(path)
+}
+
+fn percent_decode(text: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, after)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = std::str::from_utf8(after.get(..2)?).ok()?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            rest = after.get(2..)?;
+        } else {
+            bytes.push(byte);
+            rest = after;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn content_type(path: &Utf8Path) -> &'static str {
+    match path.extension() {
+        Some("css") => "text/css; charset=utf-8",
+        Some("js" | "mjs") => "text/javascript; charset=utf-8",
+        Some("json") => "application/json",
+        Some("svg") => "image/svg+xml",
+        Some("png") => "image/png",
+        Some("jpg" | "jpeg") => "image/jpeg",
+        Some("gif") => "image/gif",
+        Some("webp") => "image/webp",
+        Some("ico") => "image/x-icon",
+        Some("woff") => "font/woff",
+        Some("woff2") => "font/woff2",
+        Some("txt" | "md") => "text/plain; charset=utf-8",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Adds a script to a page that reloads it once the docs have been built
+/// again.
+fn with_reload_script(mut html: Vec<u8>, version: u64) -> Vec<u8> {
+    let script = format!(
+        r#"<script>
+  setInterval(async () => {{
+    try {{
+      const response = await fetch("{VERSION_PATH}");
+      if ((await response.text()) !== "{version}") location.reload();
+    }} catch {{}}
+  }}, 1000);
+</script>
+"#
+    );
+    html.extend_from_slice(script.as_bytes());
+    html
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_path_serves_index_of_directories() {
+        let out = Utf8PathBuf::from("/nonexistent/docs");
+        assert_eq!(
+            file_path(&out, "/app/wibble.html?search=1"),
+            Some(out.join("app/wibble.html"))
+        );
+        assert_eq!(
+            file_path(&out, "/with%20space.html"),
+            Some(out.join("with space.html"))
+        );
+    }
+
+    #[test]
+    fn file_path_does_not_leave_the_documentation() {
+        let out = Utf8PathBuf::from("/nonexistent/docs");
+        assert_eq!(file_path(&out, "/../secret.txt"), None);
+        assert_eq!(file_path(&out, "/%2e%2e/secret.txt"), None);
+        assert_eq!(file_path(&out, "/assets/..%2Fsecret.txt"), None);
+    }
+
+    #[test]
+    fn reload_script_checks_the_version() {
+        let html = with_reload_script(b"<p>Hello</p>".to_vec(), 3);
+        let html = String::from_utf8(html).expect("utf8");
+        assert!(html.starts_with("<p>Hello</p><script>"));
+        assert!(html.contains(r#"(await response.text()) !== "3""#));
+    }
+}
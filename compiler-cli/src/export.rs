@@ -1,8 +1,8 @@
 use camino::Utf8PathBuf;
 use gleam_core::{
     analyse::TargetSupport,
-    build::{Codegen, Compile, Mode, Options, Target},
-    Result,
+    build::{Codegen, Compile, Mode, ModuleFormat, Options, Target},
+    Error, Result,
 };
 
 #[cfg(target_os = "windows")]
@@ -49,6 +49,7 @@ pub(crate) fn erlang_shipment() -> Result<()> {
             mode,
             target: Some(target),
             no_print_progress: false,
+            doctests: false,
         },
         crate::build::download_dependencies(crate::cli::Reporter::new())?,
     )?;
@@ -100,6 +101,149 @@ the {ENTRYPOINT_FILENAME} script.
     Ok(())
 }
 
+/// Generate a single executable escript containing the precompiled Erlang of
+/// the project and all of its dependencies. When run it calls the `main`
+/// function of the module with the same name as the project.
+///
+/// The `ebin` directory of each package is added to the escript's archive,
+/// where it is found by the Erlang code server when the escript is run. The
+/// `priv` directories are not included, so packages that read files from
+/// them at runtime will not work when run as an escript.
+pub(crate) fn escript() -> Result<()> {
+    let paths = crate::find_project_paths()?;
+    let target = Target::Erlang;
+    let mode = Mode::Prod;
+    let build = paths.build_directory_for_target(mode, target);
+
+    // Reset the build directory to ensure no old code ends up in the escript
+    crate::fs::delete_directory(&build)?;
+
+    // Build project in production mode
+    let built = crate::build::main(
+        Options {
+            root_target_support: TargetSupport::Enforced,
+            warnings_as_errors: false,
+            codegen: Codegen::All,
+            compile: Compile::All,
+            mode,
+            target: Some(target),
+            no_print_progress: false,
+            doctests: false,
+        },
+        crate::build::download_dependencies(crate::cli::Reporter::new())?,
+    )?;
+    let name = &built.root_package.config.name;
+
+    let mut files = vec![];
+    for entry in crate::fs::read_dir(&build)?.filter_map(Result::ok) {
+        let path = entry.path();
+
+        // We are only interested in package directories
+        if !path.is_dir() {
+            continue;
+        }
+
+        let package = path.file_name().expect("Directory name");
+        let ebin = path.join("ebin");
+        if !ebin.is_dir() {
+            continue;
+        }
+        for entry in crate::fs::read_dir(&ebin)?.filter_map(Result::ok) {
+            let path = entry.path();
+            if !path.is_file() {
+                continue;
+            }
+            let file_name = path.file_name().expect("File name");
+            let bytes = crate::fs::read_bytes(path)?;
+            files.push((format!("{package}/ebin/{file_name}"), bytes));
+        }
+    }
+    files.sort_by(|(one, _), (other, _)| one.cmp(other));
+
+    // The emulator arguments make the escript call the `main/1` function of
+    // the Erlang entrypoint module generated for the project.
+    let mut escript =
+        format!("#!/usr/bin/env escript\n%% {name}\n%%! -escript main {name}@@main\n").into_bytes();
+    escript.extend(zip_archive(&files)?);
+
+    let out = paths.build_export_escript(name);
+    crate::fs::write_bytes(&out, &escript)?;
+    crate::fs::make_executable(&out)?;
+
+    crate::cli::print_exported(name);
+
+    println!(
+        "
+Your escript has been generated to {out}.
+
+It can be copied to any computer with Erlang installed and run directly.
+",
+    );
+
+    Ok(())
+}
+
+/// Creates a zip archive containing the given files, as used by escripts.
+/// The files are stored without compression, so the archive can't hold more
+/// than 65535 files or 4GiB of data.
+fn zip_archive(files: &[(String, Vec<u8>)]) -> Result<Vec<u8>> {
+    let mut archive = vec![];
+    let mut central_directory = vec![];
+
+    for (name, bytes) in files {
+        let mut crc = flate2::Crc::new();
+        crc.update(bytes);
+        let offset = u32::try_from(archive.len()).map_err(|_| Error::EscriptTooLarge)?;
+        let size = u32::try_from(bytes.len()).map_err(|_| Error::EscriptTooLarge)?;
+        let name_length = u16::try_from(name.len()).map_err(|_| Error::EscriptTooLarge)?;
+
+        // The fields shared by the local header and the central directory.
+        let mut fields = vec![];
+        fields.extend(20u16.to_le_bytes()); // Version needed to extract
+        fields.extend(0u16.to_le_bytes()); // Flags
+        fields.extend(0u16.to_le_bytes()); // Compression method: stored
+        fields.extend(0u16.to_le_bytes()); // Modification time
+        fields.extend(0x21u16.to_le_bytes()); // Modification date: 1980-01-01
+        fields.extend(crc.sum().to_le_bytes());
+        fields.extend(size.to_le_bytes()); // Compressed size
+        fields.extend(size.to_le_bytes()); // Uncompressed size
+        fields.extend(name_length.to_le_bytes());
+        fields.extend(0u16.to_le_bytes()); // Extra field length
+
+        archive.extend(0x04034b50u32.to_le_bytes());
+        archive.extend(&fields);
+        archive.extend(name.as_bytes());
+        archive.extend(bytes);
+
+        central_directory.extend(0x02014b50u32.to_le_bytes());
+        central_directory.extend(20u16.to_le_bytes()); // Version made by
+        central_directory.extend(&fields);
+        central_directory.extend(0u16.to_le_bytes()); // Comment length
+        central_directory.extend(0u16.to_le_bytes()); // Disk number
+        central_directory.extend(0u16.to_le_bytes()); // Internal attributes
+        central_directory.extend(0u32.to_le_bytes()); // External attributes
+        central_directory.extend(offset.to_le_bytes());
+        central_directory.extend(name.as_bytes());
+    }
+
+    let entries = u16::try_from(files.len()).map_err(|_| Error::EscriptTooLarge)?;
+    let central_directory_offset =
+        u32::try_from(archive.len()).map_err(|_| Error::EscriptTooLarge)?;
+    let central_directory_size =
+        u32::try_from(central_directory.len()).map_err(|_| Error::EscriptTooLarge)?;
+    archive.extend(&central_directory);
+
+    archive.extend(0x06054b50u32.to_le_bytes());
+    archive.extend(0u16.to_le_bytes()); // Disk number
+    archive.extend(0u16.to_le_bytes()); // Disk with the central directory
+    archive.extend(entries.to_le_bytes()); // Entries on this disk
+    archive.extend(entries.to_le_bytes()); // Total entries
+    archive.extend(central_directory_size.to_le_bytes());
+    archive.extend(central_directory_offset.to_le_bytes());
+    archive.extend(0u16.to_le_bytes()); // Comment length
+    Ok(archive)
+}
+
 pub fn hex_tarball() -> Result<()> {
     let paths = crate::find_project_paths()?;
     let mut config = crate::config::root_config()?;
@@ -116,6 +260,90 @@ Your hex tarball has been generated in {}.
     Ok(())
 }
 
+/// Generate a directory of precompiled JavaScript along with an entrypoint
+/// module and HTML page that run the `main` function of the project's main
+/// module. Suitable for serving to a browser or passing to a bundler.
+///
+/// Neither the prelude nor the generated modules use any Node builtins, so no
+/// polyfills are needed.
+pub(crate) fn javascript_browser() -> Result<()> {
+    let paths = crate::find_project_paths()?;
+    let target = Target::JavaScript;
+    let mode = Mode::Prod;
+    let build = paths.build_directory_for_target(mode, target);
+    let out = paths.javascript_browser_directory();
+
+    // Browsers can only load ES modules
+    if crate::config::root_config()?.javascript.module_format != ModuleFormat::Esm {
+        return Err(Error::BrowserExportRequiresEsModules);
+    }
+
+    // Reset the directories to ensure we have a clean slate and no old code
+    crate::fs::delete_directory(&build)?;
+    crate::fs::delete_directory(&out)?;
+
+    // Build project in production mode
+    let built = crate::build::main(
+        Options {
+            root_target_support: TargetSupport::Enforced,
+            warnings_as_errors: false,
+            codegen: Codegen::All,
+            compile: Compile::All,
+            mode,
+            target: Some(target),
+            no_print_progress: false,
+            doctests: false,
+        },
+        crate::build::download_dependencies(crate::cli::Reporter::new())?,
+    )?;
+    let name = built.root_package.config.name.clone();
+    let _ = built.get_main_function(&name, target)?;
+
+    crate::fs::copy_dir(&build, &out)?;
+
+    // The cache files of the build are of no use to the browser
+    for entry in crate::fs::read_dir(&out)?.filter_map(Result::ok) {
+        crate::fs::delete_directory(&entry.path().join("_gleam_artefacts"))?;
+    }
+
+    // Write the entrypoint module and a page that loads it
+    let entrypoint = out.join("gleam.main.mjs");
+    crate::fs::write(
+        &entrypoint,
+        &format!("import {{ main }} from \"./{name}/{name}.mjs\";\nmain();\n"),
+    )?;
+    crate::fs::write(
+        &out.join("index.html"),
+        &format!(
+            r#"<!doctype html>
+<html>
+  <head>
+    <meta charset="utf-8">
+    <title>{name}</title>
+    <script type="module" src="./gleam.main.mjs"></script>
+  </head>
+  <body></body>
+</html>
+"#
+        ),
+    )?;
+
+    crate::cli::print_exported(&name);
+
+    println!(
+        "
+Your browser build has been generated to {out}.
+
+It can be served by any web server and opened in a browser, or the entrypoint
+module can be given to a bundler.
+
+    {entrypoint}
+",
+    );
+
+    Ok(())
+}
+
 pub fn javascript_prelude() -> Result<()> {
     print!("{}", gleam_core::javascript::PRELUDE);
     Ok(())
@@ -137,6 +365,7 @@ pub fn package_interface(path: Utf8PathBuf) -> Result<()> {
             warnings_as_errors: false,
             root_target_support: TargetSupport::Enforced,
             no_print_progress: false,
+            doctests: false,
         },
         crate::build::download_dependencies(crate::cli::Reporter::new())?,
     )?;
@@ -146,3 +375,47 @@ pub fn package_interface(path: Utf8PathBuf) -> Result<()> {
     crate::fs::write_outputs_under(&[out], crate::find_project_paths()?.root())?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn u32_at(bytes: &[u8], index: usize) -> usize {
+        let bytes = bytes
+            .get(index..index + 4)
+            .and_then(|bytes| bytes.try_into().ok())
+            .expect("u32 in bounds");
+        u32::from_le_bytes(bytes) as usize
+    }
+
+    #[test]
+    fn zip_archive_test() {
+        let files = vec![
+            (
+                "app/ebin/app.app".to_string(),
+                b"{application, app, []}.".to_vec(),
+            ),
+            ("app/ebin/app.beam".to_string(), vec![0, 1, 2, 3]),
+        ];
+        let archive = zip_archive(&files).expect("zip archive");
+
+        // Each file is stored after its local header.
+        assert_eq!(archive.get(0..4), Some(&0x04034b50u32.to_le_bytes()[..]));
+        assert_eq!(archive.get(30..46), Some(&b"app/ebin/app.app"[..]));
+        assert_eq!(archive.get(46..69), Some(&b"{application, app, []}."[..]));
+
+        // The end of central directory record points back to the central
+        // directory, which lists both files.
+        let end_offset = archive.len() - 22;
+        let end = archive.get(end_offset..).expect("end of central directory");
+        assert_eq!(end.get(0..4), Some(&0x06054b50u32.to_le_bytes()[..]));
+        assert_eq!(end.get(10..12), Some(&2u16.to_le_bytes()[..]));
+        let size = u32_at(end, 12);
+        let offset = u32_at(end, 16);
+        assert_eq!(offset + size, end_offset);
+        assert_eq!(
+            archive.get(offset..offset + 4),
+            Some(&0x02014b50u32.to_le_bytes()[..])
+        );
+    }
+}
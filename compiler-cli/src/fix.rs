@@ -27,6 +27,7 @@ pub fn run() -> Result<()> {
             mode: Mode::Dev,
             target: None,
             no_print_progress: false,
+            doctests: false,
         },
         build::download_dependencies(cli::Reporter::new())?,
         warnings.clone(),
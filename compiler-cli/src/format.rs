@@ -1,27 +1,100 @@
 use gleam_core::{
+    config::FormatConfig,
     error::{Error, FileIoAction, FileKind, Result, StandardIoAction, Unformatted},
     io::Content,
     io::OutputFile,
+    paths::ProjectPaths,
 };
-use std::{io::Read, str::FromStr};
+use std::{collections::HashSet, io::Read, str::FromStr};
 
 use camino::{Utf8Path, Utf8PathBuf};
+use ecow::EcoString;
 
-pub fn run(stdin: bool, check: bool, files: Vec<String>) -> Result<()> {
-    if stdin {
-        process_stdin(check)
+pub fn run(stdin: bool, check: bool, check_idempotent: bool, files: Vec<String>) -> Result<()> {
+    let config = format_config()?;
+    let files = if files.is_empty() {
+        default_files(&config)?
+    } else {
+        files
+    };
+    if check_idempotent {
+        check_idempotent_inputs(stdin, files, &config)
+    } else if stdin {
+        process_stdin(check, &config)
     } else {
-        process_files(check, files)
+        process_files(check, files, &config)
+    }
+}
+
+/// The `[format]` config of the project in the current directory, or the
+/// default config when not in a project.
+fn format_config() -> Result<FormatConfig> {
+    let Ok(root) = crate::fs::get_project_root(crate::fs::get_current_directory()?) else {
+        return Ok(FormatConfig::default());
+    };
+    let config = crate::config::read(ProjectPaths::new(root).root_config())?;
+    Ok(config.format)
+}
+
+/// The files formatted when none are given. That is the current directory,
+/// unless the `[format]` config has an `include` list, in which case it is the
+/// project's `src` and `test` directories and the included directories, which
+/// are relative to the project root.
+fn default_files(config: &FormatConfig) -> Result<Vec<String>> {
+    if config.include.is_empty() {
+        return Ok(vec![".".into()]);
     }
+    let paths = ProjectPaths::new(crate::fs::get_project_root(
+        crate::fs::get_current_directory()?,
+    )?);
+    let files = [paths.src_directory(), paths.test_directory()]
+        .into_iter()
+        .filter(|directory| directory.is_dir())
+        .chain(config.include.iter().map(|dir| paths.root().join(dir)))
+        .map(|path| path.to_string())
+        .collect();
+    Ok(files)
+}
+
+/// Checks that formatting the formatted code again leaves it as it is,
+/// returning an error showing what changed for the first input where it
+/// doesn't.
+fn check_idempotent_inputs(stdin: bool, files: Vec<String>, config: &FormatConfig) -> Result<()> {
+    if stdin {
+        let src = read_stdin()?.into();
+        return check_idempotent(&src, Utf8Path::new("<stdin>"), config);
+    }
+
+    for path in gleam_files(files)? {
+        let src = crate::fs::read(&path)?.into();
+        check_idempotent(&src, &path, config)?;
+    }
+    Ok(())
 }
 
-fn process_stdin(check: bool) -> Result<()> {
+fn check_idempotent(src: &EcoString, path: &Utf8Path, config: &FormatConfig) -> Result<()> {
+    match gleam_core::format::check_idempotent(src, path, config)? {
+        None => Ok(()),
+        Some(unstable) => Err(Error::UnstableFormat {
+            path: path.to_path_buf(),
+            unstable,
+        }),
+    }
+}
+
+fn process_stdin(check: bool, config: &FormatConfig) -> Result<()> {
     let src = read_stdin()?.into();
     let mut out = String::new();
-    gleam_core::format::pretty(&mut out, &src, Utf8Path::new("<stdin>"))?;
+    let errors =
+        gleam_core::format::pretty_tolerant(&mut out, &src, Utf8Path::new("<stdin>"), config)?;
 
     if !check {
         print!("{out}");
+    }
+    if let Some(error) = errors.into_iter().next() {
+        return Err(error);
+    }
+    if !check {
         return Ok(());
     }
 
@@ -39,36 +112,61 @@ fn process_stdin(check: bool) -> Result<()> {
     Ok(())
 }
 
-fn process_files(check: bool, files: Vec<String>) -> Result<()> {
+fn process_files(check: bool, files: Vec<String>, config: &FormatConfig) -> Result<()> {
     if check {
-        check_files(files)
+        check_files(files, config)
     } else {
-        format_files(files)
+        format_files(files, config)
     }
 }
 
-fn check_files(files: Vec<String>) -> Result<()> {
-    let problem_files = unformatted_files(files)?;
+fn check_files(files: Vec<String>, config: &FormatConfig) -> Result<()> {
+    let (problem_files, errors) = unformatted_files(files, config)?;
 
-    if problem_files.is_empty() {
+    if let Some(error) = errors.into_iter().next() {
+        Err(error)
+    } else if problem_files.is_empty() {
         Ok(())
     } else {
         Err(Error::Format { problem_files })
     }
 }
 
-fn format_files(files: Vec<String>) -> Result<()> {
-    for file in unformatted_files(files)? {
+fn format_files(files: Vec<String>, config: &FormatConfig) -> Result<()> {
+    let (problem_files, errors) = unformatted_files(files, config)?;
+    for file in problem_files {
         crate::fs::write_output(&OutputFile {
             path: file.destination,
             content: Content::Text(file.output),
         })?;
     }
-    Ok(())
+    match errors.into_iter().next() {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
 }
 
-pub fn unformatted_files(files: Vec<String>) -> Result<Vec<Unformatted>> {
+/// The files whose formatting would change, and the syntax errors found in
+/// them. The parts of a file around a syntax error are still formatted.
+pub fn unformatted_files(
+    files: Vec<String>,
+    config: &FormatConfig,
+) -> Result<(Vec<Unformatted>, Vec<Error>)> {
     let mut problem_files = Vec::with_capacity(files.len());
+    let mut errors = vec![];
+
+    for path in gleam_files(files)? {
+        format_file(&mut problem_files, &mut errors, path, config)?;
+    }
+
+    Ok((problem_files, errors))
+}
+
+/// The given files, and the Gleam files found in the given directories. A file
+/// found more than once, such as in a directory within another given one, is
+/// only returned once.
+fn gleam_files(files: Vec<String>) -> Result<Vec<Utf8PathBuf>> {
+    let mut paths = Vec::with_capacity(files.len());
 
     for file_path in files {
         let path = Utf8PathBuf::from_str(&file_path).map_err(|e| Error::FileIo {
@@ -79,21 +177,32 @@ pub fn unformatted_files(files: Vec<String>) -> Result<Vec<Unformatted>> {
         })?;
 
         if path.is_dir() {
-            for path in crate::fs::gleam_files_excluding_gitignore(&path) {
-                format_file(&mut problem_files, path)?;
-            }
+            paths.extend(crate::fs::gleam_files_excluding_gitignore(&path));
         } else {
-            format_file(&mut problem_files, path)?;
+            paths.push(path);
         }
     }
 
-    Ok(problem_files)
+    let mut seen = HashSet::new();
+    paths
+        .retain(|path| seen.insert(crate::fs::canonicalise(path).unwrap_or_else(|_| path.clone())));
+    Ok(paths)
 }
 
-fn format_file(problem_files: &mut Vec<Unformatted>, path: Utf8PathBuf) -> Result<()> {
+fn format_file(
+    problem_files: &mut Vec<Unformatted>,
+    errors: &mut Vec<Error>,
+    path: Utf8PathBuf,
+    config: &FormatConfig,
+) -> Result<()> {
     let src = crate::fs::read(&path)?.into();
     let mut output = String::new();
-    gleam_core::format::pretty(&mut output, &src, &path)?;
+    errors.extend(gleam_core::format::pretty_tolerant(
+        &mut output,
+        &src,
+        &path,
+        config,
+    )?);
 
     if src != output {
         problem_files.push(Unformatted {
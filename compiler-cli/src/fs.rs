@@ -2,8 +2,8 @@ use gleam_core::{
     build::{NullTelemetry, Target},
     error::{parse_os, Error, FileIoAction, FileKind, OS},
     io::{
-        BeamCompiler, CommandExecutor, Content, DirEntry, FileSystemReader, FileSystemWriter,
-        OutputFile, ReadDir, Stdio, WrappedReader,
+        BeamCompiler, CommandExecutor, Content, DirEntry, ErlangCompilerDiagnostic,
+        FileSystemReader, FileSystemWriter, OutputFile, ReadDir, Stdio, WrappedReader,
     },
     language_server::{DownloadDependencies, Locker, MakeLocker},
     manifest::Manifest,
@@ -228,7 +228,7 @@ impl BeamCompiler for ProjectIO {
         lib: &Utf8Path,
         modules: &HashSet<Utf8PathBuf>,
         stdio: Stdio,
-    ) -> Result<(), Error> {
+    ) -> Result<Vec<ErlangCompilerDiagnostic>, Error> {
         self.beam_compiler
             .lock()
             .as_mut()
@@ -408,7 +408,10 @@ pub fn native_files(dir: &Utf8Path) -> Result<impl Iterator<Item = Utf8PathBuf>
         .map(|e| e.into_path())
         .filter(|path| {
             let extension = path.extension().unwrap_or_default();
-            matches!(extension, "erl" | "hrl" | "ex" | "js" | "mjs" | "ts")
+            matches!(
+                extension,
+                "erl" | "hrl" | "ex" | "js" | "mjs" | "cjs" | "ts"
+            )
         }))
 }
 
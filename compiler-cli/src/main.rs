@@ -56,6 +56,7 @@ mod cli;
 mod compile_package;
 mod config;
 mod dependencies;
+mod dialyzer;
 mod docs;
 mod export;
 mod fix;
@@ -118,18 +119,7 @@ struct UpdateOptions {
 )]
 enum Command {
     /// Build the project
-    Build {
-        /// Emit compile time warnings as errors
-        #[arg(long)]
-        warnings_as_errors: bool,
-
-        #[arg(short, long, ignore_case = true, help = target_doc())]
-        target: Option<Target>,
-
-        /// Don't print progress information
-        #[clap(long)]
-        no_print_progress: bool,
-    },
+    Build(BuildOptions),
 
     /// Type check the project
     Check {
@@ -172,8 +162,8 @@ enum Command {
 
     /// Format source code
     Format {
-        /// Files to format
-        #[arg(default_value = ".")]
+        /// Files to format. Defaults to the current directory and the
+        /// directories in the `include` list of the `[format]` config
         files: Vec<String>,
 
         /// Read source from STDIN
@@ -183,6 +173,11 @@ enum Command {
         /// Check if inputs are formatted without changing them
         #[arg(long)]
         check: bool,
+
+        /// Check that formatting the formatted inputs again doesn't change
+        /// them, without changing them
+        #[arg(long, conflicts_with = "check")]
+        check_idempotent: bool,
     },
     /// Rewrite deprecated Gleam code
     Fix,
@@ -276,8 +271,13 @@ fn runtime_doc() -> String {
 pub enum ExportTarget {
     /// Precompiled Erlang, suitable for deployment
     ErlangShipment,
+    /// A single executable escript of the project and its dependencies
+    Escript,
     /// The package bundled into a tarball, suitable for publishing to Hex
     HexTarball,
+    /// Precompiled JavaScript with an entrypoint module, suitable for loading
+    /// in a browser or bundler
+    JavascriptBrowser,
     /// The JavaScript prelude module
     JavascriptPrelude,
     /// The TypeScript prelude module
@@ -290,6 +290,30 @@ pub enum ExportTarget {
     },
 }
 
+#[derive(Args, Debug, Clone, Copy)]
+pub struct BuildOptions {
+    /// Emit compile time warnings as errors
+    #[arg(long)]
+    warnings_as_errors: bool,
+
+    #[arg(short, long, ignore_case = true, help = target_doc())]
+    target: Option<Target>,
+
+    /// Don't print progress information
+    #[clap(long)]
+    no_print_progress: bool,
+
+    /// Report public functions that are not used by any `main` function
+    /// or test of the project
+    #[arg(long)]
+    dead_code: bool,
+
+    /// Run Dialyzer over the generated Erlang, reporting its findings
+    /// in the Gleam source they come from
+    #[arg(long, conflicts_with = "target")]
+    dialyzer: bool,
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct NewOptions {
     /// Location of the project root
@@ -410,7 +434,7 @@ enum Hex {
 
 #[derive(Subcommand, Debug)]
 enum Docs {
-    /// Render HTML docs locally
+    /// Render docs locally
     Build {
         /// Opens the docs in a browser after rendering
         #[arg(long)]
@@ -418,6 +442,24 @@ enum Docs {
 
         #[arg(short, long, ignore_case = true, help = target_doc())]
         target: Option<Target>,
+
+        /// Whether to render the docs as HTML pages or as a single JSON file
+        #[arg(long, ignore_case = true, default_value = "html")]
+        format: docs::DocsFormat,
+    },
+
+    /// Serve docs locally, rendering them again when the package changes
+    Serve {
+        /// Opens the docs in a browser once the server has started
+        #[arg(long)]
+        open: bool,
+
+        #[arg(short, long, ignore_case = true, help = target_doc())]
+        target: Option<Target>,
+
+        /// The port to serve the docs on
+        #[arg(long, default_value = "8000")]
+        port: u16,
     },
 
     /// Publish HTML docs to HexDocs
@@ -455,16 +497,22 @@ fn main() {
     let stderr = cli::stderr_buffer_writer();
 
     let result = match Command::parse() {
-        Command::Build {
-            target,
-            warnings_as_errors,
-            no_print_progress,
-        } => command_build(target, warnings_as_errors, no_print_progress),
+        Command::Build(options) => command_build(options),
 
         Command::Check { target } => command_check(target),
 
-        Command::Docs(Docs::Build { open, target }) => {
-            docs::build(docs::BuildOptions { open, target })
+        Command::Docs(Docs::Build {
+            open,
+            target,
+            format,
+        }) => docs::build(docs::BuildOptions {
+            open,
+            target,
+            format,
+        }),
+
+        Command::Docs(Docs::Serve { open, target, port }) => {
+            docs::serve(docs::ServeOptions { open, target, port })
         }
 
         Command::Docs(Docs::Publish) => docs::publish(),
@@ -475,7 +523,8 @@ fn main() {
             stdin,
             files,
             check,
-        } => format::run(stdin, check, files),
+            check_idempotent,
+        } => format::run(stdin, check, check_idempotent, files),
 
         Command::Fix => fix::run(),
 
@@ -540,7 +589,9 @@ fn main() {
         Command::LanguageServer => lsp::main(),
 
         Command::Export(ExportTarget::ErlangShipment) => export::erlang_shipment(),
+        Command::Export(ExportTarget::Escript) => export::escript(),
         Command::Export(ExportTarget::HexTarball) => export::hex_tarball(),
+        Command::Export(ExportTarget::JavascriptBrowser) => export::javascript_browser(),
         Command::Export(ExportTarget::JavascriptPrelude) => export::javascript_prelude(),
         Command::Export(ExportTarget::TypescriptPrelude) => export::typescript_prelude(),
         Command::Export(ExportTarget::PackageInterface { output }) => {
@@ -572,23 +623,33 @@ fn command_check(target: Option<Target>) -> Result<()> {
             mode: Mode::Dev,
             target,
             no_print_progress: false,
+            doctests: false,
         },
         build::download_dependencies(cli::Reporter::new())?,
     )?;
     Ok(())
 }
 
-fn command_build(
-    target: Option<Target>,
-    warnings_as_errors: bool,
-    no_print_progress: bool,
-) -> Result<()> {
+fn command_build(options: BuildOptions) -> Result<()> {
+    let BuildOptions {
+        warnings_as_errors,
+        target,
+        no_print_progress,
+        dead_code,
+        dialyzer,
+    } = options;
     let manifest = if no_print_progress {
         build::download_dependencies(NullTelemetry)?
     } else {
         build::download_dependencies(cli::Reporter::new())?
     };
-    let _ = build::main(
+    // Dialyzer can only check the Erlang target.
+    let target = if dialyzer {
+        Some(Target::Erlang)
+    } else {
+        target
+    };
+    let built = build::main(
         Options {
             root_target_support: TargetSupport::Enforced,
             warnings_as_errors,
@@ -597,9 +658,16 @@ fn command_build(
             mode: Mode::Dev,
             target,
             no_print_progress,
+            doctests: false,
         },
         manifest,
     )?;
+    if dead_code {
+        build::print_unused_public_functions(&built)?;
+    }
+    if dialyzer {
+        dialyzer::check(&find_project_paths()?, &built)?;
+    }
     Ok(())
 }
 
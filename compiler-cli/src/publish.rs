@@ -283,6 +283,7 @@ fn do_build_hex_tarball(paths: &ProjectPaths, config: &mut PackageConfig) -> Res
             codegen: Codegen::All,
             compile: Compile::All,
             no_print_progress: false,
+            doctests: false,
         },
         build::download_dependencies(cli::Reporter::new())?,
     )?;
@@ -1,16 +1,20 @@
 use std::sync::OnceLock;
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Path, Utf8PathBuf};
 use ecow::EcoString;
 use gleam_core::{
     analyse::TargetSupport,
-    build::{Built, Codegen, Compile, Mode, NullTelemetry, Options, Runtime, Target, Telemetry},
+    build::{
+        Built, Codegen, Compile, Mode, ModuleFormat, NullTelemetry, Options, Origin, Runtime,
+        Target, Telemetry,
+    },
     config::{DenoFlag, PackageConfig},
     error::Error,
     io::{CommandExecutor, Stdio},
     paths::ProjectPaths,
     type_::ModuleFunction,
 };
+use itertools::Itertools;
 
 use crate::{config::PackageKind, fs::ProjectIO};
 
@@ -95,17 +99,65 @@ pub fn command(
             PackageKind::Dependency => TargetSupport::NotEnforced,
         },
         no_print_progress,
+        doctests: matches!(which, Which::Test),
     };
 
     let built = crate::build::main(options, manifest)?;
 
+    // Any tests generated from the examples in the documentation are run
+    // before the tests themselves.
+    let doctests = match which {
+        Which::Test => built.test_functions(Origin::Doctest, target),
+        Which::Src => vec![],
+    };
+
+    // On JavaScript a test module without a main function has its tests run
+    // by a generated test runner instead.
+    let tests = match (which, target) {
+        (Which::Test, Target::JavaScript) => javascript_tests(&built, &module, target),
+        (Which::Test | Which::Src, _) => None,
+    };
+
     // A module can not be run if it does not exist or does not have a public main function.
-    let main_function = get_or_suggest_main_function(built, &module, target)?;
+    let package = match &tests {
+        Some(_) => root_config.name.clone(),
+        None => get_or_suggest_main_function(built, &module, target)?.package,
+    };
 
     // Don't exit on ctrl+c as it is used by child erlang shell
     ctrlc::set_handler(move || {}).expect("Error setting Ctrl-C handler");
 
-    telemetry.running(&format!("{module}.main"));
+    // Run the doctests
+    let doctests_status = match (target, runtime) {
+        _ if doctests.is_empty() => 0,
+        (Target::Erlang, Some(r)) => {
+            return Err(Error::InvalidRuntime {
+                target: Target::Erlang,
+                invalid_runtime: r,
+            })
+        }
+        (Target::Erlang, None) => {
+            telemetry.running("doctests");
+            run_erlang_doctests(&paths, &doctests)?
+        }
+        (Target::JavaScript, runtime) => {
+            telemetry.running("doctests");
+            let entrypoint = write_javascript_test_runner(
+                &paths,
+                &root_config,
+                &root_config.name,
+                &doctests,
+                "doctest",
+            )?;
+            let runtime = runtime.unwrap_or(mod_config.javascript.runtime);
+            run_javascript(&root_config, runtime, &entrypoint, vec![])?
+        }
+    };
+
+    match &tests {
+        Some(_) => telemetry.running("tests"),
+        None => telemetry.running(&format!("{module}.main")),
+    }
 
     // Run the command
     let status = match target {
@@ -116,39 +168,43 @@ pub fn command(
             }),
             _ => run_erlang(&paths, &root_config.name, &module, arguments),
         },
-        Target::JavaScript => match runtime.unwrap_or(mod_config.javascript.runtime) {
-            Runtime::Deno => run_javascript_deno(
-                &paths,
-                &root_config,
-                &main_function.package,
-                &module,
-                arguments,
-            ),
-            Runtime::NodeJs => {
-                run_javascript_node(&paths, &main_function.package, &module, arguments)
-            }
-            Runtime::Bun => run_javascript_bun(&paths, &main_function.package, &module, arguments),
-        },
+        Target::JavaScript => {
+            let entrypoint = match &tests {
+                Some(tests) => {
+                    write_javascript_test_runner(&paths, &root_config, &package, tests, "test")?
+                }
+                None => write_javascript_entrypoint(&paths, &root_config, &package, &module)?,
+            };
+            let runtime = runtime.unwrap_or(mod_config.javascript.runtime);
+            run_javascript(&root_config, runtime, &entrypoint, arguments)
+        }
     }?;
 
+    // The tests failing takes precedence over the doctests failing.
+    let status = if status == 0 { doctests_status } else { status };
     std::process::exit(status);
 }
 
+fn run_javascript(
+    config: &PackageConfig,
+    runtime: Runtime,
+    entrypoint: &Utf8Path,
+    arguments: Vec<String>,
+) -> Result<i32, Error> {
+    match runtime {
+        Runtime::Deno => run_javascript_deno(config, entrypoint, arguments),
+        Runtime::NodeJs => run_javascript_node(entrypoint, arguments),
+        Runtime::Bun => run_javascript_bun(entrypoint, arguments),
+    }
+}
+
 fn run_erlang(
     paths: &ProjectPaths,
     package: &str,
     module: &str,
     arguments: Vec<String>,
 ) -> Result<i32, Error> {
-    let mut args = vec![];
-
-    // Specify locations of Erlang applications
-    let packages = paths.build_directory_for_target(Mode::Dev, Target::Erlang);
-
-    for entry in crate::fs::read_dir(packages)?.filter_map(Result::ok) {
-        args.push("-pa".into());
-        args.push(entry.path().join("ebin").into());
-    }
+    let mut args = erlang_code_paths(paths)?;
 
     // gleam modules are separated by `/`. Erlang modules are separated by `@`.
     let module = module.replace('/', "@");
@@ -168,16 +224,63 @@ fn run_erlang(
     ProjectIO::new().exec("erl", &args, &[], None, Stdio::Inherit)
 }
 
-fn run_javascript_bun(
+/// The arguments telling the BEAM where to find the compiled modules of each
+/// package.
+fn erlang_code_paths(paths: &ProjectPaths) -> Result<Vec<String>, Error> {
+    let mut args = vec![];
+    let packages = paths.build_directory_for_target(Mode::Dev, Target::Erlang);
+    for entry in crate::fs::read_dir(packages)?.filter_map(Result::ok) {
+        args.push("-pa".into());
+        args.push(entry.path().join("ebin").into());
+    }
+    Ok(args)
+}
+
+fn run_erlang_doctests(
     paths: &ProjectPaths,
-    package: &str,
-    module: &str,
-    arguments: Vec<String>,
+    doctests: &[(EcoString, Vec<EcoString>)],
 ) -> Result<i32, Error> {
+    let mut args = erlang_code_paths(paths)?;
+    args.push("-eval".into());
+    args.push(erlang_test_runner(doctests));
+    args.push("-noshell".into());
+    ProjectIO::new().exec("erl", &args, &[], None, Stdio::Inherit)
+}
+
+/// An Erlang expression that runs each of the given test functions, reporting
+/// the ones that fail in the same way as the JavaScript test runner.
+fn erlang_test_runner(tests: &[(EcoString, Vec<EcoString>)]) -> String {
+    let tests = tests
+        .iter()
+        .flat_map(|(module, names)| {
+            let erlang_module = module.replace("/", "@");
+            names
+                .iter()
+                .map(move |name| format!("{{\"{module}\", '{erlang_module}', '{name}'}}"))
+        })
+        .join(", ");
+    format!(
+        r#"io:setopts([{{encoding, unicode}}]),
+Tests = [{tests}],
+Failures = lists:foldl(fun({{Name, Module, Function}}, Failures) ->
+    try Module:Function() of
+        _ -> Failures
+    catch
+        Class:Reason ->
+            io:format("\e[31m~ts\e[0m ~ts.~ts~n  ~tp: ~tp~n", [[10007], Name, Function, Class, Reason]),
+            Failures + 1
+    end
+end, 0, Tests),
+Colour = case Failures of 0 -> 32; _ -> 31 end,
+io:format("\e[~bm~b passed, ~b failed\e[0m~n", [Colour, length(Tests) - Failures, Failures]),
+erlang:halt(min(Failures, 1))."#
+    )
+}
+
+fn run_javascript_bun(entrypoint: &Utf8Path, arguments: Vec<String>) -> Result<i32, Error> {
     let mut args = vec!["run".to_string()];
-    let entry = write_javascript_entrypoint(paths, package, module)?;
 
-    args.push(entry.to_string());
+    args.push(entrypoint.to_string());
 
     for arg in arguments.into_iter() {
         args.push(arg);
@@ -186,16 +289,10 @@ fn run_javascript_bun(
     ProjectIO::new().exec("bun", &args, &[], None, Stdio::Inherit)
 }
 
-fn run_javascript_node(
-    paths: &ProjectPaths,
-    package: &str,
-    module: &str,
-    arguments: Vec<String>,
-) -> Result<i32, Error> {
+fn run_javascript_node(entrypoint: &Utf8Path, arguments: Vec<String>) -> Result<i32, Error> {
     let mut args = vec![];
-    let entry = write_javascript_entrypoint(paths, package, module)?;
 
-    args.push(entry.to_string());
+    args.push(entrypoint.to_string());
 
     for argument in arguments.into_iter() {
         args.push(argument);
@@ -206,27 +303,155 @@ fn run_javascript_node(
 
 fn write_javascript_entrypoint(
     paths: &ProjectPaths,
+    config: &PackageConfig,
     package: &str,
     module: &str,
 ) -> Result<Utf8PathBuf, Error> {
+    let module_format = config.javascript.module_format;
+    let extension = module_format.extension();
     let path = paths
         .build_directory_for_package(Mode::Dev, Target::JavaScript, package)
         .to_path_buf()
-        .join("gleam.main.mjs");
-    let module = format!(
-        r#"import {{ main }} from "./{module}.mjs";
-main();
-"#,
-    );
+        .join(format!("gleam.main.{extension}"));
+    let module = javascript_entrypoint(module_format, module);
     crate::fs::write(&path, &module)?;
     Ok(path)
 }
 
-fn run_javascript_deno(
+/// The test functions of each of the root package's test modules, if the
+/// given test module is to be replaced by a generated test runner. That is the
+/// case when it does not exist or has no main function, and there are tests
+/// to run.
+fn javascript_tests(
+    built: &Built,
+    module: &str,
+    target: Target,
+) -> Option<Vec<(EcoString, Vec<EcoString>)>> {
+    match built.get_main_function(&module.into(), target) {
+        Err(Error::ModuleDoesNotExist { .. } | Error::ModuleDoesNotHaveMainFunction { .. }) => (),
+        Ok(_) | Err(_) => return None,
+    }
+    let tests = built.test_functions(Origin::Test, target);
+    if tests.is_empty() {
+        None
+    } else {
+        Some(tests)
+    }
+}
+
+fn write_javascript_test_runner(
     paths: &ProjectPaths,
     config: &PackageConfig,
     package: &str,
-    module: &str,
+    tests: &[(EcoString, Vec<EcoString>)],
+    name: &str,
+) -> Result<Utf8PathBuf, Error> {
+    let module_format = config.javascript.module_format;
+    let path = paths
+        .build_directory_for_package(Mode::Dev, Target::JavaScript, package)
+        .join(format!("gleam.{name}.{}", module_format.extension()));
+    crate::fs::write(&path, &javascript_test_runner(module_format, tests))?;
+    Ok(path)
+}
+
+/// A module that runs each of the given test functions, reporting the ones
+/// that fail and exiting with a non-zero exit code if any did.
+fn javascript_test_runner(
+    module_format: ModuleFormat,
+    tests: &[(EcoString, Vec<EcoString>)],
+) -> String {
+    let extension = module_format.extension();
+    let mut imports = String::new();
+    let mut functions = String::new();
+    for (index, (module, names)) in tests.iter().enumerate() {
+        imports.push_str(&match module_format {
+            ModuleFormat::Esm => {
+                format!("import * as $test{index} from \"./{module}.{extension}\";\n")
+            }
+            ModuleFormat::CommonJs => {
+                format!("const $test{index} = require(\"./{module}.{extension}\");\n")
+            }
+        });
+        for name in names {
+            functions.push_str(&format!(
+                "  [\"{module}\", \"{name}\", $test{index}.{name}],\n"
+            ));
+        }
+    }
+    let run = match module_format {
+        ModuleFormat::Esm => "await run();",
+        // CommonJS modules cannot use top-level await.
+        ModuleFormat::CommonJs => "run();",
+    };
+    format!(
+        r#"{imports}
+const tests = [
+{functions}];
+
+async function run() {{
+  let failures = 0;
+  for (const [module, name, test] of tests) {{
+    try {{
+      await test();
+    }} catch (error) {{
+      failures++;
+      console.log(`\u001b[31m✗\u001b[0m ${{module}}.${{name}}`);
+      const location = error?.gleam_error ? ` (${{error.module}}:${{error.line}})` : "";
+      console.log(`  ${{error?.message ?? error}}${{location}}`);
+    }}
+  }}
+  const passes = tests.length - failures;
+  const colour = failures === 0 ? 32 : 31;
+  console.log(`\u001b[${{colour}}m${{passes}} passed, ${{failures}} failed\u001b[0m`);
+  if (failures === 0) return;
+  if (globalThis.Deno) {{
+    Deno.exitCode = 1;
+  }} else {{
+    process.exitCode = 1;
+  }}
+}}
+
+{run}
+"#
+    )
+}
+
+/// The module run by the JavaScript runtimes. It waits for the promise returned
+/// by `main`, if any, and uses an `Int` it returns as the exit code of the
+/// process.
+fn javascript_entrypoint(module_format: ModuleFormat, module: &str) -> String {
+    let extension = module_format.extension();
+    let run = match module_format {
+        ModuleFormat::Esm => format!(
+            r#"import {{ main }} from "./{module}.{extension}";
+
+setExitCode(await main());"#
+        ),
+        // CommonJS modules cannot use top-level await.
+        ModuleFormat::CommonJs => format!(
+            r#"const {{ main }} = require("./{module}.{extension}");
+
+Promise.resolve(main()).then(setExitCode);"#
+        ),
+    };
+    format!(
+        r#"{run}
+
+function setExitCode(result) {{
+  if (typeof result !== "number" && typeof result !== "bigint") return;
+  if (globalThis.Deno) {{
+    Deno.exitCode = Number(result);
+  }} else {{
+    process.exitCode = Number(result);
+  }}
+}}
+"#
+    )
+}
+
+fn run_javascript_deno(
+    config: &PackageConfig,
+    entrypoint: &Utf8Path,
     arguments: Vec<String>,
 ) -> Result<i32, Error> {
     let mut args = vec![];
@@ -288,7 +513,6 @@ fn run_javascript_deno(
         );
     }
 
-    let entrypoint = write_javascript_entrypoint(paths, package, module)?;
     args.push(entrypoint.to_string());
 
     for argument in arguments.into_iter() {
@@ -375,3 +599,83 @@ fn valid_module_names() {
         assert!(is_gleam_module(mod_name));
     }
 }
+
+#[test]
+fn javascript_entrypoint_awaits_main() {
+    assert_eq!(
+        javascript_entrypoint(ModuleFormat::Esm, "app"),
+        r#"import { main } from "./app.mjs";
+
+setExitCode(await main());
+
+function setExitCode(result) {
+  if (typeof result !== "number" && typeof result !== "bigint") return;
+  if (globalThis.Deno) {
+    Deno.exitCode = Number(result);
+  } else {
+    process.exitCode = Number(result);
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn commonjs_javascript_entrypoint_waits_for_main() {
+    assert!(
+        javascript_entrypoint(ModuleFormat::CommonJs, "app").starts_with(
+            r#"const { main } = require("./app.cjs");
+
+Promise.resolve(main()).then(setExitCode);
+"#
+        )
+    );
+}
+
+#[test]
+fn javascript_test_runner_imports_each_test_module() {
+    let runner = javascript_test_runner(
+        ModuleFormat::Esm,
+        &[
+            ("app/nested_test".into(), vec!["one_test".into()]),
+            (
+                "app_test".into(),
+                vec!["two_test".into(), "three_test".into()],
+            ),
+        ],
+    );
+    assert!(runner.starts_with(
+        r#"import * as $test0 from "./app/nested_test.mjs";
+import * as $test1 from "./app_test.mjs";
+
+const tests = [
+  ["app/nested_test", "one_test", $test0.one_test],
+  ["app_test", "two_test", $test1.two_test],
+  ["app_test", "three_test", $test1.three_test],
+];
+"#
+    ));
+    assert!(runner.ends_with("\nawait run();\n"));
+}
+
+#[test]
+fn commonjs_javascript_test_runner_does_not_use_top_level_await() {
+    let runner = javascript_test_runner(
+        ModuleFormat::CommonJs,
+        &[("app_test".into(), vec!["one_test".into()])],
+    );
+    assert!(runner.starts_with(r#"const $test0 = require("./app_test.cjs");"#));
+    assert!(runner.ends_with("\nrun();\n"));
+}
+
+#[test]
+fn erlang_test_runner_calls_each_test_function() {
+    let runner = erlang_test_runner(&[
+        ("_doctest/app/wibble_1".into(), vec!["doctest_test".into()]),
+        ("_doctest/app_3".into(), vec!["doctest_test".into()]),
+    ]);
+    assert!(runner.contains(
+        r#"Tests = [{"_doctest/app/wibble_1", '_doctest@app@wibble_1', 'doctest_test'}, {"_doctest/app_3", '_doctest@app_3', 'doctest_test'}],"#
+    ));
+    assert!(runner.ends_with("erlang:halt(min(Failures, 1))."));
+}
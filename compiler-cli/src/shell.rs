@@ -18,6 +18,7 @@ pub fn command() -> Result<(), Error> {
             mode: Mode::Dev,
             target: Some(Target::Erlang),
             no_print_progress: false,
+            doctests: false,
         },
         crate::build::download_dependencies(crate::cli::Reporter::new())?,
     )?;
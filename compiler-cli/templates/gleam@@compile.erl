@@ -76,24 +76,58 @@ start_compiler_workers(Out) ->
     NumSchedulers.
 
 worker_loop(Parent, Out) ->
-    Options = [report_errors, report_warnings, debug_info, {outdir, Out}],
+    Options = [return_errors, return_warnings, debug_info, {outdir, Out}],
     erlang:send(Parent, {work_please, self()}),
     receive
         {module, Module} ->
             log({compiling, Module}),
             case compile:file(Module, Options) of
-                {ok, ModuleName} ->
+                {ok, ModuleName, Warnings} ->
+                    ok = report(warning, Warnings),
                     Beam = filename:join(Out, ModuleName) ++ ".beam",
                     Message = {compiled, Beam},
                     log(Message),
                     erlang:send(Parent, Message);
-                error ->
+                {error, Errors, Warnings} ->
+                    ok = report(warning, Warnings),
+                    ok = report(error, Errors),
                     log({failed, Module}),
                     erlang:send(Parent, failed)
             end,
             worker_loop(Parent, Out)
     end.
 
+% Print the warnings or errors of the compiler one per line, so they can be
+% read by the build tool and shown as Gleam diagnostics. The fields are
+% separated by tabs, so tabs, newlines and backslashes are escaped.
+report(Kind, FileProblems) ->
+    Report = fun({File, Problems}) ->
+        lists:foreach(fun({Location, Module, Description}) ->
+            Message = Module:format_error(Description),
+            io:put_chars([
+                "gleam-compile-", atom_to_list(Kind), $\t,
+                escape(File), $\t,
+                location_line(Location), $\t,
+                escape(Message), $\n
+            ])
+        end, Problems)
+    end,
+    lists:foreach(Report, FileProblems).
+
+location_line({Line, _Column}) when is_integer(Line) -> integer_to_list(Line);
+location_line(Line) when is_integer(Line) -> integer_to_list(Line);
+location_line(_) -> "".
+
+escape(Chars) ->
+    Binary = unicode:characters_to_binary(Chars),
+    binary:replace(
+        binary:replace(
+            binary:replace(Binary, <<"\\">>, <<"\\\\">>, [global]),
+            <<"\n">>, <<"\\n">>, [global]
+        ),
+        <<"\t">>, <<"\\t">>, [global]
+    ).
+
 compile_elixir(Modules, Out) ->
     Error = [
         "The program elixir was not found. Is it installed?",
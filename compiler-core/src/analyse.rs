@@ -41,7 +41,7 @@ use hexpm::version::Version;
 use itertools::Itertools;
 use name::{check_argument_names, check_name_case};
 use std::{
-    collections::HashMap,
+    collections::{HashMap, HashSet},
     ops::Deref,
     sync::{Arc, OnceLock},
 };
@@ -199,7 +199,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         }
 
         let documentation = std::mem::take(&mut module.documentation);
-        let env = Environment::new(
+        let mut env = Environment::new(
             self.ids.clone(),
             self.package_config.name.clone(),
             self.package_config.gleam_version.clone(),
@@ -209,6 +209,13 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             self.target_support,
         );
 
+        env.values_for_other_targets = values_for_other_targets(&module, self.target);
+        check_public_values_defined_for_target(&module, self.target, &mut self.problems);
+        env.supported_targets = vec![self.target, self.package_config.target];
+        env.warn_on_shadowing = self.package_config.warnings.shadowing;
+        env.minimum_otp_version = self.package_config.erlang.minimum_otp_version;
+        env.javascript_int_representation = self.package_config.javascript.int_representation;
+
         let statements = GroupedStatements::new(module.into_iter_statements(self.target));
         let statements_count = statements.len();
 
@@ -300,9 +307,13 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             module_values: values,
             accessors,
             names: type_names,
+            mut inferred_annotations,
+            mut references,
             ..
         } = env;
 
+        inferred_annotations.sort_by_key(|annotation| annotation.location.start);
+
         let is_internal = self
             .package_config
             .is_internal_module(self.module_name.as_str());
@@ -318,6 +329,14 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             self.warnings.emit(warning.clone());
         }
 
+        let function_references = crate::dead_code::function_references(&typed_statements);
+
+        // Type annotations can be hydrated more than once, so any duplicate
+        // references are removed.
+        references.extend(crate::reference::value_references(&typed_statements));
+        references.sort_by_key(|reference| reference.location.start);
+        references.dedup_by_key(|reference| reference.location);
+
         let module = ast::Module {
             documentation,
             name: self.module_name.clone(),
@@ -335,6 +354,9 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
                 src_path: self.src_path,
                 warnings,
                 minimum_required_version: self.minimum_required_version,
+                inferred_annotations,
+                function_references,
+                references,
             },
             names: type_names,
         };
@@ -372,6 +394,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             has_erlang_external: false,
             has_javascript_external: false,
         };
+        environment.current_constant = Some(name.clone());
         let mut expr_typer = ExprTyper::new(environment, definition, &mut self.problems);
         let typed_expr = expr_typer.infer_const(&annotation, *value);
         let type_ = typed_expr.type_();
@@ -381,6 +404,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         if minimum_required_version > self.minimum_required_version {
             self.minimum_required_version = minimum_required_version;
         }
+        environment.current_constant = None;
 
         match publicity {
             Publicity::Private
@@ -476,7 +500,18 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
 
         // Ensure that folks are not writing inline JavaScript expressions as
         // the implementation for JS externals.
-        self.assert_valid_javascript_external(&name, external_javascript.as_ref(), location);
+        self.assert_valid_javascript_external(
+            &name,
+            external_javascript.as_ref(),
+            arguments.len(),
+            location,
+        );
+        self.assert_valid_erlang_external(
+            &name,
+            external_erlang.as_ref(),
+            arguments.len(),
+            location,
+        );
 
         // Find the external implementation for the current target, if one has been given.
         let external =
@@ -489,6 +524,14 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             &external_javascript,
             location,
         );
+        if has_implementation && publicity.is_importable() {
+            self.warn_if_external_is_missing_for_supported_target(
+                &body,
+                &external_erlang,
+                &external_javascript,
+                location,
+            );
+        }
 
         if external.is_some() {
             // There was an external implementation, so type annotations are
@@ -526,6 +569,12 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             )?;
             let args_types = args.iter().map(|a| a.type_.clone()).collect();
             let type_ = fn_(args_types, body.last().type_());
+            if expr_typer.previous_panics {
+                let _ = expr_typer
+                    .environment
+                    .diverging_functions
+                    .insert(name.clone());
+            }
             Ok((
                 type_,
                 body,
@@ -652,6 +701,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         &mut self,
         function_name: &EcoString,
         external_javascript: Option<&(EcoString, EcoString, SrcSpan)>,
+        arity: usize,
         location: SrcSpan,
     ) {
         use regex::Regex;
@@ -663,6 +713,9 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             None => return,
             Some((module, function, _location)) => (module, function),
         };
+        if self.assert_external_arity(Target::JavaScript, function, arity, location) {
+            return;
+        }
         if !MODULE
             .get_or_init(|| Regex::new("^[@a-zA-Z0-9\\./:_-]+$").expect("regex"))
             .is_match(module)
@@ -686,6 +739,110 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
         }
     }
 
+    fn assert_valid_erlang_external(
+        &mut self,
+        function_name: &EcoString,
+        external_erlang: Option<&(EcoString, EcoString, SrcSpan)>,
+        arity: usize,
+        location: SrcSpan,
+    ) {
+        let (module, function) = match external_erlang {
+            None => return,
+            Some((module, function, _location)) => (module, function),
+        };
+        if self.assert_external_arity(Target::Erlang, function, arity, location) {
+            return;
+        }
+
+        // Any atom can be used as the name of an Erlang module or function,
+        // but an empty name or one with spaces or slashes is almost certainly
+        // a mistake.
+        let is_valid_atom = |name: &EcoString| {
+            !name.is_empty()
+                && !name
+                    .chars()
+                    .any(|char| char.is_whitespace() || char.is_control() || char == '/')
+        };
+        if !is_valid_atom(module) {
+            self.problems.warning(Warning::InvalidExternalErlangModule {
+                location,
+                module: module.clone(),
+                name: function_name.clone(),
+            });
+        }
+        if !is_valid_atom(function) {
+            self.problems
+                .warning(Warning::InvalidExternalErlangFunction {
+                    location,
+                    function: function.clone(),
+                    name: function_name.clone(),
+                });
+        }
+    }
+
+    /// Externals are referenced by name only, but folks used to Erlang might
+    /// write `function/2` instead. If the arity they wrote doesn't match the
+    /// number of arguments of the Gleam function we report it, as that's
+    /// more helpful than just saying the name is not valid.
+    ///
+    /// Returns `true` if an error was reported.
+    ///
+    fn assert_external_arity(
+        &mut self,
+        target: Target,
+        function: &EcoString,
+        arity: usize,
+        location: SrcSpan,
+    ) -> bool {
+        let Some((_, external_arity)) = function.rsplit_once('/') else {
+            return false;
+        };
+        let Ok(external_arity) = external_arity.parse::<usize>() else {
+            return false;
+        };
+        if external_arity == arity {
+            return false;
+        }
+        self.problems.error(Error::ExternalArityMismatch {
+            location,
+            target,
+            function: function.clone(),
+            external_arity,
+            arity,
+        });
+        true
+    }
+
+    /// If the package is used on both targets then a public function that
+    /// only has an external implementation for one of them, and no Gleam
+    /// implementation to fall back to, can't be used on the other one.
+    ///
+    /// A package is used on both targets when it is compiled for a target
+    /// other than the one set in its `gleam.toml`.
+    ///
+    fn warn_if_external_is_missing_for_supported_target(
+        &mut self,
+        body: &Vec1<UntypedStatement>,
+        external_erlang: &Option<(EcoString, EcoString, SrcSpan)>,
+        external_javascript: &Option<(EcoString, EcoString, SrcSpan)>,
+        location: SrcSpan,
+    ) {
+        if !body.first().is_placeholder() || self.package_config.target == self.target {
+            return;
+        }
+
+        let missing_target = match (external_erlang, external_javascript) {
+            (Some(_), None) => Target::JavaScript,
+            (None, Some(_)) => Target::Erlang,
+            (None, None) | (Some(_), Some(_)) => return,
+        };
+        self.problems
+            .warning(Warning::MissingExternalForSupportedTarget {
+                location,
+                missing_target,
+            });
+    }
+
     fn ensure_annotations_present(
         &mut self,
         arguments: &[UntypedArg],
@@ -808,6 +965,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             parameters,
             constructors,
             deprecation,
+            phantom,
             ..
         } = t;
 
@@ -886,6 +1044,33 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             .parameters
             .clone();
 
+        if phantom {
+            self.track_feature_usage(FeatureKind::PhantomAnnotation, location);
+        }
+
+        // A type parameter that none of the constructors use is most likely a
+        // mistake, unless the type is explicitly marked as `@phantom`. External
+        // types have no constructors, so all their parameters are phantom.
+        if !phantom && !constructors.is_empty() {
+            for ((parameter_location, parameter_name), parameter) in
+                parameters.iter().zip(&typed_parameters)
+            {
+                let is_used = parameter.type_variable_id().is_none_or(|id| {
+                    constructors
+                        .iter()
+                        .flat_map(|constructor| &constructor.arguments)
+                        .any(|argument| argument.type_.contains_type_variable(id))
+                });
+                if !is_used {
+                    self.problems.warning(Warning::UnusedTypeParameter {
+                        location: *parameter_location,
+                        name: parameter_name.clone(),
+                        type_name: name.clone(),
+                    });
+                }
+            }
+        }
+
         // Check if all constructors are deprecated if so error.
         if !constructors.is_empty()
             && constructors
@@ -922,6 +1107,7 @@ impl<'a, A> ModuleAnalyzer<'a, A> {
             end_position,
             publicity,
             opaque,
+            phantom,
             name,
             name_location,
             parameters,
@@ -1458,6 +1644,84 @@ fn validate_module_name(name: &EcoString) -> Result<(), Error> {
     Ok(())
 }
 
+/// Collects the functions and constants that are only defined for a target
+/// other than the one being compiled for.
+fn values_for_other_targets(module: &UntypedModule, target: Target) -> HashMap<EcoString, Target> {
+    module
+        .definitions
+        .iter()
+        .filter_map(|definition| {
+            let definition_target = definition.target.filter(|t| *t != target)?;
+            let name = match &definition.definition {
+                Definition::Function(Function {
+                    name: Some((_, name)),
+                    ..
+                }) => name,
+                Definition::ModuleConstant(ModuleConstant { name, .. }) => name,
+                Definition::Function(_)
+                | Definition::TypeAlias(_)
+                | Definition::CustomType(_)
+                | Definition::Import(_) => return None,
+            };
+            Some((name.clone(), definition_target))
+        })
+        .collect()
+}
+
+/// Warns about public functions and constants that are only defined for a
+/// target other than the one being compiled for, as the module would then
+/// have a different public API depending on the target.
+fn check_public_values_defined_for_target(
+    module: &UntypedModule,
+    target: Target,
+    problems: &mut Problems,
+) {
+    let public_value = |definition: &Definition<_, _, _, _>| match definition {
+        Definition::Function(Function {
+            name: Some((_, name)),
+            publicity,
+            location,
+            ..
+        }) => Some((name.clone(), *publicity, *location)),
+        Definition::ModuleConstant(ModuleConstant {
+            name,
+            publicity,
+            location,
+            ..
+        }) => Some((name.clone(), *publicity, *location)),
+        Definition::Function(_)
+        | Definition::TypeAlias(_)
+        | Definition::CustomType(_)
+        | Definition::Import(_) => None,
+    };
+
+    let defined_for_target: HashSet<EcoString> = module
+        .definitions
+        .iter()
+        .filter(|definition| definition.is_for(target))
+        .filter_map(|definition| public_value(&definition.definition))
+        .map(|(name, _, _)| name)
+        .collect();
+
+    for definition in &module.definitions {
+        let Some(defined_for) = definition.target.filter(|t| *t != target) else {
+            continue;
+        };
+        let Some((name, publicity, location)) = public_value(&definition.definition) else {
+            continue;
+        };
+        if publicity.is_private() || defined_for_target.contains(&name) {
+            continue;
+        }
+        problems.warning(Warning::PublicValueNotDefinedForTarget {
+            location,
+            name,
+            target,
+            defined_for,
+        });
+    }
+}
+
 fn target_function_implementation<'a>(
     target: Target,
     external_erlang: &'a Option<(EcoString, EcoString, SrcSpan)>,
@@ -1881,15 +2145,21 @@ fn sorted_type_aliases(aliases: &Vec<UntypedTypeAlias>) -> Result<Vec<&UntypedTy
     let sorted_deps = dep_tree::toposort_deps(deps).map_err(|err| {
         let dep_tree::Error::Cycle(cycle) = err;
 
-        let last = cycle.last().expect("Cycle should not be empty");
-        let alias = aliases
-            .iter()
-            .find(|alias| alias.alias == *last)
-            .expect("Could not find alias for cycle");
+        let cycle = cycle
+            .into_iter()
+            .map(|name| {
+                let alias = aliases
+                    .iter()
+                    .find(|alias| alias.alias == name)
+                    .expect("Could not find alias for cycle");
+                (name, alias.location)
+            })
+            .collect_vec();
 
+        let (_, location) = cycle.first().expect("Cycle should not be empty");
         Error::RecursiveTypeAlias {
+            location: *location,
             cycle,
-            location: alias.location,
         }
     })?;
 
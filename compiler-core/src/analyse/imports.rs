@@ -4,6 +4,7 @@ use crate::{
     ast::{SrcSpan, UnqualifiedImport, UntypedImport},
     build::Origin,
     type_::{
+        error::{unknown_module_value_error, ModuleValueUsageContext},
         EntityKind, Environment, Error, ModuleInterface, Problems, UnusedModuleAlias,
         ValueConstructorVariant,
     },
@@ -146,14 +147,12 @@ impl<'context, 'problems> Importer<'context, 'problems> {
                 &value.variant
             }
             None => {
-                self.problems.error(Error::UnknownModuleValue {
+                self.problems.error(unknown_module_value_error(
+                    module,
+                    import_name,
                     location,
-                    name: import_name.clone(),
-                    module_name: module.name.clone(),
-                    value_constructors: module.public_value_names(),
-                    type_with_same_name: module.get_public_type(import_name).is_some(),
-                    context: crate::type_::error::ModuleValueUsageContext::UnqualifiedImport,
-                });
+                    ModuleValueUsageContext::UnqualifiedImport,
+                ));
                 return;
             }
         };
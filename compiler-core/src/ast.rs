@@ -757,6 +757,9 @@ pub struct CustomType<T> {
     pub documentation: Option<(u32, EcoString)>,
     pub deprecation: Deprecation,
     pub opaque: bool,
+    /// Whether the type is annotated with `@phantom`, meaning that any type
+    /// parameters not used by its constructors are intentional.
+    pub phantom: bool,
     /// The names of the type parameters.
     pub parameters: Vec<SpannedString>,
     /// Once type checked this field will contain the type information for the
@@ -172,6 +172,7 @@ impl UntypedExpr {
         match self {
             Self::Block { location, .. } => location.start,
             Self::PipeLine { expressions, .. } => expressions.first().start_byte_index(),
+            Self::BinOp { left, .. } => left.start_byte_index(),
             _ => self.location().start,
         }
     }
@@ -27,6 +27,41 @@ where
     type_options(input_options, false, must_have_size)
 }
 
+/// 16 bit float segments are only supported by the Erlang compiler starting
+/// from OTP 24, so they can't be used by packages supporting older releases.
+pub fn check_otp_support<TypedValue>(
+    input_options: &[BitArrayOption<TypedValue>],
+    minimum_otp_version: u32,
+) -> Result<(), Error>
+where
+    TypedValue: GetLiteralValue,
+{
+    if minimum_otp_version >= SIXTEEN_BIT_FLOAT_OTP_VERSION {
+        return Ok(());
+    }
+    let is_float = input_options
+        .iter()
+        .any(|option| matches!(option, BitArrayOption::Float { .. }));
+    let sixteen_bit_size = input_options.iter().find_map(|option| match option {
+        BitArrayOption::Size {
+            value, location, ..
+        } if value.as_int_literal() == Some(16) => Some(*location),
+        _ => None,
+    });
+    match sixteen_bit_size {
+        Some(location) if is_float => err(
+            ErrorType::FloatSizeRequiresNewerOtp {
+                minimum_otp_version,
+                required_otp_version: SIXTEEN_BIT_FLOAT_OTP_VERSION,
+            },
+            location,
+        ),
+        _ => Ok(()),
+    }
+}
+
+const SIXTEEN_BIT_FLOAT_OTP_VERSION: u32 = 24;
+
 struct SegmentOptionCategories<'a, T> {
     type_: Option<&'a BitArrayOption<T>>,
     signed: Option<&'a BitArrayOption<T>>,
@@ -321,18 +356,34 @@ pub struct Error {
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum ErrorType {
-    ConflictingEndiannessOptions { existing_endianness: EcoString },
-    ConflictingSignednessOptions { existing_signed: EcoString },
+    ConflictingEndiannessOptions {
+        existing_endianness: EcoString,
+    },
+    ConflictingSignednessOptions {
+        existing_signed: EcoString,
+    },
     ConflictingSizeOptions,
-    ConflictingTypeOptions { existing_type: EcoString },
+    ConflictingTypeOptions {
+        existing_type: EcoString,
+    },
     ConflictingUnitOptions,
     FloatWithSize,
+    FloatSizeRequiresNewerOtp {
+        minimum_otp_version: u32,
+        required_otp_version: u32,
+    },
     InvalidEndianness,
     OptionNotAllowedInValue,
     SegmentMustHaveSize,
-    SignednessUsedOnNonInt { type_: EcoString },
-    TypeDoesNotAllowSize { type_: EcoString },
-    TypeDoesNotAllowUnit { type_: EcoString },
+    SignednessUsedOnNonInt {
+        type_: EcoString,
+    },
+    TypeDoesNotAllowSize {
+        type_: EcoString,
+    },
+    TypeDoesNotAllowUnit {
+        type_: EcoString,
+    },
     UnitMustHaveSize,
     VariableUtfSegmentInPattern,
 }
@@ -1,5 +1,7 @@
 #![allow(warnings)]
 
+mod beam_manifest;
+mod doctests;
 mod elixir_libraries;
 mod module_loader;
 mod native_file_copier;
@@ -138,11 +140,86 @@ impl Default for Runtime {
     }
 }
 
+/// The module system used by the generated JavaScript.
+#[derive(
+    Debug,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    EnumVariantNames,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+)]
+pub enum ModuleFormat {
+    #[default]
+    #[strum(serialize = "esm")]
+    #[serde(rename = "esm")]
+    Esm,
+    #[strum(serialize = "commonjs")]
+    #[serde(rename = "commonjs")]
+    CommonJs,
+}
+
+impl ModuleFormat {
+    /// The extension of the generated JavaScript modules.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Esm => "mjs",
+            Self::CommonJs => "cjs",
+        }
+    }
+
+    /// The extension of the generated TypeScript declaration files.
+    pub fn declaration_extension(&self) -> &'static str {
+        match self {
+            Self::Esm => "d.mts",
+            Self::CommonJs => "d.cts",
+        }
+    }
+}
+
+/// How the `Int` type is represented in the generated JavaScript.
+#[derive(
+    Debug,
+    Serialize,
+    Deserialize,
+    Display,
+    EnumString,
+    EnumVariantNames,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+)]
+pub enum IntRepresentation {
+    /// JavaScript numbers, which are only precise between `-(2^53 - 1)` and
+    /// `2^53 - 1`.
+    #[default]
+    #[strum(serialize = "number")]
+    #[serde(rename = "number")]
+    Number,
+    /// JavaScript BigInts, which are arbitrary precision like Erlang's ints.
+    #[strum(serialize = "bigint")]
+    #[serde(rename = "bigint")]
+    BigInt,
+}
+
 #[derive(Debug)]
 pub enum TargetCodegenConfiguration {
     JavaScript {
         emit_typescript_definitions: bool,
         prelude_location: Utf8PathBuf,
+        module_format: ModuleFormat,
+        int_representation: IntRepresentation,
+        /// The representation of ints the package's FFI modules are written
+        /// for, which is always numbers for dependencies.
+        ffi_int_representation: IntRepresentation,
+        freeze_values: bool,
     },
     Erlang {
         app_file: Option<ErlangAppCodegenConfiguration>,
@@ -252,7 +329,7 @@ impl Module {
     }
 
     pub fn is_test(&self) -> bool {
-        self.origin == Origin::Test
+        matches!(self.origin, Origin::Test | Origin::Doctest)
     }
 
     pub fn find_node(&self, byte_index: u32) -> Option<Located<'_>> {
@@ -417,6 +494,9 @@ pub fn type_constructor_from_modules(
 pub enum Origin {
     Src,
     Test,
+    /// A test module generated from a code block in the documentation of a
+    /// `Src` module.
+    Doctest,
 }
 
 impl Origin {
@@ -459,12 +539,22 @@ fn doc_comments_before<'a>(
     (comment_start, comments)
 }
 
-#[derive(Debug, PartialEq, Clone, Serialize, Deserialize)]
+#[derive(Debug, PartialEq, Eq, Clone, Serialize, Deserialize)]
 pub(crate) struct SourceFingerprint(u64);
 
 impl SourceFingerprint {
     pub(crate) fn new(source: &str) -> Self {
-        SourceFingerprint(xxhash_rust::xxh3::xxh3_64(source.as_bytes()))
+        Self::from_bytes(source.as_bytes())
+    }
+
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Self {
+        SourceFingerprint(xxhash_rust::xxh3::xxh3_64(bytes))
+    }
+}
+
+impl std::fmt::LowerHex for SourceFingerprint {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::LowerHex::fmt(&self.0, f)
     }
 }
 
@@ -0,0 +1,103 @@
+//! A manifest of the compiled BEAM modules of every package in a build.
+//!
+//! Hot code reloading tools and release handlers can compare the manifests of
+//! two builds to find exactly which modules were added, removed or changed,
+//! without having to know how Gleam lays out the build directory.
+//!
+//! The manifest is written as Erlang terms, one map per module, so it can be
+//! read with `file:consult/1`:
+//!
+//! ```erlang
+//! #{module => 'wibble', package => <<"app">>, beam => <<"app/ebin/wibble.beam">>, fingerprint => <<"...">>}.
+//! ```
+
+use camino::Utf8Path;
+use ecow::{eco_format, EcoString};
+use itertools::Itertools;
+
+use crate::{
+    build::SourceFingerprint,
+    error::{FileIoAction, FileKind},
+    io::FileSystemReader,
+    Error, Result,
+};
+
+pub const BEAM_MANIFEST_FILE_NAME: &str = "beam_manifest.eterm";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BeamModule {
+    pub name: EcoString,
+    pub package: EcoString,
+    /// The path of the `.beam` file, relative to the build directory of the
+    /// target. Forward slashes are used on all platforms.
+    pub beam: EcoString,
+    /// The fingerprint of the contents of the `.beam` file, which changes
+    /// whenever the module is compiled differently.
+    pub fingerprint: SourceFingerprint,
+}
+
+/// Finds the `.beam` files in the `ebin` directory of each of the given
+/// packages, sorted by package and module name.
+pub fn beam_modules<'a>(
+    io: &impl FileSystemReader,
+    build_directory: &Utf8Path,
+    packages: impl IntoIterator<Item = &'a str>,
+) -> Result<Vec<BeamModule>> {
+    let mut modules = vec![];
+    for package in packages {
+        let ebin = build_directory.join(package).join("ebin");
+        if !io.is_directory(&ebin) {
+            continue;
+        }
+        for entry in io.read_dir(&ebin)? {
+            let path = entry
+                .map_err(|_| Error::FileIo {
+                    kind: FileKind::Directory,
+                    action: FileIoAction::Read,
+                    path: ebin.clone(),
+                    err: None,
+                })?
+                .into_path();
+            if path.extension() != Some("beam") {
+                continue;
+            }
+            let Some(name) = path.file_stem() else {
+                continue;
+            };
+            let fingerprint = SourceFingerprint::from_bytes(&io.read_bytes(&path)?);
+            modules.push(BeamModule {
+                name: name.into(),
+                package: package.into(),
+                beam: eco_format!("{package}/ebin/{name}.beam"),
+                fingerprint,
+            });
+        }
+    }
+    modules.sort_by(|one, other| (&one.package, &one.name).cmp(&(&other.package, &other.name)));
+    Ok(modules)
+}
+
+pub fn render(modules: &[BeamModule]) -> String {
+    let mut manifest =
+        String::from("%% Generated by the Gleam build tool. Read with `file:consult/1`.\n");
+    for module in modules {
+        manifest.push_str(&format!(
+            "#{{module => '{}', package => <<\"{}\">>, beam => <<\"{}\">>, fingerprint => <<\"{:016x}\">>}}.\n",
+            escape(&module.name, '\''),
+            escape(&module.package, '"'),
+            escape(&module.beam, '"'),
+            module.fingerprint,
+        ));
+    }
+    manifest
+}
+
+fn escape(text: &str, quote: char) -> String {
+    text.chars()
+        .map(|char| match char {
+            '\\' => "\\\\".into(),
+            char if char == quote => format!("\\{char}"),
+            char => char.to_string(),
+        })
+        .join("")
+}
@@ -0,0 +1,136 @@
+//! Doctests are the Gleam code blocks in the documentation of a module,
+//! compiled and run as tests so the examples don't fall out of date.
+//!
+//! Each code block becomes a test module of its own. Any imports in the block
+//! are moved to the top of the module and the rest of it becomes the body of
+//! a test function. Blocks that define functions, types or constants are kept
+//! as they are, so they are only compiled.
+
+#[cfg(test)]
+mod tests;
+
+use ecow::{eco_format, EcoString};
+
+/// The first segment of the names of the generated test modules. Module names
+/// can't start with an underscore, so these never collide with the modules of
+/// the package.
+pub const DOCTEST_MODULE_PREFIX: &str = "_doctest";
+
+/// The name of the test function of a doctest module.
+pub const DOCTEST_FUNCTION_NAME: &str = "doctest_test";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Doctest {
+    /// The name of the generated test module.
+    pub name: EcoString,
+    /// The line of the documented module the code block starts on.
+    pub line: u32,
+    pub code: EcoString,
+}
+
+/// The doctests of a module, one for each of the code blocks marked as Gleam
+/// in its documentation comments.
+pub fn doctests(module: &str, code: &str) -> Vec<Doctest> {
+    let mut doctests = vec![];
+    let mut block: Option<(u32, Vec<&str>)> = None;
+    for (index, line) in code.lines().enumerate() {
+        let line_number = index as u32 + 1;
+        let Some(comment) = documentation_line(line) else {
+            // A code block can't continue past the end of the comment.
+            block = None;
+            continue;
+        };
+        let fence = comment.trim_start().strip_prefix("```");
+        match (&mut block, fence) {
+            (None, Some(language)) if language.trim() == "gleam" => {
+                block = Some((line_number, vec![]));
+            }
+            (Some((start, lines)), Some(_)) => {
+                if let Some(code) = doctest_module(module, lines) {
+                    doctests.push(Doctest {
+                        name: eco_format!("{DOCTEST_MODULE_PREFIX}/{module}_{start}"),
+                        line: *start,
+                        code,
+                    });
+                }
+                block = None;
+            }
+            (Some((_, lines)), None) => lines.push(comment),
+            (None, _) => (),
+        }
+    }
+    doctests
+}
+
+/// The text of a line of a documentation comment, without the slashes and
+/// the space that follows them.
+fn documentation_line(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    let text = line
+        .strip_prefix("////")
+        .or_else(|| line.strip_prefix("///"))?;
+    Some(text.strip_prefix(' ').unwrap_or(text))
+}
+
+fn doctest_module(module: &str, lines: &[&str]) -> Option<EcoString> {
+    let (imports, body): (Vec<&str>, Vec<&str>) =
+        lines.iter().partition(|line| line.starts_with("import "));
+    if body.iter().all(|line| line.trim().is_empty()) && imports.is_empty() {
+        return None;
+    }
+
+    let mut code = EcoString::new();
+    // The documented module is imported if the example uses it without
+    // importing it itself.
+    let module_alias = module.rsplit('/').next().unwrap_or(module);
+    let imports_module = imports
+        .iter()
+        .any(|import| imported_module(import) == module);
+    if !imports_module && body.iter().any(|line| uses_module(line, module_alias)) {
+        code.push_str(&eco_format!("import {module}\n"));
+    }
+    for import in &imports {
+        code.push_str(import);
+        code.push('\n');
+    }
+
+    let is_definitions = body.iter().any(|line| {
+        ["fn ", "pub ", "type ", "const ", "@"]
+            .iter()
+            .any(|keyword| line.starts_with(keyword))
+    });
+    if is_definitions {
+        for line in body {
+            code.push_str(line);
+            code.push('\n');
+        }
+    } else if body.iter().any(|line| !line.trim().is_empty()) {
+        code.push_str(&eco_format!("\npub fn {DOCTEST_FUNCTION_NAME}() {{\n"));
+        for line in body {
+            code.push_str(line);
+            code.push('\n');
+        }
+        code.push_str("}\n");
+    }
+    Some(code)
+}
+
+/// The name of the module an import imports.
+fn imported_module(import: &str) -> &str {
+    let path = import.trim_start_matches("import ").trim_start();
+    let end = path
+        .find(|char: char| !(char.is_ascii_alphanumeric() || char == '_' || char == '/'))
+        .unwrap_or(path.len());
+    path.get(..end).unwrap_or(path)
+}
+
+/// Whether a line of code refers to a module by the given name, as in
+/// `module.function`.
+fn uses_module(line: &str, alias: &str) -> bool {
+    line.match_indices(alias).any(|(index, _)| {
+        let before = line.get(..index).and_then(|before| before.chars().last());
+        let after = line.get(index + alias.len()..).unwrap_or_default();
+        after.starts_with('.')
+            && !before.is_some_and(|char| char.is_ascii_alphanumeric() || char == '_')
+    })
+}
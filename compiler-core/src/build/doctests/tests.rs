@@ -0,0 +1,148 @@
+use super::*;
+
+fn doctest_modules(code: &str) -> Vec<(EcoString, u32, EcoString)> {
+    doctests("app/wibble", code)
+        .into_iter()
+        .map(|doctest| (doctest.name, doctest.line, doctest.code))
+        .collect()
+}
+
+#[test]
+fn code_block_becomes_test_function() {
+    let code = r#"/// Adds one.
+///
+/// ```gleam
+/// import gleam/int
+/// wibble.add_one(1)
+/// |> int.to_string
+/// // -> "2"
+/// ```
+pub fn add_one(x: Int) -> Int { x + 1 }
+"#;
+    assert_eq!(
+        doctest_modules(code),
+        vec![(
+            "_doctest/app/wibble_3".into(),
+            3,
+            r#"import app/wibble
+import gleam/int
+
+pub fn doctest_test() {
+wibble.add_one(1)
+|> int.to_string
+// -> "2"
+}
+"#
+            .into()
+        )]
+    );
+}
+
+#[test]
+fn module_is_not_imported_twice() {
+    let code = "/// ```gleam
+/// import app/wibble.{add_one}
+/// wibble.add_one(add_one(1))
+/// ```
+";
+    assert_eq!(
+        doctest_modules(code),
+        vec![(
+            "_doctest/app/wibble_1".into(),
+            1,
+            "import app/wibble.{add_one}
+
+pub fn doctest_test() {
+wibble.add_one(add_one(1))
+}
+"
+            .into()
+        )]
+    );
+}
+
+#[test]
+fn module_is_not_imported_if_unused() {
+    let code = "//// ```gleam
+//// let wobble = #(1)
+//// wobble.0
+//// ```
+";
+    assert_eq!(
+        doctest_modules(code),
+        vec![(
+            "_doctest/app/wibble_1".into(),
+            1,
+            "
+pub fn doctest_test() {
+let wobble = #(1)
+wobble.0
+}
+"
+            .into()
+        )]
+    );
+}
+
+#[test]
+fn definitions_are_not_wrapped_in_a_function() {
+    let code = "/// ```gleam
+/// pub fn main() {
+///   wibble.add_one(1)
+/// }
+/// ```
+";
+    assert_eq!(
+        doctest_modules(code),
+        vec![(
+            "_doctest/app/wibble_1".into(),
+            1,
+            "import app/wibble
+pub fn main() {
+  wibble.add_one(1)
+}
+"
+            .into()
+        )]
+    );
+}
+
+#[test]
+fn only_gleam_code_blocks_are_doctests() {
+    let code = "/// ```
+/// wibble.add_one(1)
+/// ```
+///
+/// ```erlang
+/// wibble:add_one(1).
+/// ```
+///
+/// ```gleam
+/// wibble.add_one(1)
+/// ```
+";
+    let names = doctest_modules(code)
+        .into_iter()
+        .map(|(name, _, _)| name)
+        .collect::<Vec<_>>();
+    assert_eq!(names, vec![EcoString::from("_doctest/app/wibble_9")]);
+}
+
+#[test]
+fn unclosed_code_block_is_not_a_doctest() {
+    let code = "/// ```gleam
+/// wibble.add_one(1)
+pub fn add_one(x: Int) -> Int { x + 1 }
+/// ```
+";
+    assert!(doctest_modules(code).is_empty());
+}
+
+#[test]
+fn empty_code_block_is_not_a_doctest() {
+    let code = "/// ```gleam
+///
+/// ```
+";
+    assert!(doctest_modules(code).is_empty());
+}
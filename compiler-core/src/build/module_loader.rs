@@ -154,7 +154,28 @@ where
     IO: FileSystemReader + FileSystemWriter + CommandExecutor + Clone,
 {
     let code: EcoString = io.read(&path)?.into();
+    parse_source(
+        target,
+        origin,
+        path,
+        name,
+        package_name,
+        code,
+        mtime,
+        emitter,
+    )
+}
 
+pub(crate) fn parse_source(
+    target: Target,
+    origin: Origin,
+    path: Utf8PathBuf,
+    name: EcoString,
+    package_name: EcoString,
+    code: EcoString,
+    mtime: SystemTime,
+    emitter: WarningEmitter,
+) -> Result<UncompiledModule> {
     let parsed = crate::parse::parse_module(path.clone(), &code, &emitter).map_err(|error| {
         Error::Parse {
             path: path.clone(),
@@ -95,7 +95,10 @@ where
         }
 
         // Skip unknown file formats that are not supported native files
-        if !matches!(extension, "mjs" | "js" | "ts" | "hrl" | "erl" | "ex") {
+        if !matches!(
+            extension,
+            "mjs" | "cjs" | "js" | "ts" | "hrl" | "erl" | "ex"
+        ) {
             return Ok(());
         }
 
@@ -7,12 +7,16 @@ use crate::{
         elixir_libraries::ElixirLibraries,
         native_file_copier::NativeFileCopier,
         package_loader::{CodegenRequired, PackageLoader, StaleTracker},
-        Mode, Module, Origin, Outcome, Package, SourceFingerprint, Target,
+        IntRepresentation, Mode, Module, ModuleFormat, Origin, Outcome, Package, SourceFingerprint,
+        Target,
     },
     codegen::{Erlang, ErlangApp, JavaScript, TypeScriptDeclarations},
     config::PackageConfig,
     dep_tree, error,
-    io::{BeamCompiler, CommandExecutor, FileSystemReader, FileSystemWriter, Stdio},
+    io::{
+        BeamCompiler, CommandExecutor, ErlangCompilerDiagnostic, FileSystemReader,
+        FileSystemWriter, Stdio,
+    },
     metadata::ModuleEncoder,
     parse::extra::ModuleExtra,
     paths, type_,
@@ -22,6 +26,7 @@ use crate::{
 };
 use askama::Template;
 use ecow::EcoString;
+use itertools::Itertools;
 use std::collections::HashSet;
 use std::{collections::HashMap, fmt::write, time::SystemTime};
 use vec1::Vec1;
@@ -49,7 +54,12 @@ pub struct PackageCompiler<'a, IO> {
     /// entry point nonetheless.
     ///
     pub compile_modules: bool,
+    /// Whether this is the root package, whose entrypoints are written: the
+    /// Erlang entrypoint module, and the `package.json` when one is enabled.
     pub write_entrypoint: bool,
+    /// Whether test modules are generated from the code blocks in the
+    /// documentation of the package, which is only done for `gleam test`.
+    pub compile_doctests: bool,
     pub copy_native_files: bool,
     pub compile_beam_bytecode: bool,
     pub subprocess_stdio: Stdio,
@@ -84,6 +94,7 @@ where
             perform_codegen: true,
             compile_modules: true,
             write_entrypoint: false,
+            compile_doctests: false,
             copy_native_files: true,
             compile_beam_bytecode: true,
             subprocess_stdio: Stdio::Inherit,
@@ -134,6 +145,7 @@ where
             stale_modules,
             already_defined_modules,
             incomplete_modules,
+            self.compile_doctests,
         );
 
         let loaded = if self.compile_modules {
@@ -145,6 +157,16 @@ where
             Loaded::empty()
         };
 
+        // The cached modules are not compiled again, but are still part of
+        // the package, so they are remembered for the files that list every
+        // module.
+        let cached_modules = loaded
+            .cached
+            .iter()
+            .filter(|module| module.origin == Origin::Src)
+            .map(|module| module.name.clone())
+            .collect_vec();
+
         // Load the cached modules that have previously been compiled
         for module in loaded.cached.into_iter() {
             // Emit any cached warnings.
@@ -182,14 +204,23 @@ where
             incomplete_modules,
         );
 
-        let modules = match outcome {
+        let mut modules = match outcome {
             Outcome::Ok(modules) => modules,
             Outcome::PartialFailure(_, _) | Outcome::TotalFailure(_) => return outcome,
         };
 
+        // The module documentation is included in the generated Erlang.
+        if self.perform_codegen && self.target.target() == Target::Erlang {
+            for module in &mut modules {
+                module.attach_doc_and_module_comments();
+            }
+        }
+
         tracing::debug!("performing_code_generation");
 
-        if let Err(error) = self.perform_codegen(&modules) {
+        if let Err(error) =
+            self.perform_codegen(&modules, &cached_modules, existing_modules, warnings)
+        {
             return error.into();
         }
 
@@ -200,7 +231,12 @@ where
         Outcome::Ok(modules)
     }
 
-    fn compile_erlang_to_beam(&mut self, modules: &HashSet<Utf8PathBuf>) -> Result<(), Error> {
+    fn compile_erlang_to_beam(
+        &mut self,
+        modules: &HashSet<Utf8PathBuf>,
+        gleam_modules: &[Module],
+        warnings: &WarningEmitter,
+    ) -> Result<(), Error> {
         if modules.is_empty() {
             tracing::debug!("no_erlang_to_compile");
             return Ok(());
@@ -208,8 +244,28 @@ where
 
         tracing::debug!("compiling_erlang");
 
-        self.io
+        let erlang_warnings = self
+            .io
             .compile_beam(self.out, self.lib, modules, self.subprocess_stdio)
+            .map_err(|error| match error {
+                Error::ErlangCompilation { mut errors } => {
+                    for error in &mut errors {
+                        locate_in_gleam_modules(error, gleam_modules);
+                    }
+                    Error::ErlangCompilation { errors }
+                }
+                error => error,
+            })?;
+
+        // As with cached warnings, the warnings of dependency packages are not
+        // shown as the programmer cannot fix them directly.
+        if self.cached_warnings.should_use() {
+            for mut warning in erlang_warnings {
+                locate_in_gleam_modules(&mut warning, gleam_modules);
+                warnings.emit(Warning::ErlangCompiler { warning });
+            }
+        }
+        Ok(())
     }
 
     fn copy_project_native_files(
@@ -293,7 +349,13 @@ where
         Ok(())
     }
 
-    fn perform_codegen(&mut self, modules: &[Module]) -> Result<()> {
+    fn perform_codegen(
+        &mut self,
+        modules: &[Module],
+        cached_modules: &[EcoString],
+        module_interfaces: &im::HashMap<EcoString, type_::ModuleInterface>,
+        warnings: &WarningEmitter,
+    ) -> Result<()> {
         if !self.perform_codegen {
             tracing::debug!("skipping_codegen");
             return Ok(());
@@ -303,13 +365,22 @@ where
             TargetCodegenConfiguration::JavaScript {
                 emit_typescript_definitions,
                 prelude_location,
+                module_format,
+                int_representation,
+                ffi_int_representation,
+                freeze_values,
             } => self.perform_javascript_codegen(
                 modules,
+                cached_modules,
                 *emit_typescript_definitions,
                 prelude_location,
+                *module_format,
+                *int_representation,
+                *ffi_int_representation,
+                *freeze_values,
             ),
             TargetCodegenConfiguration::Erlang { app_file } => {
-                self.perform_erlang_codegen(modules, app_file.as_ref())
+                self.perform_erlang_codegen(modules, module_interfaces, app_file.as_ref(), warnings)
             }
         }
     }
@@ -317,7 +388,9 @@ where
     fn perform_erlang_codegen(
         &mut self,
         modules: &[Module],
+        module_interfaces: &im::HashMap<EcoString, type_::ModuleInterface>,
         app_file_config: Option<&ErlangAppCodegenConfiguration>,
+        warnings: &WarningEmitter,
     ) -> Result<(), Error> {
         let mut written = HashSet::new();
         let build_dir = self.out.join(paths::ARTEFACT_DIRECTORY_NAME);
@@ -333,6 +406,7 @@ where
         }
 
         if let Some(config) = app_file_config {
+            check_application_start_module(&self.config, module_interfaces)?;
             ErlangApp::new(&self.out.join("ebin"), config).render(
                 io.clone(),
                 &self.config,
@@ -350,11 +424,16 @@ where
         // we overwrite any precompiled Erlang that was included in the Hex
         // package. Otherwise we will build the potentially outdated precompiled
         // version and not the newly compiled version.
-        Erlang::new(&build_dir, &include_dir).render(io, modules)?;
+        Erlang::new(
+            &build_dir,
+            &include_dir,
+            self.config.erlang.minimum_otp_version,
+        )
+        .render(io, modules)?;
 
         if self.compile_beam_bytecode {
             written.extend(modules.iter().map(Module::compiled_erlang_path));
-            self.compile_erlang_to_beam(&written)?;
+            self.compile_erlang_to_beam(&written, modules, warnings)?;
         } else {
             tracing::debug!("skipping_erlang_bytecode_compilation");
         }
@@ -364,8 +443,13 @@ where
     fn perform_javascript_codegen(
         &mut self,
         modules: &[Module],
+        cached_modules: &[EcoString],
         typescript: bool,
         prelude_location: &Utf8Path,
+        module_format: ModuleFormat,
+        int_representation: IntRepresentation,
+        ffi_int_representation: IntRepresentation,
+        freeze_values: bool,
     ) -> Result<(), Error> {
         let mut written = HashSet::new();
         let typescript = if typescript {
@@ -374,8 +458,37 @@ where
             TypeScriptDeclarations::None
         };
 
-        JavaScript::new(&self.out, typescript, prelude_location, self.target_support)
-            .render(&self.io, modules)?;
+        let javascript = JavaScript::new(
+            &self.out,
+            typescript,
+            prelude_location,
+            self.target_support,
+            module_format,
+            int_representation,
+            ffi_int_representation,
+            freeze_values,
+        );
+        javascript.render(&self.io, modules)?;
+
+        // The package.json is written to the directory shared with the
+        // dependencies and the prelude, which the generated modules import, so
+        // the whole directory can be published.
+        if self.write_entrypoint && self.config.javascript.package_json {
+            let modules = modules
+                .iter()
+                .filter(|module| module.origin == Origin::Src)
+                .map(|module| module.name.clone())
+                .chain(cached_modules.iter().cloned())
+                .sorted()
+                .collect_vec();
+            let package_json = javascript.package_json(self.config, &modules);
+            let path = self.lib.join("package.json");
+            // Only write the file when it changes, as rewriting it can
+            // confuse watchers and HMR build tools
+            if self.io.read(&path).ok().as_ref() != Some(&package_json) {
+                self.io.write(&path, &package_json)?;
+            }
+        }
 
         if self.copy_native_files {
             self.copy_project_native_files(&self.out, &mut written)?;
@@ -677,3 +790,62 @@ impl CachedWarnings {
         }
     }
 }
+
+/// Applications are started by calling the `start/2` function of their start
+/// module, so if that module is a Gleam module it must define one that can run
+/// on Erlang. Start modules written in Erlang or Elixir are not checked.
+///
+/// The interfaces of both the newly compiled and the cached modules are
+/// checked, so the start module is checked even if it has not changed.
+fn check_application_start_module(
+    config: &PackageConfig,
+    module_interfaces: &im::HashMap<EcoString, type_::ModuleInterface>,
+) -> Result<()> {
+    let Some(start_module) = &config.erlang.application_start_module else {
+        return Ok(());
+    };
+    let Some(module) = module_interfaces
+        .get(start_module)
+        .filter(|module| module.package == config.name)
+    else {
+        return Ok(());
+    };
+    let has_start_function = module.get_public_value("start").is_some_and(|value| {
+        matches!(
+            &value.variant,
+            type_::ValueConstructorVariant::ModuleFn { arity: 2, implementations, .. }
+                if implementations.supports(Target::Erlang)
+        )
+    });
+    if has_start_function {
+        Ok(())
+    } else {
+        Err(Error::ApplicationStartModuleWithoutStartFunction {
+            module: start_module.clone(),
+        })
+    }
+}
+
+/// The generated Erlang for a Gleam module uses `-file` attributes to point
+/// back to the Gleam source, so any problem reported in it can be shown in the
+/// Gleam function it was generated from.
+pub fn locate_in_gleam_modules(diagnostic: &mut ErlangCompilerDiagnostic, modules: &[Module]) {
+    let Some(line) = diagnostic.line else {
+        return;
+    };
+    let Some(module) = modules
+        .iter()
+        .find(|module| module.ast.type_info.src_path == diagnostic.path)
+    else {
+        return;
+    };
+    let line_numbers = LineNumbers::new(&module.code);
+    let Ok(erlang) = crate::erlang::module(&module.ast, &line_numbers, None) else {
+        return;
+    };
+    if let Some(function) =
+        crate::erlang::function_at_reported_line(&module.ast, &line_numbers, &erlang, line)
+    {
+        diagnostic.locate_in_gleam_source(&module.code, function.location);
+    }
+}
@@ -16,7 +16,10 @@ use vec1::Vec1;
 
 use crate::{
     ast::SrcSpan,
-    build::{module_loader::ModuleLoader, package_compiler::module_name, Module, Origin},
+    build::{
+        doctests::doctests, module_loader::ModuleLoader, package_compiler::module_name, Module,
+        Origin,
+    },
     config::PackageConfig,
     dep_tree,
     error::{FileIoAction, FileKind, ImportCycleLocationDetails},
@@ -30,7 +33,7 @@ use crate::{
 };
 
 use super::{
-    module_loader::read_source,
+    module_loader::{parse_source, read_source},
     package_compiler::{
         CacheMetadata, CachedModule, CachedWarnings, Input, Loaded, UncompiledModule,
     },
@@ -68,6 +71,9 @@ pub struct PackageLoader<'a, IO> {
     already_defined_modules: &'a mut im::HashMap<EcoString, Utf8PathBuf>,
     incomplete_modules: &'a HashSet<EcoString>,
     cached_warnings: CachedWarnings,
+    /// Whether to generate test modules from the code blocks in the
+    /// documentation of the package's modules.
+    doctests: bool,
 }
 
 impl<'a, IO> PackageLoader<'a, IO>
@@ -88,6 +94,7 @@ where
         stale_modules: &'a mut StaleTracker,
         already_defined_modules: &'a mut im::HashMap<EcoString, Utf8PathBuf>,
         incomplete_modules: &'a HashSet<EcoString>,
+        doctests: bool,
     ) -> Self {
         Self {
             io,
@@ -103,6 +110,7 @@ where
             stale_modules,
             already_defined_modules,
             incomplete_modules,
+            doctests,
         }
     }
 
@@ -184,6 +192,9 @@ where
         let path = dir.join(name.as_ref()).with_extension("cache");
         let bytes = self.io.read_bytes(&path)?;
         let mut module = metadata::ModuleDecoder::new(self.ids.clone()).read(bytes.as_slice())?;
+        // The origin of a module is not part of its metadata, so we restore it
+        // from the cache info.
+        module.origin = info.origin;
 
         // Load warnings
         if self.cached_warnings.should_use() {
@@ -254,6 +265,12 @@ where
                 continue;
             }
 
+            if self.doctests {
+                for module in self.load_doctests(&src, &path)? {
+                    inputs.insert(Input::New(module))?;
+                }
+            }
+
             let input = loader.load(path)?;
             inputs.insert(input)?;
         }
@@ -290,6 +307,33 @@ where
         Ok(inputs.collection)
     }
 
+    /// The test modules generated from the documentation of a source module.
+    /// These are never cached, so they are always compiled again.
+    fn load_doctests(
+        &self,
+        source_directory: &Utf8Path,
+        path: &Utf8Path,
+    ) -> Result<Vec<UncompiledModule>> {
+        let name = module_name(source_directory, path);
+        let code = self.io.read(path)?;
+        let mtime = self.io.modification_time(path)?;
+        doctests(&name, &code)
+            .into_iter()
+            .map(|doctest| {
+                parse_source(
+                    self.target,
+                    Origin::Doctest,
+                    source_directory.join(format!("{}.gleam", doctest.name)),
+                    doctest.name,
+                    self.package_name.clone(),
+                    doctest.code,
+                    mtime,
+                    self.warnings.clone(),
+                )
+            })
+            .collect()
+    }
+
     fn load_stale_module(&self, cached: CachedModule) -> Result<UncompiledModule> {
         let mtime = self.io.modification_time(&cached.source_path)?;
 
@@ -329,8 +373,9 @@ where
                     .iter()
                     .enumerate()
                     .map(|(i, module)| {
-                        // cycles are in order of reference so get next in list or loop back to first
-                        let index_of_imported = if i == 0 { modules.len() - 1 } else { i - 1 };
+                        // Each module in the cycle imports the next one, and the last one
+                        // imports the first.
+                        let index_of_imported = (i + 1) % modules.len();
                         let imported_module = modules
                             .get(index_of_imported)
                             .expect("importing module must exist");
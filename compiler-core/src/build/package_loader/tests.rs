@@ -17,6 +17,7 @@ use std::time::Duration;
 struct LoaderTestOutput {
     to_compile: Vec<EcoString>,
     cached: Vec<EcoString>,
+    cached_origins: Vec<Origin>,
     warnings: Vec<Warning>,
 }
 
@@ -61,6 +62,9 @@ fn write_cache(
         src_path: Utf8PathBuf::from(format!("/src/{}.gleam", name)),
         warnings: vec![],
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
     let path = Utf8Path::new("/artefact").join(format!("{name}.cache"));
     fs.write_bytes(
@@ -71,6 +75,15 @@ fn write_cache(
 }
 
 fn run_loader(fs: InMemoryFileSystem, root: &Utf8Path, artefact: &Utf8Path) -> LoaderTestOutput {
+    run_loader_with_doctests(fs, root, artefact, false)
+}
+
+fn run_loader_with_doctests(
+    fs: InMemoryFileSystem,
+    root: &Utf8Path,
+    artefact: &Utf8Path,
+    doctests: bool,
+) -> LoaderTestOutput {
     let mut defined = im::HashMap::new();
     let ids = UniqueIdGenerator::new();
     let (emitter, warnings) = WarningEmitter::vector();
@@ -89,12 +102,14 @@ fn run_loader(fs: InMemoryFileSystem, root: &Utf8Path, artefact: &Utf8Path) -> L
         already_defined_modules: &mut defined,
         incomplete_modules: &mut HashSet::new(),
         cached_warnings: CachedWarnings::Ignore,
+        doctests,
     };
     let loaded = loader.run().unwrap();
 
     LoaderTestOutput {
         to_compile: loaded.to_compile.into_iter().map(|m| m.name).collect(),
-        cached: loaded.cached.into_iter().map(|m| m.name).collect(),
+        cached: loaded.cached.iter().map(|m| m.name.clone()).collect(),
+        cached_origins: loaded.cached.into_iter().map(|m| m.origin).collect(),
         warnings: warnings.take(),
     }
 }
@@ -172,6 +187,21 @@ fn reading_cache() {
     assert_eq!(loaded.cached, vec![EcoString::from("one")]);
 }
 
+#[test]
+fn reading_cache_of_test_module_keeps_its_origin() {
+    let fs = InMemoryFileSystem::new();
+    let root = Utf8Path::new("/");
+    let artefact = Utf8Path::new("/artefact");
+
+    write_src(&fs, "/test/one.gleam", 0, TEST_SOURCE_1);
+    write_cache(&fs, "one", 0, vec![], TEST_SOURCE_1);
+
+    let loaded = run_loader(fs, root, artefact);
+    assert!(loaded.to_compile.is_empty());
+    assert_eq!(loaded.cached, vec![EcoString::from("one")]);
+    assert_eq!(loaded.cached_origins, vec![Origin::Test]);
+}
+
 #[test]
 fn module_is_stale_if_cache_older() {
     let fs = InMemoryFileSystem::new();
@@ -357,3 +387,52 @@ fn invalid_nested_module_name_in_test() {
         }],
     );
 }
+
+const DOCUMENTED_SOURCE: &str = "/// ```gleam
+/// main.x
+/// ```
+pub const x = 1
+";
+
+#[test]
+fn doctests_are_loaded_with_their_module() {
+    let fs = InMemoryFileSystem::new();
+    let root = Utf8Path::new("/");
+    let artefact = Utf8Path::new("/artefact");
+
+    write_src(&fs, "/src/main.gleam", 0, DOCUMENTED_SOURCE);
+
+    let loaded = run_loader_with_doctests(fs, root, artefact, true);
+    assert_eq!(
+        loaded.to_compile,
+        vec![EcoString::from("main"), EcoString::from("_doctest/main_1")]
+    );
+    assert!(loaded.cached.is_empty());
+}
+
+#[test]
+fn doctests_are_not_cached() {
+    let fs = InMemoryFileSystem::new();
+    let root = Utf8Path::new("/");
+    let artefact = Utf8Path::new("/artefact");
+
+    write_src(&fs, "/src/main.gleam", 0, DOCUMENTED_SOURCE);
+    write_cache(&fs, "main", 0, vec![], DOCUMENTED_SOURCE);
+    write_cache(&fs, "_doctest@main_1", 0, vec![], "");
+
+    let loaded = run_loader_with_doctests(fs, root, artefact, true);
+    assert_eq!(loaded.to_compile, vec![EcoString::from("_doctest/main_1")]);
+    assert_eq!(loaded.cached, vec![EcoString::from("main")]);
+}
+
+#[test]
+fn doctests_are_only_loaded_when_enabled() {
+    let fs = InMemoryFileSystem::new();
+    let root = Utf8Path::new("/");
+    let artefact = Utf8Path::new("/artefact");
+
+    write_src(&fs, "/src/main.gleam", 0, DOCUMENTED_SOURCE);
+
+    let loaded = run_loader(fs, root, artefact);
+    assert_eq!(loaded.to_compile, vec![EcoString::from("main")]);
+}
@@ -1,11 +1,13 @@
 use crate::{
     analyse::TargetSupport,
     build::{
-        package_compiler, package_compiler::PackageCompiler, package_loader::StaleTracker,
-        project_compiler, telemetry::Telemetry, Mode, Module, Origin, Package, Target,
+        beam_manifest, package_compiler, package_compiler::PackageCompiler,
+        package_loader::StaleTracker, project_compiler, telemetry::Telemetry, IntRepresentation,
+        Mode, Module, ModuleFormat, Origin, Package, Target,
     },
     codegen::{self, ErlangApp},
     config::PackageConfig,
+    dead_code::{self, UnusedFunction},
     dep_tree,
     error::{FileIoAction, FileKind},
     io::{BeamCompiler, CommandExecutor, FileSystemReader, FileSystemWriter, Stdio},
@@ -18,7 +20,7 @@ use crate::{
     warning::{self, WarningEmitter, WarningEmitterIO},
     Error, Result, Warning,
 };
-use ecow::EcoString;
+use ecow::{eco_format, EcoString};
 use hexpm::version::Version;
 use itertools::Itertools;
 use pubgrub::range::Range;
@@ -60,6 +62,9 @@ pub struct Options {
     pub warnings_as_errors: bool,
     pub root_target_support: TargetSupport,
     pub no_print_progress: bool,
+    /// Whether the examples in the documentation of the root package are
+    /// compiled as doctests.
+    pub doctests: bool,
 }
 
 #[derive(Debug)]
@@ -84,6 +89,34 @@ impl Built {
         }
     }
 
+    /// The test functions of each of the root package's modules with the
+    /// given origin, including the modules that were loaded from the cache,
+    /// sorted by module name.
+    pub fn test_functions(
+        &self,
+        origin: Origin,
+        target: Target,
+    ) -> Vec<(EcoString, Vec<EcoString>)> {
+        self.module_interfaces
+            .values()
+            .filter(|interface| {
+                interface.origin == origin && interface.package == self.root_package.config.name
+            })
+            .map(|interface| {
+                let functions = interface.test_function_names(target);
+                (interface.name.clone(), functions)
+            })
+            .filter(|(_, functions)| !functions.is_empty())
+            .sorted()
+            .collect_vec()
+    }
+
+    /// The public functions of the root package that are never used by any
+    /// of its `main` functions or tests.
+    pub fn unused_public_functions(&self) -> Vec<UnusedFunction> {
+        dead_code::unused_public_functions(&self.root_package.config.name, &self.module_interfaces)
+    }
+
     pub fn minimum_required_version(&self) -> Version {
         self.module_interfaces
             .values()
@@ -191,6 +224,9 @@ where
 
         let root_package = self.compile_root_package().into_result()?;
 
+        // Tools that reload code need to know which modules have changed.
+        self.write_beam_manifest()?;
+
         // TODO: test
         if self.options.warnings_as_errors && self.warnings.count() > 0 {
             return Err(Error::ForbiddenWarnings {
@@ -222,7 +258,7 @@ where
         let version_path = self.paths.build_gleam_version(self.mode(), self.target());
         if self.io.is_file(&version_path) {
             let version = self.io.read(&version_path)?;
-            if version == COMPILER_VERSION {
+            if version == self.build_version() {
                 return Ok(());
             }
         }
@@ -234,7 +270,7 @@ where
         // Recreate build directory with new updated version file
         self.io.mkdir(&build_path)?;
         self.io
-            .write(&version_path, COMPILER_VERSION)
+            .write(&version_path, &self.build_version())
             .map_err(|e| Error::FileIo {
                 action: FileIoAction::WriteTo,
                 kind: FileKind::File,
@@ -243,6 +279,33 @@ where
             })
     }
 
+    /// The contents of the version file in the build directory. The module
+    /// format, the representation of ints and the freezing of values change
+    /// the JavaScript generated for every module, so switching them must also
+    /// clear the build directory.
+    fn build_version(&self) -> EcoString {
+        let mut version = EcoString::from(COMPILER_VERSION);
+        if self.target() != Target::JavaScript {
+            return version;
+        }
+        if self.config.javascript.module_format == ModuleFormat::CommonJs {
+            version.push_str("+commonjs");
+        }
+        if self.config.javascript.int_representation == IntRepresentation::BigInt {
+            version.push_str("+bigint");
+        }
+        if self.freeze_values() {
+            version.push_str("+frozen");
+        }
+        version
+    }
+
+    /// Values are only ever frozen in development builds, as freezing them
+    /// slows down their construction.
+    fn freeze_values(&self) -> bool {
+        self.config.javascript.freeze_values && self.mode() == Mode::Dev
+    }
+
     pub fn compile_dependencies(&mut self) -> Result<Vec<Module>, Error> {
         let sequence = order_packages(&self.packages)?;
         let mut modules = vec![];
@@ -266,14 +329,24 @@ where
             .build_directory_for_target(self.mode(), self.target());
 
         // Write the JavaScript prelude
-        let path = build.join("prelude.mjs");
+        let module_format = self.config.javascript.module_format;
+        let path = build
+            .join("prelude")
+            .with_extension(module_format.extension());
         if !self.io.is_file(&path) {
-            self.io.write(&path, crate::javascript::PRELUDE)?;
+            let prelude = crate::javascript::prelude(
+                module_format,
+                self.config.javascript.int_representation,
+                self.freeze_values(),
+            );
+            self.io.write(&path, &prelude)?;
         }
 
         // Write the TypeScript prelude, if asked for
         if self.config.javascript.typescript_declarations {
-            let path = build.join("prelude.d.mts");
+            let path = build
+                .join("prelude")
+                .with_extension(module_format.declaration_extension());
             if !self.io.is_file(&path) {
                 self.io.write(&path, crate::javascript::PRELUDE_TS_DEF)?;
             }
@@ -282,6 +355,28 @@ where
         Ok(())
     }
 
+    /// Writes a manifest of the `.beam` files of all the packages, for hot
+    /// code reloading tools to compare between builds.
+    fn write_beam_manifest(&self) -> Result<()> {
+        if self.target() != Target::Erlang || self.options.codegen != Codegen::All {
+            return Ok(());
+        }
+
+        let build = self
+            .paths
+            .build_directory_for_target(self.mode(), self.target());
+        let packages = self
+            .packages
+            .keys()
+            .map(String::as_str)
+            .chain(std::iter::once(self.config.name.as_str()));
+        let modules = beam_manifest::beam_modules(&self.io, &build, packages)?;
+        self.io.write(
+            &build.join(beam_manifest::BEAM_MANIFEST_FILE_NAME),
+            &beam_manifest::render(&modules),
+        )
+    }
+
     fn load_cache_or_compile_package(&mut self, name: &str) -> Result<Vec<Module>, Error> {
         // TODO: We could remove this clone if we split out the compilation of
         // packages into their own classes and then only mutate self after we no
@@ -551,7 +646,20 @@ where
             Target::JavaScript => super::TargetCodegenConfiguration::JavaScript {
                 emit_typescript_definitions: self.config.javascript.typescript_declarations,
                 // This path is relative to each package output directory
-                prelude_location: Utf8PathBuf::from("../prelude.mjs"),
+                prelude_location: Utf8PathBuf::from("../prelude")
+                    .with_extension(self.config.javascript.module_format.extension()),
+                // Dependencies use the module format of the root package, so
+                // that a CommonJS project can be loaded with `require` alone.
+                module_format: self.config.javascript.module_format,
+                int_representation: self.config.javascript.int_representation,
+                // Only the root package opts into BigInts, so the FFI modules
+                // of dependencies take and return ints as numbers.
+                ffi_int_representation: if is_root {
+                    self.config.javascript.int_representation
+                } else {
+                    IntRepresentation::Number
+                },
+                freeze_values: self.freeze_values(),
             },
         };
 
@@ -567,6 +675,8 @@ where
         );
         compiler.write_metadata = true;
         compiler.write_entrypoint = is_root;
+        compiler.compile_doctests =
+            is_root && self.options.doctests && config.documentation.doctests;
         compiler.perform_codegen = self.options.codegen.should_codegen(is_root);
         compiler.compile_beam_bytecode = self.options.codegen.should_codegen(is_root);
         compiler.compile_modules = !(self.options.compile == Compile::DepsOnly && is_root);
@@ -1,6 +1,15 @@
-use crate::{manifest::ManifestPackage, Error};
+use camino::Utf8Path;
 
-use super::project_compiler::{usable_build_tools, BuildTool};
+use crate::{
+    io::{memory::InMemoryFileSystem, FileSystemWriter},
+    manifest::ManifestPackage,
+    Error,
+};
+
+use super::{
+    beam_manifest::{beam_modules, render},
+    project_compiler::{usable_build_tools, BuildTool},
+};
 
 #[test]
 fn usable_build_tool_unknown() {
@@ -55,3 +64,25 @@ fn usable_build_tool_mix_then_rebar3() {
         Ok(vec![BuildTool::Mix, BuildTool::Rebar3])
     )
 }
+
+#[test]
+fn beam_manifest() {
+    let io = InMemoryFileSystem::new();
+    let build = Utf8Path::new("/build/dev/erlang");
+    io.write_bytes(&build.join("app/ebin/app.beam"), b"app")
+        .expect("write");
+    io.write_bytes(&build.join("app/ebin/app.app"), b"{application, app, []}.")
+        .expect("write");
+    io.write_bytes(&build.join("gleam_stdlib/ebin/gleam@list.beam"), b"list")
+        .expect("write");
+
+    let modules =
+        beam_modules(&io, build, ["app", "gleam_stdlib", "missing"]).expect("beam modules");
+    assert_eq!(
+        render(&modules),
+        "%% Generated by the Gleam build tool. Read with `file:consult/1`.
+#{module => 'app', package => <<\"app\">>, beam => <<\"app/ebin/app.beam\">>, fingerprint => <<\"5f6c72f41a0fc720\">>}.
+#{module => 'gleam@list', package => <<\"gleam_stdlib\">>, beam => <<\"gleam_stdlib/ebin/gleam@list.beam\">>, fingerprint => <<\"b04f5f3aea6a43e1\">>}.
+"
+    );
+}
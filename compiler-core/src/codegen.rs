@@ -1,6 +1,6 @@
 use crate::{
     analyse::TargetSupport,
-    build::{ErlangAppCodegenConfiguration, Module},
+    build::{ErlangAppCodegenConfiguration, IntRepresentation, Module, ModuleFormat},
     config::PackageConfig,
     erlang,
     io::FileSystemWriter,
@@ -8,8 +8,10 @@ use crate::{
     line_numbers::LineNumbers,
     Result,
 };
+use ecow::EcoString;
 use itertools::Itertools;
-use std::fmt::Debug;
+use serde::Serialize;
+use std::{collections::BTreeMap, fmt::Debug};
 
 use camino::Utf8Path;
 
@@ -19,13 +21,19 @@ use camino::Utf8Path;
 pub struct Erlang<'a> {
     build_directory: &'a Utf8Path,
     include_directory: &'a Utf8Path,
+    minimum_otp_version: Option<u32>,
 }
 
 impl<'a> Erlang<'a> {
-    pub fn new(build_directory: &'a Utf8Path, include_directory: &'a Utf8Path) -> Self {
+    pub fn new(
+        build_directory: &'a Utf8Path,
+        include_directory: &'a Utf8Path,
+        minimum_otp_version: Option<u32>,
+    ) -> Self {
         Self {
             build_directory,
             include_directory,
+            minimum_otp_version,
         }
     }
 
@@ -51,7 +59,7 @@ impl<'a> Erlang<'a> {
         let name = format!("{erl_name}.erl");
         let path = self.build_directory.join(&name);
         let line_numbers = LineNumbers::new(&module.code);
-        let output = erlang::module(&module.ast, &line_numbers);
+        let output = erlang::module(&module.ast, &line_numbers, self.minimum_otp_version);
         tracing::debug!(name = ?name, "Generated Erlang module");
         writer.write(&path, &output?)
     }
@@ -107,7 +115,7 @@ impl<'a> ErlangApp<'a> {
 
         let modules = modules
             .iter()
-            .map(|m| m.name.replace("/", "@"))
+            .map(|m| erlang::escape_atom_string(m.name.replace("/", "@").to_string()))
             .sorted()
             .join(",\n               ");
 
@@ -138,7 +146,7 @@ impl<'a> ErlangApp<'a> {
 ]}}.
 "#,
             applications = applications,
-            description = config.description,
+            description = erlang::escape_erlang_string(&config.description),
             modules = modules,
             package = config.name,
             start_module = start_module,
@@ -161,20 +169,33 @@ pub struct JavaScript<'a> {
     prelude_location: &'a Utf8Path,
     typescript: TypeScriptDeclarations,
     target_support: TargetSupport,
+    module_format: ModuleFormat,
+    int_representation: IntRepresentation,
+    ffi_int_representation: IntRepresentation,
+    freeze_values: bool,
 }
 
 impl<'a> JavaScript<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         output_directory: &'a Utf8Path,
         typescript: TypeScriptDeclarations,
         prelude_location: &'a Utf8Path,
         target_support: TargetSupport,
+        module_format: ModuleFormat,
+        int_representation: IntRepresentation,
+        ffi_int_representation: IntRepresentation,
+        freeze_values: bool,
     ) -> Self {
         Self {
             prelude_location,
             output_directory,
             target_support,
             typescript,
+            module_format,
+            int_representation,
+            ffi_int_representation,
+            freeze_values,
         }
     }
 
@@ -190,9 +211,65 @@ impl<'a> JavaScript<'a> {
         Ok(())
     }
 
+    /// Renders a `package.json` for the package so it can be published to npm
+    /// or consumed by bundlers. It belongs in the directory holding the
+    /// package's directory along with its dependencies and the prelude, which
+    /// are published with it. Each of the given modules gets an entry in the
+    /// `exports` map, apart from internal ones, and the module named after the
+    /// package is also exported as the package's main entrypoint.
+    pub fn package_json(&self, config: &PackageConfig, modules: &[EcoString]) -> String {
+        let package = self
+            .output_directory
+            .file_name()
+            .expect("JavaScript output directory name");
+        let export = |module: &str| {
+            let path = format!("./{package}/{module}.{}", self.module_format.extension());
+            match self.typescript {
+                TypeScriptDeclarations::None => PackageJsonExport::Path(path),
+                TypeScriptDeclarations::Emit => PackageJsonExport::Conditional {
+                    types: format!(
+                        "./{package}/{module}.{}",
+                        self.module_format.declaration_extension()
+                    ),
+                    default: path,
+                },
+            }
+        };
+
+        let mut exports = modules
+            .iter()
+            .filter(|module| !config.is_internal_module(module))
+            .map(|module| (format!("./{module}"), export(module)))
+            .collect::<BTreeMap<_, _>>();
+        if modules.contains(&config.name) {
+            _ = exports.insert(".".into(), export(&config.name));
+        }
+
+        let package_json = PackageJson {
+            name: &config.name,
+            version: config.version.to_string(),
+            type_: match self.module_format {
+                ModuleFormat::Esm => "module",
+                ModuleFormat::CommonJs => "commonjs",
+            },
+            exports,
+        };
+        let mut json = serde_json::to_string_pretty(&package_json).expect("package.json to json");
+        json.push('\n');
+        json
+    }
+
     fn write_prelude(&self, writer: &impl FileSystemWriter) -> Result<()> {
-        let rexport = format!("export * from \"{}\";\n", self.prelude_location);
-        let prelude_path = &self.output_directory.join("gleam.mjs");
+        let rexport = match self.module_format {
+            ModuleFormat::Esm => format!("export * from \"{}\";\n", self.prelude_location),
+            ModuleFormat::CommonJs => {
+                format!("module.exports = require(\"{}\");\n", self.prelude_location)
+            }
+        };
+        let prelude_path = &self
+            .output_directory
+            .join("gleam")
+            .with_extension(self.module_format.extension());
 
         // This check skips unnecessary `gleam.mjs` writes which confuse
         // watchers and HMR build tools
@@ -204,9 +281,13 @@ impl<'a> JavaScript<'a> {
             let rexport = format!(
                 "export * from \"{}\";\nexport type * from \"{}\";\n",
                 self.prelude_location,
-                self.prelude_location.as_str().replace(".mjs", ".d.mts")
+                self.prelude_location
+                    .with_extension(self.module_format.declaration_extension())
             );
-            let prelude_declaration_path = &self.output_directory.join("gleam.d.mts");
+            let prelude_declaration_path = &self
+                .output_directory
+                .join("gleam")
+                .with_extension(self.module_format.declaration_extension());
 
             // Type declaration may trigger badly configured watchers
             if !writer.exists(prelude_declaration_path) {
@@ -223,9 +304,15 @@ impl<'a> JavaScript<'a> {
         module: &Module,
         js_name: &str,
     ) -> Result<()> {
-        let name = format!("{js_name}.d.mts");
+        let name = format!("{js_name}.{}", self.module_format.declaration_extension());
         let path = self.output_directory.join(name);
-        let output = javascript::ts_declaration(&module.ast, &module.input_path, &module.code);
+        let output = javascript::ts_declaration(
+            &module.ast,
+            &module.input_path,
+            &module.code,
+            self.module_format,
+            self.int_representation,
+        );
         tracing::debug!(name = ?js_name, "Generated TS declaration");
         writer.write(&path, &output?)
     }
@@ -236,7 +323,7 @@ impl<'a> JavaScript<'a> {
         module: &Module,
         js_name: &str,
     ) -> Result<()> {
-        let name = format!("{js_name}.mjs");
+        let name = format!("{js_name}.{}", self.module_format.extension());
         let path = self.output_directory.join(name);
         let line_numbers = LineNumbers::new(&module.code);
         let output = javascript::module(
@@ -246,8 +333,32 @@ impl<'a> JavaScript<'a> {
             &module.code,
             self.target_support,
             self.typescript,
+            self.module_format,
+            self.int_representation,
+            self.ffi_int_representation,
+            self.freeze_values,
         );
         tracing::debug!(name = ?js_name, "Generated js module");
         writer.write(&path, &output?)
     }
 }
+
+#[derive(Debug, Serialize)]
+struct PackageJson<'a> {
+    name: &'a str,
+    version: String,
+    #[serde(rename = "type")]
+    type_: &'static str,
+    exports: BTreeMap<String, PackageJsonExport>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(untagged)]
+enum PackageJsonExport {
+    Path(String),
+    /// Bundlers and TypeScript require the `types` condition to come first.
+    Conditional {
+        types: String,
+        default: String,
+    },
+}
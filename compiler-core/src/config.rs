@@ -17,7 +17,7 @@ use std::marker::PhantomData;
 #[cfg(test)]
 use crate::manifest::ManifestPackage;
 
-use crate::build::{Mode, Runtime, Target};
+use crate::build::{IntRepresentation, Mode, ModuleFormat, Runtime, Target};
 
 fn default_version() -> Version {
     Version::parse("0.1.0").expect("default version")
@@ -101,6 +101,10 @@ pub struct PackageConfig {
     #[serde(default = "erlang_target")]
     pub target: Target,
     #[serde(default)]
+    pub warnings: WarningsConfig,
+    #[serde(default)]
+    pub format: FormatConfig,
+    #[serde(default)]
     pub internal_modules: Option<Vec<Glob>>,
 }
 
@@ -646,16 +650,137 @@ impl Default for PackageConfig {
             links: Default::default(),
             internal_modules: Default::default(),
             target: Target::Erlang,
+            warnings: Default::default(),
+            format: Default::default(),
+        }
+    }
+}
+
+/// Optional warnings that are not emitted unless enabled in the `[warnings]`
+/// section of `gleam.toml`.
+#[derive(Deserialize, Debug, PartialEq, Eq, Default, Clone, Copy)]
+pub struct WarningsConfig {
+    /// Warn when a local variable is defined with the same name as another
+    /// local variable that is still in scope.
+    #[serde(default)]
+    pub shadowing: bool,
+}
+
+/// How `gleam format` and the language server format the package's code, set
+/// in the `[format]` section of `gleam.toml`.
+#[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
+pub struct FormatConfig {
+    /// The width the formatter tries to fit lines within.
+    #[serde(default = "default_line_width")]
+    pub line_width: u32,
+    /// The most consecutive blank lines kept between two statements. Blank
+    /// lines used to group statements are kept up to this many, and any more
+    /// are removed.
+    #[serde(default = "default_max_blank_lines")]
+    pub max_blank_lines: u32,
+    /// Whether to also format the ```` ```gleam ```` code blocks written in
+    /// comments and documentation.
+    #[serde(default)]
+    pub format_code_blocks: bool,
+    /// When pipelines are split so each step is on its own line.
+    #[serde(default, deserialize_with = "string_or_int_to_pipeline_breaking")]
+    pub break_pipelines: PipelineBreaking,
+    /// Directories, relative to the root of the package, that `gleam format`
+    /// formats along with the `src` and `test` directories when not given any
+    /// files. When empty the whole current directory is formatted instead.
+    #[serde(default)]
+    pub include: Vec<Utf8PathBuf>,
+}
+
+impl Default for FormatConfig {
+    fn default() -> Self {
+        Self {
+            line_width: default_line_width(),
+            max_blank_lines: default_max_blank_lines(),
+            format_code_blocks: false,
+            break_pipelines: PipelineBreaking::default(),
+            include: vec![],
         }
     }
 }
 
+fn default_line_width() -> u32 {
+    80
+}
+
+fn default_max_blank_lines() -> u32 {
+    1
+}
+
+/// When the formatter splits a pipeline so each step is on its own line. Set
+/// with `break_pipelines` to `"as-written"`, `"always"`, `"when-too-long"` or
+/// a number of steps.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum PipelineBreaking {
+    /// Pipelines written across several lines stay split, others are split
+    /// only when they don't fit within the line width.
+    #[default]
+    AsWritten,
+    /// Pipelines are always split.
+    Always,
+    /// Pipelines with more than this many steps are split, others only when
+    /// they don't fit within the line width.
+    OverSteps(u32),
+    /// Pipelines are split only when they don't fit within the line width.
+    WhenTooLong,
+}
+
+fn string_or_int_to_pipeline_breaking<'de, D>(deserializer: D) -> Result<PipelineBreaking, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    struct StringOrInt;
+
+    impl serde::de::Visitor<'_> for StringOrInt {
+        type Value = PipelineBreaking;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str(
+                r#""as-written", "always", "when-too-long" or a number of pipeline steps"#,
+            )
+        }
+
+        fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            match value {
+                "as-written" => Ok(PipelineBreaking::AsWritten),
+                "always" => Ok(PipelineBreaking::Always),
+                "when-too-long" => Ok(PipelineBreaking::WhenTooLong),
+                _ => Err(E::invalid_value(serde::de::Unexpected::Str(value), &self)),
+            }
+        }
+
+        fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            u32::try_from(value)
+                .map(PipelineBreaking::OverSteps)
+                .map_err(|_| E::invalid_value(serde::de::Unexpected::Signed(value), &self))
+        }
+    }
+
+    deserializer.deserialize_any(StringOrInt)
+}
+
 #[derive(Deserialize, Debug, PartialEq, Eq, Default, Clone)]
 pub struct ErlangConfig {
     #[serde(default)]
     pub application_start_module: Option<EcoString>,
     #[serde(default)]
     pub extra_applications: Vec<EcoString>,
+    /// The oldest OTP release the package supports. Generated code will only
+    /// use constructs supported by this release, and using a feature that
+    /// needs a newer release is an error.
+    #[serde(default)]
+    pub minimum_otp_version: Option<u32>,
 }
 
 #[derive(Deserialize, Debug, PartialEq, Default, Clone)]
@@ -666,6 +791,28 @@ pub struct JavaScriptConfig {
     pub runtime: Runtime,
     #[serde(default, rename = "deno")]
     pub deno: DenoConfig,
+    /// Whether to generate ES modules or CommonJS modules. This is only used
+    /// from the root package's config, as all packages in a build share the
+    /// same prelude.
+    #[serde(default)]
+    pub module_format: ModuleFormat,
+    /// Whether ints are represented as JavaScript numbers or BigInts. Like
+    /// the module format this is only used from the root package's config,
+    /// as all the packages in a build must agree on it.
+    #[serde(default)]
+    pub int_representation: IntRepresentation,
+    /// Whether custom type instances and list cells are frozen when they are
+    /// constructed in development builds, so FFI code that mutates them
+    /// throws. Like the module format this is only used from the root
+    /// package's config.
+    #[serde(default)]
+    pub freeze_values: bool,
+    /// Whether a `package.json` is written to the build directory holding the
+    /// package's generated JavaScript, its dependencies and the prelude, so it
+    /// can be published to npm or consumed by bundlers. This is only used from
+    /// the root package's config.
+    #[serde(default)]
+    pub package_json: bool,
 }
 
 #[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
@@ -837,15 +984,93 @@ impl Default for Repository {
 pub struct Docs {
     #[serde(default)]
     pub pages: Vec<DocsPage>,
+    /// Whether the Gleam code blocks of the documentation are compiled and
+    /// run as tests.
+    #[serde(default)]
+    pub doctests: bool,
+    /// An image shown next to the name of the package at the top of each
+    /// page, relative to the root of the package.
+    #[serde(default)]
+    pub logo: Option<Utf8PathBuf>,
+    #[serde(default)]
+    pub favicon: Option<Utf8PathBuf>,
+    /// Files and directories copied into the documentation as they are, so
+    /// pages can use them. Each keeps its path relative to the root of the
+    /// package.
+    #[serde(default)]
+    pub assets: Vec<Utf8PathBuf>,
 }
 
-#[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
+/// A page to add to the documentation. In `gleam.toml` this is either a
+/// table with all three fields, or just the path of the markdown source, in
+/// which case the page is titled and named after the file.
+#[derive(Debug, PartialEq, Eq, Clone)]
 pub struct DocsPage {
     pub title: String,
     pub path: String,
     pub source: Utf8PathBuf,
 }
 
+impl DocsPage {
+    fn from_source(source: Utf8PathBuf) -> Self {
+        let name = source.file_stem().unwrap_or(source.as_str());
+        Self {
+            title: name.to_string(),
+            path: format!("{name}.html"),
+            source,
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for DocsPage {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        struct Page {
+            title: String,
+            path: String,
+            source: Utf8PathBuf,
+        }
+
+        struct PathOrPage;
+
+        impl<'de> serde::de::Visitor<'de> for PathOrPage {
+            type Value = DocsPage;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("path of a markdown file or table with title, path and source")
+            }
+
+            fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
+            where
+                E: serde::de::Error,
+            {
+                Ok(DocsPage::from_source(Utf8PathBuf::from(value)))
+            }
+
+            fn visit_map<M>(self, map: M) -> Result<Self::Value, M::Error>
+            where
+                M: serde::de::MapAccess<'de>,
+            {
+                let Page {
+                    title,
+                    path,
+                    source,
+                } = Deserialize::deserialize(serde::de::value::MapAccessDeserializer::new(map))?;
+                Ok(DocsPage {
+                    title,
+                    path,
+                    source,
+                })
+            }
+        }
+
+        deserializer.deserialize_any(PathOrPage)
+    }
+}
+
 #[derive(Deserialize, Debug, PartialEq, Eq, Clone)]
 pub struct Link {
     pub title: String,
@@ -938,6 +1163,119 @@ mod package_name {
     }
 }
 
+#[test]
+fn shadowing_warnings_are_disabled_by_default() {
+    let config = toml::from_str::<PackageConfig>(r#"name = "wibble""#).expect("valid config");
+    assert!(!config.warnings.shadowing);
+}
+
+#[test]
+fn shadowing_warnings_can_be_enabled() {
+    let input = r#"
+name = "wibble"
+
+[warnings]
+shadowing = true
+"#;
+    let config = toml::from_str::<PackageConfig>(input).expect("valid config");
+    assert!(config.warnings.shadowing);
+}
+
+#[test]
+fn format_line_width_is_80_by_default() {
+    let config = toml::from_str::<PackageConfig>(r#"name = "wibble""#).expect("valid config");
+    assert_eq!(config.format.line_width, 80);
+}
+
+#[test]
+fn format_line_width_can_be_set() {
+    let input = r#"
+name = "wibble"
+
+[format]
+line_width = 100
+"#;
+    let config = toml::from_str::<PackageConfig>(input).expect("valid config");
+    assert_eq!(config.format.line_width, 100);
+}
+
+#[test]
+fn format_max_blank_lines_is_1_by_default() {
+    let config = toml::from_str::<PackageConfig>(r#"name = "wibble""#).expect("valid config");
+    assert_eq!(config.format.max_blank_lines, 1);
+}
+
+#[test]
+fn format_break_pipelines_is_as_written_by_default() {
+    let config = toml::from_str::<PackageConfig>(r#"name = "wibble""#).expect("valid config");
+    assert_eq!(config.format.break_pipelines, PipelineBreaking::AsWritten);
+}
+
+#[test]
+fn format_break_pipelines_can_be_set() {
+    let config = |value: &str| {
+        toml::from_str::<PackageConfig>(&format!(
+            "name = \"wibble\"\n[format]\nbreak_pipelines = {value}"
+        ))
+        .map(|config| config.format.break_pipelines)
+    };
+    assert_eq!(config(r#""always""#).ok(), Some(PipelineBreaking::Always));
+    assert_eq!(
+        config(r#""when-too-long""#).ok(),
+        Some(PipelineBreaking::WhenTooLong)
+    );
+    assert_eq!(config("3").ok(), Some(PipelineBreaking::OverSteps(3)));
+    assert!(config(r#""sometimes""#).is_err());
+    assert!(config("-1").is_err());
+}
+
+#[test]
+fn format_include_can_be_set() {
+    let input = r#"
+name = "wibble"
+
+[format]
+include = ["examples", "benchmarks"]
+"#;
+    let config = toml::from_str::<PackageConfig>(input).expect("valid config");
+    assert_eq!(
+        config.format.include,
+        vec![
+            Utf8PathBuf::from("examples"),
+            Utf8PathBuf::from("benchmarks")
+        ]
+    );
+}
+
+#[test]
+fn documentation_pages_can_be_paths_or_tables() {
+    let input = r#"
+name = "wibble"
+
+[documentation]
+pages = [
+  "docs/getting-started.md",
+  { title = "Changes", path = "changes.html", source = "CHANGELOG.md" },
+]
+"#;
+    let config = toml::from_str::<PackageConfig>(input).expect("valid config");
+    assert_eq!(
+        config.documentation.pages,
+        vec![
+            DocsPage {
+                title: "getting-started".into(),
+                path: "getting-started.html".into(),
+                source: Utf8PathBuf::from("docs/getting-started.md"),
+            },
+            DocsPage {
+                title: "Changes".into(),
+                path: "changes.html".into(),
+                source: Utf8PathBuf::from("CHANGELOG.md"),
+            },
+        ]
+    );
+}
+
 #[test]
 fn name_with_dash() {
     let input = r#"
@@ -0,0 +1,170 @@
+//! Finding the public functions of a project that are never used.
+//!
+//! When a module is analysed we record which module functions are referenced
+//! by each of its functions, and this is stored in the module's interface so
+//! it is also available for modules loaded from the cache.
+//!
+//! Starting from the entrypoints of the project, the `main` functions of its
+//! modules and its tests, we can then walk this call graph to find all the
+//! public functions of the root package that can never be reached.
+
+#[cfg(test)]
+mod tests;
+
+use std::collections::{HashMap, HashSet};
+
+use camino::Utf8PathBuf;
+use ecow::EcoString;
+use itertools::Itertools;
+
+use crate::{
+    ast::{Definition, SrcSpan, TypedDefinition},
+    build::Origin,
+    reference::{self, ValueUsage},
+    type_::{FunctionReference, ModuleInterface, ModuleValueConstructor, ValueConstructorVariant},
+};
+
+/// A public function of the root package that can't be reached from any of
+/// the project's entrypoints.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedFunction {
+    pub module: EcoString,
+    pub name: EcoString,
+    pub src_path: Utf8PathBuf,
+    pub line: u32,
+}
+
+/// Collects the module functions referenced by each of the given functions.
+/// References to constants are followed to the functions they reference in
+/// turn.
+///
+pub fn function_references(definitions: &[TypedDefinition]) -> Vec<FunctionReference> {
+    let mut references = vec![];
+    for definition in definitions {
+        let Definition::Function(function) = definition else {
+            continue;
+        };
+        let Some((_, name)) = &function.name else {
+            continue;
+        };
+        let mut referenced = vec![];
+        reference::function_usages(function, &mut |usage| {
+            referenced_functions(usage, &mut referenced)
+        });
+        references.extend(
+            referenced
+                .into_iter()
+                .map(|(module, referenced)| FunctionReference {
+                    function: name.clone(),
+                    module,
+                    name: referenced,
+                }),
+        );
+    }
+
+    references.sort();
+    references.dedup();
+    references
+}
+
+fn referenced_functions(usage: ValueUsage<'_>, references: &mut Vec<(EcoString, EcoString)>) {
+    let mut follow_constant = |constant| {
+        reference::constant_usages(constant, &mut |usage| {
+            referenced_functions(usage, references)
+        })
+    };
+    match usage {
+        ValueUsage::Name { constructor, .. } => match &constructor.variant {
+            ValueConstructorVariant::ModuleFn { module, name, .. } => {
+                references.push((module.clone(), name.clone()))
+            }
+            ValueConstructorVariant::ModuleConstant { literal, .. } => follow_constant(literal),
+            ValueConstructorVariant::LocalVariable { .. }
+            | ValueConstructorVariant::LocalConstant { .. }
+            | ValueConstructorVariant::Record { .. } => (),
+        },
+
+        ValueUsage::ModuleSelect { constructor, .. } => match constructor {
+            ModuleValueConstructor::Fn { module, name, .. } => {
+                references.push((module.clone(), name.clone()))
+            }
+            ModuleValueConstructor::Constant { literal, .. } => follow_constant(literal),
+            ModuleValueConstructor::Record { .. } => (),
+        },
+
+        ValueUsage::Constructor { .. } => (),
+    }
+}
+
+/// Returns the public functions of the given package that can't be reached
+/// from any of its entrypoints: the `main` functions of its modules, and all
+/// the public functions of its test modules.
+///
+pub fn unused_public_functions(
+    package: &str,
+    modules: &im::HashMap<EcoString, ModuleInterface>,
+) -> Vec<UnusedFunction> {
+    let package_modules = modules
+        .values()
+        .filter(|module| module.package == package)
+        .collect_vec();
+
+    let calls: HashMap<(&EcoString, &EcoString), Vec<(&EcoString, &EcoString)>> = package_modules
+        .iter()
+        .flat_map(|module| {
+            module.function_references.iter().map(|reference| {
+                (
+                    (&module.name, &reference.function),
+                    (&reference.module, &reference.name),
+                )
+            })
+        })
+        .into_group_map();
+
+    let mut to_visit = package_modules
+        .iter()
+        .flat_map(|module| {
+            public_functions(module)
+                .filter(|(name, _)| module.origin == Origin::Test || *name == "main")
+                .map(|(name, _)| (&module.name, name))
+        })
+        .collect_vec();
+    let mut reachable = HashSet::new();
+    while let Some(function) = to_visit.pop() {
+        if reachable.insert(function) {
+            to_visit.extend(calls.get(&function).into_iter().flatten());
+        }
+    }
+
+    package_modules
+        .iter()
+        .filter(|module| module.origin == Origin::Src)
+        .flat_map(|module| {
+            public_functions(module)
+                .filter(|(name, _)| !reachable.contains(&(&module.name, *name)))
+                .map(|(name, location)| UnusedFunction {
+                    module: module.name.clone(),
+                    name: name.clone(),
+                    src_path: module.src_path.clone(),
+                    line: module.line_numbers.line_number(location.start),
+                })
+        })
+        .sorted_by(|one, other| (&one.module, one.line).cmp(&(&other.module, other.line)))
+        .collect()
+}
+
+/// The public functions defined by a module, along with their location.
+fn public_functions(module: &ModuleInterface) -> impl Iterator<Item = (&EcoString, &SrcSpan)> {
+    module
+        .values
+        .iter()
+        .filter(|(_, value)| value.publicity.is_importable())
+        .filter_map(move |(name, value)| match &value.variant {
+            ValueConstructorVariant::ModuleFn {
+                module: defining_module,
+                location,
+                ..
+            } if *defining_module == module.name => Some((name, location)),
+            _ => None,
+        })
+}
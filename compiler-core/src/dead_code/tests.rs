@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use camino::Utf8PathBuf;
+use ecow::EcoString;
+use itertools::Itertools;
+
+use crate::{
+    analyse::{ModuleAnalyzerConstructor, TargetSupport},
+    build::{Origin, Target},
+    config::PackageConfig,
+    line_numbers::LineNumbers,
+    type_::{build_prelude, PRELUDE_MODULE_NAME},
+    uid::UniqueIdGenerator,
+    warning::{TypeWarningEmitter, VectorWarningEmitterIO, WarningEmitter},
+};
+
+/// Compiles the given modules, in order, as part of the `root` package and
+/// returns the unused public functions as `module.function` strings.
+fn unused(modules: Vec<(Origin, &str, &str)>) -> Vec<String> {
+    let ids = UniqueIdGenerator::new();
+    let emitter = WarningEmitter::new(std::rc::Rc::new(VectorWarningEmitterIO::default()));
+    let mut interfaces = im::HashMap::new();
+    let _ = interfaces.insert(PRELUDE_MODULE_NAME.into(), build_prelude(&ids));
+
+    let config = PackageConfig {
+        name: "root".into(),
+        ..Default::default()
+    };
+
+    for (origin, name, src) in modules {
+        let parsed = crate::parse::parse_module(Utf8PathBuf::from("test/path"), src, &emitter)
+            .expect("syntax error");
+        let mut ast = parsed.module;
+        ast.name = name.into();
+        let module = ModuleAnalyzerConstructor::<()> {
+            target: Target::Erlang,
+            ids: &ids,
+            origin,
+            importable_modules: &interfaces,
+            warnings: &TypeWarningEmitter::null(),
+            direct_dependencies: &HashMap::new(),
+            target_support: TargetSupport::NotEnforced,
+            package_config: &config,
+        }
+        .infer_module(ast, LineNumbers::new(src), "".into())
+        .expect("should successfully infer");
+        let _ = interfaces.insert(EcoString::from(name), module.type_info);
+    }
+
+    super::unused_public_functions("root", &interfaces)
+        .into_iter()
+        .map(|function| format!("{}.{}", function.module, function.name))
+        .collect_vec()
+}
+
+#[test]
+fn functions_reachable_from_main_are_used() {
+    assert_eq!(
+        unused(vec![
+            (
+                Origin::Src,
+                "wibble",
+                "pub fn wibble() { wobble() }
+                 pub fn wobble() { 1 }
+                 pub fn unused() { 2 }"
+            ),
+            (
+                Origin::Src,
+                "root",
+                "import wibble
+                 pub fn main() { wibble.wibble() }"
+            ),
+        ]),
+        vec!["wibble.unused"]
+    );
+}
+
+#[test]
+fn functions_reachable_from_tests_are_used() {
+    assert_eq!(
+        unused(vec![
+            (
+                Origin::Src,
+                "wibble",
+                "pub fn wibble() { 1 }
+                 pub fn wobble() { 2 }"
+            ),
+            (
+                Origin::Test,
+                "wibble_test",
+                "import wibble
+                 pub fn wibble_test() { wibble.wibble() }"
+            ),
+        ]),
+        vec!["wibble.wobble"]
+    );
+}
+
+#[test]
+fn functions_reachable_through_private_functions_are_used() {
+    assert_eq!(
+        unused(vec![(
+            Origin::Src,
+            "root",
+            "pub fn main() { helper() }
+             fn helper() { wibble }
+             pub fn wibble() { 1 }"
+        )]),
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn functions_reachable_through_constants_are_used() {
+    assert_eq!(
+        unused(vec![
+            (
+                Origin::Src,
+                "wibble",
+                "pub fn wibble() { 1 }
+                 pub const functions = [wibble]"
+            ),
+            (
+                Origin::Src,
+                "root",
+                "import wibble
+                 pub fn main() { wibble.functions }"
+            ),
+        ]),
+        Vec::<String>::new()
+    );
+}
+
+#[test]
+fn functions_only_used_by_unused_functions_are_unused() {
+    assert_eq!(
+        unused(vec![(
+            Origin::Src,
+            "root",
+            "pub fn main() { 1 }
+             pub fn wibble() { wobble() }
+             pub fn wobble() { wibble() }"
+        )]),
+        vec!["root.wibble", "root.wobble"]
+    );
+}
+
+#[test]
+fn functions_used_through_unqualified_aliased_imports_are_used() {
+    assert_eq!(
+        unused(vec![
+            (Origin::Src, "wibble", "pub fn wibble() { 1 }"),
+            (
+                Origin::Src,
+                "root",
+                "import wibble.{wibble as wobble}
+                 pub fn main() { wobble() }"
+            ),
+        ]),
+        Vec::<String>::new()
+    );
+}
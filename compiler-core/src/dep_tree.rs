@@ -50,13 +50,28 @@ fn import_cycle(
     let origin = cycle.node_id();
     let mut path = vec![];
     let _ = find_cycle(origin, origin, graph, &mut path, &mut HashSet::new());
-    path.iter()
+    // The path is found starting from the end of the cycle, so it's reversed
+    // to have each value depend on the one after it.
+    let mut cycle: Vec<EcoString> = path
+        .iter()
+        .rev()
         .map(|index| {
             values
                 .remove(index)
                 .expect("dep_tree::import_cycle(): cannot find values for index")
         })
-        .collect()
+        .collect();
+    // The cycle is rotated to always start from the same value, no matter
+    // where it was found from, so that it's reported the same way each time.
+    if let Some(first) = cycle
+        .iter()
+        .enumerate()
+        .min_by_key(|(_, value)| *value)
+        .map(|(index, _)| index)
+    {
+        cycle.rotate_left(first);
+    }
+    cycle
 }
 
 fn find_cycle(
@@ -85,12 +100,15 @@ fn find_cycle(
 
 #[derive(Debug, PartialEq)]
 pub enum Error {
+    /// The values forming a cycle, each depending on the one after it and
+    /// the last one depending on the first.
     Cycle(Vec<EcoString>),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use pretty_assertions::assert_eq;
 
     #[test]
     fn toposort_deps_test() {
@@ -144,7 +162,7 @@ mod tests {
                 ("b".into(), vec!["c".into()]),
                 ("c".into(), vec!["a".into()]),
             ]),
-            Err(Error::Cycle(vec!["c".into(), "b".into(), "a".into()]))
+            Err(Error::Cycle(vec!["a".into(), "b".into(), "c".into()]))
         );
 
         // a -> b <- e
@@ -161,10 +179,10 @@ mod tests {
                 ("a".into(), vec!["f".into()]),
             ]),
             Err(Error::Cycle(vec![
-                "e".into(),
-                "d".into(),
-                "c".into(),
                 "b".into(),
+                "c".into(),
+                "d".into(),
+                "e".into(),
             ]))
         );
     }
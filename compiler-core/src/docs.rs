@@ -1,25 +1,33 @@
+mod highlight;
+mod json;
 mod source_links;
 #[cfg(test)]
 mod tests;
+mod type_links;
 
-use std::time::SystemTime;
+use std::{
+    collections::{HashMap, HashSet},
+    time::SystemTime,
+};
 
-use camino::Utf8PathBuf;
+use camino::{Utf8Component, Utf8PathBuf};
 
 use crate::{
     ast::{
         CustomType, Definition, Function, ModuleConstant, Publicity, TypeAlias, TypedDefinition,
     },
-    build::{Module, Package},
+    build::{Module, Package, Target},
     config::{DocsPage, PackageConfig},
-    docs::source_links::SourceLinker,
+    docs::{source_links::SourceLinker, type_links::TypeLinker},
     format,
-    io::{Content, FileSystemReader, OutputFile},
+    io::{Content, DirWalker, FileSystemReader, OutputFile},
     package_interface::PackageInterface,
     paths::ProjectPaths,
     pretty,
-    type_::Deprecation,
+    type_::{expression::Implementations, Deprecation},
     version::COMPILER_VERSION,
+    warning::WarningEmitter,
+    Error, Result,
 };
 use askama::Template;
 use ecow::EcoString;
@@ -28,6 +36,7 @@ use serde::Serialize;
 use serde_json::to_string as serde_to_string;
 
 const MAX_COLUMNS: isize = 65;
+pub const DOCS_VERSION_FILE_NAME: &str = "docs_version.json";
 
 #[derive(PartialEq, Eq, Copy, Clone, Debug)]
 pub enum DocContext {
@@ -35,19 +44,20 @@ pub enum DocContext {
     Build,
 }
 
+/// Generates the documentation of a package. `hex_packages` are the names of
+/// the dependencies from Hex, whose documentation is linked to on HexDocs.
+#[allow(clippy::too_many_arguments)]
 pub fn generate_html<IO: FileSystemReader>(
     paths: &ProjectPaths,
     config: &PackageConfig,
+    hex_packages: &HashSet<EcoString>,
     analysed: &[Module],
     docs_pages: &[DocsPage],
     fs: IO,
     rendering_timestamp: SystemTime,
     is_hex_publish: DocContext,
 ) -> Vec<OutputFile> {
-    let modules = analysed
-        .iter()
-        .filter(|module| !module.is_test())
-        .filter(|module| !config.is_internal_module(&module.name));
+    let modules = documented_modules(config, analysed);
 
     let rendering_timestamp = rendering_timestamp
         .duration_since(SystemTime::UNIX_EPOCH)
@@ -96,6 +106,12 @@ pub fn generate_html<IO: FileSystemReader>(
 
     let mut search_indexes = vec![];
 
+    let logo = asset_url(&config.documentation.logo);
+    let favicon = asset_url(&config.documentation.favicon);
+
+    let documented_modules: HashSet<EcoString> =
+        modules.clone().map(|module| module.name.clone()).collect();
+
     let modules_links: Vec<_> = modules
         .clone()
         .map(|m| {
@@ -141,6 +157,8 @@ pub fn generate_html<IO: FileSystemReader>(
             rendering_timestamp: &rendering_timestamp,
             host,
             unnest: &unnest,
+            logo: &logo,
+            favicon: &favicon,
         };
 
         files.push(OutputFile {
@@ -163,17 +181,19 @@ pub fn generate_html<IO: FileSystemReader>(
 
         // Read module src & create line number lookup structure
         let source_links = SourceLinker::new(paths, config, module);
+        let type_links = TypeLinker::new(&config.name, hex_packages, &documented_modules, &unnest);
 
         let documentation_content = module.ast.documentation.iter().join("\n");
-        let rendered_documentation =
-            render_markdown(&documentation_content.clone(), MarkdownSource::Comment);
+        let targeted_functions = functions_defined_for_one_target(module);
 
         let functions: Vec<DocsFunction<'_>> = module
             .ast
             .definitions
             .iter()
             .filter(|statement| !statement.is_internal())
-            .flat_map(|statement| function(&source_links, statement))
+            .flat_map(|statement| {
+                function(&source_links, &type_links, &targeted_functions, statement)
+            })
             .sorted()
             .collect();
 
@@ -182,7 +202,7 @@ pub fn generate_html<IO: FileSystemReader>(
             .definitions
             .iter()
             .filter(|statement| !statement.is_internal())
-            .flat_map(|statement| type_(&source_links, statement))
+            .flat_map(|statement| type_(&source_links, &type_links, statement))
             .sorted()
             .collect();
 
@@ -191,10 +211,21 @@ pub fn generate_html<IO: FileSystemReader>(
             .definitions
             .iter()
             .filter(|statement| !statement.is_internal())
-            .flat_map(|statement| constant(&source_links, statement))
+            .flat_map(|statement| constant(&source_links, &type_links, statement))
             .sorted()
             .collect();
 
+        // The headings of the module's documentation get ids that don't
+        // clash with those of the module's members.
+        let member_ids = types
+            .iter()
+            .map(|type_| type_.name)
+            .chain(constants.iter().map(|constant| constant.name))
+            .chain(functions.iter().map(|function| function.name))
+            .chain(MODULE_PAGE_IDS);
+        let (rendered_documentation, contents) =
+            render_module_documentation(&documentation_content, member_ids);
+
         types.iter().for_each(|type_| {
             let constructors = type_
                 .constructors
@@ -208,7 +239,7 @@ pub fn generate_html<IO: FileSystemReader>(
 
                     format!(
                         "{}\n{}\n{}",
-                        constructor.definition, constructor.text_documentation, arguments
+                        constructor.text_definition, constructor.text_documentation, arguments
                     )
                 })
                 .join("\n");
@@ -218,7 +249,7 @@ pub fn generate_html<IO: FileSystemReader>(
                 title: type_.name.to_string(),
                 content: format!(
                     "{}\n{}\n{}\n{}",
-                    type_.definition,
+                    type_.text_definition,
                     type_.text_documentation,
                     constructors,
                     import_synonyms(&module.name, type_.name)
@@ -232,7 +263,7 @@ pub fn generate_html<IO: FileSystemReader>(
                 title: constant.name.to_string(),
                 content: format!(
                     "{}\n{}\n{}",
-                    constant.definition,
+                    constant.text_definition,
                     constant.text_documentation,
                     import_synonyms(&module.name, constant.name)
                 ),
@@ -245,7 +276,7 @@ pub fn generate_html<IO: FileSystemReader>(
                 title: function.name.to_string(),
                 content: format!(
                     "{}\n{}\n{}",
-                    function.signature,
+                    function.text_signature,
                     function.text_documentation,
                     import_synonyms(&module.name, function.name)
                 ),
@@ -267,9 +298,12 @@ pub fn generate_html<IO: FileSystemReader>(
             gleam_version: COMPILER_VERSION,
             host,
             unnest,
+            logo: &logo,
+            favicon: &favicon,
             links: &links,
             pages: &pages,
             documentation: rendered_documentation,
+            contents,
             modules: &modules_links,
             project_name: &config.name,
             page_title: &page_title,
@@ -374,6 +408,23 @@ pub fn generate_html<IO: FileSystemReader>(
         )),
     });
 
+    // The version dropdown is filled in from the versions HexDocs lists in
+    // `docs_config.js`. This describes the version of the package these docs
+    // are for, so other hosts and tools can build the same list.
+    files.push(OutputFile {
+        path: Utf8PathBuf::from(DOCS_VERSION_FILE_NAME),
+        content: Content::Text(
+            serde_to_string(&DocsVersion {
+                name: &config.name,
+                version: config.version.to_string(),
+                gleam_version: COMPILER_VERSION,
+                url: (!host.is_empty())
+                    .then(|| format!("{host}/{}/{}/", config.name, config.version)),
+            })
+            .expect("docs version serialization"),
+        ),
+    });
+
     files.push(OutputFile {
         path: Utf8PathBuf::from("js/index.js"),
         content: Content::Text(std::include_str!("../templates/docs-js/index.js").to_string()),
@@ -458,6 +509,86 @@ pub fn generate_html<IO: FileSystemReader>(
     files
 }
 
+/// The files a package adds to its documentation: its logo, favicon and
+/// other assets, copied as they are to the same path relative to the root of
+/// the documentation as they have relative to the root of the package.
+/// Directories are copied with all the files within them.
+pub fn static_assets<IO: FileSystemReader>(
+    paths: &ProjectPaths,
+    config: &PackageConfig,
+    fs: &IO,
+) -> Result<Vec<OutputFile>> {
+    let documentation = &config.documentation;
+    let mut files = vec![];
+    let mut copied = HashSet::new();
+    for asset in documentation
+        .logo
+        .iter()
+        .chain(documentation.favicon.iter())
+        .chain(documentation.assets.iter())
+    {
+        if asset.is_absolute()
+            || asset
+                .components()
+                .any(|component| component == Utf8Component::ParentDir)
+        {
+            return Err(Error::DocsAssetOutsideProject {
+                path: asset.clone(),
+            });
+        }
+
+        let source = paths.root().join(asset);
+        let sources = if fs.is_directory(&source) {
+            DirWalker::new(source)
+                .into_file_iter(fs)
+                .collect::<Result<Vec<_>>>()?
+        } else {
+            vec![source]
+        };
+        for source in sources {
+            let path = source
+                .strip_prefix(paths.root())
+                .unwrap_or(&source)
+                .to_path_buf();
+            if !copied.insert(path.clone()) {
+                continue;
+            }
+            files.push(OutputFile {
+                content: Content::Binary(fs.read_bytes(&source)?),
+                path,
+            });
+        }
+    }
+    Ok(files)
+}
+
+/// The path of an asset in the documentation, as used in a link.
+fn asset_url(asset: &Option<Utf8PathBuf>) -> String {
+    asset
+        .as_ref()
+        .map(|asset| asset.components().map(|part| part.as_str()).join("/"))
+        .unwrap_or_default()
+}
+
+/// Renders the documentation of a package as JSON rather than as HTML pages.
+pub fn generate_json(
+    paths: &ProjectPaths,
+    config: &PackageConfig,
+    analysed: &[Module],
+) -> OutputFile {
+    json::generate(paths, config, documented_modules(config, analysed))
+}
+
+fn documented_modules<'a>(
+    config: &'a PackageConfig,
+    analysed: &'a [Module],
+) -> impl Iterator<Item = &'a Module> + Clone {
+    analysed
+        .iter()
+        .filter(|module| !module.is_test())
+        .filter(|module| !config.is_internal_module(&module.name))
+}
+
 pub fn generate_json_package_interface(path: Utf8PathBuf, package: &Package) -> OutputFile {
     OutputFile {
         path,
@@ -523,6 +654,8 @@ fn import_synonyms(parent: &str, child: &str) -> String {
 
 fn function<'a>(
     source_links: &SourceLinker,
+    type_links: &TypeLinker<'_>,
+    targeted_functions: &HashMap<EcoString, Target>,
     statement: &'a TypedDefinition,
 ) -> Option<DocsFunction<'a>> {
     let mut formatter = format::Formatter::new();
@@ -536,26 +669,31 @@ fn function<'a>(
             return_type: ret,
             location,
             deprecation,
+            implementations,
             ..
         }) => {
             let (_, name) = name
                 .as_ref()
                 .expect("Function in a definition must be named");
 
+            let text_signature = print(
+                formatter
+                    .docs_fn_signature(Publicity::Public, name, args, ret.clone(), location)
+                    .group(),
+            );
+            let types = args.iter().map(|arg| arg.type_.as_ref());
             Some(DocsFunction {
                 name,
                 documentation: markdown_documentation(doc),
                 text_documentation: text_documentation(doc),
-                signature: print(
-                    formatter
-                        .docs_fn_signature(Publicity::Public, name, args, ret.clone(), location)
-                        .group(),
-                ),
+                signature: type_links.highlight(&text_signature, types.chain([ret.as_ref()])),
+                text_signature,
                 source_url: source_links.url(*location),
                 deprecation_message: match deprecation {
                     Deprecation::NotDeprecated => "".to_string(),
                     Deprecation::Deprecated { message } => message.to_string(),
                 },
+                target: only_supported_target(targeted_functions.get(name), implementations),
             })
         }
 
@@ -563,6 +701,60 @@ fn function<'a>(
     }
 }
 
+/// The only target a function can be used on, if it can't be used on them
+/// all: either it is only defined for that target with `@target`, or it uses
+/// external code that is only implemented for that target.
+fn only_supported_target(
+    defined_for: Option<&Target>,
+    implementations: &Implementations,
+) -> Option<&'static str> {
+    let (erlang, javascript) = match defined_for {
+        Some(Target::Erlang) => (true, false),
+        Some(Target::JavaScript) => (false, true),
+        None => (
+            implementations.can_run_on_erlang,
+            implementations.can_run_on_javascript,
+        ),
+    };
+    match (erlang, javascript) {
+        (true, false) => Some("Erlang"),
+        (false, true) => Some("JavaScript"),
+        (true, true) | (false, false) => None,
+    }
+}
+
+/// The functions of a module that are only defined for a single target with
+/// the `@target` attribute. The typed module only has the definitions for the
+/// target it was analysed for, so the attributes are read from its source.
+fn functions_defined_for_one_target(module: &Module) -> HashMap<EcoString, Target> {
+    let Ok(parsed) = crate::parse::parse_module(
+        module.input_path.clone(),
+        &module.code,
+        &WarningEmitter::null(),
+    ) else {
+        return HashMap::new();
+    };
+
+    let mut targets: HashMap<EcoString, Vec<Option<Target>>> = HashMap::new();
+    for definition in parsed.module.definitions {
+        if let Definition::Function(Function {
+            name: Some((_, name)),
+            ..
+        }) = definition.definition
+        {
+            targets.entry(name).or_default().push(definition.target);
+        }
+    }
+
+    targets
+        .into_iter()
+        .filter_map(|(name, targets)| match targets.iter().all_equal_value() {
+            Ok(Some(target)) => Some((name, *target)),
+            _ => None,
+        })
+        .collect()
+}
+
 fn text_documentation(doc: &Option<(u32, EcoString)>) -> String {
     let raw_text = doc
         .as_ref()
@@ -590,7 +782,15 @@ enum MarkdownSource {
 }
 
 fn render_markdown(text: &str, source: MarkdownSource) -> String {
-    let text = match source {
+    let text = markdown_text(text, source);
+    let mut s = String::with_capacity(text.len() * 3 / 2);
+    let p = pulldown_cmark::Parser::new_ext(&text, pulldown_cmark::Options::all());
+    pulldown_cmark::html::push_html(&mut s, highlight_code_blocks(p));
+    s
+}
+
+fn markdown_text(text: &str, source: MarkdownSource) -> String {
+    match source {
         MarkdownSource::Standalone => text.into(),
         // Doc comments start with "///\s", which can confuse the markdown parser
         // and prevent tables from rendering correctly, so remove that first space.
@@ -598,15 +798,150 @@ fn render_markdown(text: &str, source: MarkdownSource) -> String {
             .split('\n')
             .map(|s| s.strip_prefix(' ').unwrap_or(s))
             .join("\n"),
+    }
+}
+
+/// The ids of the elements of a module's page other than its members.
+const MODULE_PAGE_IDS: [&str; 4] = [
+    "module-name",
+    "module-types",
+    "module-constants",
+    "module-functions",
+];
+
+/// Renders the documentation of a module, giving each of its headings an id
+/// so they can be linked to. The headings are returned too, for the table of
+/// contents of the module's page.
+///
+/// Headings with an id set in the markdown, as in `## Usage {#usage}`, keep
+/// it. Other headings get an id made from their text, unless that's one of
+/// the `taken_ids`, in which case a number is added to it.
+fn render_module_documentation<'a>(
+    text: &str,
+    taken_ids: impl IntoIterator<Item = &'a str>,
+) -> (String, Vec<Heading>) {
+    use pulldown_cmark::{Event, Tag, TagEnd};
+
+    let text = markdown_text(text, MarkdownSource::Comment);
+    let mut events = vec![];
+    let mut headings = vec![];
+    let mut heading: Option<(usize, String)> = None;
+    let mut taken_ids: HashSet<String> = taken_ids.into_iter().map(String::from).collect();
+    for event in pulldown_cmark::Parser::new_ext(&text, pulldown_cmark::Options::all()) {
+        match (&mut heading, &event) {
+            (None, Event::Start(Tag::Heading { .. })) => {
+                heading = Some((events.len(), String::new()));
+            }
+            (Some((_, text)), Event::Text(part) | Event::Code(part)) => text.push_str(part),
+            (Some((start, text)), Event::End(TagEnd::Heading(level))) => {
+                let text = text.trim().to_string();
+                if let Some(Event::Start(Tag::Heading { id, .. })) = events.get_mut(*start) {
+                    let heading_id = match id {
+                        Some(id) => id.to_string(),
+                        None => unique_id(slug(&text), &mut taken_ids),
+                    };
+                    *id = Some(heading_id.clone().into());
+                    headings.push(Heading {
+                        level: *level as usize,
+                        id: heading_id,
+                        text,
+                    });
+                }
+                heading = None;
+            }
+            _ => (),
+        }
+        events.push(event);
+    }
+
+    // The headings are indented in the table of contents relative to the
+    // biggest one.
+    let top_level = headings.iter().map(|heading| heading.level).min();
+    for heading in &mut headings {
+        heading.level -= top_level.unwrap_or(heading.level);
+    }
+
+    let mut html = String::with_capacity(text.len() * 3 / 2);
+    pulldown_cmark::html::push_html(&mut html, highlight_code_blocks(events.into_iter()));
+    (html, headings)
+}
+
+/// The id of a heading made from its text, as used by GitHub: lowercase, with
+/// spaces replaced by dashes and punctuation removed.
+fn slug(text: &str) -> String {
+    text.trim()
+        .chars()
+        .filter_map(|char| match char {
+            ' ' | '-' => Some('-'),
+            '_' => Some('_'),
+            char if char.is_alphanumeric() => Some(char),
+            _ => None,
+        })
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+fn unique_id(slug: String, taken_ids: &mut HashSet<String>) -> String {
+    let slug = if slug.is_empty() {
+        "section".into()
+    } else {
+        slug
     };
+    let id = std::iter::once(slug.clone())
+        .chain((1..).map(|number| format!("{slug}-{number}")))
+        .find(|id| !taken_ids.contains(id))
+        .expect("unique heading id");
+    let _ = taken_ids.insert(id.clone());
+    id
+}
 
-    let mut s = String::with_capacity(text.len() * 3 / 2);
-    let p = pulldown_cmark::Parser::new_ext(&text, pulldown_cmark::Options::all());
-    pulldown_cmark::html::push_html(&mut s, p);
-    s
+/// Replaces the code blocks of a markdown document with their highlighted
+/// HTML, for the languages that can be highlighted.
+fn highlight_code_blocks<'a>(
+    events: impl Iterator<Item = pulldown_cmark::Event<'a>>,
+) -> impl Iterator<Item = pulldown_cmark::Event<'a>> {
+    use pulldown_cmark::{CodeBlockKind, Event, Tag, TagEnd};
+
+    let mut code_block: Option<(String, Vec<Event<'a>>)> = None;
+    events.flat_map(move |event| match (&mut code_block, event) {
+        (None, event @ Event::Start(Tag::CodeBlock(_))) => {
+            let language = match &event {
+                Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info))) => {
+                    info.split_whitespace().next().unwrap_or("").to_string()
+                }
+                _ => String::new(),
+            };
+            code_block = Some((language, vec![event]));
+            vec![]
+        }
+        (Some((_, events)), event @ Event::End(TagEnd::CodeBlock)) => {
+            events.push(event);
+            let (language, events) = code_block.take().expect("code block");
+            let code = events
+                .iter()
+                .filter_map(|event| match event {
+                    Event::Text(text) => Some(text.as_ref()),
+                    _ => None,
+                })
+                .join("");
+            match highlight::code_block(&language, &code) {
+                Some(html) => vec![Event::Html(html.into())],
+                None => events,
+            }
+        }
+        (Some((_, events)), event) => {
+            events.push(event);
+            vec![]
+        }
+        (None, event) => vec![event],
+    })
 }
 
-fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedDefinition) -> Option<Type<'a>> {
+fn type_<'a>(
+    source_links: &SourceLinker,
+    type_links: &TypeLinker<'_>,
+    statement: &'a TypedDefinition,
+) -> Option<Type<'a>> {
     let mut formatter = format::Formatter::new();
 
     match statement {
@@ -614,8 +949,15 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedDefinition) -> Opt
             name: &ct.name,
             // TODO: Don't use the same printer for docs as for the formatter.
             // We are not interested in showing the exact implementation in the
-            // documentation and we could add things like colours, etc.
-            definition: print(formatter.custom_type(ct)),
+            // documentation.
+            definition: type_links.highlight(
+                &print(formatter.custom_type(ct)),
+                ct.constructors
+                    .iter()
+                    .flat_map(|constructor| &constructor.arguments)
+                    .map(|argument| argument.type_.as_ref()),
+            ),
+            text_definition: print(formatter.custom_type(ct)),
             documentation: markdown_documentation(&ct.documentation),
             text_documentation: text_documentation(&ct.documentation),
             deprecation_message: match &ct.deprecation {
@@ -626,7 +968,14 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedDefinition) -> Opt
                 .constructors
                 .iter()
                 .map(|constructor| TypeConstructor {
-                    definition: print(formatter.record_constructor(constructor)),
+                    definition: type_links.highlight(
+                        &print(formatter.record_constructor(constructor)),
+                        constructor
+                            .arguments
+                            .iter()
+                            .map(|argument| argument.type_.as_ref()),
+                    ),
+                    text_definition: print(formatter.record_constructor(constructor)),
                     documentation: markdown_documentation(&constructor.documentation),
                     text_documentation: text_documentation(&constructor.documentation),
                     arguments: constructor
@@ -654,50 +1003,66 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedDefinition) -> Opt
             location,
             deprecation,
             ..
-        }) => Some(Type {
-            name,
-            definition: print(
+        }) => {
+            let text_definition = print(
                 formatter
                     .docs_opaque_custom_type(Publicity::Public, name, parameters, location)
                     .group(),
-            ),
-            documentation: markdown_documentation(doc),
-            text_documentation: text_documentation(doc),
-            constructors: vec![],
-            source_url: source_links.url(*location),
-            deprecation_message: match deprecation {
-                Deprecation::NotDeprecated => "".to_string(),
-                Deprecation::Deprecated { message } => message.to_string(),
-            },
-            opaque: true,
-        }),
+            );
+            Some(Type {
+                name,
+                definition: type_links.highlight(&text_definition, []),
+                text_definition,
+                documentation: markdown_documentation(doc),
+                text_documentation: text_documentation(doc),
+                constructors: vec![],
+                source_url: source_links.url(*location),
+                deprecation_message: match deprecation {
+                    Deprecation::NotDeprecated => "".to_string(),
+                    Deprecation::Deprecated { message } => message.to_string(),
+                },
+                opaque: true,
+            })
+        }
 
         Definition::TypeAlias(TypeAlias {
             publicity: Publicity::Public,
             alias: name,
-            type_ast: type_,
+            type_ast,
+            type_,
             documentation: doc,
             parameters: args,
             location,
             deprecation,
             ..
-        }) => Some(Type {
-            name,
-            definition: print(
+        }) => {
+            let text_definition = print(
                 formatter
-                    .type_alias(Publicity::Public, name, args, type_, deprecation, location)
+                    .type_alias(
+                        Publicity::Public,
+                        name,
+                        args,
+                        type_ast,
+                        deprecation,
+                        location,
+                    )
                     .group(),
-            ),
-            documentation: markdown_documentation(doc),
-            text_documentation: text_documentation(doc),
-            constructors: vec![],
-            source_url: source_links.url(*location),
-            deprecation_message: match deprecation {
-                Deprecation::NotDeprecated => "".to_string(),
-                Deprecation::Deprecated { message } => message.to_string(),
-            },
-            opaque: false,
-        }),
+            );
+            Some(Type {
+                name,
+                definition: type_links.highlight(&text_definition, [type_.as_ref()]),
+                text_definition,
+                documentation: markdown_documentation(doc),
+                text_documentation: text_documentation(doc),
+                constructors: vec![],
+                source_url: source_links.url(*location),
+                deprecation_message: match deprecation {
+                    Deprecation::NotDeprecated => "".to_string(),
+                    Deprecation::Deprecated { message } => message.to_string(),
+                },
+                opaque: false,
+            })
+        }
 
         _ => None,
     }
@@ -705,6 +1070,7 @@ fn type_<'a>(source_links: &SourceLinker, statement: &'a TypedDefinition) -> Opt
 
 fn constant<'a>(
     source_links: &SourceLinker,
+    type_links: &TypeLinker<'_>,
     statement: &'a TypedDefinition,
 ) -> Option<Constant<'a>> {
     let mut formatter = format::Formatter::new();
@@ -716,13 +1082,17 @@ fn constant<'a>(
             value,
             location,
             ..
-        }) => Some(Constant {
-            name,
-            definition: print(formatter.docs_const_expr(Publicity::Public, name, value)),
-            documentation: markdown_documentation(doc),
-            text_documentation: text_documentation(doc),
-            source_url: source_links.url(*location),
-        }),
+        }) => {
+            let text_definition = print(formatter.docs_const_expr(Publicity::Public, name, value));
+            Some(Constant {
+                name,
+                definition: type_links.highlight(&text_definition, [value.type_().as_ref()]),
+                text_definition,
+                documentation: markdown_documentation(doc),
+                text_documentation: text_documentation(doc),
+                source_url: source_links.url(*location),
+            })
+        }
 
         _ => None,
     }
@@ -742,15 +1112,19 @@ struct Link {
 struct DocsFunction<'a> {
     name: &'a str,
     signature: String,
+    text_signature: String,
     documentation: String,
     text_documentation: String,
     source_url: String,
     deprecation_message: String,
+    /// The only target the function supports, if it doesn't support them all.
+    target: Option<&'static str>,
 }
 
 #[derive(PartialEq, Eq, PartialOrd, Ord, Debug)]
 struct TypeConstructor {
     definition: String,
+    text_definition: String,
     documentation: String,
     text_documentation: String,
     arguments: Vec<TypeConstructorArg>,
@@ -766,6 +1140,7 @@ struct TypeConstructorArg {
 struct Type<'a> {
     name: &'a str,
     definition: String,
+    text_definition: String,
     documentation: String,
     constructors: Vec<TypeConstructor>,
     text_documentation: String,
@@ -778,6 +1153,7 @@ struct Type<'a> {
 struct Constant<'a> {
     name: &'a str,
     definition: String,
+    text_definition: String,
     documentation: String,
     text_documentation: String,
     source_url: String,
@@ -792,6 +1168,8 @@ struct PageTemplate<'a> {
     page_title: &'a str,
     page_meta_description: &'a str,
     file_path: &'a Utf8PathBuf,
+    logo: &'a str,
+    favicon: &'a str,
     project_name: &'a str,
     project_version: &'a str,
     pages: &'a [Link],
@@ -801,6 +1179,16 @@ struct PageTemplate<'a> {
     rendering_timestamp: &'a str,
 }
 
+/// A heading of a module's documentation, listed in the table of contents.
+#[derive(PartialEq, Eq, Debug)]
+struct Heading {
+    /// How many levels smaller the heading is than the biggest heading of the
+    /// documentation.
+    level: usize,
+    id: String,
+    text: String,
+}
+
 #[derive(Template)]
 #[template(path = "documentation_module.html")]
 struct ModuleTemplate<'a> {
@@ -810,6 +1198,8 @@ struct ModuleTemplate<'a> {
     page_title: &'a str,
     page_meta_description: &'a str,
     file_path: &'a Utf8PathBuf,
+    logo: &'a str,
+    favicon: &'a str,
     module_name: EcoString,
     project_name: &'a str,
     project_version: &'a str,
@@ -820,9 +1210,20 @@ struct ModuleTemplate<'a> {
     types: Vec<Type<'a>>,
     constants: Vec<Constant<'a>>,
     documentation: String,
+    contents: Vec<Heading>,
     rendering_timestamp: &'a str,
 }
 
+#[derive(Serialize, Debug, PartialEq, Eq)]
+struct DocsVersion<'a> {
+    name: &'a str,
+    version: String,
+    gleam_version: &'a str,
+    /// Where the documentation for this version is published on HexDocs, if
+    /// it's being published.
+    url: Option<String>,
+}
+
 #[derive(Serialize, PartialEq, Eq, PartialOrd, Ord, Clone)]
 struct SearchIndex {
     doc: String,
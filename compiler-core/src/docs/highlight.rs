@@ -0,0 +1,376 @@
+//! Syntax highlighting of the code blocks in documentation, done when the
+//! documentation is generated so pages don't need to highlight them in the
+//! browser.
+//!
+//! The highlighted code uses the same `hljs-*` classes as highlight.js, so
+//! it's styled by the same themes. Code in languages that aren't highlighted
+//! here is left for highlight.js, and code blocks without a language are left
+//! plain.
+
+use crate::parse::{lexer, token::Token};
+
+/// Renders a code block as HTML, returning `None` if the language isn't one
+/// that's highlighted here.
+pub fn code_block(language: &str, code: &str) -> Option<String> {
+    let (name, highlighted) = match language {
+        "gleam" => ("gleam", gleam(code, |_, _| None)),
+        "erlang" | "erl" => ("erlang", generic(code, &ERLANG)),
+        "javascript" | "js" | "mjs" => ("javascript", generic(code, &JAVASCRIPT)),
+        "typescript" | "ts" | "mts" => ("typescript", generic(code, &TYPESCRIPT)),
+        "shell" | "sh" | "bash" | "console" => ("shell", generic(code, &SHELL)),
+        _ => return None,
+    };
+    // The `nohighlight` class stops highlight.js from highlighting the code
+    // again.
+    Some(format!(
+        "<pre><code class=\"hljs nohighlight {name}\">{highlighted}</code></pre>\n"
+    ))
+}
+
+/// Highlights Gleam code using the tokens of the lexer. If the code can't be
+/// lexed the rest of it is left as it is.
+///
+/// Type names are links if `link` returns a URL for them, given the module
+/// they're qualified with, if any, and the name.
+pub fn gleam(code: &str, link: impl Fn(Option<&str>, &str) -> Option<String>) -> String {
+    let mut html = String::with_capacity(code.len() * 2);
+    let mut position = 0;
+    let mut previous = None;
+    for token in lexer::make_tokenizer(code) {
+        let Ok((start, token, end)) = token else {
+            break;
+        };
+        let (start, end) = (start as usize, end as usize);
+        // Comment tokens start after the slashes.
+        let start = match token {
+            Token::CommentNormal | Token::CommentDoc { .. } | Token::CommentModule => code
+                .get(..start)
+                .map_or(start, |before| before.trim_end_matches('/').len()),
+            _ => start,
+        };
+        let Some(text) = code.get(start..end) else {
+            break;
+        };
+        push_escaped(&mut html, code.get(position..start).unwrap_or_default());
+        let url = match &token {
+            Token::UpName { name } => link(qualifier(code.get(..start).unwrap_or_default()), name),
+            _ => None,
+        };
+        if let Some(url) = &url {
+            html.push_str("<a href=\"");
+            push_escaped(&mut html, url);
+            html.push_str("\">");
+        }
+        match gleam_class(&token, previous.as_ref()) {
+            Some(class) => push_span(&mut html, class, text),
+            None => push_escaped(&mut html, text),
+        }
+        if url.is_some() {
+            html.push_str("</a>");
+        }
+        position = end;
+        if token != Token::NewLine {
+            previous = Some(token);
+        }
+    }
+    push_escaped(&mut html, code.get(position..).unwrap_or_default());
+    html
+}
+
+/// The module a name is qualified with, given the code before the name.
+fn qualifier(before: &str) -> Option<&str> {
+    let before = before.strip_suffix('.')?;
+    let start = before
+        .rfind(|char: char| !(char.is_ascii_alphanumeric() || char == '_' || char == '/'))
+        .map_or(0, |index| index + 1);
+    before.get(start..).filter(|module| !module.is_empty())
+}
+
+fn gleam_class(token: &Token, previous: Option<&Token>) -> Option<&'static str> {
+    match (token, previous) {
+        (Token::Name { .. }, Some(Token::Fn)) => Some("title function_"),
+        (Token::Name { .. }, Some(Token::At)) => Some("meta"),
+        (Token::At, _) => Some("meta"),
+        (Token::Name { .. }, _) => Some("variable"),
+        (Token::UpName { .. }, _) => Some("title"),
+        (Token::DiscardName { .. }, _) => Some("comment"),
+        (Token::Int { .. } | Token::Float { .. }, _) => Some("number"),
+        (Token::String { .. }, _) => Some("string"),
+        (Token::CommentNormal | Token::CommentDoc { .. } | Token::CommentModule, _) => {
+            Some("comment")
+        }
+        (
+            Token::Plus
+            | Token::Minus
+            | Token::Star
+            | Token::Slash
+            | Token::Less
+            | Token::Greater
+            | Token::LessEqual
+            | Token::GreaterEqual
+            | Token::Percent
+            | Token::PlusDot
+            | Token::MinusDot
+            | Token::StarDot
+            | Token::SlashDot
+            | Token::LessDot
+            | Token::GreaterDot
+            | Token::LessEqualDot
+            | Token::GreaterEqualDot
+            | Token::LtGt
+            | Token::Bang
+            | Token::Equal
+            | Token::EqualEqual
+            | Token::NotEqual
+            | Token::VbarVbar
+            | Token::AmperAmper
+            | Token::Pipe
+            | Token::RArrow
+            | Token::LArrow,
+            _,
+        ) => Some("operator"),
+        (token, _) if token.is_reserved_word() => Some("keyword"),
+        _ => None,
+    }
+}
+
+/// What's needed to highlight a language that isn't Gleam, which is done a
+/// word at a time rather than by parsing it.
+struct Language {
+    keywords: &'static [&'static str],
+    line_comment: &'static str,
+    block_comment: Option<(&'static str, &'static str)>,
+    quotes: &'static [char],
+    /// Whether names starting with an uppercase letter are variables, as in
+    /// Erlang.
+    uppercase_variables: bool,
+    /// Whether `$name` is a variable, as in shell scripts.
+    dollar_variables: bool,
+}
+
+const ERLANG: Language = Language {
+    keywords: &[
+        "after", "and", "andalso", "band", "begin", "bnot", "bor", "bsl", "bsr", "bxor", "case",
+        "catch", "cond", "div", "end", "fun", "if", "let", "maybe", "not", "of", "or", "orelse",
+        "receive", "rem", "try", "when", "xor",
+    ],
+    line_comment: "%",
+    block_comment: None,
+    quotes: &['"', '\''],
+    uppercase_variables: true,
+    dollar_variables: false,
+};
+
+const JAVASCRIPT_KEYWORDS: &[&str] = &[
+    "as",
+    "async",
+    "await",
+    "break",
+    "case",
+    "catch",
+    "class",
+    "const",
+    "continue",
+    "default",
+    "delete",
+    "do",
+    "else",
+    "export",
+    "extends",
+    "false",
+    "finally",
+    "for",
+    "from",
+    "function",
+    "if",
+    "import",
+    "in",
+    "instanceof",
+    "let",
+    "new",
+    "null",
+    "of",
+    "return",
+    "static",
+    "super",
+    "switch",
+    "this",
+    "throw",
+    "true",
+    "try",
+    "typeof",
+    "undefined",
+    "var",
+    "void",
+    "while",
+    "yield",
+];
+
+const JAVASCRIPT: Language = Language {
+    keywords: JAVASCRIPT_KEYWORDS,
+    line_comment: "//",
+    block_comment: Some(("/*", "*/")),
+    quotes: &['"', '\'', '`'],
+    uppercase_variables: false,
+    dollar_variables: false,
+};
+
+const TYPESCRIPT: Language = Language {
+    keywords: &[
+        "as",
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "declare",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "enum",
+        "export",
+        "extends",
+        "false",
+        "finally",
+        "for",
+        "from",
+        "function",
+        "if",
+        "implements",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "keyof",
+        "let",
+        "namespace",
+        "new",
+        "null",
+        "of",
+        "readonly",
+        "return",
+        "static",
+        "super",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "type",
+        "typeof",
+        "undefined",
+        "var",
+        "void",
+        "while",
+        "yield",
+    ],
+    ..JAVASCRIPT
+};
+
+const SHELL: Language = Language {
+    keywords: &[
+        "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+        "in", "then", "until", "while",
+    ],
+    line_comment: "#",
+    block_comment: None,
+    quotes: &['"', '\''],
+    uppercase_variables: false,
+    dollar_variables: true,
+};
+
+fn generic(code: &str, language: &Language) -> String {
+    let mut html = String::with_capacity(code.len() * 2);
+    let mut rest = code;
+    while let Some(char) = rest.chars().next() {
+        let (class, length) = if rest.starts_with(language.line_comment) {
+            (Some("comment"), rest.find('\n').unwrap_or(rest.len()))
+        } else if let Some(length) = language.block_comment.and_then(|(open, close)| {
+            let after_open = rest.strip_prefix(open)?;
+            Some(
+                after_open
+                    .find(close)
+                    .map_or(rest.len(), |end| open.len() + end + close.len()),
+            )
+        }) {
+            (Some("comment"), length)
+        } else if language.quotes.contains(&char) {
+            (Some("string"), quoted_length(rest, char))
+        } else if char.is_ascii_digit() {
+            let length = word_length(rest, |char| char == '.');
+            let number = rest.get(..length).unwrap_or_default();
+            (Some("number"), number.trim_end_matches('.').len())
+        } else if language.dollar_variables && char == '$' && rest.len() > 1 {
+            let length = 1 + word_length(rest.get(1..).unwrap_or_default(), |_| false);
+            (Some("variable"), length.max(2))
+        } else if char.is_alphabetic() || char == '_' {
+            let length = word_length(rest, |_| false);
+            let word = rest.get(..length).unwrap_or_default();
+            let class = if language.keywords.contains(&word) {
+                Some("keyword")
+            } else if language.uppercase_variables && (char.is_uppercase() || char == '_') {
+                Some("variable")
+            } else {
+                None
+            };
+            (class, length)
+        } else {
+            (None, char.len_utf8())
+        };
+
+        let (text, remaining) = rest.split_at(length.min(rest.len()));
+        match class {
+            Some(class) => push_span(&mut html, class, text),
+            None => push_escaped(&mut html, text),
+        }
+        rest = remaining;
+    }
+    html
+}
+
+/// The length of the word at the start of `text`, made of letters, digits
+/// and underscores, along with any other characters `also` accepts.
+fn word_length(text: &str, also: impl Fn(char) -> bool) -> usize {
+    text.find(|char: char| !(char.is_alphanumeric() || char == '_' || also(char)))
+        .unwrap_or(text.len())
+}
+
+/// The length of the string at the start of `text`, up to and including the
+/// closing quote. Quotes escaped with a backslash don't close the string.
+fn quoted_length(text: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (index, char) in text.char_indices().skip(1) {
+        match char {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '\n' => return index,
+            char if char == quote => return index + char.len_utf8(),
+            _ => (),
+        }
+    }
+    text.len()
+}
+
+fn push_span(html: &mut String, class: &str, text: &str) {
+    html.push_str("<span class=\"hljs-");
+    html.push_str(class);
+    html.push_str("\">");
+    push_escaped(html, text);
+    html.push_str("</span>");
+}
+
+fn push_escaped(html: &mut String, text: &str) {
+    for char in text.chars() {
+        match char {
+            '&' => html.push_str("&amp;"),
+            '<' => html.push_str("&lt;"),
+            '>' => html.push_str("&gt;"),
+            '"' => html.push_str("&quot;"),
+            char => html.push(char),
+        }
+    }
+}
@@ -0,0 +1,286 @@
+//! The documentation of a package as JSON, for sites and editor plugins that
+//! render it their own way rather than using the generated HTML.
+
+use camino::Utf8PathBuf;
+use ecow::EcoString;
+use itertools::Itertools;
+use serde::Serialize;
+
+use crate::{
+    ast::{CustomType, Definition, Function, ModuleConstant, Publicity, SrcSpan, TypeAlias},
+    build::Module,
+    config::PackageConfig,
+    format,
+    io::{Content, OutputFile},
+    line_numbers::LineNumbers,
+    paths::ProjectPaths,
+    type_::Deprecation,
+    version::COMPILER_VERSION,
+};
+
+use super::{print, source_links::SourceLinker};
+
+const DOCS_JSON_FILE_NAME: &str = "docs.json";
+
+#[derive(Serialize, Debug)]
+struct PackageDocumentation<'a> {
+    name: &'a str,
+    version: String,
+    gleam_version: &'a str,
+    modules: Vec<ModuleDocumentation<'a>>,
+}
+
+#[derive(Serialize, Debug)]
+struct ModuleDocumentation<'a> {
+    name: &'a str,
+    /// The path of the module's source, relative to the root of the package.
+    path: Utf8PathBuf,
+    documentation: String,
+    types: Vec<TypeDocumentation<'a>>,
+    constants: Vec<ValueDocumentation<'a>>,
+    functions: Vec<ValueDocumentation<'a>>,
+}
+
+#[derive(Serialize, Debug)]
+struct TypeDocumentation<'a> {
+    name: &'a str,
+    definition: String,
+    documentation: Option<String>,
+    deprecation: Option<&'a str>,
+    opaque: bool,
+    constructors: Vec<ConstructorDocumentation<'a>>,
+    location: Location,
+    source_url: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+struct ConstructorDocumentation<'a> {
+    name: &'a str,
+    definition: String,
+    documentation: Option<String>,
+}
+
+/// A constant or a function.
+#[derive(Serialize, Debug)]
+struct ValueDocumentation<'a> {
+    name: &'a str,
+    definition: String,
+    documentation: Option<String>,
+    deprecation: Option<&'a str>,
+    location: Location,
+    source_url: Option<String>,
+}
+
+/// The lines of the module a definition is on, starting from 1.
+#[derive(Serialize, Debug)]
+struct Location {
+    start_line: u32,
+    end_line: u32,
+}
+
+pub fn generate<'a>(
+    paths: &ProjectPaths,
+    config: &PackageConfig,
+    modules: impl Iterator<Item = &'a Module>,
+) -> OutputFile {
+    let modules = modules
+        .map(|module| module_documentation(paths, config, module))
+        .sorted_by(|one, other| one.name.cmp(other.name))
+        .collect();
+    let documentation = PackageDocumentation {
+        name: &config.name,
+        version: config.version.to_string(),
+        gleam_version: COMPILER_VERSION,
+        modules,
+    };
+    OutputFile {
+        path: Utf8PathBuf::from(DOCS_JSON_FILE_NAME),
+        content: Content::Text(
+            serde_json::to_string(&documentation).expect("documentation serialization"),
+        ),
+    }
+}
+
+fn module_documentation<'a>(
+    paths: &ProjectPaths,
+    config: &PackageConfig,
+    module: &'a Module,
+) -> ModuleDocumentation<'a> {
+    let source_links = SourceLinker::new(paths, config, module);
+    let line_numbers = LineNumbers::new(&module.code);
+    let location = |span: SrcSpan| Location {
+        start_line: line_numbers.line_number(span.start),
+        end_line: line_numbers.line_number(span.end),
+    };
+    let source_url = |span: SrcSpan| Some(source_links.url(span)).filter(|url| !url.is_empty());
+
+    let mut types = vec![];
+    let mut constants = vec![];
+    let mut functions = vec![];
+    let mut formatter = format::Formatter::new();
+    for definition in module
+        .ast
+        .definitions
+        .iter()
+        .filter(|definition| !definition.is_internal())
+    {
+        match definition {
+            Definition::Function(Function {
+                publicity: Publicity::Public,
+                name: Some((_, name)),
+                documentation,
+                arguments,
+                return_type,
+                location: span,
+                deprecation,
+                ..
+            }) => functions.push(ValueDocumentation {
+                name,
+                definition: print(
+                    formatter
+                        .docs_fn_signature(
+                            Publicity::Public,
+                            name,
+                            arguments,
+                            return_type.clone(),
+                            span,
+                        )
+                        .group(),
+                ),
+                documentation: markdown(documentation),
+                deprecation: deprecation_message(deprecation),
+                location: location(*span),
+                source_url: source_url(*span),
+            }),
+
+            Definition::ModuleConstant(ModuleConstant {
+                publicity: Publicity::Public,
+                documentation,
+                name,
+                value,
+                location: span,
+                deprecation,
+                ..
+            }) => constants.push(ValueDocumentation {
+                name,
+                definition: print(formatter.docs_const_expr(Publicity::Public, name, value)),
+                documentation: markdown(documentation),
+                deprecation: deprecation_message(deprecation),
+                location: location(*span),
+                source_url: source_url(*span),
+            }),
+
+            Definition::CustomType(custom_type @ CustomType { opaque, .. })
+                if custom_type.publicity.is_public() =>
+            {
+                let definition = if *opaque {
+                    formatter
+                        .docs_opaque_custom_type(
+                            Publicity::Public,
+                            &custom_type.name,
+                            &custom_type.parameters,
+                            &custom_type.location,
+                        )
+                        .group()
+                } else {
+                    formatter.custom_type(custom_type)
+                };
+                let constructors = if *opaque {
+                    vec![]
+                } else {
+                    custom_type
+                        .constructors
+                        .iter()
+                        .map(|constructor| ConstructorDocumentation {
+                            name: &constructor.name,
+                            definition: print(formatter.record_constructor(constructor)),
+                            documentation: markdown(&constructor.documentation),
+                        })
+                        .collect()
+                };
+                types.push(TypeDocumentation {
+                    name: &custom_type.name,
+                    definition: print(definition),
+                    documentation: markdown(&custom_type.documentation),
+                    deprecation: deprecation_message(&custom_type.deprecation),
+                    opaque: *opaque,
+                    constructors,
+                    location: location(custom_type.location),
+                    source_url: source_url(custom_type.location),
+                });
+            }
+
+            Definition::TypeAlias(TypeAlias {
+                publicity: Publicity::Public,
+                alias: name,
+                type_ast,
+                documentation,
+                parameters,
+                location: span,
+                deprecation,
+                ..
+            }) => types.push(TypeDocumentation {
+                name,
+                definition: print(
+                    formatter
+                        .type_alias(
+                            Publicity::Public,
+                            name,
+                            parameters,
+                            type_ast,
+                            deprecation,
+                            span,
+                        )
+                        .group(),
+                ),
+                documentation: markdown(documentation),
+                deprecation: deprecation_message(deprecation),
+                opaque: false,
+                constructors: vec![],
+                location: location(*span),
+                source_url: source_url(*span),
+            }),
+
+            _ => (),
+        }
+    }
+    types.sort_by(|one, other| one.name.cmp(other.name));
+    constants.sort_by(|one, other| one.name.cmp(other.name));
+    functions.sort_by(|one, other| one.name.cmp(other.name));
+
+    let path = module
+        .input_path
+        .strip_prefix(paths.root())
+        .unwrap_or(&module.input_path)
+        .to_path_buf();
+    ModuleDocumentation {
+        name: &module.name,
+        path,
+        documentation: strip_comment_spaces(&module.ast.documentation.iter().join("\n")),
+        types,
+        constants,
+        functions,
+    }
+}
+
+/// The markdown of a documentation comment. Each line of a doc comment
+/// starts with the space after the `///`, which is removed.
+fn markdown(documentation: &Option<(u32, EcoString)>) -> Option<String> {
+    documentation
+        .as_ref()
+        .map(|(_, documentation)| strip_comment_spaces(documentation))
+}
+
+fn strip_comment_spaces(documentation: &str) -> String {
+    documentation
+        .split('\n')
+        .map(|line| line.strip_prefix(' ').unwrap_or(line))
+        .join("\n")
+}
+
+fn deprecation_message(deprecation: &Deprecation) -> Option<&str> {
+    match deprecation {
+        Deprecation::NotDeprecated => None,
+        Deprecation::Deprecated { message } => Some(message),
+    }
+}
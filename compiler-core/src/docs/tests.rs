@@ -1,7 +1,9 @@
 use std::{collections::HashSet, time::SystemTime};
 
 use crate::{
-    build::{Mode, NullTelemetry, PackageCompiler, StaleTracker, TargetCodegenConfiguration},
+    build::{
+        Mode, Module, NullTelemetry, PackageCompiler, StaleTracker, TargetCodegenConfiguration,
+    },
     config::{DocsPage, PackageConfig, Repository},
     docs::DocContext,
     io::{memory::InMemoryFileSystem, FileSystemWriter},
@@ -25,17 +27,11 @@ fn compile_with_markdown_pages(
     markdown_pages: Vec<(&str, &str)>,
     opts: CompileWithMarkdownPagesOpts,
 ) -> EcoString {
-    let fs = InMemoryFileSystem::new();
-    for (name, src) in modules {
-        fs.write(&Utf8PathBuf::from(format!("/src/{name}")), src)
-            .unwrap();
-    }
-
     // We're saving the pages under a different `InMemoryFileSystem` for these
     // tests so we don't have to juggle with borrows and lifetimes.
-    // The package compiler is going to take ownership of `fs` but later
-    // `generate_html` also needs a `FileSystemReader` to go and read the
-    // markdown pages' content.
+    // The package compiler is going to take ownership of the modules' file
+    // system but later `generate_html` also needs a `FileSystemReader` to go
+    // and read the markdown pages' content.
     let pages_fs = InMemoryFileSystem::new();
     for (title, src) in markdown_pages.iter() {
         pages_fs
@@ -43,35 +39,8 @@ fn compile_with_markdown_pages(
             .unwrap();
     }
 
-    let ids = UniqueIdGenerator::new();
-    let mut type_manifests = im::HashMap::new();
-    let mut defined_modules = im::HashMap::new();
-    let warnings = WarningEmitter::null();
-    let target = TargetCodegenConfiguration::Erlang { app_file: None };
-
-    let root = Utf8PathBuf::from("/");
-    let build = root.join("build");
-    let lib = root.join("lib");
-    let paths = ProjectPaths::new(root.clone());
-    let mut compiler =
-        PackageCompiler::new(&config, Mode::Dev, &root, &build, &lib, &target, ids, fs);
-    compiler.write_entrypoint = false;
-    compiler.write_metadata = false;
-    compiler.compile_beam_bytecode = true;
-    let mut modules = compiler
-        .compile(
-            &warnings,
-            &mut type_manifests,
-            &mut defined_modules,
-            &mut StaleTracker::default(),
-            &mut HashSet::new(),
-            &NullTelemetry,
-        )
-        .unwrap();
-
-    for module in &mut modules {
-        module.attach_doc_and_module_comments();
-    }
+    let modules = compile_modules(&config, modules);
+    let paths = ProjectPaths::new(Utf8PathBuf::from("/"));
 
     let docs_pages = markdown_pages
         .into_iter()
@@ -85,6 +54,7 @@ fn compile_with_markdown_pages(
     super::generate_html(
         &paths,
         &config,
+        &HashSet::new(),
         &modules,
         &docs_pages,
         pages_fs,
@@ -112,6 +82,44 @@ fn compile_with_markdown_pages(
     .collect()
 }
 
+fn compile_modules(config: &PackageConfig, modules: Vec<(&str, &str)>) -> Vec<Module> {
+    let fs = InMemoryFileSystem::new();
+    for (name, src) in modules {
+        fs.write(&Utf8PathBuf::from(format!("/src/{name}")), src)
+            .unwrap();
+    }
+
+    let ids = UniqueIdGenerator::new();
+    let mut type_manifests = im::HashMap::new();
+    let mut defined_modules = im::HashMap::new();
+    let warnings = WarningEmitter::null();
+    let target = TargetCodegenConfiguration::Erlang { app_file: None };
+
+    let root = Utf8PathBuf::from("/");
+    let build = root.join("build");
+    let lib = root.join("lib");
+    let mut compiler =
+        PackageCompiler::new(config, Mode::Dev, &root, &build, &lib, &target, ids, fs);
+    compiler.write_entrypoint = false;
+    compiler.write_metadata = false;
+    compiler.compile_beam_bytecode = true;
+    let mut modules = compiler
+        .compile(
+            &warnings,
+            &mut type_manifests,
+            &mut defined_modules,
+            &mut StaleTracker::default(),
+            &mut HashSet::new(),
+            &NullTelemetry,
+        )
+        .unwrap();
+
+    for module in &mut modules {
+        module.attach_doc_and_module_comments();
+    }
+    modules
+}
+
 pub fn compile(config: PackageConfig, modules: Vec<(&str, &str)>) -> EcoString {
     compile_with_markdown_pages(
         config,
@@ -481,3 +489,412 @@ pub fn one() {
         }
     ));
 }
+
+#[test]
+fn gleam_code_blocks_are_highlighted() {
+    assert_eq!(
+        super::highlight::code_block("gleam", "pub fn main() { \"<a>\" |> io.println } // ok"),
+        Some(
+            "<pre><code class=\"hljs nohighlight gleam\"><span class=\"hljs-keyword\">pub</span> \
+<span class=\"hljs-keyword\">fn</span> <span class=\"hljs-title function_\">main</span>() { \
+<span class=\"hljs-string\">&quot;&lt;a&gt;&quot;</span> <span class=\"hljs-operator\">|&gt;</span> \
+<span class=\"hljs-variable\">io</span>.<span class=\"hljs-variable\">println</span> } \
+<span class=\"hljs-comment\">// ok</span></code></pre>\n"
+                .into()
+        )
+    );
+}
+
+#[test]
+fn erlang_code_blocks_are_highlighted() {
+    assert_eq!(
+        super::highlight::code_block("erlang", "f(X) -> case X of 1.0 -> \"one\" end. % ok"),
+        Some(
+            "<pre><code class=\"hljs nohighlight erlang\">f(<span class=\"hljs-variable\">X</span>) -&gt; \
+<span class=\"hljs-keyword\">case</span> <span class=\"hljs-variable\">X</span> \
+<span class=\"hljs-keyword\">of</span> <span class=\"hljs-number\">1.0</span> -&gt; \
+<span class=\"hljs-string\">&quot;one&quot;</span> <span class=\"hljs-keyword\">end</span>. \
+<span class=\"hljs-comment\">% ok</span></code></pre>\n"
+                .into()
+        )
+    );
+}
+
+#[test]
+fn javascript_code_blocks_are_highlighted() {
+    assert_eq!(
+        super::highlight::code_block("js", "const x = `a` /* b */ + 1;"),
+        Some(
+            "<pre><code class=\"hljs nohighlight javascript\"><span class=\"hljs-keyword\">const</span> \
+x = <span class=\"hljs-string\">`a`</span> <span class=\"hljs-comment\">/* b */</span> + \
+<span class=\"hljs-number\">1</span>;</code></pre>\n"
+                .into()
+        )
+    );
+}
+
+#[test]
+fn typescript_code_blocks_are_highlighted() {
+    assert_eq!(
+        super::highlight::code_block("ts", "type X = string // ok"),
+        Some(
+            "<pre><code class=\"hljs nohighlight typescript\"><span class=\"hljs-keyword\">type</span> \
+X = string <span class=\"hljs-comment\">// ok</span></code></pre>\n"
+                .into()
+        )
+    );
+}
+
+#[test]
+fn shell_code_blocks_are_highlighted() {
+    assert_eq!(
+        super::highlight::code_block("sh", "export PATH=$HOME/bin # ok"),
+        Some(
+            "<pre><code class=\"hljs nohighlight shell\"><span class=\"hljs-keyword\">export</span> \
+PATH=<span class=\"hljs-variable\">$HOME</span>/bin <span class=\"hljs-comment\"># ok</span>\
+</code></pre>\n"
+                .into()
+        )
+    );
+}
+
+#[test]
+fn code_blocks_in_other_languages_are_left_for_the_browser() {
+    assert_eq!(super::highlight::code_block("elixir", "IO.puts(1)"), None);
+}
+
+#[test]
+fn code_blocks_without_a_language_are_left_plain() {
+    assert_eq!(
+        super::highlight::code_block("", "pub fn main() { 1 }"),
+        None
+    );
+}
+
+#[test]
+fn code_blocks_in_documentation_are_highlighted() {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("test_project_name");
+    let modules = vec![(
+        "app.gleam",
+        "
+/// Calls a function from Erlang:
+///
+/// ```gleam
+/// @external(erlang, \"app_ffi\", \"wibble\")
+/// pub fn wibble() -> Int
+/// ```
+///
+/// ```erlang
+/// wibble() -> 1.
+/// ```
+///
+/// ```elixir
+/// def wibble, do: 1
+/// ```
+pub fn wibble() {
+  1
+}
+",
+    )];
+    insta::assert_snapshot!(compile(config, modules));
+}
+
+#[test]
+fn types_in_signatures_link_to_their_documentation() {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("test_project_name");
+    let modules = vec![
+        (
+            "app/wibble.gleam",
+            "
+pub type Wibble {
+  Wibble
+}
+
+pub type Wobble(a) {
+  Wobble(a)
+}
+",
+        ),
+        (
+            "app.gleam",
+            "
+import app/wibble.{type Wibble}
+
+pub type Wubble {
+  Wubble(wibble: Wibble)
+}
+
+pub const wibble = wibble.Wibble
+
+pub fn wobble(wibble: Wibble, count: Int) -> wibble.Wobble(Wubble) {
+  todo
+}
+",
+        ),
+    ];
+    insta::assert_snapshot!(compile(config, modules));
+}
+
+#[test]
+fn types_from_hex_dependencies_link_to_hexdocs() {
+    let hex_packages = HashSet::from(["gleam_stdlib".into()]);
+    let documented_modules = HashSet::new();
+    let linker = super::type_links::TypeLinker::new("app", &hex_packages, &documented_modules, ".");
+    let type_ = crate::type_::Type::Named {
+        publicity: crate::ast::Publicity::Public,
+        package: "gleam_stdlib".into(),
+        module: "gleam/option".into(),
+        name: "Option".into(),
+        args: vec![crate::type_::int()],
+        inferred_variant: None,
+    };
+    assert_eq!(
+        linker.highlight("fn wibble() -> option.Option(Int)", [&type_]),
+        "<span class=\"hljs-keyword\">fn</span> <span class=\"hljs-title function_\">wibble</span>() \
+<span class=\"hljs-operator\">-&gt;</span> <span class=\"hljs-variable\">option</span>.\
+<a href=\"https://hexdocs.pm/gleam_stdlib/gleam/option.html#Option\">\
+<span class=\"hljs-title\">Option</span></a>(<span class=\"hljs-title\">Int</span>)"
+    );
+}
+
+#[test]
+fn types_from_other_dependencies_are_not_linked() {
+    let hex_packages = HashSet::from(["gleam_stdlib".into()]);
+    let documented_modules = HashSet::new();
+    let linker = super::type_links::TypeLinker::new("app", &hex_packages, &documented_modules, ".");
+    let type_ = crate::type_::Type::Named {
+        publicity: crate::ast::Publicity::Public,
+        package: "local_package".into(),
+        module: "local/wibble".into(),
+        name: "Wibble".into(),
+        args: vec![],
+        inferred_variant: None,
+    };
+    assert_eq!(
+        linker.highlight("fn wibble() -> wibble.Wibble", [&type_]),
+        "<span class=\"hljs-keyword\">fn</span> <span class=\"hljs-title function_\">wibble</span>() \
+<span class=\"hljs-operator\">-&gt;</span> <span class=\"hljs-variable\">wibble</span>.\
+<span class=\"hljs-title\">Wibble</span>"
+    );
+}
+
+#[test]
+fn docs_version_is_written() {
+    let config = PackageConfig {
+        name: EcoString::from("test_project_name"),
+        version: hexpm::version::Version::new(1, 2, 3),
+        ..Default::default()
+    };
+    let paths = ProjectPaths::new(Utf8PathBuf::from("/"));
+    let version = super::generate_html(
+        &paths,
+        &config,
+        &HashSet::new(),
+        &[],
+        &[],
+        InMemoryFileSystem::new(),
+        SystemTime::UNIX_EPOCH,
+        DocContext::HexPublish,
+    )
+    .into_iter()
+    .find(|file| file.path.as_str() == super::DOCS_VERSION_FILE_NAME)
+    .expect("docs version file")
+    .content
+    .text()
+    .expect("text content")
+    .replace(COMPILER_VERSION, "GLEAM_VERSION_HERE");
+    assert_eq!(
+        version,
+        r#"{"name":"test_project_name","version":"1.2.3","gleam_version":"GLEAM_VERSION_HERE","url":"https://hexdocs.pm/test_project_name/1.2.3/"}"#
+    );
+}
+
+#[test]
+fn documentation_as_json() {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("test_project_name");
+    config.repository = Repository::GitHub {
+        user: "wibble".to_string(),
+        repo: "wobble".to_string(),
+        path: None,
+    };
+    let modules = vec![(
+        "app.gleam",
+        "
+//// The app.
+
+/// A wibble.
+pub type Wibble {
+  /// The only wibble.
+  Wibble(Int)
+  Wobble
+}
+
+@deprecated(\"Use wobble\")
+pub const wibble = 1
+
+/// Wobbles.
+///
+/// ```gleam
+/// wobble(1)
+/// ```
+pub fn wobble(times: Int) -> Wibble {
+  todo
+}
+
+@internal
+pub fn wubble() { 1 }
+",
+    )];
+    let modules = compile_modules(&config, modules);
+    let paths = ProjectPaths::new(Utf8PathBuf::from("/"));
+    let json = super::generate_json(&paths, &config, &modules);
+    let json: serde_json::Value =
+        serde_json::from_str(json.content.text().expect("text content")).expect("valid JSON");
+    insta::assert_snapshot!(serde_json::to_string_pretty(&json)
+        .expect("JSON")
+        .replace(COMPILER_VERSION, "GLEAM_VERSION_HERE"));
+}
+
+#[test]
+fn module_documentation_headings_are_linked_from_a_table_of_contents() {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("test_project_name");
+    let modules = vec![(
+        "app.gleam",
+        "
+//// An app.
+////
+//// ## Getting started
+////
+//// ### Installing `app`
+////
+//// ## Usage {#how-to-use}
+////
+
+pub fn main() { 1 }
+",
+    )];
+    insta::assert_snapshot!(compile(config, modules));
+}
+
+#[test]
+fn module_documentation_heading_ids_do_not_clash_with_members() {
+    let (html, headings) = super::render_module_documentation(
+        " # Main\n ## Main\n ## Types",
+        ["main", "module-types"],
+    );
+    assert_eq!(
+        html,
+        "<h1 id=\"main-1\">Main</h1>\n<h2 id=\"main-2\">Main</h2>\n<h2 id=\"types\">Types</h2>\n"
+    );
+    assert_eq!(
+        headings
+            .iter()
+            .map(|heading| (heading.level, heading.id.as_str()))
+            .collect_vec(),
+        vec![(0, "main-1"), (1, "main-2"), (1, "types")]
+    );
+}
+
+#[test]
+fn functions_limited_to_one_target_show_it() {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("test_project_name");
+    let modules = vec![(
+        "app.gleam",
+        r#"
+@external(erlang, "app_ffi", "erlang_only")
+pub fn erlang_only() -> Int
+
+@external(erlang, "app_ffi", "both")
+@external(javascript, "./app_ffi.mjs", "both")
+pub fn both() -> Int
+
+pub fn pure_gleam() -> Int {
+  1
+}
+
+@target(erlang)
+pub fn erlang_target() -> Int {
+  1
+}
+
+@target(erlang)
+pub fn both_targets() -> Int {
+  1
+}
+
+@target(javascript)
+pub fn both_targets() -> Int {
+  2
+}
+"#,
+    )];
+    insta::assert_snapshot!(compile(config, modules));
+}
+
+#[test]
+fn static_assets_are_copied() {
+    let mut config = PackageConfig::default();
+    config.documentation.logo = Some(Utf8PathBuf::from("assets/logo.svg"));
+    config.documentation.favicon = Some(Utf8PathBuf::from("favicon.png"));
+    config.documentation.assets = vec![Utf8PathBuf::from("assets")];
+    let fs = InMemoryFileSystem::new();
+    for path in [
+        "/assets/logo.svg",
+        "/assets/diagrams/flow.png",
+        "/favicon.png",
+    ] {
+        fs.write_bytes(&Utf8PathBuf::from(path), path.as_bytes())
+            .expect("write asset");
+    }
+    let paths = ProjectPaths::new(Utf8PathBuf::from("/"));
+
+    let files = super::static_assets(&paths, &config, &fs).expect("static assets");
+    assert_eq!(
+        files
+            .iter()
+            .map(|file| (file.path.as_str(), file.content.as_bytes()))
+            .sorted()
+            .collect_vec(),
+        vec![
+            (
+                "assets/diagrams/flow.png",
+                "/assets/diagrams/flow.png".as_bytes()
+            ),
+            ("assets/logo.svg", "/assets/logo.svg".as_bytes()),
+            ("favicon.png", "/favicon.png".as_bytes()),
+        ]
+    );
+}
+
+#[test]
+fn static_assets_must_be_within_the_package() {
+    let mut config = PackageConfig::default();
+    config.documentation.assets = vec![Utf8PathBuf::from("../secrets.txt")];
+    let fs = InMemoryFileSystem::new();
+    let paths = ProjectPaths::new(Utf8PathBuf::from("/project"));
+
+    assert_eq!(
+        super::static_assets(&paths, &config, &fs),
+        Err(crate::Error::DocsAssetOutsideProject {
+            path: Utf8PathBuf::from("../secrets.txt")
+        })
+    );
+}
+
+#[test]
+fn pages_link_to_the_logo_and_favicon() {
+    let mut config = PackageConfig::default();
+    config.name = EcoString::from("test_project_name");
+    config.documentation.logo = Some(Utf8PathBuf::from("assets/logo.svg"));
+    config.documentation.favicon = Some(Utf8PathBuf::from("favicon.png"));
+    let modules = vec![("app/wibble.gleam", "pub fn one() { 1 }")];
+
+    let html = compile(config, modules);
+    assert!(html.contains(r#"<link rel="icon" href=".."#));
+    assert!(html.contains(r#"<img class="project-logo" src="../assets/logo.svg" alt=""/>"#));
+}
@@ -0,0 +1,115 @@
+use std::collections::HashSet;
+
+use ecow::EcoString;
+
+use crate::{
+    ast::Publicity,
+    type_::{Type, TypeVar, PRELUDE_MODULE_NAME},
+};
+
+use super::highlight;
+
+/// Links the types named in printed definitions to their documentation, be
+/// it a module of the package being documented or a Hex dependency on
+/// HexDocs. Types from other dependencies, such as path and git ones, have no
+/// published documentation so they aren't linked.
+pub struct TypeLinker<'a> {
+    package: &'a str,
+    hex_packages: &'a HashSet<EcoString>,
+    documented_modules: &'a HashSet<EcoString>,
+    unnest: &'a str,
+}
+
+struct NamedType {
+    module: EcoString,
+    name: EcoString,
+    url: Option<String>,
+}
+
+impl<'a> TypeLinker<'a> {
+    pub fn new(
+        package: &'a str,
+        hex_packages: &'a HashSet<EcoString>,
+        documented_modules: &'a HashSet<EcoString>,
+        unnest: &'a str,
+    ) -> Self {
+        Self {
+            package,
+            hex_packages,
+            documented_modules,
+            unnest,
+        }
+    }
+
+    /// Highlights a printed definition, linking the names of the types it
+    /// uses. The types must be in the order they're printed, as a type that
+    /// clashes with one printed before it is qualified with its module.
+    pub fn highlight<'b>(&self, code: &str, types: impl IntoIterator<Item = &'b Type>) -> String {
+        let mut named_types = vec![];
+        for type_ in types {
+            self.named_types(type_, &mut named_types);
+        }
+        highlight::gleam(code, |qualifier, name| {
+            let mut candidates = named_types.iter().filter(|type_| type_.name == name);
+            let type_ = match qualifier {
+                Some(qualifier) => candidates.find(|type_| {
+                    type_.module == qualifier || type_.module.ends_with(&format!("/{qualifier}"))
+                }),
+                None => candidates.next(),
+            };
+            type_?.url.clone()
+        })
+    }
+
+    fn named_types(&self, type_: &Type, into: &mut Vec<NamedType>) {
+        match type_ {
+            Type::Named {
+                publicity,
+                package,
+                module,
+                name,
+                args,
+                ..
+            } => {
+                into.push(NamedType {
+                    module: module.clone(),
+                    name: name.clone(),
+                    url: self.url(*publicity, package, module, name),
+                });
+                for arg in args {
+                    self.named_types(arg, into);
+                }
+            }
+            Type::Fn { args, retrn } => {
+                for arg in args {
+                    self.named_types(arg, into);
+                }
+                self.named_types(retrn, into);
+            }
+            Type::Var { type_ } => {
+                if let TypeVar::Link { type_ } = &*type_.borrow() {
+                    self.named_types(type_, into);
+                }
+            }
+            Type::Tuple { elems } => {
+                for elem in elems {
+                    self.named_types(elem, into);
+                }
+            }
+        }
+    }
+
+    fn url(&self, publicity: Publicity, package: &str, module: &str, name: &str) -> Option<String> {
+        if !publicity.is_public() || module == PRELUDE_MODULE_NAME {
+            None
+        } else if package == self.package {
+            self.documented_modules
+                .contains(module)
+                .then(|| format!("{}/{module}.html#{name}", self.unnest))
+        } else if self.hex_packages.contains(package) {
+            Some(format!("https://hexdocs.pm/{package}/{module}.html#{name}"))
+        } else {
+            None
+        }
+    }
+}
@@ -24,10 +24,12 @@ use ecow::{eco_format, EcoString};
 use heck::ToSnakeCase;
 use im::HashSet;
 use itertools::Itertools;
+use num_bigint::BigInt;
+use num_traits::Signed;
 use pattern::pattern;
 use regex::{Captures, Regex};
 use std::sync::OnceLock;
-use std::{collections::HashMap, ops::Deref, str::FromStr, sync::Arc};
+use std::{collections::HashMap, ops::Deref, sync::Arc};
 use vec1::Vec1;
 
 const INDENT: isize = 4;
@@ -99,32 +101,52 @@ pub fn records(module: &TypedModule) -> Vec<(&str, String)> {
             }) => Some(constructors),
             _ => None,
         })
-        .flatten()
-        .filter(|constructor| !constructor.arguments.is_empty())
-        .filter_map(|constructor| {
-            constructor
-                .arguments
+        .flat_map(|constructors| {
+            // The only constructor of a type always gets a record definition,
+            // with positional names for any unlabelled fields, so that Erlang
+            // code can work with it without relying on tuple indices.
+            let single_constructor = constructors.len() == 1;
+            constructors
                 .iter()
-                .map(
-                    |RecordConstructorArg {
-                         label,
-                         ast: _,
-                         location: _,
-                         type_,
-                         ..
-                     }| {
-                        label
-                            .as_ref()
-                            .map(|(_, label)| (label.as_str(), type_.clone()))
-                    },
-                )
-                .collect::<Option<Vec<_>>>()
-                .map(|fields| (constructor.name.as_str(), fields))
+                .filter(|constructor| !constructor.arguments.is_empty())
+                .filter_map(move |constructor| {
+                    record_fields(&constructor.arguments, single_constructor)
+                        .map(|fields| (constructor.name.as_str(), fields))
+                })
+        })
+        .map(|(name, fields)| {
+            let fields = fields
+                .iter()
+                .map(|(name, type_)| (name.as_str(), type_.clone()))
+                .collect_vec();
+            (name, record_definition(name, &fields))
         })
-        .map(|(name, fields)| (name, record_definition(name, &fields)))
         .collect()
 }
 
+fn record_fields(
+    arguments: &[RecordConstructorArg<Arc<Type>>],
+    positional_names: bool,
+) -> Option<Vec<(EcoString, Arc<Type>)>> {
+    let fields = arguments
+        .iter()
+        .enumerate()
+        .map(|(index, argument)| {
+            let name = match &argument.label {
+                Some((_, label)) => label.clone(),
+                None if positional_names => eco_format!("field_{index}"),
+                None => return None,
+            };
+            Some((name, argument.type_.clone()))
+        })
+        .collect::<Option<Vec<_>>>()?;
+
+    // A positional name could clash with the label of another field, in
+    // which case no valid record can be defined.
+    let unique = fields.iter().map(|(name, _)| name).all_unique();
+    unique.then_some(fields)
+}
+
 pub fn record_definition(name: &str, fields: &[(&str, Arc<Type>)]) -> String {
     let name = &name.to_snake_case();
     let type_printer = TypePrinter::new("").var_as_any();
@@ -148,13 +170,25 @@ pub fn record_definition(name: &str, fields: &[(&str, Arc<Type>)]) -> String {
     .to_pretty_string(MAX_COLUMNS)
 }
 
-pub fn module<'a>(module: &'a TypedModule, line_numbers: &'a LineNumbers) -> Result<String> {
-    Ok(module_document(module, line_numbers)?.to_pretty_string(MAX_COLUMNS))
+/// Generates the Erlang module for a Gleam module. The minimum OTP version
+/// from the package's `gleam.toml`, if any, is used to pick the constructs
+/// that all the supported OTP releases can compile.
+pub fn module<'a>(
+    module: &'a TypedModule,
+    line_numbers: &'a LineNumbers,
+    minimum_otp_version: Option<u32>,
+) -> Result<String> {
+    let documentation = match minimum_otp_version {
+        Some(version) if version >= DOCUMENTATION_OTP_VERSION => DocumentationAttributes::Native,
+        Some(_) | None => DocumentationAttributes::Conditional,
+    };
+    Ok(module_document(module, line_numbers, documentation)?.to_pretty_string(MAX_COLUMNS))
 }
 
 fn module_document<'a>(
     module: &'a TypedModule,
     line_numbers: &'a LineNumbers,
+    documentation: DocumentationAttributes,
 ) -> Result<Document<'a>> {
     let mut exports = vec![];
     let mut type_defs = vec![];
@@ -162,7 +196,7 @@ fn module_document<'a>(
 
     let header = "-module("
         .to_doc()
-        .append(module.name.replace("/", "@"))
+        .append(module_name_atom(&module.name))
         .append(").")
         .append(line());
 
@@ -218,22 +252,138 @@ fn module_document<'a>(
     let src_path = EcoString::from(module.type_info.src_path.as_str());
 
     let statements = join(
-        module
-            .definitions
-            .iter()
-            .flat_map(|s| module_statement(s, &module.name, line_numbers, &src_path)),
+        module.definitions.iter().flat_map(|s| {
+            module_statement(s, &module.name, line_numbers, &src_path, documentation)
+        }),
         lines(2),
     );
 
+    let documentation = if module_has_documentation(module) {
+        let module_documentation = if module.documentation.is_empty() {
+            nil()
+        } else {
+            let text = module.documentation.iter().join("\n");
+            docvec![
+                documentation.module_doc_attribute(),
+                "(\"",
+                escape_erlang_string(&text),
+                "\").",
+                lines(2)
+            ]
+        };
+        match documentation {
+            DocumentationAttributes::Native => module_documentation,
+            DocumentationAttributes::Conditional => {
+                docvec![DOCUMENTATION_MACROS, lines(2), module_documentation]
+            }
+        }
+    } else {
+        nil()
+    };
+
     Ok(header
         .append("-compile([no_auto_import, nowarn_unused_vars, nowarn_unused_function, nowarn_nomatch]).")
         .append(lines(2))
+        .append(documentation)
         .append(exports)
         .append(type_defs)
         .append(statements)
         .append(line()))
 }
 
+/// The `-moduledoc` and `-doc` attributes are only supported starting from
+/// OTP 27.
+const DOCUMENTATION_OTP_VERSION: u32 = 27;
+
+/// How documentation is included in the generated Erlang.
+#[derive(Debug, Clone, Copy)]
+enum DocumentationAttributes {
+    /// All the OTP releases supported by the package support the
+    /// documentation attributes, so they are used directly.
+    Native,
+    /// The documentation attributes are used through macros which discard the
+    /// documentation on OTP releases that don't support them.
+    Conditional,
+}
+
+impl DocumentationAttributes {
+    fn module_doc_attribute(&self) -> &'static str {
+        match self {
+            DocumentationAttributes::Native => "-moduledoc",
+            DocumentationAttributes::Conditional => "?MODULEDOC",
+        }
+    }
+
+    fn doc_attribute(&self) -> &'static str {
+        match self {
+            DocumentationAttributes::Native => "-doc",
+            DocumentationAttributes::Conditional => "?DOC",
+        }
+    }
+}
+
+/// On older OTP releases the documentation is discarded.
+const DOCUMENTATION_MACROS: &str = "-if(?OTP_RELEASE >= 27).
+-define(MODULEDOC(Str), -moduledoc(Str)).
+-define(DOC(Str), -doc(Str)).
+-else.
+-define(MODULEDOC(Str), -compile([])).
+-define(DOC(Str), -compile([])).
+-endif.";
+
+fn module_has_documentation(module: &TypedModule) -> bool {
+    !module.documentation.is_empty()
+        || module
+            .definitions
+            .iter()
+            .any(|definition| match definition {
+                Definition::Function(function) => function_documentation(function).is_some(),
+                Definition::TypeAlias(_)
+                | Definition::CustomType(_)
+                | Definition::Import(_)
+                | Definition::ModuleConstant(_) => false,
+            })
+}
+
+/// The documentation of a function, if it is exported and documented, to be
+/// included in the EEP-48 documentation chunk of the compiled module.
+fn function_documentation(function: &TypedFunction) -> Option<&EcoString> {
+    if !function.publicity.is_importable() || !function.implementations.supports(Target::Erlang) {
+        return None;
+    }
+    function.documentation.as_ref().map(|(_, text)| text)
+}
+
+/// The text of a function's documentation, starting with its Gleam signature
+/// as it would be shown in the package's HTML documentation.
+fn function_documentation_text(function: &TypedFunction, documentation: &str) -> String {
+    let name = function
+        .name
+        .as_ref()
+        .map(|(_, name)| name.as_str())
+        .unwrap_or_default();
+    let signature = crate::format::Formatter::new()
+        .docs_fn_signature(
+            Publicity::Public,
+            name,
+            &function.arguments,
+            function.return_type.clone(),
+            &function.location,
+        )
+        .group()
+        .to_pretty_string(80);
+    format!("```gleam\n{signature}\n```\n\n{documentation}")
+}
+
+/// Escapes a string so it can be written in an Erlang string literal.
+pub fn escape_erlang_string(value: &str) -> EcoString {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .into()
+}
+
 fn register_imports(
     s: &TypedDefinition,
     exports: &mut Vec<Document<'_>>,
@@ -287,8 +437,9 @@ fn register_imports(
                     type_: Arc::new(std::cell::RefCell::new(TypeVar::Generic { id })),
                 })
                 .collect();
+            let var_names = collect_type_var_names(typed_parameters, None);
             let phantom_vars_constructor = if !phantom_vars.is_empty() {
-                let type_printer = TypePrinter::new(module_name);
+                let type_printer = TypePrinter::new(module_name).with_var_names(&var_names);
                 Some(tuple(
                     std::iter::once("gleam_phantom".to_doc())
                         .chain(phantom_vars.iter().map(|pv| type_printer.print(pv))),
@@ -316,7 +467,8 @@ fn register_imports(
                         if c.arguments.is_empty() {
                             name
                         } else {
-                            let type_printer = TypePrinter::new(module_name);
+                            let type_printer =
+                                TypePrinter::new(module_name).with_var_names(&var_names);
                             let args = c.arguments.iter().map(|a| type_printer.print(&a.type_));
                             tuple(std::iter::once(name).chain(args))
                         }
@@ -325,7 +477,7 @@ fn register_imports(
                 join(constructors, break_(" |", " | "))
             }
             .nest(INDENT);
-            let type_printer = TypePrinter::new(module_name);
+            let type_printer = TypePrinter::new(module_name).with_var_names(&var_names);
             let params = join(
                 typed_parameters.iter().map(|a| type_printer.print(a)),
                 ", ".to_doc(),
@@ -354,6 +506,7 @@ fn module_statement<'a>(
     module: &'a str,
     line_numbers: &'a LineNumbers,
     src_path: &EcoString,
+    documentation: DocumentationAttributes,
 ) -> Option<Document<'a>> {
     match statement {
         Definition::TypeAlias(TypeAlias { .. })
@@ -361,9 +514,13 @@ fn module_statement<'a>(
         | Definition::Import(Import { .. })
         | Definition::ModuleConstant(ModuleConstant { .. }) => None,
 
-        Definition::Function(function) => {
-            module_function(function, module, line_numbers, src_path.clone())
-        }
+        Definition::Function(function) => module_function(
+            function,
+            module,
+            line_numbers,
+            src_path.clone(),
+            documentation,
+        ),
     }
 }
 
@@ -372,6 +529,7 @@ fn module_function<'a>(
     module: &'a str,
     line_numbers: &'a LineNumbers,
     src_path: EcoString,
+    documentation_attributes: DocumentationAttributes,
 ) -> Option<Document<'a>> {
     // Private external functions don't need to render anything, the underlying
     // Erlang implementation is used directly at the call site.
@@ -397,7 +555,17 @@ fn module_function<'a>(
         HashMap::new(),
         std::iter::once(&function.return_type).chain(function.arguments.iter().map(|a| &a.type_)),
     );
-    let type_printer = TypePrinter::new(module).with_var_usages(&var_usages);
+    let var_names = collect_type_var_names(
+        function
+            .arguments
+            .iter()
+            .map(|a| &a.type_)
+            .chain(std::iter::once(&function.return_type)),
+        Some(&var_usages),
+    );
+    let type_printer = TypePrinter::new(module)
+        .with_var_usages(&var_usages)
+        .with_var_names(&var_names);
     let args_spec = function
         .arguments
         .iter()
@@ -424,10 +592,22 @@ fn module_function<'a>(
         })
         .unwrap_or_else(|| statement_sequence(&function.body, &mut env));
 
+    let documentation = match function_documentation(function) {
+        Some(text) => docvec![
+            documentation_attributes.doc_attribute(),
+            "(\"",
+            escape_erlang_string(&function_documentation_text(function, text)),
+            "\").",
+            line()
+        ],
+        None => nil(),
+    };
+
     Some(docvec![
+        documentation,
+        spec,
         file_attribute,
         line(),
-        spec,
         atom_string(escape_erlang_existing_name(function_name).to_string()),
         arguments,
         " ->",
@@ -436,16 +616,86 @@ fn module_function<'a>(
     ])
 }
 
+/// The `-file` attribute placed right before a function's head, after its
+/// documentation and spec, so that stack traces point to the Gleam source.
 fn file_attribute<'a>(
     path: EcoString,
     function: &'a TypedFunction,
     line_numbers: &'a LineNumbers,
 ) -> Document<'a> {
-    let line = line_numbers.line_number(function.location.start);
+    let line = file_attribute_line(function, line_numbers);
     let path = path.replace("\\", "\\\\");
     docvec!["-file(\"", path, "\", ", line, ")."]
 }
 
+/// The line used in the `-file` attribute of a function, which numbers the
+/// function's head.
+///
+/// The body of the function starts on the line after its head, so the head is
+/// numbered as the line before the first statement of the body. That way the
+/// first statement lines up with the Gleam source, even when it's on the same
+/// line as the function or the arguments span multiple lines.
+fn file_attribute_line(function: &TypedFunction, line_numbers: &LineNumbers) -> u32 {
+    match function.external_erlang {
+        Some(_) => line_numbers.line_number(function.location.start),
+        None => {
+            let body_line = line_numbers.line_number(function.body.first().location().start);
+            body_line.saturating_sub(1).max(1)
+        }
+    }
+}
+
+/// Finds the Gleam function a line reported by the Erlang compiler for the
+/// generated code of a module is in.
+///
+/// The `-file` attribute of a function only sets the line number of the line
+/// after it, the following lines are numbered from there and so they don't
+/// line up with the lines of the Gleam function. Instead the function whose
+/// generated code spans the reported line is found. If the generated code of
+/// multiple functions spans that line it can't be known which one it is in,
+/// and `None` is returned.
+pub fn function_at_reported_line<'a>(
+    module: &'a TypedModule,
+    line_numbers: &LineNumbers,
+    erlang: &str,
+    line: u32,
+) -> Option<&'a TypedFunction> {
+    // The first line and the number of lines of the code following each
+    // `-file` attribute.
+    let mut sections: Vec<(u32, u32)> = vec![];
+    for erlang_line in erlang.lines() {
+        let attribute_line = erlang_line
+            .strip_prefix("-file(")
+            .and_then(|attribute| attribute.strip_suffix(")."))
+            .and_then(|attribute| attribute.rsplit(", ").next())
+            .and_then(|line| line.parse().ok());
+        match (attribute_line, sections.last_mut()) {
+            (Some(first_line), _) => sections.push((first_line, 0)),
+            (None, Some((_, length))) => *length += 1,
+            (None, None) => (),
+        }
+    }
+
+    let first_line = sections
+        .iter()
+        .filter(|(first_line, length)| (*first_line..first_line + length).contains(&line))
+        .map(|(first_line, _)| *first_line)
+        .exactly_one()
+        .ok()?;
+
+    module
+        .definitions
+        .iter()
+        .find_map(|definition| match definition {
+            Definition::Function(function)
+                if file_attribute_line(function, line_numbers) == first_line =>
+            {
+                Some(function)
+            }
+            _ => None,
+        })
+}
+
 fn external_fun_args<'a>(args: &'a [TypedArg], env: &mut Env<'a>) -> Document<'a> {
     wrap_args(args.iter().map(|a| {
         let name = match &a.names {
@@ -521,7 +771,7 @@ fn atom(value: &str) -> Document<'_> {
     }
 }
 
-fn escape_atom_string(value: String) -> EcoString {
+pub(crate) fn escape_atom_string(value: String) -> EcoString {
     if is_erlang_reserved_word(&value) {
         // Escape because of keyword collision
         eco_format!("'{value}'")
@@ -637,9 +887,31 @@ fn string_concatenate<'a>(
     right: &'a TypedExpr,
     env: &mut Env<'a>,
 ) -> Document<'a> {
-    let left = string_concatenate_argument(left, env);
-    let right = string_concatenate_argument(right, env);
-    bit_array([left, right])
+    // A chain of concatenations is built as a single binary so that no
+    // intermediate binaries are created for each step of the chain.
+    let mut operands = vec![];
+    string_concatenate_operands(left, &mut operands);
+    string_concatenate_operands(right, &mut operands);
+    let operands = operands
+        .into_iter()
+        .map(|operand| string_concatenate_argument(operand, env))
+        .collect_vec();
+    bit_array(operands)
+}
+
+fn string_concatenate_operands<'a>(value: &'a TypedExpr, operands: &mut Vec<&'a TypedExpr>) {
+    match value {
+        TypedExpr::BinOp {
+            name: BinOp::Concatenate,
+            left,
+            right,
+            ..
+        } => {
+            string_concatenate_operands(left, operands);
+            string_concatenate_operands(right, operands);
+        }
+        _ => operands.push(value),
+    }
 }
 
 fn string_concatenate_argument<'a>(value: &'a TypedExpr, env: &mut Env<'a>) -> Document<'a> {
@@ -668,11 +940,6 @@ fn string_concatenate_argument<'a>(value: &'a TypedExpr, env: &mut Env<'a>) -> D
             ..
         } => docvec![env.local_var_name(name), "/binary"],
 
-        TypedExpr::BinOp {
-            name: BinOp::Concatenate,
-            ..
-        } => docvec![expr(value, env), "/binary"],
-
         _ => docvec!["(", maybe_block_expr(value, env), ")/binary"],
     }
 }
@@ -759,11 +1026,22 @@ fn expr_segment<'a>(
     };
 
     let size = |expression: &'a TypedExpr, env: &mut Env<'a>| match expression {
-        TypedExpr::Int { value, .. } => {
-            let v = value.replace("_", "");
-            let v = u64::from_str(&v).unwrap_or(0);
-            Some(eco_format!(":{v}").to_doc())
-        }
+        TypedExpr::Int { int_value, .. } => Some(known_segment_size(int_value)),
+
+        // Sizes given by an integer constant are known at compile time too,
+        // so they don't need to be checked at runtime.
+        TypedExpr::Var {
+            constructor:
+                ValueConstructor {
+                    variant:
+                        ValueConstructorVariant::ModuleConstant {
+                            literal: Constant::Int { int_value, .. },
+                            ..
+                        },
+                    ..
+                },
+            ..
+        } => Some(known_segment_size(int_value)),
 
         _ => {
             let inner_expr = expr(expression, env).surround("(", ")");
@@ -791,6 +1069,16 @@ fn expr_segment<'a>(
     )
 }
 
+/// The size of a segment that is known at compile time. Negative sizes are
+/// treated as zero, the same as sizes which are only known at runtime.
+fn known_segment_size<'a>(size: &BigInt) -> Document<'a> {
+    if size.is_negative() {
+        ":0".to_doc()
+    } else {
+        eco_format!(":{size}").to_doc()
+    }
+}
+
 fn bit_array_segment<'a, Value: 'a, CreateDoc, SizeToDoc, UnitToDoc>(
     mut create_document: CreateDoc,
     options: &'a [BitArrayOption<Value>],
@@ -1477,15 +1765,36 @@ fn clause_guard<'a>(guard: &'a TypedClauseGuard, env: &mut Env<'a>) -> Document<
 }
 
 fn clauses<'a>(cs: &'a [TypedClause], env: &mut Env<'a>) -> Document<'a> {
-    join(
-        cs.iter().map(|c| {
-            let vars = env.current_scope_vars.clone();
-            let erl = clause(c, env);
-            env.current_scope_vars = vars; // Reset the known variables now the clauses' scope has ended
-            erl
-        }),
+    // The variables bound in a clause can't be used by the other clauses, so
+    // each clause starts numbering its variables from where the numbering was
+    // before the case expression, keeping the suffixes as small as possible.
+    // Once the case expression is done the numbering continues from the
+    // highest number used by any of the clauses, so the variables defined
+    // afterwards can never clash with the ones bound in the clauses.
+    let initial_erlang_vars = env.erl_function_scope_vars.clone();
+    let mut end_erlang_vars = initial_erlang_vars.clone();
+
+    let doc = join(
+        cs.iter()
+            .map(|c| {
+                let vars = env.current_scope_vars.clone();
+                env.erl_function_scope_vars = initial_erlang_vars.clone();
+                let erl = clause(c, env);
+                env.current_scope_vars = vars; // Reset the known variables now the clauses' scope has ended
+                for (name, count) in &env.erl_function_scope_vars {
+                    let _ = end_erlang_vars
+                        .entry(name.clone())
+                        .and_modify(|end_count| *end_count = (*end_count).max(*count))
+                        .or_insert(*count);
+                }
+                erl
+            })
+            .collect_vec(),
         ";".to_doc().append(lines(2)),
-    )
+    );
+
+    env.erl_function_scope_vars = end_erlang_vars;
+    doc
 }
 
 fn case<'a>(subjects: &'a [TypedExpr], cs: &'a [TypedClause], env: &mut Env<'a>) -> Document<'a> {
@@ -2125,6 +2434,51 @@ fn collect_type_var_usages<'a>(
     ids
 }
 
+// Type variables are named `A`, `B`, `C`... in the order they first appear in
+// the given types, so that the generated names only depend on the types
+// themselves and not on the ids the type checker happened to give them.
+// Variables printed as `any()` are skipped so they don't use up a name.
+fn collect_type_var_names<'a>(
+    types: impl IntoIterator<Item = &'a Arc<Type>>,
+    var_usages: Option<&HashMap<u64, u64>>,
+) -> HashMap<u64, u64> {
+    let mut ids = vec![];
+    for type_ in types {
+        ordered_type_var_ids(type_, &mut ids);
+    }
+    ids.into_iter()
+        .filter(|id| var_usages.is_none_or(|usages| usages.get(id).is_some_and(|n| *n > 1)))
+        .unique()
+        .enumerate()
+        .map(|(index, id)| (id, index as u64))
+        .collect()
+}
+
+fn ordered_type_var_ids(type_: &Type, ids: &mut Vec<u64>) {
+    match type_ {
+        Type::Var { type_ } => match type_.borrow().deref() {
+            TypeVar::Generic { id, .. } | TypeVar::Unbound { id, .. } => ids.push(*id),
+            TypeVar::Link { type_ } => ordered_type_var_ids(type_, ids),
+        },
+        Type::Named { args, .. } => {
+            for arg in args {
+                ordered_type_var_ids(arg, ids)
+            }
+        }
+        Type::Fn { args, retrn } => {
+            for arg in args {
+                ordered_type_var_ids(arg, ids)
+            }
+            ordered_type_var_ids(retrn, ids);
+        }
+        Type::Tuple { elems } => {
+            for elem in elems {
+                ordered_type_var_ids(elem, ids)
+            }
+        }
+    }
+}
+
 fn result_type_var_ids(ids: &mut HashMap<u64, u64>, arg_ok: &Type, arg_err: &Type) {
     let mut ok_ids = HashMap::new();
     type_var_ids(arg_ok, &mut ok_ids);
@@ -2242,6 +2596,7 @@ struct TypePrinter<'a> {
     var_as_any: bool,
     current_module: &'a str,
     var_usages: Option<&'a HashMap<u64, u64>>,
+    var_names: Option<&'a HashMap<u64, u64>>,
 }
 
 impl<'a> TypePrinter<'a> {
@@ -2249,6 +2604,7 @@ impl<'a> TypePrinter<'a> {
         Self {
             current_module,
             var_usages: None,
+            var_names: None,
             var_as_any: false,
         }
     }
@@ -2258,6 +2614,13 @@ impl<'a> TypePrinter<'a> {
         self
     }
 
+    /// Print type vars using the names collected by `collect_type_var_names`
+    /// rather than their ids, so that they don't change between compilations.
+    pub fn with_var_names(mut self, var_names: &'a HashMap<u64, u64>) -> Self {
+        self.var_names = Some(var_names);
+        self
+    }
+
     pub fn print(&self, type_: &Type) -> Document<'static> {
         match type_ {
             Type::Var { type_ } => self.print_var(&type_.borrow()),
@@ -2285,14 +2648,21 @@ impl<'a> TypePrinter<'a> {
                 Some(usages) => match usages.get(id) {
                     Some(&0) => nil(),
                     Some(&1) => "any()".to_doc(),
-                    _ => id_to_type_var(*id),
+                    _ => self.print_var_name(*id),
                 },
-                None => id_to_type_var(*id),
+                None => self.print_var_name(*id),
             },
             TypeVar::Link { type_ } => self.print(type_),
         }
     }
 
+    fn print_var_name(&self, id: u64) -> Document<'static> {
+        match self.var_names.and_then(|names| names.get(&id)) {
+            Some(index) => id_to_type_var(*index),
+            None => id_to_type_var(id),
+        }
+    }
+
     fn print_prelude_type(&self, name: &str, args: &[Arc<Type>]) -> Document<'static> {
         match name {
             "Nil" => "nil".to_doc(),
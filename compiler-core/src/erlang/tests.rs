@@ -1,12 +1,13 @@
 use camino::Utf8PathBuf;
+use std::time::SystemTime;
 
 use crate::analyse::TargetSupport;
 use crate::config::PackageConfig;
 use crate::type_::PRELUDE_MODULE_NAME;
 use crate::warning::WarningEmitter;
 use crate::{
-    build::{Origin, Target},
-    erlang::module,
+    build::{Module, Origin, Target},
+    erlang,
     line_numbers::LineNumbers,
     uid::UniqueIdGenerator,
     warning::TypeWarningEmitter,
@@ -17,6 +18,7 @@ mod case;
 mod conditional_compilation;
 mod consts;
 mod custom_types;
+mod documentation;
 mod external_fn;
 mod functions;
 mod guards;
@@ -26,6 +28,7 @@ mod panic;
 mod patterns;
 mod pipes;
 mod records;
+mod reported_lines;
 mod reserved;
 mod strings;
 mod todo;
@@ -34,6 +37,12 @@ mod use_;
 mod variables;
 
 pub fn compile_test_project(src: &str, src_path: &str, dep: Option<(&str, &str, &str)>) -> String {
+    let module = analyse_test_project(src, src_path, dep);
+    let line_numbers = LineNumbers::new(src);
+    erlang::module(&module.ast, &line_numbers, None).unwrap()
+}
+
+fn analyse_test_project(src: &str, src_path: &str, dep: Option<(&str, &str, &str)>) -> Module {
     let mut modules = im::HashMap::new();
     let ids = UniqueIdGenerator::new();
     // DUPE: preludeinsertion
@@ -91,10 +100,20 @@ pub fn compile_test_project(src: &str, src_path: &str, dep: Option<(&str, &str,
         target_support: TargetSupport::NotEnforced,
         package_config: &config,
     }
-    .infer_module(ast, line_numbers, path)
+    .infer_module(ast, line_numbers, path.clone())
     .expect("should successfully infer root Erlang");
-    let line_numbers = LineNumbers::new(src);
-    module(&ast, &line_numbers).unwrap()
+    let mut module = Module {
+        name: "my/mod".into(),
+        code: src.into(),
+        mtime: SystemTime::UNIX_EPOCH,
+        input_path: path,
+        origin: Origin::Src,
+        ast,
+        extra: parsed.extra,
+        dependencies: vec![],
+    };
+    module.attach_doc_and_module_comments();
+    module
 }
 
 #[macro_export]
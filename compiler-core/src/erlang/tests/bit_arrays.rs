@@ -204,3 +204,17 @@ pub fn main() {
 }"#
     );
 }
+
+#[test]
+fn statically_known_sizes() {
+    // Sizes known at compile time don't need to be clamped to zero at runtime
+    assert_erl!(
+        r#"
+const size = 16
+const negative = -8
+pub fn main() {
+  <<1:size(size), 2:size(0x10), 3:size(negative), 4:size(1_024)>>
+}
+"#
+    );
+}
@@ -101,3 +101,27 @@ pub fn main() {
 "#,
     );
 }
+
+#[test]
+fn nested_patterns_are_flat_clauses() {
+    // Nested patterns and multiple subjects are compiled to the clauses of a
+    // single Erlang case expression, which the Erlang compiler turns into a
+    // decision tree, rather than to nested case expressions.
+    assert_erl!(
+        r#"
+pub type Shape {
+  Circle(radius: Int)
+  Rectangle(width: Int, height: Int)
+}
+
+pub fn main(shapes, scale) {
+  case shapes, scale {
+    [Circle(0), ..], _ -> 0
+    [Circle(radius), Rectangle(width, _)], 1 -> radius + width
+    [Rectangle(width, height), ..], _ -> width * height * scale
+    _, _ -> -1
+  }
+}
+"#
+    );
+}
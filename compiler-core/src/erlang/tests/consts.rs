@@ -206,3 +206,30 @@ fn use_private_in_tuple() {
         "#
     )
 }
+
+#[test]
+fn imported_constants_are_inlined() {
+    // References to constants are compiled to the constant's value rather
+    // than a call to the module that defines it.
+    assert_erl!(
+        (
+            "thepackage",
+            "other",
+            r#"
+pub const limit = 100
+pub const names = ["Lucy", "Nubi"]
+pub const greeting = "Hello, " <> "Joe"
+"#
+        ),
+        r#"
+import other.{names}
+
+pub fn loop(i) {
+  case i > other.limit {
+    True -> #(names, other.greeting)
+    False -> loop(i + 1)
+  }
+}
+"#
+    );
+}
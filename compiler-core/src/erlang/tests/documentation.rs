@@ -0,0 +1,64 @@
+use crate::assert_erl;
+
+#[test]
+fn module_documentation() {
+    assert_erl!(
+        r#"
+//// This module has some documentation.
+//// It spans multiple lines.
+
+pub fn main() {
+  1
+}
+"#
+    );
+}
+
+#[test]
+fn function_documentation() {
+    assert_erl!(
+        r#"
+/// Adds one to the given number.
+/// Escapes "quotes" and \backslashes\.
+pub fn add_one(n: Int) -> Int {
+  n + 1
+}
+
+/// Private functions are not documented.
+fn private() {
+  1
+}
+
+pub fn main() {
+  private()
+}
+"#
+    );
+}
+
+#[test]
+fn no_documentation_macros_without_documentation() {
+    assert_erl!(
+        r#"
+/// Private functions are not documented.
+fn private() {
+  1
+}
+
+pub fn main() {
+  private()
+}
+"#
+    );
+}
+
+#[test]
+fn external_function_documentation() {
+    assert_erl!(
+        r#"
+/// Returns the current time.
+@external(erlang, "erlang", "system_time")
+pub fn now() -> Int
+"#
+    );
+}
@@ -137,3 +137,22 @@ pub fn main() {
 "
     );
 }
+
+#[test]
+fn file_attribute_lines_up_function_body() {
+    // The `-file` attribute right before each function head numbers it as the
+    // line before the first statement of the body, so the first statement has
+    // the line it has in the Gleam source.
+    assert_erl!(
+        r#"
+pub fn add(
+  x: Int,
+  y: Int,
+) -> Int {
+  x + y
+}
+
+pub fn one() { 1 }
+"#
+    );
+}
@@ -0,0 +1,94 @@
+use crate::{erlang, line_numbers::LineNumbers};
+
+use super::analyse_test_project;
+
+/// The name of the Gleam function the Erlang compiler reported line is mapped
+/// back to.
+fn function_at_reported_line(src: &str, line: u32) -> Option<String> {
+    let module = analyse_test_project(src, "/root/project/test/my/mod.gleam", None);
+    let line_numbers = LineNumbers::new(src);
+    let erlang = erlang::module(&module.ast, &line_numbers, None).expect("Erlang code");
+    let function = erlang::function_at_reported_line(&module.ast, &line_numbers, &erlang, line)?;
+    let (_, name) = function.name.as_ref()?;
+    Some(name.to_string())
+}
+
+/// The line number the Erlang compiler gives to a line of generated code. The
+/// line after a `-file` attribute has the line number of the attribute.
+fn reported_line(src: &str, erlang_line: &str) -> Option<u32> {
+    let module = analyse_test_project(src, "/root/project/test/my/mod.gleam", None);
+    let line_numbers = LineNumbers::new(src);
+    let erlang = erlang::module(&module.ast, &line_numbers, None).expect("Erlang code");
+    let mut line = None;
+    for text in erlang.lines() {
+        let attribute_line = text
+            .strip_prefix("-file(")
+            .and_then(|attribute| attribute.strip_suffix(")."))
+            .and_then(|attribute| attribute.rsplit(", ").next())
+            .and_then(|line| line.parse::<u32>().ok());
+        line = match (attribute_line, line) {
+            (Some(attribute_line), _) => Some(attribute_line - 1),
+            (None, Some(line)) => Some(line + 1),
+            (None, None) => None,
+        };
+        if attribute_line.is_none() && text.trim() == erlang_line {
+            return line;
+        }
+    }
+    None
+}
+
+#[test]
+fn line_in_multi_line_body() {
+    let src = r#"pub fn main() {
+  let x = 1
+  let y = 2
+  x + y
+}
+
+pub fn other() {
+  2
+}
+"#;
+    // The `-file` attribute of `main` numbers its head as line 1, so the 3
+    // lines of its body are lines 2 to 4.
+    assert_eq!(function_at_reported_line(src, 2), Some("main".into()));
+    assert_eq!(function_at_reported_line(src, 4), Some("main".into()));
+    assert_eq!(function_at_reported_line(src, 8), Some("other".into()));
+}
+
+#[test]
+fn line_in_body_longer_than_gleam_function() {
+    // The generated code of `main` spans more lines than the Gleam function,
+    // so it overlaps with the lines of `other`.
+    let src = r#"pub fn main() { let x = 1 let y = 2 x + y }
+pub fn other() { 2 }
+"#;
+    assert_eq!(function_at_reported_line(src, 2), None);
+    assert_eq!(function_at_reported_line(src, 4), Some("main".into()));
+}
+
+#[test]
+fn line_outside_of_any_function() {
+    let src = r#"pub fn main() {
+  1
+}
+"#;
+    assert_eq!(function_at_reported_line(src, 100), None);
+}
+
+#[test]
+fn first_statement_of_body_is_on_its_gleam_line() {
+    let src = r#"/// Adds two numbers.
+pub fn add(
+  x: Int,
+  y: Int,
+) -> Int {
+  x + y
+}
+
+pub fn one() { 1 }
+"#;
+    assert_eq!(reported_line(src, "X + Y."), Some(6));
+    assert_eq!(reported_line(src, "1."), Some(9));
+}
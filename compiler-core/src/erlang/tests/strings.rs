@@ -134,6 +134,19 @@ pub fn go(x, y, z) {
     );
 }
 
+#[test]
+fn concat_chain_is_a_single_binary() {
+    assert_erl!(
+        r#"
+pub fn go(name, count) {
+  "Hello, " <> name <> "! You have " <> count() <> " new messages." <> {
+    "Bye" <> name
+  }
+}
+"#,
+    );
+}
+
 #[test]
 fn string_prefix() {
     assert_erl!(
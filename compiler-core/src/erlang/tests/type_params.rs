@@ -111,3 +111,29 @@ fn custom_type_tuple_type_params_count_twice() {
         "
     );
 }
+
+#[test]
+fn type_variables_are_named_in_order_of_appearance() {
+    assert_erl!(
+        "
+        pub type Pair(a, b) {
+          Pair(first: a, second: b)
+        }
+
+        pub fn swap(pair: Pair(x, y), default: y, other: x) -> #(y, x) {
+          #(default, other)
+        }
+        "
+    );
+}
+
+#[test]
+fn type_variables_used_once_do_not_use_up_names() {
+    assert_erl!(
+        "
+        pub fn second(_first: a, second: b, _third: c) -> fn(d) -> b {
+          fn(_) { second }
+        }
+        "
+    );
+}
@@ -109,3 +109,20 @@ pub fn main() {
 "
     )
 }
+
+#[test]
+fn case_clauses_reuse_variable_names() {
+    assert_erl!(
+        r#"
+pub fn main(x) {
+  let y = case x {
+    [first] -> first
+    [first, second] -> first + second
+    _ -> 0
+  }
+  let first = y
+  first
+}
+"#
+    );
+}
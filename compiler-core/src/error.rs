@@ -2,10 +2,10 @@
 use crate::build::{Outcome, Runtime, Target};
 use crate::diagnostic::{Diagnostic, ExtraLabel, Label, Location};
 use crate::type_::error::{
-    MissingAnnotation, ModuleValueUsageContext, Named, UnknownField, UnknownTypeHint,
-    UnsafeRecordUpdateReason,
+    ArityFix, MissingAnnotation, ModuleValueUsageContext, Named, TypePathStep, UnknownField,
+    UnknownTypeHint, UnsafeRecordUpdateReason,
 };
-use crate::type_::printer::{Names, Printer};
+use crate::type_::printer::{NameContextInformation, Names, Printer};
 use crate::type_::{error::PatternMatchKind, FieldAccessUsage};
 use crate::{ast::BinOp, parse::error::ParseErrorType, type_::Type};
 use crate::{bit_array, diagnostic::Level, javascript, type_::UnifyErrorSituation};
@@ -135,6 +135,12 @@ pub enum Error {
     #[error("source code incorrectly formatted")]
     Format { problem_files: Vec<Unformatted> },
 
+    #[error("formatting is not idempotent")]
+    UnstableFormat {
+        path: Utf8PathBuf,
+        unstable: crate::format::Unstable,
+    },
+
     #[error("Hex error: {0}")]
     Hex(String),
 
@@ -153,6 +159,11 @@ pub enum Error {
     #[error("shell program `{program}` not found")]
     ShellProgramNotFound { program: String, os: OS },
 
+    #[error("Erlang compilation failed")]
+    ErlangCompilation {
+        errors: Vec<crate::io::ErlangCompilerDiagnostic>,
+    },
+
     #[error("shell program `{program}` failed")]
     ShellCommand {
         program: String,
@@ -183,6 +194,9 @@ pub enum Error {
     #[error("{module}'s main function does not support the current target")]
     MainFunctionDoesNotSupportTarget { module: EcoString, target: Target },
 
+    #[error("application start module {module} does not have a start/2 function")]
+    ApplicationStartModuleWithoutStartFunction { module: EcoString },
+
     #[error("{input} is not a valid version. {error}")]
     InvalidVersionFormat { input: String, error: String },
 
@@ -275,6 +289,12 @@ file_names.iter().map(|x| x.as_str()).join(", "))]
     #[error("Opening docs at {path} failed: {error}")]
     FailedToOpenDocs { path: Utf8PathBuf, error: String },
 
+    #[error("Serving docs at {address} failed: {error}")]
+    FailedToStartDocsServer { address: String, error: String },
+
+    #[error("The documentation asset {path} is outside of the package")]
+    DocsAssetOutsideProject { path: Utf8PathBuf },
+
     #[error(
         "The package {package} requires a Gleam version satisfying \
 {required_version} and you are using v{gleam_version}"
@@ -288,6 +308,12 @@ file_names.iter().map(|x| x.as_str()).join(", "))]
     #[error("The --javascript-prelude flag must be given when compiling to JavaScript")]
     JavaScriptPreludeRequired,
 
+    #[error("Browser exports require ES modules")]
+    BrowserExportRequiresEsModules,
+
+    #[error("The escript is too large")]
+    EscriptTooLarge,
+
     #[error("The modules {unfinished:?} contain todo expressions and so cannot be published")]
     CannotPublishTodo { unfinished: Vec<EcoString> },
 
@@ -905,6 +931,22 @@ target, so it cannot be run."
                 hint: None,
             }],
 
+            Error::ApplicationStartModuleWithoutStartFunction { module } => vec![Diagnostic {
+                title: "Invalid application start module".into(),
+                text: wrap_format!(
+                    "`{module}` is the application start module in gleam.toml, but it \
+does not have a public `start` function that takes 2 arguments and can run \
+on Erlang, so the application can not be started."
+                ),
+                level: Level::Error,
+                location: None,
+                hint: Some(
+                    "Add a public `start` function that starts the application's \
+supervision tree to the module."
+                        .into(),
+                ),
+            }],
+
             Error::MainFunctionHasWrongArity { module, arity } => vec![Diagnostic {
                 title: "Main function has wrong arity".into(),
                 text: format!(
@@ -1138,6 +1180,13 @@ https://git-scm.com/book/en/v2/Getting-Started-Installing-Git",
                 }]
             }
 
+            Error::ErlangCompilation { errors } => errors
+                .iter()
+                .map(|error| {
+                    erlang_compiler_diagnostic(error, "Erlang compilation error", Level::Error)
+                })
+                .collect_vec(),
+
             Error::ShellCommand {
                 program: command,
                 err: None,
@@ -1714,20 +1763,113 @@ Names in a Gleam module must be unique so one will need to be renamed."
                     }
                 }
 
-                TypeError::RecursiveType { location } => {
-                    let text = wrap("I don't know how to work out what type this \
-value has. It seems to be defined in terms of itself.
+                TypeError::CaseClauseMismatch { location, clauses } => {
+                    let mut printer = Printer::new(names);
+                    let clauses = clauses
+                        .iter()
+                        .map(|(span, type_)| (*span, printer.print_type(type_)))
+                        .collect_vec();
+                    let mut text = wrap(
+                        "All the clauses of a case expression must return the same type, \
+but these clauses were found to return different types:",
+                    );
+                    text.push('\n');
+                    for type_ in clauses.iter().map(|(_, type_)| type_).unique() {
+                        text.push_str(&format!("\n    {type_}"));
+                    }
+                    let label = |span: crate::ast::SrcSpan, type_: &EcoString| Label {
+                        text: Some(format!("This returns `{type_}`")),
+                        span,
+                    };
+                    let extra_labels = clauses
+                        .iter()
+                        .filter(|(span, _)| span != location)
+                        .map(|(span, type_)| ExtraLabel {
+                            src_info: None,
+                            label: label(*span, type_),
+                        })
+                        .collect();
+                    let primary_type = clauses
+                        .iter()
+                        .find(|(span, _)| span == location)
+                        .map(|(_, type_)| type_.clone())
+                        .unwrap_or_default();
+                    Diagnostic {
+                        title: "Type mismatch".into(),
+                        text,
+                        hint: None,
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: label(*location, &primary_type),
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels,
+                        }),
+                    }
+                }
+
+                TypeError::RecursiveType {
+                    location,
+                    variable,
+                    type_,
+                } => {
+                    let mut printer = Printer::new(names);
+                    let variable_name = printer.print_type(variable);
+                    let type_name = printer.print_type(type_);
+                    // To show how the recursion could be broken we print the
+                    // type again, with the variable replaced by a custom type
+                    // taking any other type variables as parameters.
+                    let variable_id = variable.type_variable_id();
+                    let mut wrapper_names = names.clone();
+                    let parameters = type_
+                        .type_variable_ids()
+                        .into_iter()
+                        .filter(|id| Some(*id) != variable_id)
+                        .map(|id| {
+                            let name = printer.type_variable(id);
+                            wrapper_names.type_variable_in_scope(id, name.clone());
+                            name
+                        })
+                        .collect_vec();
+                    let wrapper = if parameters.is_empty() {
+                        "Wrapper".to_string()
+                    } else {
+                        format!("Wrapper({})", parameters.join(", "))
+                    };
+                    if let Some(id) = variable_id {
+                        wrapper_names.type_variable_in_scope(id, wrapper.as_str().into());
+                    }
+                    let wrapped = Printer::new(&wrapper_names).print_type(type_);
+                    let text = format!(
+                        "The type of this value would need to be defined in terms of itself:
+
+    {variable_name} = {type_name}
+
+{}
+
+{}
+
+    type {wrapper} {{
+      Wrapper({wrapped})
+    }}",
+                        wrap(&format!(
+                            "As `{variable_name}` appears in its own definition \
+this type would be infinitely large, but Gleam's types must be finite."
+                        )),
+                        wrap(
+                            "Hint: If you need a recursive type you can wrap the \
+value in a custom type, for example:"
+                        )
+                    );
 
-Hint: Add some type annotations and try again.")
-                        ;
                     Diagnostic {
-                        title: "Recursive type".into(),
+                        title: "Infinite type".into(),
                         text,
                         hint: None,
                         level: Level::Error,
                         location: Some(Location {
                             label: Label {
-                                text: None,
+                                text: Some("This would have an infinite type".into()),
                                 span: *location,
                             },
                             path: path.clone(),
@@ -1793,7 +1935,50 @@ across all variants.");
                     }
 
                     match variants {
-                        UnknownField::AppearsInAVariant => {
+                        UnknownField::AppearsInAVariant {
+                            variants,
+                            module,
+                            record_location,
+                        } if variants.iter().any(|variant| !variant.has_field) => {
+                            let msg = wrap(
+                                "Note: The field you are trying to access is \
+not defined in all variants of this custom type. These variants do not have it:",
+                            );
+                            text.push_str("\n\n");
+                            text.push_str(&msg);
+                            text.push('\n');
+                            for variant in variants.iter().filter(|variant| !variant.has_field) {
+                                text.push_str("\n    ");
+                                text.push_str(&variant.name);
+                            }
+
+                            let subject = record_location
+                                .and_then(|location| src.get(location.start as usize..location.end as usize))
+                                .filter(|subject| !subject.contains('\n'))
+                                .unwrap_or("value");
+                            text.push_str("\n\nYou can use a case expression to access it:\n\n    case ");
+                            text.push_str(subject);
+                            text.push_str(" {");
+                            for variant in variants {
+                                let name = match names.named_constructor(module, &variant.name) {
+                                    NameContextInformation::Qualified(module, name) => format!("{module}.{name}"),
+                                    NameContextInformation::Unqualified(name) => name.to_string(),
+                                    NameContextInformation::Unimported(name) => {
+                                        format!("{}.{name}", module.split('/').next_back().unwrap_or(module))
+                                    }
+                                };
+                                let clause = match (variant.has_field, variant.arity) {
+                                    (_, 0) => format!("{name} -> todo"),
+                                    (true, 1) => format!("{name}({label}:) -> {label}"),
+                                    (true, _) => format!("{name}({label}:, ..) -> {label}"),
+                                    (false, _) => format!("{name}(..) -> todo"),
+                                };
+                                text.push_str("\n      ");
+                                text.push_str(&clause);
+                            }
+                            text.push_str("\n    }");
+                        }
+                        UnknownField::AppearsInAVariant { .. } => {
                             let msg = wrap(
                                 "Note: The field you are trying to access is \
 not defined consistently across all variants of this custom type. To fix this, \
@@ -1857,6 +2042,7 @@ to call a method on this value you may want to use the function syntax instead."
                     expected,
                     given,
                     situation: Some(UnifyErrorSituation::Operator(op)),
+                    ..
                 } => {
                     let mut printer = Printer::new(names);
                     let mut text = format!(
@@ -1898,6 +2084,7 @@ But this argument has this type:
                     expected,
                     given,
                     situation: Some(UnifyErrorSituation::PipeTypeMismatch),
+                    ..
                 } => {
                     // Remap the pipe function type into just the type expected by the pipe.
                     let expected = expected
@@ -1947,6 +2134,7 @@ But function expects:
                     expected,
                     given,
                     situation,
+                    mismatch_path,
                 } => {
                     let mut printer = Printer::new(names);
                     let mut text = if let Some(description) = situation.as_ref().and_then(|s| s.description()) {
@@ -1957,10 +2145,10 @@ But function expects:
                     } else {
                         "".into()
                     };
-                    text.push_str("Expected type:\n\n    ");
-                    text.push_str(&printer.print_type(expected));
-                    text.push_str("\n\nFound type:\n\n    ");
-                    text.push_str(&printer.print_type(given));
+                    text.push_str("Expected type:\n\n");
+                    text.push_str(&print_mismatched_type(&mut printer, expected, mismatch_path));
+                    text.push_str("\n\nFound type:\n\n");
+                    text.push_str(&print_mismatched_type(&mut printer, given, mismatch_path));
                     Diagnostic {
                         title: "Type mismatch".into(),
                         text,
@@ -2013,6 +2201,7 @@ called with their expected number of arguments.");
                     location,
                     expected,
                     given,
+                    fixes,
                 } => {
                     let text = if labels.is_empty() {
                         "".into()
@@ -2030,10 +2219,11 @@ called with their expected number of arguments.");
                         _ => format!("{expected} arguments"),
                     };
                     let label = format!("Expected {expected}, got {given}");
+                    let hint = arity_fixes_hint(fixes, names);
                     Diagnostic {
                         title: "Incorrect arity".into(),
                         text,
-                        hint: None,
+                        hint,
                         level: Level::Error,
                         location: Some(Location {
                             label: Label {
@@ -2095,9 +2285,19 @@ constructing a new record with its values.");
                                 }),
                             }
                         },
-                        UnsafeRecordUpdateReason::WrongVariant {constructed_variant, spread_variant} => {
+                        UnsafeRecordUpdateReason::WrongVariant {constructed_variant, spread_variant, missing_fields} => {
+                            let missing_fields = if missing_fields.is_empty() {
+                                "".into()
+                            } else {
+                                let fields = missing_fields
+                                    .iter()
+                                    .map(|field| format!("  - {field}"))
+                                    .join("\n");
+                                format!("\n\nA `{spread_variant}` doesn't have these fields \
+that would be copied into the new `{constructed_variant}`:\n\n{fields}")
+                            };
                             let text = wrap_format!("This value is a `{spread_variant}` so \
-it cannot be used to build a `{constructed_variant}`, even if they share some fields.
+it cannot be used to build a `{constructed_variant}`, even if they share some fields.{missing_fields}
 
 Note: If you want to change one variant of a type into another, you should \
 specify all fields explicitly instead of using the record update syntax.");
@@ -2216,6 +2416,36 @@ but no type in scope with that name."
                     }
                 }
 
+                TypeError::ValueDefinedForOtherTarget {
+                    location,
+                    name,
+                    target,
+                    defined_for,
+                } => {
+                    let text = wrap_format!(
+                        "`{name}` is only defined for the {defined_for} target, so it \
+cannot be used when compiling for the {target} target."
+                    );
+                    Diagnostic {
+                        title: "Value not defined for target".into(),
+                        text,
+                        hint: Some(format!(
+                            "Define `{name}` for the {target} target too, \
+or use `@target({defined_for})` here as well."
+                        )),
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
                 TypeError::PrivateTypeLeak { location, leaked } => {
                     let mut printer = Printer::new(names);
 
@@ -2342,6 +2572,33 @@ Private types can only be used within the module that defines them.",
                     }
                 }
 
+                TypeError::OpaqueTypeConstructor {
+                    location,
+                    name,
+                    module_name,
+                    type_name,
+                } => {
+                    let text = wrap_format!("The type `{type_name}` is opaque, so its \
+constructors can only be used in the `{module_name}` module where it is defined.");
+                    Diagnostic {
+                        title: "Use of opaque type constructor".into(),
+                        text,
+                        hint: Some(format!(
+                            "Use the functions exposed by the `{module_name}` module instead."
+                        )),
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: Some(format!("`{name}` is not public")),
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
                 TypeError::ModuleAliasUsedAsName {
                     location,
                     name
@@ -2643,6 +2900,18 @@ function and try again.");
                                 vec!["Hint: floats have an exact size of 16/32/64 bits.".into()],
                             ),
 
+                            bit_array::ErrorType::FloatSizeRequiresNewerOtp {
+                                minimum_otp_version,
+                                required_otp_version,
+                            } => (
+                                "This requires a newer OTP release",
+                                vec![wrap(&format!(
+                                    "Hint: 16-bit floats require OTP {required_otp_version} \
+or later, but this package supports OTP {minimum_otp_version}. \
+Increase `minimum_otp_version` in the `[erlang]` section of gleam.toml."
+                                ))],
+                            ),
+
                             bit_array::ErrorType::InvalidEndianness => (
                                 "This option is invalid here",
                                     vec![wrap("Hint: signed and unsigned can only be used with \
@@ -2837,24 +3106,49 @@ value, or use `let assert` rather than `let`."
 
                 TypeError::RecursiveTypeAlias { location, cycle } => {
                     let mut text = "This type alias is defined in terms of itself.\n".into();
-                    write_cycle(&mut text, cycle);
+                    let names = cycle.iter().map(|(name, _)| name.clone()).collect_vec();
+                    write_cycle(&mut text, &names);
                     text.push_str(
                         "If we tried to compile this recursive type it would expand
 forever in a loop, and we'd never get the final type.",
                     );
+
+                    // Each alias in the cycle refers to the one after it.
+                    let next = cycle.iter().cycle().skip(1);
+                    let mut label_text = None;
+                    let mut extra_labels = vec![];
+                    for ((name, alias_location), (referenced, _)) in cycle.iter().zip(next) {
+                        let text = if name == referenced {
+                            format!("`{name}` refers to itself")
+                        } else {
+                            format!("`{name}` refers to `{referenced}`")
+                        };
+                        if alias_location == location {
+                            label_text = Some(text);
+                        } else {
+                            extra_labels.push(ExtraLabel {
+                                src_info: None,
+                                label: Label {
+                                    text: Some(text),
+                                    span: *alias_location,
+                                },
+                            });
+                        }
+                    }
+
                     Diagnostic {
                         title: "Type cycle".into(),
                         text,
-                        hint: None,
+                        hint: Some("Custom types can be recursive, use one instead.".into()),
                         level: Level::Error,
                         location: Some(Location {
                             label: Label {
-                                text: None,
+                                text: label_text,
                                 span: *location,
                             },
                             path: path.clone(),
                             src: src.clone(),
-                            extra_labels: vec![],
+                            extra_labels,
                         }),
                     }
                 }
@@ -2961,6 +3255,43 @@ implementation but the function name `{function}` is not valid."
                     }
                 }
 
+                TypeError::ExternalArityMismatch {
+                    location,
+                    target,
+                    function,
+                    external_arity,
+                    arity,
+                } => {
+                    let target = match target {
+                        Target::Erlang => "Erlang",
+                        Target::JavaScript => "JavaScript",
+                    };
+                    let arguments = if *arity == 1 { "argument" } else { "arguments" };
+                    let text = wrap_format!(
+                        "The external {target} function `{function}` has an \
+arity of {external_arity}, but this function takes {arity} {arguments}."
+                    );
+                    let hint = wrap(
+                        "External functions are referenced by name only, \
+their arity is the number of arguments of the Gleam function.",
+                    );
+                    Diagnostic {
+                        title: "Incorrect external arity".into(),
+                        text,
+                        hint: Some(hint),
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }
+                }
+
                 TypeError::InexhaustiveLetAssignment { location, missing } => {
                     let mut text =wrap(
                         "This assignment uses a pattern that does not \
@@ -3413,17 +3744,24 @@ Consider removing the deprecation attribute on the variant.");
             }
 
             Error::ImportCycle { modules } => {
-                let first_location = &modules.first().1;
-                let rest_locations = modules.iter().skip(1).map(|(_, l)| ExtraLabel {
+                // Each module in the cycle imports the one after it.
+                let mut labels = modules
+                    .iter()
+                    .zip(modules.iter().cycle().skip(1))
+                    .map(|((_, details), (imported, _))| (details, format!("Imports `{imported}`")));
+                let (first_location, first_label) = labels.next().expect("non empty cycle");
+                let rest_locations = labels.map(|(details, text)| ExtraLabel {
                     label: Label {
-                        text: Some("Imported here".into()),
-                        span: l.location
+                        text: Some(text),
+                        span: details.location
                     },
-                    src_info: Some((l.src.clone(), l.path.clone())),
+                    src_info: Some((details.src.clone(), details.path.clone())),
                 }).collect_vec();
-                let mut text = "The import statements for these modules form a cycle:
-"
-                .into();
+                let mut text = wrap(
+                    "The import statements for these modules form a cycle, \
+with each module importing the one after it:",
+                );
+                text.push('\n');
                 let mod_names = modules.iter().map(|m| m.0.clone()).collect_vec();
                 write_cycle(&mut text, &mod_names);
                 text.push_str(
@@ -3437,7 +3775,7 @@ cycle to continue.",
                     level: Level::Error,
                     location: Some(Location {
                         label: Label {
-                            text: Some("Imported here".into()),
+                            text: Some(first_label),
                             span: first_location.location,
                         },
                         path: first_location.path.clone(),
@@ -3520,9 +3858,11 @@ but it cannot be found."
             Error::Format { problem_files } => {
                 let files: Vec<_> = problem_files
                     .iter()
-                    .map(|formatted| formatted.source.as_str())
-                    .map(|p| format!("  - {p}"))
-                    .sorted()
+                    .sorted_by(|one, other| one.source.cmp(&other.source))
+                    .map(|file| {
+                        let lines = changed_lines(&file.input, &file.output);
+                        format!("  - {} ({lines})", file.source)
+                    })
                     .collect();
                 let mut text = files.iter().join("\n");
                 text.push('\n');
@@ -3535,6 +3875,33 @@ but it cannot be found."
                 }]
             }
 
+            Error::UnstableFormat { path, unstable } => {
+                let text = format!(
+                    "Formatting the formatted code of {path} again changed it, \
+starting at line {line}.
+
+First formatting:
+{first}
+
+Second formatting:
+{second}
+
+This is a bug in the formatter. Please open an issue in our tracker: \
+https://github.com/gleam-lang/gleam/issues
+",
+                    line = unstable.line,
+                    first = unstable.first,
+                    second = unstable.second,
+                );
+                vec![Diagnostic {
+                    title: "Formatting is not stable".into(),
+                    text,
+                    hint: None,
+                    location: None,
+                    level: Level::Error,
+                }]
+            }
+
             Error::ForbiddenWarnings { count } => {
                 let word_warning = match count {
                     1 => "warning",
@@ -3568,6 +3935,57 @@ Fix the warnings and try again."
                         extra_labels: vec![],
                     }),
                 }],
+                javascript::Error::EsModuleRequiredByCommonJs { module, location } => {
+                    vec![Diagnostic {
+                        title: "ES module FFI in a CommonJS project".into(),
+                        text: wrap(&format!(
+                            "This project is configured to generate CommonJS modules, \
+which can't load the ES module `{module}`."
+                        )),
+                        hint: Some(
+                            "Write the FFI module as a CommonJS `.cjs` module, or remove \
+the `module_format` setting from the `[javascript]` section of `gleam.toml` to \
+generate ES modules instead."
+                                .into(),
+                        ),
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }]
+                }
+                javascript::Error::FfiIntsCannotBeConverted { type_, location } => {
+                    vec![Diagnostic {
+                        title: "FFI ints can't be converted".into(),
+                        text: wrap(&format!(
+                            "This project represents ints as BigInts, while the FFI code \
+of this package takes ints as numbers. The ints of external functions are only \
+converted in tuples, lists and results, so the ints in a value of type \
+`{type_}` can't be converted."
+                        )),
+                        hint: Some(
+                            "Remove the `int_representation` setting from the \
+`[javascript]` section of `gleam.toml` to represent ints as numbers."
+                                .into(),
+                        ),
+                        level: Level::Error,
+                        location: Some(Location {
+                            label: Label {
+                                text: None,
+                                span: *location,
+                            },
+                            path: path.clone(),
+                            src: src.clone(),
+                            extra_labels: vec![],
+                        }),
+                    }]
+                }
             },
 
             Error::DownloadPackageError {
@@ -3792,6 +4210,41 @@ issue in our tracker: https://github.com/gleam-lang/gleam/issues",
                 }]
             }
 
+            Error::FailedToStartDocsServer { address, error } => {
+                let error = format!("\nThe error message from the library was:\n\n    {error}\n");
+                let text = format!(
+                    "An error occurred while trying to serve the docs at:
+
+    {address}
+{error}",
+                );
+                vec![Diagnostic {
+                    title: "Failed to serve docs".into(),
+                    text,
+                    hint: Some("Use the --port flag to serve the docs on another port.".into()),
+                    level: Level::Error,
+                    location: None,
+                }]
+            }
+
+            Error::DocsAssetOutsideProject { path } => {
+                let text = format!(
+                    "The documentation of this package uses this file or directory:
+
+    {path}
+
+The files copied into the documentation must be within the package, so
+their paths must be relative to the root of the package and can't use `..`.",
+                );
+                vec![Diagnostic {
+                    title: "Documentation asset outside of the package".into(),
+                    text,
+                    hint: None,
+                    level: Level::Error,
+                    location: None,
+                }]
+            }
+
             Error::IncompatibleCompilerVersion {
                 package,
                 required_version,
@@ -3843,6 +4296,26 @@ satisfying {required_version} but you are using v{gleam_version}.",
                 location: None,
                 hint: None,
             }],
+            Error::BrowserExportRequiresEsModules => vec![Diagnostic {
+                title: "Browser exports require ES modules".into(),
+                text: "This project is configured to generate CommonJS modules, which
+browsers cannot load. Remove the `module_format` setting from the
+`[javascript]` section of `gleam.toml` to generate ES modules instead."
+                    .into(),
+                level: Level::Error,
+                location: None,
+                hint: None,
+            }],
+            Error::EscriptTooLarge => vec![Diagnostic {
+                title: "Escript too large".into(),
+                text: "Escripts store their compiled modules in a zip archive, which
+can hold at most 65535 files and 4GiB of data. This project is too large to
+be exported as an escript."
+                    .into(),
+                level: Level::Error,
+                location: None,
+                hint: Some("Use `gleam export erlang-shipment` instead.".into()),
+            }],
             Error::CorruptManifest => vec![Diagnostic {
                 title: "Corrupt manifest.toml".into(),
                 text: "The `manifest.toml` file is corrupt.".into(),
@@ -3921,6 +4394,70 @@ fn write_cycle(buffer: &mut String, cycle: &[EcoString]) {
     buffer.push_str("    └─────┘\n");
 }
 
+/// Prints a type for a type mismatch error, underlining the part of it that
+/// the mismatch path points to:
+///
+/// ```text
+///     #(Int, String)
+///            ^^^^^^
+/// ```
+///
+fn print_mismatched_type(printer: &mut Printer<'_>, type_: &Type, path: &[TypePathStep]) -> String {
+    if path.is_empty() {
+        return format!("    {}", printer.print_type(type_));
+    }
+
+    let (printed, highlight) = printer.print_type_highlighting(type_, path);
+    match highlight {
+        Some(range) => {
+            let padding = printed[..range.start].chars().count();
+            let width = printed[range].chars().count().max(1);
+            format!(
+                "    {printed}\n    {}{}",
+                " ".repeat(padding),
+                "^".repeat(width)
+            )
+        }
+        None => format!("    {printed}"),
+    }
+}
+
+fn arity_fixes_hint(fixes: &[ArityFix], names: &Names) -> Option<String> {
+    let mut printer = Printer::new(names);
+    let mut missing = vec![];
+    let mut extra = 0;
+    for fix in fixes {
+        match fix {
+            ArityFix::InsertArguments { arguments, .. } => missing.extend(
+                arguments
+                    .iter()
+                    .map(|argument| (&argument.label, printer.print_type(&argument.type_))),
+            ),
+            ArityFix::RemoveArguments { count, .. } => extra += count,
+        }
+    }
+
+    match (missing.as_slice(), extra) {
+        ([], 0) => None,
+        ([], 1) => Some("Remove the extra argument.".into()),
+        ([], _) => Some(format!("Remove the {extra} extra arguments.")),
+        ([(None, type_)], _) => Some(format!("Add the missing argument of type `{type_}`.")),
+        ([(Some(label), type_)], _) => Some(format!(
+            "Add the missing `{label}` argument of type `{type_}`."
+        )),
+        (arguments, _) => Some(format!(
+            "Add the missing arguments:\n{}",
+            arguments
+                .iter()
+                .map(|(label, type_)| match label {
+                    Some(label) => format!("  - `{label}: {type_}`"),
+                    None => format!("  - `{type_}`"),
+                })
+                .join("\n")
+        )),
+    }
+}
+
 fn hint_alternative_operator(op: &BinOp, given: &Type) -> Option<String> {
     match op {
         BinOp::AddInt if given.is_float() => Some(hint_numeric_message("+.", "Float")),
@@ -3967,6 +4504,32 @@ pub struct Unformatted {
     pub output: String,
 }
 
+/// The lines of a file that formatting changes, such as `lines 1-3, 7`.
+fn changed_lines(input: &str, output: &str) -> String {
+    let line_of = |offset: u32| {
+        input
+            .get(..offset as usize)
+            .map_or(0, |before| before.matches('\n').count())
+            + 1
+    };
+    let ranges = crate::format::edits(input, output)
+        .into_iter()
+        .map(|edit| {
+            let start = line_of(edit.location.start);
+            let end = line_of(edit.location.end.saturating_sub(1)).max(start);
+            if start == end {
+                start.to_string()
+            } else {
+                format!("{start}-{end}")
+            }
+        })
+        .collect_vec();
+    match ranges.as_slice() {
+        [range] if !range.contains('-') => format!("line {range}"),
+        _ => format!("lines {}", ranges.join(", ")),
+    }
+}
+
 pub fn wrap(text: &str) -> String {
     let mut result = String::with_capacity(text.len());
 
@@ -4058,3 +4621,41 @@ fn break_word(word: &str, width: usize) -> (Vec<Cow<'_, str>>, &str) {
 
     (new_lines, remainder)
 }
+
+/// A diagnostic for a warning or error reported by the Erlang compiler. When
+/// it could be mapped back to the Gleam source that the Erlang was generated
+/// from then that line is highlighted.
+pub(crate) fn erlang_compiler_diagnostic(
+    diagnostic: &crate::io::ErlangCompilerDiagnostic,
+    title: &str,
+    level: Level,
+) -> Diagnostic {
+    match &diagnostic.source {
+        Some((src, location)) => Diagnostic {
+            title: title.into(),
+            text: diagnostic.message.to_string(),
+            hint: None,
+            level,
+            location: Some(Location {
+                label: Label {
+                    text: None,
+                    span: *location,
+                },
+                path: diagnostic.path.clone(),
+                src: src.clone(),
+                extra_labels: vec![],
+            }),
+        },
+        None => Diagnostic {
+            title: title.into(),
+            text: format!(
+                "{}: {}",
+                diagnostic.location_description(),
+                diagnostic.message
+            ),
+            hint: None,
+            level,
+            location: None,
+        },
+    }
+}
@@ -35,3 +35,25 @@ fn test_shell_program_not_found_error() {
         }
     }
 }
+
+#[test]
+fn erlang_compilation_error_in_gleam_source() {
+    let src: EcoString = "pub fn main() {\n  wibble()\n}\n".into();
+    let mut located = crate::io::ErlangCompilerDiagnostic {
+        path: Utf8PathBuf::from("src/main.gleam"),
+        line: Some(2),
+        message: "function wibble/0 undefined".into(),
+        source: None,
+    };
+    located.locate_in_gleam_source(&src, crate::ast::SrcSpan::new(18, 26));
+    let native = crate::io::ErlangCompilerDiagnostic {
+        path: Utf8PathBuf::from("src/ffi.erl"),
+        line: Some(7),
+        message: "syntax error before: ')'".into(),
+        source: None,
+    };
+    let error = Error::ErlangCompilation {
+        errors: vec![located, native],
+    };
+    assert_snapshot!(error.pretty_string());
+}
@@ -49,7 +49,7 @@ use id_arena::Arena;
 use itertools::Itertools;
 use std::{cell::RefCell, collections::HashMap, sync::Arc};
 
-pub use self::pattern::PatternArena;
+pub use self::pattern::{PatternArena, StaticMatch};
 
 /// The body of code to evaluate in case of a match.
 #[derive(Clone, Eq, PartialEq, Debug)]
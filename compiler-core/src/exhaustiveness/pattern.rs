@@ -1,6 +1,7 @@
 use crate::{
-    ast::{AssignName, TypedPattern},
-    type_::Type,
+    ast::{AssignName, TypedConstant, TypedExpr, TypedPattern},
+    parse::parse_int_value,
+    type_::{Environment, ModuleValueConstructor, Type, ValueConstructor, ValueConstructorVariant},
 };
 use ecow::EcoString;
 use id_arena::{Arena, Id};
@@ -204,3 +205,360 @@ impl PatternArena {
         self.arena
     }
 }
+
+/// Whether a pattern can be determined to match a value known at compile time.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum StaticMatch {
+    Always,
+    Never,
+    Unknown,
+}
+
+impl StaticMatch {
+    /// Combines the results for all the parts of a value: the whole only
+    /// matches if all of its parts do.
+    fn all(results: impl IntoIterator<Item = StaticMatch>) -> StaticMatch {
+        let mut all = StaticMatch::Always;
+        for result in results {
+            match result {
+                StaticMatch::Never => return StaticMatch::Never,
+                StaticMatch::Unknown => all = StaticMatch::Unknown,
+                StaticMatch::Always => (),
+            }
+        }
+        all
+    }
+
+    fn from_bool(matches: bool) -> StaticMatch {
+        if matches {
+            StaticMatch::Always
+        } else {
+            StaticMatch::Never
+        }
+    }
+}
+
+impl PatternArena {
+    /// Registers the value of a case subject if it is a literal or a constant,
+    /// so that patterns can be checked against it with `static_match`.
+    /// Any part of the value that is not known at compile time is registered
+    /// as a discard.
+    ///
+    pub fn register_known_value(
+        &mut self,
+        subject: &TypedExpr,
+        environment: &Environment<'_>,
+    ) -> Option<PatternId> {
+        let pattern = match subject {
+            TypedExpr::Int { value, .. } => Pattern::Int {
+                value: value.clone(),
+            },
+            TypedExpr::Float { value, .. } => Pattern::Float {
+                value: value.clone(),
+            },
+            TypedExpr::String { value, .. } => Pattern::String {
+                value: value.clone(),
+            },
+
+            TypedExpr::Tuple { elems, .. } => {
+                let elements = elems
+                    .iter()
+                    .map(|elem| self.register_known_value_or_discard(elem, environment))
+                    .collect_vec();
+                Pattern::Tuple { elements }
+            }
+
+            TypedExpr::List { elements, tail, .. } => {
+                let mut list = match tail {
+                    Some(tail) => self.register_known_value_or_discard(tail, environment),
+                    None => self.insert(Pattern::EmptyList),
+                };
+                for element in elements.iter().rev() {
+                    let first = self.register_known_value_or_discard(element, environment);
+                    list = self.insert(Pattern::List { first, rest: list });
+                }
+                return Some(list);
+            }
+
+            TypedExpr::Var { constructor, .. } => match &constructor.variant {
+                ValueConstructorVariant::Record {
+                    arity: 0,
+                    variant_index,
+                    ..
+                } => Pattern::Constructor {
+                    constructor: Constructor::Variant {
+                        type_: constructor.type_.clone(),
+                        index: *variant_index,
+                    },
+                    arguments: vec![],
+                },
+                ValueConstructorVariant::ModuleConstant { literal, .. }
+                | ValueConstructorVariant::LocalConstant { literal } => {
+                    return Some(self.register_constant(literal, environment))
+                }
+                ValueConstructorVariant::Record { .. }
+                | ValueConstructorVariant::LocalVariable { .. }
+                | ValueConstructorVariant::ModuleFn { .. } => return None,
+            },
+
+            TypedExpr::ModuleSelect {
+                constructor, type_, ..
+            } => match constructor {
+                ModuleValueConstructor::Record { arity: 0, name, .. } => {
+                    let index = variant_index(type_, name, environment)?;
+                    Pattern::Constructor {
+                        constructor: Constructor::Variant {
+                            type_: type_.clone(),
+                            index,
+                        },
+                        arguments: vec![],
+                    }
+                }
+                ModuleValueConstructor::Constant { literal, .. } => {
+                    return Some(self.register_constant(literal, environment))
+                }
+                ModuleValueConstructor::Record { .. } | ModuleValueConstructor::Fn { .. } => {
+                    return None
+                }
+            },
+
+            TypedExpr::Call {
+                fun, args, type_, ..
+            } => {
+                let index = match fun.as_ref() {
+                    TypedExpr::Var {
+                        constructor:
+                            ValueConstructor {
+                                variant: ValueConstructorVariant::Record { variant_index, .. },
+                                ..
+                            },
+                        ..
+                    } => *variant_index,
+                    TypedExpr::ModuleSelect {
+                        constructor: ModuleValueConstructor::Record { name, .. },
+                        ..
+                    } => variant_index(type_, name, environment)?,
+                    _ => return None,
+                };
+                let arguments = args
+                    .iter()
+                    .map(|arg| self.register_known_value_or_discard(&arg.value, environment))
+                    .collect_vec();
+                Pattern::Constructor {
+                    constructor: Constructor::Variant {
+                        type_: type_.clone(),
+                        index,
+                    },
+                    arguments,
+                }
+            }
+
+            _ => return None,
+        };
+        Some(self.insert(pattern))
+    }
+
+    /// Registers a value that is not known at compile time.
+    pub fn register_unknown_value(&mut self) -> PatternId {
+        self.insert(Pattern::Discard)
+    }
+
+    fn register_known_value_or_discard(
+        &mut self,
+        value: &TypedExpr,
+        environment: &Environment<'_>,
+    ) -> PatternId {
+        match self.register_known_value(value, environment) {
+            Some(id) => id,
+            None => self.insert(Pattern::Discard),
+        }
+    }
+
+    fn register_constant(
+        &mut self,
+        constant: &TypedConstant,
+        environment: &Environment<'_>,
+    ) -> PatternId {
+        let pattern = match constant {
+            TypedConstant::Int { value, .. } => Pattern::Int {
+                value: value.clone(),
+            },
+            TypedConstant::Float { value, .. } => Pattern::Float {
+                value: value.clone(),
+            },
+            TypedConstant::String { value, .. } => Pattern::String {
+                value: value.clone(),
+            },
+
+            TypedConstant::Tuple { elements, .. } => {
+                let elements = elements
+                    .iter()
+                    .map(|element| self.register_constant(element, environment))
+                    .collect_vec();
+                Pattern::Tuple { elements }
+            }
+
+            TypedConstant::List { elements, .. } => {
+                let mut list = self.insert(Pattern::EmptyList);
+                for element in elements.iter().rev() {
+                    let first = self.register_constant(element, environment);
+                    list = self.insert(Pattern::List { first, rest: list });
+                }
+                return list;
+            }
+
+            TypedConstant::Record {
+                name, args, type_, ..
+            } => match variant_index(type_, name, environment) {
+                Some(index) => {
+                    let arguments = args
+                        .iter()
+                        .map(|arg| self.register_constant(&arg.value, environment))
+                        .collect_vec();
+                    Pattern::Constructor {
+                        constructor: Constructor::Variant {
+                            type_: type_.clone(),
+                            index,
+                        },
+                        arguments,
+                    }
+                }
+                None => Pattern::Discard,
+            },
+
+            TypedConstant::Var {
+                constructor: Some(constructor),
+                ..
+            } => match &constructor.variant {
+                ValueConstructorVariant::ModuleConstant { literal, .. }
+                | ValueConstructorVariant::LocalConstant { literal } => {
+                    return self.register_constant(literal, environment)
+                }
+                ValueConstructorVariant::LocalVariable { .. }
+                | ValueConstructorVariant::ModuleFn { .. }
+                | ValueConstructorVariant::Record { .. } => Pattern::Discard,
+            },
+
+            TypedConstant::Var {
+                constructor: None, ..
+            }
+            | TypedConstant::BitArray { .. }
+            | TypedConstant::StringConcatenation { .. }
+            | TypedConstant::Invalid { .. } => Pattern::Discard,
+        };
+        self.insert(pattern)
+    }
+
+    /// Checks whether a pattern always or never matches a value registered
+    /// with `register_known_value`. Discards in the value stand for parts that
+    /// are not known, so only patterns that match anything of the right type
+    /// can be said to always match them.
+    ///
+    pub fn static_match(&self, pattern_id: PatternId, value_id: PatternId) -> StaticMatch {
+        let (Some(pattern), Some(value)) = (self.arena.get(pattern_id), self.arena.get(value_id))
+        else {
+            return StaticMatch::Unknown;
+        };
+
+        match (pattern, value) {
+            (Pattern::Discard | Pattern::Variable { .. }, _) => StaticMatch::Always,
+            (Pattern::Assign { pattern, .. }, _) => self.static_match(*pattern, value_id),
+            (Pattern::Or { left, right }, _) => {
+                let left = self.static_match(*left, value_id);
+                let right = self.static_match(*right, value_id);
+                match (left, right) {
+                    (StaticMatch::Always, _) | (_, StaticMatch::Always) => StaticMatch::Always,
+                    (StaticMatch::Never, StaticMatch::Never) => StaticMatch::Never,
+                    _ => StaticMatch::Unknown,
+                }
+            }
+
+            (Pattern::Tuple { elements }, Pattern::Discard) => StaticMatch::all(
+                elements
+                    .iter()
+                    .map(|element| self.static_match(*element, value_id)),
+            ),
+            (_, Pattern::Discard) => StaticMatch::Unknown,
+
+            (Pattern::Int { value: pattern }, Pattern::Int { value }) => {
+                match (parse_int_value(pattern), parse_int_value(value)) {
+                    (Some(pattern), Some(value)) => StaticMatch::from_bool(pattern == value),
+                    _ => StaticMatch::Unknown,
+                }
+            }
+            (Pattern::Float { value: pattern }, Pattern::Float { value }) => {
+                match (parse_float_value(pattern), parse_float_value(value)) {
+                    (Some(pattern), Some(value)) => StaticMatch::from_bool(pattern == value),
+                    _ => StaticMatch::Unknown,
+                }
+            }
+            (Pattern::String { value: pattern }, Pattern::String { value }) => {
+                StaticMatch::from_bool(pattern == value)
+            }
+            (Pattern::StringPrefix { prefix, .. }, Pattern::String { value }) => {
+                StaticMatch::from_bool(value.starts_with(prefix.as_str()))
+            }
+
+            (Pattern::Tuple { elements }, Pattern::Tuple { elements: values }) => StaticMatch::all(
+                elements
+                    .iter()
+                    .zip(values)
+                    .map(|(element, value)| self.static_match(*element, *value)),
+            ),
+
+            (
+                Pattern::Constructor {
+                    constructor,
+                    arguments,
+                },
+                Pattern::Constructor {
+                    constructor: value_constructor,
+                    arguments: values,
+                },
+            ) => {
+                if constructor.index() != value_constructor.index() {
+                    return StaticMatch::Never;
+                }
+                StaticMatch::all(
+                    arguments
+                        .iter()
+                        .zip(values)
+                        .map(|(argument, value)| self.static_match(*argument, *value)),
+                )
+            }
+
+            (Pattern::EmptyList, Pattern::EmptyList) => StaticMatch::Always,
+            (Pattern::EmptyList, Pattern::List { .. })
+            | (Pattern::List { .. }, Pattern::EmptyList) => StaticMatch::Never,
+            (
+                Pattern::List { first, rest },
+                Pattern::List {
+                    first: value_first,
+                    rest: value_rest,
+                },
+            ) => StaticMatch::all([
+                self.static_match(*first, *value_first),
+                self.static_match(*rest, *value_rest),
+            ]),
+
+            _ => StaticMatch::Unknown,
+        }
+    }
+}
+
+/// The index of the variant with the given name in a custom type.
+fn variant_index(type_: &Type, name: &EcoString, environment: &Environment<'_>) -> Option<u16> {
+    let (module, type_name, _) = type_.named_type_information()?;
+    let constructors = environment
+        .get_constructors_for_type(&module, &type_name)
+        .ok()?;
+    let index = constructors
+        .variants
+        .iter()
+        .position(|variant| variant.name == *name)?;
+    Some(index as u16)
+}
+
+fn parse_float_value(value: &str) -> Option<f64> {
+    value.replace("_", "").parse().ok()
+}
@@ -1,4 +1,5 @@
 use crate::{
+    config::FormatConfig,
     format::{Formatter, Intermediate},
     warning::WarningEmitter,
     Error, Result,
@@ -22,9 +23,10 @@ pub fn parse_fix_and_format(src: &EcoString, path: &Utf8Path) -> Result<String>
 
     // Format
     let mut buffer = String::new();
-    Formatter::with_comments(&intermediate)
+    let config = FormatConfig::default();
+    Formatter::with_comments(&intermediate, &config)
         .module(&module)
-        .pretty_print(80, &mut buffer)?;
+        .pretty_print(config.line_width as isize, &mut buffer)?;
 
     Ok(buffer)
 }
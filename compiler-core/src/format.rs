@@ -1,14 +1,19 @@
+mod code_blocks;
+mod diff;
 #[cfg(test)]
 mod tests;
 
+use self::code_blocks::{CodeBlock, CodeBlocks};
 use crate::{
     ast::{
         CustomType, Import, ModuleConstant, TypeAlias, TypeAstConstructor, TypeAstFn, TypeAstHole,
         TypeAstTuple, TypeAstVar, *,
     },
     build::Target,
+    config::{FormatConfig, PipelineBreaking},
     docvec,
     io::Utf8Writer,
+    parse::error::ParseError,
     parse::extra::{Comment, ModuleExtra},
     parse::SpannedString,
     pretty::{self, *},
@@ -19,6 +24,7 @@ use crate::{
 use ecow::{eco_format, EcoString};
 use itertools::Itertools;
 use std::{cmp::Ordering, sync::Arc};
+use unicode_segmentation::UnicodeSegmentation;
 use vec1::Vec1;
 
 use crate::type_::Deprecation;
@@ -26,17 +32,330 @@ use camino::Utf8Path;
 
 const INDENT: isize = 2;
 
-pub fn pretty(writer: &mut impl Utf8Writer, src: &EcoString, path: &Utf8Path) -> Result<()> {
+/// The widest a bit array segment's value can be for its options to be lined up
+/// with those of the other segments.
+const MAX_ALIGNED_SEGMENT_WIDTH: isize = 24;
+
+pub fn pretty(
+    writer: &mut impl Utf8Writer,
+    src: &EcoString,
+    path: &Utf8Path,
+    config: &FormatConfig,
+) -> Result<()> {
     let parsed = crate::parse::parse_module(path.to_owned(), src, &WarningEmitter::null())
         .map_err(|error| Error::Parse {
             path: path.to_path_buf(),
             src: src.clone(),
             error,
         })?;
-    let intermediate = Intermediate::from_extra(&parsed.extra, src);
-    Formatter::with_comments(&intermediate)
+    let code_blocks = CodeBlocks::new(&parsed.extra, src, config);
+    let intermediate = Intermediate::from_extra(&parsed.extra, src).with_code_blocks(&code_blocks);
+    Formatter::with_comments(&intermediate, config)
         .module(&parsed.module)
-        .pretty_print(80, writer)
+        .pretty_print(config.line_width as isize, writer)
+}
+
+/// Formats the module like `pretty`, but if it has syntax errors the
+/// definitions around each error are left as they were written while the rest
+/// of the module is still formatted. The syntax errors are returned, so a
+/// module being edited can be formatted on save.
+pub fn pretty_tolerant(
+    writer: &mut impl Utf8Writer,
+    src: &EcoString,
+    path: &Utf8Path,
+    config: &FormatConfig,
+) -> Result<Vec<Error>> {
+    let mut pieces = vec![];
+    let mut errors = vec![];
+    format_tolerant(src, 0, path, config, &mut pieces, &mut errors)?;
+    writer.str_write(&pieces.join("\n"))?;
+    Ok(errors
+        .into_iter()
+        .map(|error| Error::Parse {
+            path: path.to_path_buf(),
+            src: src.clone(),
+            error,
+        })
+        .collect())
+}
+
+/// Formats a part of a module that starts at `offset`, splitting it around the
+/// definition that has a syntax error and formatting what comes before and
+/// after that separately.
+fn format_tolerant(
+    src: &str,
+    offset: u32,
+    path: &Utf8Path,
+    config: &FormatConfig,
+    pieces: &mut Vec<String>,
+    errors: &mut Vec<ParseError>,
+) -> Result<()> {
+    if src.trim().is_empty() {
+        return Ok(());
+    }
+    let mut formatted = String::new();
+    let error = match pretty(&mut formatted, &src.into(), path, config) {
+        Ok(()) => {
+            pieces.push(formatted);
+            return Ok(());
+        }
+        Err(Error::Parse { error, .. }) => error,
+        Err(error) => return Err(error),
+    };
+
+    // The broken part runs from the start of the definition the error is in to
+    // the start of the next one. An error at the start of a definition is
+    // usually caused by the one before it, such as a missing closing brace.
+    let definitions = definition_starts(src);
+    let start = definitions
+        .iter()
+        .rev()
+        .find(|(_, keyword)| *keyword < error.location.start)
+        .map_or(0, |(start, _)| *start);
+    let end = definitions
+        .iter()
+        .find(|(next, keyword)| *next > start && *keyword >= error.location.start)
+        .map_or(src.len() as u32, |(next, _)| *next);
+    errors.push(ParseError {
+        error: error.error,
+        location: SrcSpan::new(error.location.start + offset, error.location.end + offset),
+    });
+
+    let (before, rest) = src.split_at(start as usize);
+    let (broken, after) = rest.split_at((end - start) as usize);
+    format_tolerant(before, offset, path, config, pieces, errors)?;
+    pieces.push(format!(
+        "{}\n",
+        broken.trim_start_matches(['\r', '\n']).trim_end()
+    ));
+    format_tolerant(after, offset + end, path, config, pieces, errors)
+}
+
+/// The positions of the lines where each top level definition starts,
+/// including the comments and attributes written right before it, and of the
+/// line with its first keyword. Only used to find the definitions of a module
+/// that doesn't parse, so it goes by how the lines start rather than by
+/// parsing them.
+fn definition_starts(src: &str) -> Vec<(u32, u32)> {
+    let mut lines = vec![];
+    let mut position = 0;
+    for line in src.split_inclusive('\n') {
+        lines.push((position, line));
+        position += line.len() as u32;
+    }
+
+    let is_definition = |line: &str| {
+        ["pub ", "fn ", "type ", "const ", "import ", "@"]
+            .iter()
+            .any(|keyword| line.starts_with(keyword))
+    };
+    let is_attached =
+        |line: &str| (line.starts_with("//") && !line.starts_with("////")) || line.starts_with('@');
+
+    let mut starts: Vec<(u32, u32)> = vec![];
+    for (index, (keyword, line)) in lines.iter().enumerate() {
+        if !is_definition(line) {
+            continue;
+        }
+        let first = lines
+            .get(..index)
+            .unwrap_or_default()
+            .iter()
+            .rev()
+            .take_while(|(_, line)| is_attached(line))
+            .count();
+        let (start, _) = lines.get(index - first).unwrap_or(&(0, ""));
+        if starts.last().map(|(start, _)| start) != Some(start) {
+            starts.push((*start, *keyword));
+        }
+    }
+    starts
+}
+
+/// Where the formatter's output changed when the output was formatted again.
+/// Formatting is meant to be idempotent, so this is always a formatter bug.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Unstable {
+    /// The first line of the output that changed, starting from one.
+    pub line: u32,
+    /// The lines that changed, as they were the first time.
+    pub first: String,
+    /// The lines that changed, as they were the second time.
+    pub second: String,
+}
+
+/// Formats the source and then formats the output a second time, returning
+/// the first part of the output that changed if the two renderings differ.
+pub fn check_idempotent(
+    src: &EcoString,
+    path: &Utf8Path,
+    config: &FormatConfig,
+) -> Result<Option<Unstable>> {
+    let mut first = String::new();
+    pretty(&mut first, src, path, config)?;
+    let mut second = String::new();
+    pretty(&mut second, &first.as_str().into(), path, config)?;
+    Ok(unstable(&first, &second))
+}
+
+fn unstable(first: &str, second: &str) -> Option<Unstable> {
+    if first == second {
+        return None;
+    }
+    let first_lines = first.split('\n').collect_vec();
+    let second_lines = second.split('\n').collect_vec();
+
+    let same_start = first_lines
+        .iter()
+        .zip(&second_lines)
+        .take_while(|(one, other)| one == other)
+        .count();
+    let same_end = first_lines
+        .iter()
+        .rev()
+        .zip(second_lines.iter().rev())
+        .take(first_lines.len().min(second_lines.len()) - same_start)
+        .take_while(|(one, other)| one == other)
+        .count();
+    let changed = |lines: &[&str]| {
+        lines
+            .get(same_start..lines.len() - same_end)
+            .unwrap_or_default()
+            .join("\n")
+    };
+
+    Some(Unstable {
+        line: same_start as u32 + 1,
+        first: changed(&first_lines),
+        second: changed(&second_lines),
+    })
+}
+
+/// A change to a Gleam source file, replacing the text at `location` with
+/// `replacement`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edit {
+    pub location: SrcSpan,
+    pub replacement: String,
+}
+
+/// The edits that turn `src` into `formatted`, each replacing whole lines. Lines
+/// that are the same in both are left out, so tools can apply formatting as
+/// small changes rather than by replacing the whole module.
+pub fn edits(src: &str, formatted: &str) -> Vec<Edit> {
+    diff::line_edits(src, formatted)
+}
+
+/// Formats the module, returning the edits that turn the source into the
+/// formatted code.
+pub fn pretty_edits(src: &EcoString, path: &Utf8Path, config: &FormatConfig) -> Result<Vec<Edit>> {
+    let mut formatted = String::new();
+    pretty(&mut formatted, src, path, config)?;
+    Ok(edits(src, &formatted))
+}
+
+/// Formats only the top level definitions that overlap `range`, returning an
+/// edit for each one whose formatting changes. The rest of the module is left
+/// untouched, so formatting a selection or some pasted code doesn't reflow
+/// unrelated definitions.
+///
+/// A definition is formatted together with the comments and attributes that
+/// come before it. Consecutive imports are formatted as one, as they are
+/// sorted together.
+pub fn pretty_range(
+    src: &EcoString,
+    path: &Utf8Path,
+    range: SrcSpan,
+    config: &FormatConfig,
+) -> Result<Vec<Edit>> {
+    let parsed = crate::parse::parse_module(path.to_owned(), src, &WarningEmitter::null())
+        .map_err(|error| Error::Parse {
+            path: path.to_path_buf(),
+            src: src.clone(),
+            error,
+        })?;
+    let code_blocks = CodeBlocks::new(&parsed.extra, src, config);
+    let intermediate = Intermediate::from_extra(&parsed.extra, src).with_code_blocks(&code_blocks);
+
+    let mut edits = vec![];
+    let mut previous_end = 0;
+    for (is_import_group, definitions) in &parsed
+        .module
+        .definitions
+        .iter()
+        .chunk_by(|definition| definition.definition.is_import())
+    {
+        let units = if is_import_group {
+            vec![definitions.collect_vec()]
+        } else {
+            definitions.map(|definition| vec![definition]).collect_vec()
+        };
+
+        for unit in units {
+            let (Some(first), Some(last)) = (unit.first(), unit.last()) else {
+                continue;
+            };
+            let start = definition_text_start(src, &parsed.extra, previous_end, first);
+            let end = definition_end(&last.definition);
+            previous_end = end;
+            if start > range.end || end < range.start {
+                continue;
+            }
+
+            let mut formatter = Formatter::with_comments_between(&intermediate, config, start, end);
+            let document = if is_import_group {
+                concat(formatter.imports(unit))
+            } else {
+                formatter.targeted_definition(first)
+            };
+            let mut replacement = String::new();
+            document.pretty_print(config.line_width as isize, &mut replacement)?;
+
+            let location = SrcSpan::new(start, end);
+            if src.get(start as usize..end as usize) != Some(replacement.as_str()) {
+                edits.push(Edit {
+                    location,
+                    replacement,
+                });
+            }
+        }
+    }
+    Ok(edits)
+}
+
+/// Where the text of a definition starts, including the comments and
+/// attributes before it: the first non-whitespace character after the
+/// previous definition that isn't part of a module comment.
+fn definition_text_start(
+    src: &str,
+    extra: &ModuleExtra,
+    previous_end: u32,
+    definition: &TargetedDefinition,
+) -> u32 {
+    let definition_start = definition.definition.location().start;
+    let after = extra
+        .module_comments
+        .iter()
+        .map(|comment| comment.end)
+        .filter(|end| *end < definition_start)
+        .fold(previous_end, u32::max);
+    let whitespace = src
+        .get(after as usize..definition_start as usize)
+        .map(|text| text.len() - text.trim_start().len())
+        .unwrap_or(0);
+    after + whitespace as u32
+}
+
+/// Where the text of a definition ends. The `location` of some definitions
+/// only covers their head.
+fn definition_end(definition: &UntypedDefinition) -> u32 {
+    match definition {
+        Definition::Function(function) => function.end_position,
+        Definition::CustomType(custom_type) => custom_type.end_position,
+        Definition::ModuleConstant(constant) => constant.value.location().end,
+        Definition::TypeAlias(alias) => alias.location.end.max(alias.type_ast.location().end),
+        Definition::Import(import) => import.location.end,
+    }
 }
 
 pub(crate) struct Intermediate<'a> {
@@ -69,6 +388,29 @@ impl<'a> Intermediate<'a> {
             new_lines: &extra.new_lines,
         }
     }
+
+    /// Replaces the lines of the comments' code blocks with their formatted
+    /// lines.
+    fn with_code_blocks(mut self, code_blocks: &'a CodeBlocks) -> Self {
+        fn replace<'a>(comments: &mut Vec<Comment<'a>>, blocks: &'a [CodeBlock]) {
+            if blocks.is_empty() {
+                return;
+            }
+            comments.retain(|comment| !blocks.iter().any(|block| block.replaces(comment.start)));
+            comments.extend(blocks.iter().flat_map(|block| {
+                block.lines.iter().map(|(start, line)| Comment {
+                    start: *start,
+                    content: line,
+                })
+            }));
+            comments.sort_by_key(|comment| comment.start);
+        }
+
+        replace(&mut self.comments, &code_blocks.comments);
+        replace(&mut self.doc_comments, &code_blocks.doc_comments);
+        replace(&mut self.module_comments, &code_blocks.module_comments);
+        self
+    }
 }
 
 #[derive(Debug)]
@@ -85,6 +427,7 @@ pub struct Formatter<'a> {
     module_comments: &'a [Comment<'a>],
     empty_lines: &'a [u32],
     new_lines: &'a [u32],
+    config: FormatConfig,
 }
 
 impl<'comments> Formatter<'comments> {
@@ -92,13 +435,41 @@ impl<'comments> Formatter<'comments> {
         Default::default()
     }
 
-    pub(crate) fn with_comments(extra: &'comments Intermediate<'comments>) -> Self {
+    pub(crate) fn with_comments(
+        extra: &'comments Intermediate<'comments>,
+        config: &FormatConfig,
+    ) -> Self {
         Self {
             comments: &extra.comments,
             doc_comments: &extra.doc_comments,
             module_comments: &extra.module_comments,
             empty_lines: extra.empty_lines,
             new_lines: extra.new_lines,
+            config: config.clone(),
+        }
+    }
+
+    /// A formatter for the part of the module between `start` and `end`, which
+    /// only knows about the comments and empty lines found there.
+    fn with_comments_between(
+        extra: &'comments Intermediate<'comments>,
+        config: &FormatConfig,
+        start: u32,
+        end: u32,
+    ) -> Self {
+        fn between<T>(items: &[T], start: u32, end: u32, position: impl Fn(&T) -> u32) -> &[T] {
+            let from = items.partition_point(|item| position(item) < start);
+            let to = items.partition_point(|item| position(item) <= end);
+            items.get(from..to).unwrap_or_default()
+        }
+
+        Self {
+            comments: between(&extra.comments, start, end, |comment| comment.start),
+            doc_comments: between(&extra.doc_comments, start, end, |comment| comment.start),
+            module_comments: &[],
+            empty_lines: between(extra.empty_lines, start, end, |line| *line),
+            new_lines: extra.new_lines,
+            config: config.clone(),
         }
     }
 
@@ -161,6 +532,26 @@ impl<'comments> Formatter<'comments> {
         end != 0
     }
 
+    /// The line breaks to put between two statements, keeping up to
+    /// `max_blank_lines` of the empty lines written between them. Empty lines
+    /// between comments that come before the next statement are handled when
+    /// printing those comments.
+    fn lines_between_statements<'a>(&mut self, previous_end: u32, next_start: u32) -> Document<'a> {
+        // Any empty lines left within the previous statement are not ours to
+        // print.
+        let _ = self.pop_empty_lines(previous_end);
+        let end = match self.comments.first() {
+            Some(comment) if comment.start < next_start => comment.start,
+            _ => next_start,
+        };
+        let count = self.empty_lines.partition_point(|line| *line < end);
+        self.empty_lines = self
+            .empty_lines
+            .get(count..)
+            .expect("Pop empty lines slicing");
+        lines(1 + count.min(self.config.max_blank_lines as usize))
+    }
+
     fn targeted_definition<'a>(&mut self, definition: &'a TargetedDefinition) -> Document<'a> {
         let target = definition.target;
         let definition = &definition.definition;
@@ -169,11 +560,33 @@ impl<'comments> Formatter<'comments> {
         let comments = self.pop_comments_with_position(start);
         let comments = self.printed_documented_comments(comments);
         let document = self.documented_definition(definition);
-        let document = match target {
-            None => document,
-            Some(Target::Erlang) => docvec!["@target(erlang)", line(), document],
-            Some(Target::JavaScript) => docvec!["@target(javascript)", line(), document],
-        };
+        let document = target_attribute(target, document);
+
+        comments.to_doc().append(document.group())
+    }
+
+    /// Prints an import merged with the later imports of the same module in
+    /// its group, which are then dropped. Their unqualified types and values
+    /// are all imported by the merged import.
+    fn merged_import<'a>(
+        &mut self,
+        import: &'a TargetedDefinition,
+        duplicates: &[&'a TargetedDefinition],
+    ) -> Document<'a> {
+        let start = import.definition.location().start;
+
+        let comments = self.pop_comments_with_position(start);
+        let comments = self.printed_documented_comments(comments);
+        let doc_comments = self.doc_comments(start);
+        let imports = std::iter::once(import)
+            .chain(duplicates.iter().copied())
+            .filter_map(|definition| match &definition.definition {
+                Definition::Import(import) => Some(import),
+                _ => None,
+            })
+            .collect_vec();
+        let document = doc_comments.append(self.import(&imports).group()).group();
+        let document = target_attribute(import.target, document);
 
         comments.to_doc().append(document.group())
     }
@@ -314,18 +727,46 @@ impl<'comments> Formatter<'comments> {
 
     /// Prints the imports as a single sorted group of import statements.
     ///
+    /// Imports of the same module with the same name and target are merged
+    /// into a single import:
+    ///
+    /// ```gleam
+    /// import wibble.{wobble}
+    /// import wibble.{type Wubble}
+    /// // is formatted as
+    /// import wibble.{type Wubble, wobble}
+    /// ```
+    ///
     fn sorted_import_group<'a>(&mut self, imports: &[&'a TargetedDefinition]) -> Document<'a> {
-        let imports = imports
-            .iter()
-            .sorted_by(|one, other| match (&one.definition, &other.definition) {
-                (Definition::Import(one), Definition::Import(other)) => {
-                    one.module.cmp(&other.module)
-                }
-                // It shouldn't really be possible for a non import to be here so
-                // we just return a default value.
-                _ => Ordering::Equal,
-            })
-            .map(|import| self.targeted_definition(import));
+        let mut merged: Vec<Vec<&'a TargetedDefinition>> = vec![];
+        let sorted =
+            imports
+                .iter()
+                .sorted_by(|one, other| match (&one.definition, &other.definition) {
+                    (Definition::Import(one), Definition::Import(other)) => {
+                        one.module.cmp(&other.module)
+                    }
+                    // It shouldn't really be possible for a non import to be here so
+                    // we just return a default value.
+                    _ => Ordering::Equal,
+                });
+        for import in sorted {
+            let duplicate_of = merged.iter_mut().find(|group| {
+                group
+                    .first()
+                    .is_some_and(|first| same_import(first, import))
+            });
+            match duplicate_of {
+                Some(group) => group.push(import),
+                None => merged.push(vec![import]),
+            }
+        }
+
+        let imports = merged.into_iter().map(|group| match group.as_slice() {
+            [import] => self.targeted_definition(import),
+            [import, duplicates @ ..] => self.merged_import(import, duplicates),
+            [] => nil(),
+        });
 
         // This should really be `.intersperse(line())` but I can't do that
         // because of https://github.com/rust-lang/rust/issues/48919.
@@ -357,56 +798,7 @@ impl<'comments> Formatter<'comments> {
 
             Definition::CustomType(ct) => self.custom_type(ct),
 
-            Definition::Import(Import {
-                module,
-                as_name,
-                unqualified_values,
-                unqualified_types,
-                ..
-            }) => {
-                let second = if unqualified_values.is_empty() && unqualified_types.is_empty() {
-                    nil()
-                } else {
-                    let unqualified_types = unqualified_types
-                        .iter()
-                        .sorted_by(|a, b| a.name.cmp(&b.name))
-                        .map(|e| docvec!["type ", e]);
-                    let unqualified_values = unqualified_values
-                        .iter()
-                        .sorted_by(|a, b| a.name.cmp(&b.name))
-                        .map(|e| e.to_doc());
-                    let unqualified = join(
-                        unqualified_types.chain(unqualified_values),
-                        flex_break(",", ", "),
-                    );
-                    let unqualified = break_("", "")
-                        .append(unqualified)
-                        .nest(INDENT)
-                        .append(break_(",", ""))
-                        .group();
-                    ".{".to_doc().append(unqualified).append("}")
-                };
-
-                let doc = docvec!["import ", module.as_str(), second];
-                let default_module_access_name = module.split('/').last().map(EcoString::from);
-                match (default_module_access_name, as_name) {
-                    // If the `as name` is the same as the module name that would be
-                    // used anyways we won't render it. For example:
-                    // ```gleam
-                    // import gleam/int as int
-                    //                  ^^^^^^ this is redundant and removed
-                    // ```
-                    (Some(module_name), Some((AssignName::Variable(name), _)))
-                        if &module_name == name =>
-                    {
-                        doc
-                    }
-                    (_, None) => doc,
-                    (_, Some((AssignName::Variable(name) | AssignName::Discard(name), _))) => {
-                        doc.append(" as ").append(name)
-                    }
-                }
-            }
+            Definition::Import(import) => self.import(&[import]),
 
             Definition::ModuleConstant(ModuleConstant {
                 publicity,
@@ -429,6 +821,70 @@ impl<'comments> Formatter<'comments> {
         }
     }
 
+    /// Prints an import of a module, importing the unqualified types and values
+    /// of all the given imports of it. The module and its name are taken from
+    /// the first import.
+    fn import<'a>(&self, imports: &[&'a UntypedImport]) -> Document<'a> {
+        let Some(Import {
+            module, as_name, ..
+        }) = imports.first()
+        else {
+            return nil();
+        };
+
+        let unqualified_types = imports
+            .iter()
+            .flat_map(|import| &import.unqualified_types)
+            .unique_by(|unqualified| (&unqualified.name, &unqualified.as_name))
+            .collect_vec();
+        let unqualified_values = imports
+            .iter()
+            .flat_map(|import| &import.unqualified_values)
+            .unique_by(|unqualified| (&unqualified.name, &unqualified.as_name))
+            .collect_vec();
+
+        let second = if unqualified_values.is_empty() && unqualified_types.is_empty() {
+            nil()
+        } else {
+            let unqualified_types = unqualified_types
+                .into_iter()
+                .sorted_by(|a, b| a.name.cmp(&b.name))
+                .map(|e| docvec!["type ", e]);
+            let unqualified_values = unqualified_values
+                .into_iter()
+                .sorted_by(|a, b| a.name.cmp(&b.name))
+                .map(|e| e.to_doc());
+            let unqualified = join(
+                unqualified_types.chain(unqualified_values),
+                flex_break(",", ", "),
+            );
+            let unqualified = break_("", "")
+                .append(unqualified)
+                .nest(INDENT)
+                .append(break_(",", ""))
+                .group();
+            ".{".to_doc().append(unqualified).append("}")
+        };
+
+        let doc = docvec!["import ", module.as_str(), second];
+        let default_module_access_name = module.split('/').last().map(EcoString::from);
+        match (default_module_access_name, as_name) {
+            // If the `as name` is the same as the module name that would be
+            // used anyways we won't render it. For example:
+            // ```gleam
+            // import gleam/int as int
+            //                  ^^^^^^ this is redundant and removed
+            // ```
+            (Some(module_name), Some((AssignName::Variable(name), _))) if &module_name == name => {
+                doc
+            }
+            (_, None) => doc,
+            (_, Some((AssignName::Variable(name) | AssignName::Discard(name), _))) => {
+                doc.append(" as ").append(name)
+            }
+        }
+    }
+
     fn const_expr<'a, A, B>(&mut self, value: &'a Constant<A, B>) -> Document<'a> {
         let comments = self.pop_comments(value.location().start);
         let document = match value {
@@ -513,12 +969,14 @@ impl<'comments> Formatter<'comments> {
                 ..
             } => docvec![module, ".", name],
 
-            Constant::StringConcatenation { left, right, .. } => self
-                .const_expr(left)
-                .append(break_("", " ").append("<>".to_doc()))
-                .nest(INDENT)
-                .append(" ")
-                .append(self.const_expr(right)),
+            Constant::StringConcatenation { left, right, .. } => {
+                let left = self.const_expr(left);
+                let comments = self.pop_comments(right.location().start);
+                left.append(break_("", " ").append(commented("<>".to_doc(), comments)))
+                    .nest(INDENT)
+                    .append(" ")
+                    .append(self.const_expr(right))
+            }
 
             Constant::Invalid { .. } => {
                 panic!("invalid constants can not be in an untyped ast")
@@ -868,11 +1326,9 @@ impl<'comments> Formatter<'comments> {
         let count = statements.len();
         let mut documents = Vec::with_capacity(count * 2);
         for (i, statement) in statements.iter().enumerate() {
-            let preceding_newline = self.pop_empty_lines(previous_position + 1);
-            if i != 0 && preceding_newline {
-                documents.push(lines(2));
-            } else if i != 0 {
-                documents.push(line());
+            if i != 0 {
+                let start = statement.location().start;
+                documents.push(self.lines_between_statements(previous_position, start));
             }
             previous_position = statement.location().end;
             documents.push(self.statement(statement).group());
@@ -1446,7 +1902,7 @@ impl<'comments> Formatter<'comments> {
 
         let pipeline_start = expressions.first().location().start;
         let pipeline_end = expressions.last().location().end;
-        let try_to_keep_on_one_line = self
+        let written_on_one_line = self
             .new_lines
             .binary_search_by(|newline| {
                 if *newline <= pipeline_start {
@@ -1459,9 +1915,16 @@ impl<'comments> Formatter<'comments> {
                     Ordering::Equal
                 }
             })
+            .is_err();
+        let steps = expressions.len() - 1;
+        let try_to_keep_on_one_line = match self.config.break_pipelines {
             // If we couldn't find any newline between the start and end of
             // the pipeline then we will try and keep it on a single line.
-            .is_err();
+            PipelineBreaking::AsWritten => written_on_one_line,
+            PipelineBreaking::Always => false,
+            PipelineBreaking::OverSteps(max_steps) => steps <= max_steps as usize,
+            PipelineBreaking::WhenTooLong => true,
+        };
 
         for expr in expressions.iter().skip(1) {
             let comments = self.pop_comments(expr.location().start);
@@ -1627,6 +2090,7 @@ impl<'comments> Formatter<'comments> {
         let attributes = AttributesPrinter::new()
             .set_deprecation(&ct.deprecation)
             .set_internal(ct.publicity)
+            .set_phantom(ct.phantom)
             .to_doc();
 
         let doc = attributes
@@ -2215,9 +2679,15 @@ impl<'comments> Formatter<'comments> {
         left: &'a UntypedClauseGuard,
         right: &'a UntypedClauseGuard,
     ) -> Document<'a> {
-        self.clause_guard_bin_op_side(name, left, left.precedence())
-            .append(break_("", " "))
-            .append(name.to_doc())
+        let left_side = self.clause_guard_bin_op_side(name, left, left.precedence());
+
+        // Comments before the right side are kept in front of its operator,
+        // as they are in expressions.
+        let comments = self.pop_comments(right.location().start);
+        let name_doc = break_("", " ").append(commented(name.to_doc(), comments));
+
+        left_side
+            .append(name_doc)
             .append(" ")
             .append(self.clause_guard_bin_op_side(name, right, right.precedence() - 1))
     }
@@ -2396,9 +2866,13 @@ impl<'comments> Formatter<'comments> {
         commented(doc, comments)
     }
 
+    /// A bit array is printed on a single line if it fits. Otherwise each
+    /// segment goes on its own line, with the options of the segments lined
+    /// up; only bit arrays of simple values without any options are packed onto
+    /// as few lines as possible instead.
     fn bit_array<'a>(
         &mut self,
-        segments: Vec<Document<'a>>,
+        segments: Vec<(Document<'a>, Option<Document<'a>>)>,
         is_simple: bool,
         location: &SrcSpan,
     ) -> Document<'a> {
@@ -2425,12 +2899,44 @@ impl<'comments> Formatter<'comments> {
                     .force_break(),
             };
         }
-        let comma = if is_simple {
+        let has_options = segments.iter().any(|(_, options)| options.is_some());
+        let comma = if is_simple && !has_options {
             flex_break(",", ", ")
         } else {
             break_(",", ", ")
         };
 
+        // The options are lined up one space after the widest value, as long
+        // as there's more than one segment with options and all their values
+        // fit on a single line and are short enough not to push the options
+        // of the others far away from them.
+        let widths = segments
+            .iter()
+            .map(|(value, options)| options.as_ref().and_then(|_| single_line_width(value)))
+            .collect_vec();
+        let options_column = segments
+            .iter()
+            .zip(&widths)
+            .filter(|((_, options), _)| options.is_some())
+            .map(|(_, width)| width.filter(|width| *width <= MAX_ALIGNED_SEGMENT_WIDTH))
+            .collect::<Option<Vec<_>>>()
+            .filter(|widths| widths.len() > 1)
+            .and_then(|widths| widths.into_iter().max())
+            .map(|width| width + 1);
+        let segments = segments
+            .into_iter()
+            .zip(widths)
+            .map(|((value, options), width)| match options {
+                None => value,
+                Some(options) => {
+                    let padding = options_column
+                        .zip(width)
+                        .map_or(0, |(column, width)| column - width);
+                    docvec![value, ":", padding_if_broken(padding), options]
+                }
+            })
+            .collect_vec();
+
         let last_break = break_(",", "");
         let doc = break_("<<", "<<")
             .append(join(segments, comma))
@@ -2671,6 +3177,27 @@ impl<'comments> Formatter<'comments> {
     }
 }
 
+fn target_attribute(target: Option<Target>, document: Document<'_>) -> Document<'_> {
+    match target {
+        None => document,
+        Some(Target::Erlang) => docvec!["@target(erlang)", line(), document],
+        Some(Target::JavaScript) => docvec!["@target(javascript)", line(), document],
+    }
+}
+
+/// Whether two imports import the same module under the same name, for the
+/// same target, so they can be merged into one.
+fn same_import(one: &TargetedDefinition, other: &TargetedDefinition) -> bool {
+    match (&one.definition, &other.definition) {
+        (Definition::Import(one_import), Definition::Import(other_import)) => {
+            one.target == other.target
+                && one_import.module == other_import.module
+                && one_import.used_name() == other_import.used_name()
+        }
+        _ => false,
+    }
+}
+
 fn init_and_last<T>(vec: &[T]) -> Option<(&[T], &T)> {
     match vec {
         [] => None,
@@ -2810,21 +3337,37 @@ fn commented<'a, 'comments>(
     }
 }
 
+/// The documents for the value of a bit array segment and for its options, if
+/// it has any.
 fn bit_array_segment<Value, Type, ToDoc>(
     segment: &BitArraySegment<Value, Type>,
     mut to_doc: ToDoc,
-) -> Document<'_>
+) -> (Document<'_>, Option<Document<'_>>)
 where
     ToDoc: FnMut(&Value) -> Document<'_>,
 {
-    match segment {
-        BitArraySegment { value, options, .. } if options.is_empty() => to_doc(value),
+    let BitArraySegment { value, options, .. } = segment;
+    let value = to_doc(value);
+    if options.is_empty() {
+        return (value, None);
+    }
+    let options = join(
+        options.iter().map(|o| segment_option(o, |e| to_doc(e))),
+        "-".to_doc(),
+    );
+    (value, Some(options))
+}
 
-        BitArraySegment { value, options, .. } => to_doc(value).append(":").append(join(
-            options.iter().map(|o| segment_option(o, |e| to_doc(e))),
-            "-".to_doc(),
-        )),
-    }
+/// The width of a document printed on a single line, ignoring any comments
+/// printed on the lines before it. Returns `None` if it can't be printed on a
+/// single line.
+fn single_line_width(document: &Document<'_>) -> Option<isize> {
+    let printed = document.clone().to_pretty_string(isize::MAX);
+    let (line,) = printed
+        .lines()
+        .filter(|line| !line.trim_start().starts_with("//"))
+        .collect_tuple()?;
+    Some(line.graphemes(true).count() as isize)
 }
 
 fn segment_option<ToDoc, Value>(option: &BitArrayOption<Value>, mut to_doc: ToDoc) -> Document<'_>
@@ -3000,6 +3543,7 @@ struct AttributesPrinter<'a> {
     external_javascript: &'a Option<(EcoString, EcoString, SrcSpan)>,
     deprecation: &'a Deprecation,
     internal: bool,
+    phantom: bool,
 }
 
 impl<'a> AttributesPrinter<'a> {
@@ -3009,6 +3553,7 @@ impl<'a> AttributesPrinter<'a> {
             external_javascript: &None,
             deprecation: &Deprecation::NotDeprecated,
             internal: false,
+            phantom: false,
         }
     }
 
@@ -3037,6 +3582,11 @@ impl<'a> AttributesPrinter<'a> {
         self.deprecation = deprecation;
         self
     }
+
+    pub fn set_phantom(mut self, phantom: bool) -> Self {
+        self.phantom = phantom;
+        self
+    }
 }
 
 impl<'a> Documentable<'a> for AttributesPrinter<'a> {
@@ -3062,6 +3612,11 @@ impl<'a> Documentable<'a> for AttributesPrinter<'a> {
             attributes.push("@internal".to_doc());
         };
 
+        // @phantom attribute
+        if self.phantom {
+            attributes.push("@phantom".to_doc());
+        };
+
         if attributes.is_empty() {
             nil()
         } else {
@@ -0,0 +1,188 @@
+//! Formatting the ```` ```gleam ```` code blocks written in comments, so the
+//! examples in documentation are formatted like the rest of the code.
+//!
+//! Each line of a comment is its own `Comment`, so a code block is formatted
+//! up front and its lines replace the ones that were in the source. A block
+//! that can't be parsed, either as a module or as the body of a function, is
+//! left as it was.
+
+use std::ops::RangeInclusive;
+
+use ecow::EcoString;
+
+use crate::{ast::SrcSpan, config::FormatConfig, parse::extra::ModuleExtra};
+
+use super::pretty;
+
+#[derive(Debug, Default)]
+pub(crate) struct CodeBlocks {
+    pub comments: Vec<CodeBlock>,
+    pub doc_comments: Vec<CodeBlock>,
+    pub module_comments: Vec<CodeBlock>,
+}
+
+#[derive(Debug)]
+pub(crate) struct CodeBlock {
+    /// The start of the first and last lines of code in the block.
+    replaced: RangeInclusive<u32>,
+    /// The formatted lines, with the position each one takes the place of.
+    pub lines: Vec<(u32, String)>,
+}
+
+impl CodeBlock {
+    pub fn replaces(&self, start: u32) -> bool {
+        self.replaced.contains(&start)
+    }
+}
+
+impl CodeBlocks {
+    pub fn new(extra: &ModuleExtra, src: &str, config: &FormatConfig) -> Self {
+        if !config.format_code_blocks {
+            return Self::default();
+        }
+        Self {
+            comments: code_blocks(&extra.comments, src, "//", config),
+            doc_comments: code_blocks(&extra.doc_comments, src, "///", config),
+            module_comments: code_blocks(&extra.module_comments, src, "////", config),
+        }
+    }
+}
+
+/// Finds and formats the code blocks in comments of one kind, each of which
+/// starts with the given `marker`.
+fn code_blocks(
+    spans: &[SrcSpan],
+    src: &str,
+    marker: &str,
+    config: &FormatConfig,
+) -> Vec<CodeBlock> {
+    let line = |span: &SrcSpan| {
+        src.get(span.start as usize..span.end as usize)
+            .unwrap_or("")
+    };
+    // Two comments are part of the same block only if nothing but whitespace
+    // comes between them.
+    let adjacent = |one: &SrcSpan, other: &SrcSpan| {
+        src.get(one.end as usize..other.start as usize)
+            .is_some_and(|between| between.trim_start() == marker)
+    };
+
+    let mut blocks = vec![];
+    let mut index = 0;
+    while let Some(opening) = spans.get(index) {
+        index += 1;
+        let Some(indentation) = line(opening).trim_end().strip_suffix("```gleam") else {
+            continue;
+        };
+        if !indentation.trim().is_empty() {
+            continue;
+        }
+
+        let mut previous = opening;
+        let mut closing = None;
+        for (offset, span) in spans.get(index..).unwrap_or_default().iter().enumerate() {
+            if !adjacent(previous, span) {
+                break;
+            }
+            if line(span).trim() == "```" {
+                closing = Some(index + offset);
+                break;
+            }
+            previous = span;
+        }
+        let Some(closing) = closing else {
+            continue;
+        };
+        let code = spans.get(index..closing).unwrap_or_default();
+        index = closing + 1;
+
+        let (Some(first), Some(last)) = (code.first(), code.last()) else {
+            continue;
+        };
+        let Some(formatted) = format_code(code.iter().map(line), indentation, marker, config)
+        else {
+            continue;
+        };
+        let lines = formatted
+            .into_iter()
+            .enumerate()
+            .map(|(i, line)| {
+                let start = code.get(i).unwrap_or(last).start;
+                (start, line)
+            })
+            .collect();
+        blocks.push(CodeBlock {
+            replaced: first.start..=last.start,
+            lines,
+        });
+    }
+    blocks
+}
+
+/// Formats the lines of a code block, returning the new lines with the
+/// block's indentation, or `None` if the code couldn't be formatted.
+fn format_code<'a>(
+    lines: impl Iterator<Item = &'a str>,
+    indentation: &str,
+    marker: &str,
+    config: &FormatConfig,
+) -> Option<Vec<String>> {
+    let mut code = String::new();
+    for line in lines {
+        match line.strip_prefix(indentation) {
+            Some(line) => code.push_str(line),
+            None if line.trim().is_empty() => (),
+            None => return None,
+        }
+        code.push('\n');
+    }
+
+    let width = config
+        .line_width
+        .saturating_sub((marker.len() + indentation.len()) as u32);
+    let config = FormatConfig {
+        line_width: width,
+        ..config.clone()
+    };
+    let format = |src: String, config: &FormatConfig| {
+        let mut formatted = String::new();
+        pretty(
+            &mut formatted,
+            &EcoString::from(src),
+            camino::Utf8Path::new("<code block>"),
+            config,
+        )
+        .ok()
+        .map(|()| formatted)
+    };
+
+    let formatted = match format(code.clone(), &config) {
+        Some(formatted) => formatted,
+        // Examples are often a few expressions rather than a whole module, so
+        // those are formatted as the body of a function.
+        None => {
+            let config = FormatConfig {
+                line_width: width + 2,
+                ..config
+            };
+            let formatted = format(format!("fn main() {{\n{code}}}\n"), &config)?;
+            formatted
+                .strip_prefix("fn main() {\n")?
+                .strip_suffix("}\n")?
+                .lines()
+                .map(|line| line.strip_prefix("  ").unwrap_or(line))
+                .map(|line| format!("{line}\n"))
+                .collect()
+        }
+    };
+
+    Some(
+        formatted
+            .lines()
+            .map(|line| match line {
+                "" => String::new(),
+                line => format!("{indentation}{line}"),
+            })
+            .collect(),
+    )
+}
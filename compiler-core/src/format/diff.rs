@@ -0,0 +1,142 @@
+//! Finding the lines that differ between a module and its formatted code, using
+//! Myers' diff algorithm on whole lines.
+
+use itertools::Itertools;
+
+use crate::ast::SrcSpan;
+
+use super::Edit;
+
+/// The most insertions and deletions of lines that are looked for before the
+/// changed part of a module is replaced as a whole, which keeps diffing a
+/// badly formatted module from taking too long.
+const MAX_DIFFERENCES: usize = 1000;
+
+pub fn line_edits(src: &str, formatted: &str) -> Vec<Edit> {
+    let old = src.split_inclusive('\n').collect_vec();
+    let new = formatted.split_inclusive('\n').collect_vec();
+    let mut line_starts = Vec::with_capacity(old.len() + 1);
+    let mut position = 0;
+    for line in &old {
+        line_starts.push(position);
+        position += line.len() as u32;
+    }
+    line_starts.push(position);
+
+    // Most of a module is usually left as it is, so the same lines at the
+    // start and end are skipped before diffing what's left.
+    let prefix = old
+        .iter()
+        .zip(&new)
+        .take_while(|(one, other)| one == other)
+        .count();
+    let suffix = old
+        .iter()
+        .skip(prefix)
+        .rev()
+        .zip(new.iter().skip(prefix).rev())
+        .take_while(|(one, other)| one == other)
+        .count();
+    let old_changed = old.get(prefix..old.len() - suffix).unwrap_or_default();
+    let new_changed = new.get(prefix..new.len() - suffix).unwrap_or_default();
+
+    let mut kept = common_lines(old_changed, new_changed).unwrap_or_default();
+    kept.push((old_changed.len(), new_changed.len()));
+
+    let mut edits = vec![];
+    let (mut old_start, mut new_start) = (0, 0);
+    for (old_end, new_end) in kept {
+        if old_end > old_start || new_end > new_start {
+            let start = line_starts
+                .get(prefix + old_start)
+                .copied()
+                .unwrap_or(position);
+            let end = line_starts
+                .get(prefix + old_end)
+                .copied()
+                .unwrap_or(position);
+            edits.push(Edit {
+                location: SrcSpan::new(start, end),
+                replacement: new_changed
+                    .get(new_start..new_end)
+                    .unwrap_or_default()
+                    .concat(),
+            });
+        }
+        (old_start, new_start) = (old_end + 1, new_end + 1);
+    }
+    edits
+}
+
+/// The indices of the lines that are the same in both, in order. Returns
+/// `None` if more than `MAX_DIFFERENCES` lines were inserted or deleted.
+fn common_lines(old: &[&str], new: &[&str]) -> Option<Vec<(usize, usize)>> {
+    let n = old.len() as isize;
+    let m = new.len() as isize;
+    let max = (old.len() + new.len()).min(MAX_DIFFERENCES) as isize;
+    let offset = max + 1;
+    // The furthest line of `old` reached on each diagonal `k`, where a
+    // diagonal is the lines at `x` in `old` and `y` in `new` with `x - y = k`.
+    let mut furthest = vec![0; 2 * max as usize + 3];
+    let at = |v: &[isize], k: isize| v.get((k + offset) as usize).copied().unwrap_or(0);
+    let mut trace = vec![];
+
+    for d in 0..=max {
+        trace.push(
+            furthest
+                .get((offset - d) as usize..=(offset + d) as usize)
+                .unwrap_or_default()
+                .to_vec(),
+        );
+        for k in (-d..=d).step_by(2) {
+            let mut x = if k == -d || (k != d && at(&furthest, k - 1) < at(&furthest, k + 1)) {
+                at(&furthest, k + 1)
+            } else {
+                at(&furthest, k - 1) + 1
+            };
+            let mut y = x - k;
+            while x < n && y < m && old.get(x as usize) == new.get(y as usize) {
+                x += 1;
+                y += 1;
+            }
+            if let Some(slot) = furthest.get_mut((k + offset) as usize) {
+                *slot = x;
+            }
+            if x >= n && y >= m {
+                return Some(backtrack(&trace, n, m));
+            }
+        }
+    }
+    None
+}
+
+/// Walks back through the furthest lines reached for each number of
+/// differences, collecting the lines kept along the way.
+fn backtrack(trace: &[Vec<isize>], n: isize, m: isize) -> Vec<(usize, usize)> {
+    let (mut x, mut y) = (n, m);
+    let mut kept = vec![];
+    for (d, furthest) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        let at = |k: isize| furthest.get((k + d) as usize).copied().unwrap_or(0);
+        let (previous_x, previous_y) = if d == 0 {
+            (0, 0)
+        } else {
+            let k = x - y;
+            let previous_k = if k == -d || (k != d && at(k - 1) < at(k + 1)) {
+                k + 1
+            } else {
+                k - 1
+            };
+            let previous_x = at(previous_k);
+            (previous_x, previous_x - previous_k)
+        };
+        while x > previous_x && y > previous_y {
+            x -= 1;
+            y -= 1;
+            kept.push((x as usize, y as usize));
+        }
+        (x, y) = (previous_x, previous_y);
+    }
+    kept.reverse();
+    kept
+}
@@ -5,15 +5,19 @@ mod binary_operators;
 mod bit_array;
 mod blocks;
 mod cases;
+mod code_blocks;
 mod conditional_compilation;
 mod custom_type;
+mod edits;
 mod external_fn;
 mod external_types;
 mod function;
 mod guards;
 mod imports;
 mod pipeline;
+mod range;
 mod record_update;
+mod tolerant;
 mod tuple;
 mod use_;
 
@@ -21,8 +25,13 @@ mod use_;
 macro_rules! assert_format {
     ($src:expr $(,)?) => {
         let mut writer = String::new();
-        $crate::format::pretty(&mut writer, &$src.into(), camino::Utf8Path::new("<stdin>"))
-            .unwrap();
+        $crate::format::pretty(
+            &mut writer,
+            &$src.into(),
+            camino::Utf8Path::new("<stdin>"),
+            &Default::default(),
+        )
+        .unwrap();
         assert_eq!($src, writer);
     };
 }
@@ -31,12 +40,135 @@ macro_rules! assert_format {
 macro_rules! assert_format_rewrite {
     ($src:expr, $expected:expr  $(,)?) => {
         let mut writer = String::new();
-        $crate::format::pretty(&mut writer, &$src.into(), camino::Utf8Path::new("<stdin>"))
-            .unwrap();
+        $crate::format::pretty(
+            &mut writer,
+            &$src.into(),
+            camino::Utf8Path::new("<stdin>"),
+            &Default::default(),
+        )
+        .unwrap();
         assert_eq!(writer, $expected);
+        // Formatting the rewritten code again must leave it as it is
+        assert_eq!(
+            $crate::format::check_idempotent(
+                &$src.into(),
+                camino::Utf8Path::new("<stdin>"),
+                &Default::default(),
+            )
+            .unwrap(),
+            None
+        );
     };
 }
 
+#[test]
+fn formatted_code_is_stable() {
+    assert_eq!(
+        super::check_idempotent(
+            &"pub fn main( ) { 1+2 }".into(),
+            camino::Utf8Path::new("<stdin>"),
+            &Default::default(),
+        )
+        .expect("format"),
+        None
+    );
+}
+
+#[test]
+fn unstable_reports_the_changed_lines() {
+    assert_eq!(
+        super::unstable(
+            "import wibble\n\npub fn main() {\n  1\n}\n",
+            "import wibble\n\npub fn main() {\n  1\n\n}\n",
+        ),
+        Some(super::Unstable {
+            line: 5,
+            first: "".into(),
+            second: "".into(),
+        })
+    );
+    assert_eq!(
+        super::unstable("one\ntwo\nthree\n", "one\n2\n3\nthree\n"),
+        Some(super::Unstable {
+            line: 2,
+            first: "two".into(),
+            second: "2\n3".into(),
+        })
+    );
+    assert_eq!(super::unstable("one\n", "one\n"), None);
+}
+
+#[test]
+fn line_width_can_be_configured() {
+    let src = "pub fn main() {
+  wibble(wobble, wubble, wabble)
+}
+";
+    let mut narrow = String::new();
+    super::pretty(
+        &mut narrow,
+        &src.into(),
+        camino::Utf8Path::new("<stdin>"),
+        &crate::config::FormatConfig {
+            line_width: 30,
+            ..Default::default()
+        },
+    )
+    .expect("format");
+    assert_eq!(
+        narrow,
+        "pub fn main() {
+  wibble(
+    wobble,
+    wubble,
+    wabble,
+  )
+}
+"
+    );
+}
+
+#[test]
+fn max_blank_lines_can_be_configured() {
+    let src = "pub fn main() {
+  let x = 1
+
+
+
+  let y = 2
+
+
+  // Comment
+  x + y
+}
+";
+    let mut formatted = String::new();
+    super::pretty(
+        &mut formatted,
+        &src.into(),
+        camino::Utf8Path::new("<stdin>"),
+        &crate::config::FormatConfig {
+            max_blank_lines: 2,
+            ..Default::default()
+        },
+    )
+    .expect("format");
+    assert_eq!(
+        formatted,
+        "pub fn main() {
+  let x = 1
+
+
+  let y = 2
+
+
+  // Comment
+  x + y
+}
+"
+    );
+}
+
 #[test]
 fn imports() {
     assert_format!("\n");
@@ -4331,6 +4463,39 @@ fn empty_lines_work_with_trailing_space() {
     assert_format_rewrite!(src, expected);
 }
 
+#[test]
+fn empty_line_after_statement_with_trailing_space() {
+    assert_format_rewrite!(
+        "pub fn main() {\n  let x = 1   \n\n  x\n}\n",
+        "pub fn main() {
+  let x = 1
+
+  x
+}
+"
+    );
+}
+
+#[test]
+fn empty_lines_within_a_statement_are_not_moved_after_it() {
+    assert_format_rewrite!(
+        "pub fn main() {
+  let x = [
+    1,
+
+    2,
+  ]
+  x
+}
+",
+        "pub fn main() {
+  let x = [1, 2]
+  x
+}
+"
+    );
+}
+
 #[test]
 fn empty_lines_work_with_eol_normalisation() {
     let src = "pub fn main() {
@@ -5754,6 +5919,30 @@ pub const wibble = 1
     );
 }
 
+#[test]
+fn phantom_attribute_on_type() {
+    assert_format!(
+        r#"@phantom
+pub type Id(a) {
+  Id(Int)
+}
+"#
+    );
+}
+
+#[test]
+fn phantom_attribute_with_other_attributes() {
+    assert_format!(
+        r#"@deprecated("Use something else")
+@internal
+@phantom
+pub type Id(a) {
+  Id(Int)
+}
+"#
+    );
+}
+
 #[test]
 fn comments_inside_contant_list() {
     assert_format!(
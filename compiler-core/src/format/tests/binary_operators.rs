@@ -273,3 +273,30 @@ fn binop_as_argument_in_variant_with_spread_gets_nested() {
 "#
     );
 }
+
+#[test]
+fn comments_in_mixed_precedence_chain_stay_on_their_operand() {
+    assert_format!(
+        r#"pub fn main() {
+  let wibble =
+    1
+    // Added
+    + 2
+    // Multiplied
+    * 3
+}
+"#
+    );
+}
+
+#[test]
+fn comments_in_constant_string_concatenation_stay_on_their_operand() {
+    assert_format!(
+        r#"const wibble = "wibble"
+  // The middle
+  <> "wobble"
+  // The end
+  <> "wubble"
+"#
+    );
+}
@@ -1,4 +1,4 @@
-use crate::assert_format;
+use crate::{assert_format, assert_format_rewrite};
 
 #[test]
 fn construction() {
@@ -202,3 +202,112 @@ fn block_value() {
 "#
     );
 }
+
+#[test]
+fn broken_segment_options_are_aligned() {
+    assert_format!(
+        "fn main() {
+  <<
+    version:         4,
+    header_length:   size(4),
+    type_of_service: 8,
+    total_length:    16,
+    identification:  16,
+  >>
+}
+"
+    );
+}
+
+#[test]
+fn broken_segments_with_options_go_on_their_own_line() {
+    assert_format_rewrite!(
+        "fn main() {
+  <<1:8, 2:8, 3:8, 4:8, 5:8, 6:8, 7:8, 8:8, 9:8, 10:8, 11:8, 12:8, 13:8, 14:8, 15:8>>
+}
+",
+        "fn main() {
+  <<
+    1:  8,
+    2:  8,
+    3:  8,
+    4:  8,
+    5:  8,
+    6:  8,
+    7:  8,
+    8:  8,
+    9:  8,
+    10: 8,
+    11: 8,
+    12: 8,
+    13: 8,
+    14: 8,
+    15: 8,
+  >>
+}
+"
+    );
+}
+
+#[test]
+fn unbroken_segment_options_are_not_aligned() {
+    assert_format!(
+        "fn main() {
+  <<version:4, header_length:size(4)>>
+}
+"
+    );
+}
+
+#[test]
+fn broken_pattern_segment_options_are_aligned() {
+    assert_format!(
+        "fn main() {
+  case wibble {
+    <<
+      version:         4,
+      header_length:   size(4),
+      type_of_service: 8,
+      total_length:    16,
+      rest:            bits,
+    >> -> 1
+    _ -> 2
+  }
+}
+"
+    );
+}
+
+#[test]
+fn wide_segment_values_stop_options_being_aligned() {
+    assert_format!(
+        "fn main() {
+  <<
+    1:size(8),
+    wibble_wobble_wubble_wabble:size(16),
+    <<1, 2, 3>>:bits,
+    wibble:bytes,
+    wobble:bytes,
+    wubble:bytes,
+  >>
+}
+"
+    );
+}
+
+#[test]
+fn comments_before_aligned_segments() {
+    assert_format!(
+        "fn main() {
+  <<
+    // The version
+    version:       4,
+    // The length of the header
+    header_length: size(4),
+    wibble_wobble: 8,
+    wubble_wabble: 8,
+  >>
+}
+"
+    );
+}
@@ -0,0 +1,174 @@
+use camino::Utf8Path;
+
+use crate::{assert_format, config::FormatConfig, format::pretty};
+
+fn format_code_blocks(src: &str) -> String {
+    let mut formatted = String::new();
+    pretty(
+        &mut formatted,
+        &src.into(),
+        Utf8Path::new("<stdin>"),
+        &FormatConfig {
+            format_code_blocks: true,
+            ..Default::default()
+        },
+    )
+    .expect("format");
+    formatted
+}
+
+#[test]
+fn code_blocks_are_not_formatted_by_default() {
+    assert_format!(
+        "/// ```gleam
+/// wibble( 1 )
+/// ```
+pub fn wibble(x) {
+  x
+}
+"
+    );
+}
+
+#[test]
+fn doc_comment_code_block_is_formatted() {
+    assert_eq!(
+        format_code_blocks(
+            "/// Adds one.
+///
+/// ```gleam
+/// wibble( 1 )
+/// // -> 2
+/// ```
+pub fn wibble(x) {
+  x + 1
+}
+"
+        ),
+        "/// Adds one.
+///
+/// ```gleam
+/// wibble(1)
+/// // -> 2
+/// ```
+pub fn wibble(x) {
+  x + 1
+}
+"
+    );
+}
+
+#[test]
+fn module_comment_code_block_is_formatted() {
+    assert_eq!(
+        format_code_blocks(
+            "//// ```gleam
+//// import gleam/io
+//// pub fn main() { io.println(\"Hello\") }
+//// ```
+
+pub fn wibble() {
+  Nil
+}
+"
+        ),
+        "//// ```gleam
+//// import gleam/io
+////
+//// pub fn main() {
+////   io.println(\"Hello\")
+//// }
+//// ```
+
+pub fn wibble() {
+  Nil
+}
+"
+    );
+}
+
+#[test]
+fn comment_code_block_is_formatted() {
+    assert_eq!(
+        format_code_blocks(
+            "pub fn main() {
+  // ```gleam
+  // let x = [1,2,3]
+  // ```
+  Nil
+}
+"
+        ),
+        "pub fn main() {
+  // ```gleam
+  // let x = [1, 2, 3]
+  // ```
+  Nil
+}
+"
+    );
+}
+
+#[test]
+fn code_block_is_formatted_to_fit_within_the_comment() {
+    assert_eq!(
+        format_code_blocks(
+            "/// ```gleam
+/// wibble(wobble, wubble, wabble, wibble_wobble, wibble_wubble, wobble_wubble_wabble)
+/// ```
+pub fn wibble() {
+  Nil
+}
+"
+        ),
+        "/// ```gleam
+/// wibble(
+///   wobble,
+///   wubble,
+///   wabble,
+///   wibble_wobble,
+///   wibble_wubble,
+///   wobble_wubble_wabble,
+/// )
+/// ```
+pub fn wibble() {
+  Nil
+}
+"
+    );
+}
+
+#[test]
+fn code_block_with_a_syntax_error_is_left_alone() {
+    let src = "/// ```gleam
+/// wibble(  1
+/// ```
+pub fn wibble() {
+  Nil
+}
+";
+    assert_eq!(format_code_blocks(src), src);
+}
+
+#[test]
+fn code_blocks_in_other_languages_are_left_alone() {
+    let src = "/// ```erlang
+/// wibble( 1 ).
+/// ```
+pub fn wibble() {
+  Nil
+}
+";
+    assert_eq!(format_code_blocks(src), src);
+}
+
+#[test]
+fn unclosed_code_block_is_left_alone() {
+    let src = "/// ```gleam
+/// wibble( 1 )
+pub fn wibble() {
+  Nil
+}
+";
+    assert_eq!(format_code_blocks(src), src);
+}
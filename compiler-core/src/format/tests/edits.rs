@@ -0,0 +1,102 @@
+use camino::Utf8Path;
+
+use crate::{
+    ast::SrcSpan,
+    format::{edits, pretty_edits, Edit},
+};
+
+fn apply(src: &str, edits: &[Edit]) -> String {
+    let mut result = src.to_string();
+    for edit in edits.iter().rev() {
+        result.replace_range(
+            edit.location.start as usize..edit.location.end as usize,
+            &edit.replacement,
+        );
+    }
+    result
+}
+
+fn assert_edits_apply(src: &str, formatted: &str) {
+    assert_eq!(apply(src, &edits(src, formatted)), formatted);
+}
+
+#[test]
+fn no_edits_for_the_same_text() {
+    assert_eq!(edits("one\ntwo\n", "one\ntwo\n"), vec![]);
+}
+
+#[test]
+fn changed_line() {
+    assert_eq!(
+        edits("one\ntwo\nthree\n", "one\n2\nthree\n"),
+        vec![Edit {
+            location: SrcSpan::new(4, 8),
+            replacement: "2\n".into(),
+        }]
+    );
+}
+
+#[test]
+fn inserted_and_removed_lines() {
+    assert_eq!(
+        edits("one\ntwo\nthree\nfour\n", "zero\none\nthree\nfour\nfive\n"),
+        vec![
+            Edit {
+                location: SrcSpan::new(0, 0),
+                replacement: "zero\n".into(),
+            },
+            Edit {
+                location: SrcSpan::new(4, 8),
+                replacement: "".into(),
+            },
+            Edit {
+                location: SrcSpan::new(19, 19),
+                replacement: "five\n".into(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn edits_turn_the_source_into_the_formatted_code() {
+    assert_edits_apply("", "one\n");
+    assert_edits_apply("one\n", "");
+    assert_edits_apply("one", "one\n");
+    assert_edits_apply("a\nb\nc\na\nb\nb\na\n", "c\nb\na\nb\na\nc\n");
+    assert_edits_apply("one\r\ntwo\r\n", "one\ntwo\n");
+}
+
+#[test]
+fn very_different_text_is_replaced_as_a_whole() {
+    let src = (0..1500).map(|i| format!("{i}\n")).collect::<String>();
+    let formatted = (0..1500).map(|i| format!("line {i}\n")).collect::<String>();
+    assert_eq!(
+        edits(&src, &formatted),
+        vec![Edit {
+            location: SrcSpan::new(0, src.len() as u32),
+            replacement: formatted,
+        }]
+    );
+}
+
+#[test]
+fn pretty_edits_only_change_unformatted_lines() {
+    let src = "pub fn wibble() {
+  1
+}
+
+pub fn wobble( ) { 2 }
+";
+    let edits =
+        pretty_edits(&src.into(), Utf8Path::new("<stdin>"), &Default::default()).expect("format");
+    assert_eq!(
+        edits,
+        vec![Edit {
+            location: SrcSpan::new(
+                src.find("pub fn wobble").expect("wobble") as u32,
+                src.len() as u32
+            ),
+            replacement: "pub fn wobble() {\n  2\n}\n".into(),
+        }]
+    );
+}
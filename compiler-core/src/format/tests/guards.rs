@@ -79,3 +79,20 @@ fn long_guard_with_alternative_patterns() {
 "#
     );
 }
+
+#[test]
+fn comments_in_guard_operator_chain_stay_on_their_operand() {
+    assert_format!(
+        r#"pub fn main() {
+  case x {
+    _
+      if wibble
+      // Otherwise
+      || wobble
+    -> 1
+    _ -> 0
+  }
+}
+"#
+    );
+}
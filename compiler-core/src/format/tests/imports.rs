@@ -218,3 +218,68 @@ import gleam/list
 "#
     );
 }
+
+#[test]
+fn duplicate_imports_are_merged() {
+    assert_format_rewrite!(
+        "import wibble.{wobble}
+import gleam/io
+import wibble.{type Wubble, wabble, wobble}
+",
+        "import gleam/io
+import wibble.{type Wubble, wabble, wobble}
+"
+    );
+}
+
+#[test]
+fn duplicate_imports_with_redundant_as_name_are_merged() {
+    assert_format_rewrite!(
+        "import wibble/wobble as wobble
+import wibble/wobble.{wubble}
+",
+        "import wibble/wobble.{wubble}
+"
+    );
+}
+
+#[test]
+fn imports_of_the_same_module_with_different_names_are_not_merged() {
+    assert_format!(
+        "import wibble.{wobble}
+import wibble.{wubble} as wabble
+"
+    );
+}
+
+#[test]
+fn unqualified_imports_with_different_names_are_kept_when_merging() {
+    assert_format_rewrite!(
+        "import wibble.{wobble}
+import wibble.{wobble as wubble}
+",
+        "import wibble.{wobble, wobble as wubble}
+"
+    );
+}
+
+#[test]
+fn duplicate_imports_in_different_groups_are_not_merged() {
+    assert_format!(
+        "import wibble.{wobble}
+
+import wibble.{wubble}
+"
+    );
+}
+
+#[test]
+fn duplicate_imports_for_different_targets_are_not_merged() {
+    assert_format!(
+        "@target(erlang)
+import wibble.{wobble}
+@target(javascript)
+import wibble.{wubble}
+"
+    );
+}
@@ -1,4 +1,22 @@
-use crate::{assert_format, assert_format_rewrite};
+use crate::{
+    assert_format, assert_format_rewrite,
+    config::{FormatConfig, PipelineBreaking},
+};
+
+fn format_breaking_pipelines(src: &str, break_pipelines: PipelineBreaking) -> String {
+    let mut formatted = String::new();
+    crate::format::pretty(
+        &mut formatted,
+        &src.into(),
+        camino::Utf8Path::new("<stdin>"),
+        &FormatConfig {
+            break_pipelines,
+            ..Default::default()
+        },
+    )
+    .expect("format");
+    formatted
+}
 
 #[test]
 pub fn single_line_pipeline_longer_than_line_limit_gets_split() {
@@ -132,3 +150,75 @@ fn main() {
 "
     );
 }
+
+#[test]
+fn comments_in_pipeline_compared_with_operator_stay_on_their_step() {
+    assert_format!(
+        r#"pub fn main() {
+  let wibble =
+    wobble
+    // First step
+    |> wubble
+    // Second step
+    |> wabble
+    == 1
+}
+"#
+    );
+}
+
+const ONE_LINE_PIPELINE: &str = "pub fn main() {
+  wibble |> wobble |> wubble
+}
+";
+
+const SPLIT_PIPELINE: &str = "pub fn main() {
+  wibble
+  |> wobble
+  |> wubble
+}
+";
+
+#[test]
+fn pipelines_can_always_be_split() {
+    assert_eq!(
+        format_breaking_pipelines(ONE_LINE_PIPELINE, PipelineBreaking::Always),
+        SPLIT_PIPELINE
+    );
+}
+
+#[test]
+fn pipelines_can_be_split_only_when_too_long() {
+    assert_eq!(
+        format_breaking_pipelines(SPLIT_PIPELINE, PipelineBreaking::WhenTooLong),
+        ONE_LINE_PIPELINE
+    );
+    assert_eq!(
+        format_breaking_pipelines(
+            "pub fn main() {
+  wibble_wobble_wubble |> wibble_wobble_wubble |> wibble_wobble_wubble |> wubble_wabble
+}
+",
+            PipelineBreaking::WhenTooLong
+        ),
+        "pub fn main() {
+  wibble_wobble_wubble
+  |> wibble_wobble_wubble
+  |> wibble_wobble_wubble
+  |> wubble_wabble
+}
+"
+    );
+}
+
+#[test]
+fn pipelines_can_be_split_over_a_number_of_steps() {
+    assert_eq!(
+        format_breaking_pipelines(ONE_LINE_PIPELINE, PipelineBreaking::OverSteps(1)),
+        SPLIT_PIPELINE
+    );
+    assert_eq!(
+        format_breaking_pipelines(SPLIT_PIPELINE, PipelineBreaking::OverSteps(2)),
+        ONE_LINE_PIPELINE
+    );
+}
@@ -0,0 +1,195 @@
+use camino::Utf8Path;
+
+use crate::{ast::SrcSpan, format::pretty_range};
+
+/// Formats the definitions of `src` that overlap the first occurrence of
+/// `selected`, returning the source with the edits applied.
+fn format_range(src: &str, selected: &str) -> String {
+    let start = src.find(selected).expect("selected text in source") as u32;
+    let range = SrcSpan::new(start, start + selected.len() as u32);
+    let edits = pretty_range(
+        &src.into(),
+        Utf8Path::new("<stdin>"),
+        range,
+        &Default::default(),
+    )
+    .expect("format range");
+
+    let mut result = src.to_string();
+    for edit in edits.into_iter().rev() {
+        result.replace_range(
+            edit.location.start as usize..edit.location.end as usize,
+            &edit.replacement,
+        );
+    }
+    result
+}
+
+#[test]
+fn only_the_selected_definition_is_formatted() {
+    assert_eq!(
+        format_range(
+            "pub fn wibble( ) { 1+2 }
+
+pub fn wobble( ) { 3+4 }
+",
+            "wobble"
+        ),
+        "pub fn wibble( ) { 1+2 }
+
+pub fn wobble() {
+  3 + 4
+}
+"
+    );
+}
+
+#[test]
+fn selection_spanning_definitions_formats_each_of_them() {
+    assert_eq!(
+        format_range(
+            "pub fn wibble( ) { 1+2 }
+
+pub fn wobble( ) { 3+4 }
+
+pub fn wubble( ) { 5+6 }
+",
+            "1+2 }
+
+pub fn wobble"
+        ),
+        "pub fn wibble() {
+  1 + 2
+}
+
+pub fn wobble() {
+  3 + 4
+}
+
+pub fn wubble( ) { 5+6 }
+"
+    );
+}
+
+#[test]
+fn formatted_definitions_have_no_edits() {
+    let src = "pub fn wibble() {
+  1 + 2
+}
+";
+    let edits = pretty_range(
+        &src.into(),
+        Utf8Path::new("<stdin>"),
+        SrcSpan::new(0, src.len() as u32),
+        &Default::default(),
+    )
+    .expect("format range");
+    assert_eq!(edits, vec![]);
+}
+
+#[test]
+fn comments_and_attributes_before_the_definition_are_formatted() {
+    assert_eq!(
+        format_range(
+            "pub const wibble   = 1
+
+// A comment
+///   Some documentation
+@deprecated(   \"Use wibble\" )
+pub   fn wobble() { wibble }
+",
+            "wobble"
+        ),
+        "pub const wibble   = 1
+
+// A comment
+///   Some documentation
+@deprecated(\"Use wibble\")
+pub fn wobble() {
+  wibble
+}
+"
+    );
+}
+
+#[test]
+fn comments_inside_the_definition_are_kept() {
+    assert_eq!(
+        format_range(
+            "pub fn wibble() {
+    // One
+    1
+    // Two
+}
+",
+            "One"
+        ),
+        "pub fn wibble() {
+  // One
+  1
+  // Two
+}
+"
+    );
+}
+
+#[test]
+fn module_comments_are_left_alone() {
+    assert_eq!(
+        format_range(
+            "//// The module
+pub type   Wibble { Wobble }
+",
+            "Wibble"
+        ),
+        "//// The module
+pub type Wibble {
+  Wobble
+}
+"
+    );
+}
+
+#[test]
+fn imports_are_formatted_and_sorted_together() {
+    assert_eq!(
+        format_range(
+            "import wobble
+import   wibble.{ b, a }
+pub const x =    1
+",
+            "wobble"
+        ),
+        "import wibble.{a, b}
+import wobble
+pub const x =    1
+"
+    );
+}
+
+#[test]
+fn constants_and_type_aliases_are_formatted_to_their_end() {
+    assert_eq!(
+        format_range(
+            "pub const wibble   = [ 1,2 ]
+pub type Wobble   = List( Int )
+",
+            "pub"
+        ),
+        "pub const wibble = [1, 2]
+pub type Wobble   = List( Int )
+"
+    );
+    assert_eq!(
+        format_range(
+            "pub const wibble   = [ 1,2 ]
+pub type Wobble   = List( Int )
+",
+            "Wobble"
+        ),
+        "pub const wibble   = [ 1,2 ]
+pub type Wobble =
+  List(Int)
+"
+    );
+}
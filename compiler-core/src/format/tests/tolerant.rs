@@ -0,0 +1,134 @@
+use camino::Utf8Path;
+
+use crate::{format::pretty_tolerant, Error};
+
+/// Formats the source around its syntax errors, returning the output and the
+/// start of each error.
+fn format_tolerant(src: &str) -> (String, Vec<u32>) {
+    let mut output = String::new();
+    let errors = pretty_tolerant(
+        &mut output,
+        &src.into(),
+        Utf8Path::new("<stdin>"),
+        &Default::default(),
+    )
+    .expect("format");
+    let starts = errors
+        .into_iter()
+        .map(|error| match error {
+            Error::Parse { error, .. } => error.location.start,
+            error => panic!("unexpected error {error:?}"),
+        })
+        .collect();
+    (output, starts)
+}
+
+#[test]
+fn module_without_errors_is_formatted_as_usual() {
+    let src = "pub fn main( ) { 1+1 }\n";
+    let (output, errors) = format_tolerant(src);
+    assert_eq!(output, "pub fn main() {\n  1 + 1\n}\n");
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn definition_with_error_is_left_as_written() {
+    let src = "pub fn wibble( ) { 1+1 }
+
+pub fn wobble( ) {
+  1 +
+}
+
+pub fn wubble( ) { 2+2 }
+";
+    let (output, errors) = format_tolerant(src);
+    assert_eq!(
+        output,
+        "pub fn wibble() {
+  1 + 1
+}
+
+pub fn wobble( ) {
+  1 +
+}
+
+pub fn wubble() {
+  2 + 2
+}
+"
+    );
+    assert_eq!(errors, vec![src.find("+\n").expect("operator") as u32]);
+}
+
+#[test]
+fn missing_closing_brace_leaves_the_unclosed_definition() {
+    let src = "pub fn wibble( ) {
+  1
+
+/// Some docs
+pub fn wobble( ) { 2+2 }
+";
+    let (output, errors) = format_tolerant(src);
+    assert_eq!(
+        output,
+        "pub fn wibble( ) {
+  1
+
+/// Some docs
+pub fn wobble() {
+  2 + 2
+}
+"
+    );
+    assert_eq!(errors.len(), 1);
+}
+
+#[test]
+fn each_definition_with_an_error_is_reported() {
+    let src = "import wibble
+
+pub fn wobble( ) { 1 + }
+
+pub fn wubble( ) { 2+2 }
+
+pub fn wabble( ) { \"unclosed }
+";
+    let (output, errors) = format_tolerant(src);
+    assert_eq!(
+        output,
+        "import wibble
+
+pub fn wobble( ) { 1 + }
+
+pub fn wubble() {
+  2 + 2
+}
+
+pub fn wabble( ) { \"unclosed }
+"
+    );
+    assert_eq!(errors.len(), 2);
+    assert!(errors.first() < errors.last());
+}
+
+#[test]
+fn attributes_are_kept_with_their_definition() {
+    let src = "pub fn wibble( ) { 1+1 }
+
+// A comment
+@deprecated(\"Use wubble\")
+pub fn wobble( ) { 1 + }
+";
+    let (output, _) = format_tolerant(src);
+    assert_eq!(
+        output,
+        "pub fn wibble() {
+  1 + 1
+}
+
+// A comment
+@deprecated(\"Use wubble\")
+pub fn wobble( ) { 1 + }
+"
+    );
+}
@@ -1,5 +1,6 @@
 pub mod memory;
 
+use crate::ast::SrcSpan;
 use crate::error::{Error, FileIoAction, FileKind, Result};
 use async_trait::async_trait;
 use debug_ignore::DebugIgnore;
@@ -15,6 +16,7 @@ use std::{
 use tar::{Archive, Entry};
 
 use camino::{Utf8Path, Utf8PathBuf};
+use ecow::EcoString;
 
 /// Takes in a source path and a target path and determines a relative path
 /// from source -> target.
@@ -333,6 +335,7 @@ impl Stdio {
 }
 
 /// A trait used to compile Erlang and Elixir modules to BEAM bytecode.
+/// On success the warnings reported by the Erlang compiler are returned.
 pub trait BeamCompiler {
     fn compile_beam(
         &self,
@@ -340,7 +343,94 @@ pub trait BeamCompiler {
         lib: &Utf8Path,
         modules: &HashSet<Utf8PathBuf>,
         stdio: Stdio,
-    ) -> Result<(), Error>;
+    ) -> Result<Vec<ErlangCompilerDiagnostic>, Error>;
+}
+
+/// A warning or error reported by the Erlang compiler for one of the modules
+/// it compiled.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErlangCompilerDiagnostic {
+    /// The file the problem is in, as reported by the compiler. For generated
+    /// Erlang this is the Gleam module the code was generated from.
+    pub path: Utf8PathBuf,
+    pub line: Option<u32>,
+    pub message: EcoString,
+    /// The source code and location the problem was mapped back to, when it
+    /// is in Erlang generated from a Gleam module.
+    pub source: Option<(EcoString, SrcSpan)>,
+}
+
+impl ErlangCompilerDiagnostic {
+    /// Parses a diagnostic printed by the `gleam@@compile.erl` escript, in the
+    /// format `path\tline\tmessage`. Tabs, newlines and backslashes in the path
+    /// and message are escaped with a backslash.
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut parts = text.splitn(3, '\t');
+        let path = unescape_diagnostic_field(parts.next()?);
+        let line = parts.next()?.parse().ok();
+        let message = unescape_diagnostic_field(parts.next()?);
+        Some(Self {
+            path: Utf8PathBuf::from(path),
+            line,
+            message: message.into(),
+            source: None,
+        })
+    }
+
+    /// Maps the diagnostic back to the Gleam code it was reported for.
+    pub fn locate_in_gleam_source(&mut self, src: &EcoString, location: SrcSpan) {
+        self.source = Some((src.clone(), location));
+    }
+
+    pub fn location_description(&self) -> String {
+        match self.line {
+            Some(line) => format!("{}:{line}", self.path),
+            None => self.path.to_string(),
+        }
+    }
+}
+
+fn unescape_diagnostic_field(text: &str) -> String {
+    let mut unescaped = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(char) = chars.next() {
+        match char {
+            '\\' => match chars.next() {
+                Some('n') => unescaped.push('\n'),
+                Some('t') => unescaped.push('\t'),
+                Some(other) => unescaped.push(other),
+                None => unescaped.push('\\'),
+            },
+            _ => unescaped.push(char),
+        }
+    }
+    unescaped
+}
+
+#[test]
+fn erlang_compiler_diagnostic_parse() {
+    assert_eq!(
+        ErlangCompilerDiagnostic::parse("src/wibble.erl\t3\tfunction wobble/0 undefined"),
+        Some(ErlangCompilerDiagnostic {
+            path: Utf8PathBuf::from("src/wibble.erl"),
+            line: Some(3),
+            message: "function wobble/0 undefined".into(),
+            source: None,
+        })
+    );
+}
+
+#[test]
+fn erlang_compiler_diagnostic_parse_escaped() {
+    assert_eq!(
+        ErlangCompilerDiagnostic::parse("C:\\\\wibble.erl\t\tline one\\nline\\ttwo"),
+        Some(ErlangCompilerDiagnostic {
+            path: Utf8PathBuf::from("C:\\wibble.erl"),
+            line: None,
+            message: "line one\nline\ttwo".into(),
+            source: None,
+        })
+    );
 }
 
 /// A trait used to write files.
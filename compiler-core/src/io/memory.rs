@@ -443,8 +443,8 @@ impl BeamCompiler for InMemoryFileSystem {
         _lib: &Utf8Path,
         _modules: &HashSet<Utf8PathBuf>,
         _stdio: Stdio,
-    ) -> Result<(), Error> {
-        Ok(()) // Always succeed.
+    ) -> Result<Vec<ErlangCompilerDiagnostic>, Error> {
+        Ok(vec![]) // Always succeed.
     }
 }
 
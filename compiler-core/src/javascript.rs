@@ -10,9 +10,9 @@ use num_bigint::BigInt;
 use num_traits::ToPrimitive;
 
 use crate::analyse::TargetSupport;
-use crate::build::Target;
+use crate::build::{IntRepresentation, ModuleFormat, Target};
 use crate::codegen::TypeScriptDeclarations;
-use crate::type_::PRELUDE_MODULE_NAME;
+use crate::type_::{Type, PRELUDE_MODULE_NAME};
 use crate::{
     ast::{CustomType, Function, Import, ModuleConstant, TypeAlias, *},
     docvec,
@@ -31,6 +31,71 @@ const INDENT: isize = 2;
 pub const PRELUDE: &str = include_str!("../templates/prelude.mjs");
 pub const PRELUDE_TS_DEF: &str = include_str!("../templates/prelude.d.mts");
 
+/// The prelude to write to the build directory. When values are frozen the
+/// prelude's constructors freeze the values they build, and when ints are
+/// BigInts the prelude shows every number as a float.
+///
+/// The prelude is written as an ES module. When the project uses CommonJS
+/// modules it is rewritten to export its definitions with `module.exports`.
+pub fn prelude(
+    module_format: ModuleFormat,
+    int_representation: IntRepresentation,
+    freeze_values: bool,
+) -> String {
+    let mut prelude = String::from(PRELUDE);
+    if freeze_values {
+        prelude = prelude.replacen(
+            "const freezeValues = false;",
+            "const freezeValues = true;",
+            1,
+        );
+    }
+    if int_representation == IntRepresentation::BigInt {
+        prelude = prelude.replacen(
+            "const intsAreBigInts = false;",
+            "const intsAreBigInts = true;",
+            1,
+        );
+    }
+
+    match module_format {
+        ModuleFormat::Esm => prelude,
+        ModuleFormat::CommonJs => commonjs_prelude(&prelude),
+    }
+}
+
+/// Every definition the prelude exports starts on its own line with one of
+/// these keywords.
+const PRELUDE_EXPORT_KEYWORDS: [&str; 4] = ["class ", "function ", "const ", "let "];
+
+fn commonjs_prelude(prelude: &str) -> String {
+    let mut exports = vec![];
+    let mut output = String::from("\"use strict\";\n\n");
+    for line in prelude.lines() {
+        let definition = line.strip_prefix("export ").and_then(|definition| {
+            let name = PRELUDE_EXPORT_KEYWORDS
+                .iter()
+                .find_map(|keyword| definition.strip_prefix(keyword))?
+                .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+                .next()?;
+            Some((definition, name))
+        });
+        match definition {
+            Some((definition, name)) => {
+                exports.push(name);
+                output.push_str(definition);
+            }
+            None => output.push_str(line),
+        }
+        output.push('\n');
+    }
+    output.push_str(&format!(
+        "\nmodule.exports = {{ {} }};\n",
+        exports.join(", ")
+    ));
+    output
+}
+
 pub type Output<'a> = Result<Document<'a>, Error>;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -48,14 +113,30 @@ pub struct Generator<'a> {
     current_module_name_segments_count: usize,
     target_support: TargetSupport,
     typescript: TypeScriptDeclarations,
+    module_format: ModuleFormat,
+    int_representation: IntRepresentation,
+    /// The representation of ints the FFI modules of the package are written
+    /// for. When it differs from `int_representation` the external functions
+    /// are wrapped in functions that convert their ints.
+    ffi_int_representation: IntRepresentation,
+    /// Whether the constructors of custom types freeze the values they build.
+    freeze_values: bool,
+    /// The names of the public definitions of the module, which a CommonJS
+    /// module exports after all of them have been defined.
+    commonjs_exports: Vec<EcoString>,
 }
 
 impl<'a> Generator<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         line_numbers: &'a LineNumbers,
         module: &'a TypedModule,
         target_support: TargetSupport,
         typescript: TypeScriptDeclarations,
+        module_format: ModuleFormat,
+        int_representation: IntRepresentation,
+        ffi_int_representation: IntRepresentation,
+        freeze_values: bool,
     ) -> Self {
         let current_module_name_segments_count = module.name.split('/').count();
 
@@ -67,6 +148,11 @@ impl<'a> Generator<'a> {
             module_scope: Default::default(),
             target_support,
             typescript,
+            module_format,
+            int_representation,
+            ffi_int_representation,
+            freeze_values,
+            commonjs_exports: vec![],
         }
     }
 
@@ -84,10 +170,24 @@ impl<'a> Generator<'a> {
             .last()
             .expect("JavaScript generator could not identify imported module name.");
 
-        docvec!["/// <reference types=\"./", module, ".d.mts\" />", line()]
+        docvec![
+            "/// <reference types=\"./",
+            module,
+            ".",
+            self.module_format.declaration_extension(),
+            "\" />",
+            line()
+        ]
     }
 
     pub fn compile(&mut self) -> Output<'a> {
+        if self.module_format == ModuleFormat::CommonJs {
+            self.check_external_modules_can_be_required()?;
+        }
+        if self.int_representation != self.ffi_int_representation {
+            self.check_external_function_ints_can_be_converted()?;
+        }
+
         let type_reference = self.type_reference();
 
         // Determine what JavaScript imports we need to generate
@@ -152,6 +252,10 @@ impl<'a> Generator<'a> {
             self.register_prelude_usage(&mut imports, "isEqual", None);
         };
 
+        if self.tracker.int_narrowing_used {
+            self.register_prelude_usage(&mut imports, "bigIntToNumber", None);
+        };
+
         if self.tracker.bit_array_literal_used {
             self.register_prelude_usage(&mut imports, "toBitArray", None);
         };
@@ -174,6 +278,10 @@ impl<'a> Generator<'a> {
 
         // Put it all together
 
+        if self.module_format == ModuleFormat::CommonJs {
+            return Ok(self.commonjs_module(type_reference, imports, statements));
+        }
+
         if imports.is_empty() && statements.is_empty() {
             Ok(docvec![type_reference, "export {}", line()])
         } else if imports.is_empty() {
@@ -195,6 +303,101 @@ impl<'a> Generator<'a> {
         }
     }
 
+    /// A CommonJS module can't `require` an ES module, so its external
+    /// functions can't be defined in `.mjs` FFI modules.
+    fn check_external_modules_can_be_required(&self) -> Result<(), Error> {
+        let external_es_module = self
+            .module
+            .definitions
+            .iter()
+            .filter_map(|definition| match definition {
+                Definition::Function(Function {
+                    external_javascript: Some((module, _, location)),
+                    ..
+                }) => Some((module, location)),
+                _ => None,
+            })
+            .find(|(module, _)| module.ends_with(".mjs"));
+
+        match external_es_module {
+            Some((module, location)) => Err(Error::EsModuleRequiredByCommonJs {
+                module: module.clone(),
+                location: *location,
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// The ints of an external function using another representation of ints
+    /// are only converted in tuples, lists and results. Ints in values of other
+    /// types would reach the FFI code in the wrong representation.
+    fn check_external_function_ints_can_be_converted(&self) -> Result<(), Error> {
+        for definition in &self.module.definitions {
+            let Definition::Function(Function {
+                arguments,
+                return_type,
+                external_javascript: Some((_, _, location)),
+                ..
+            }) = definition
+            else {
+                continue;
+            };
+            let types = arguments.iter().map(|arg| &arg.type_).chain([return_type]);
+            for type_ in types {
+                if !ints_can_be_converted(type_) {
+                    return Err(Error::FfiIntsCannotBeConverted {
+                        type_: crate::type_::pretty::Printer::new()
+                            .pretty_print(type_, 0)
+                            .into(),
+                        location: *location,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn commonjs_module(
+        &mut self,
+        type_reference: Document<'a>,
+        imports: Imports<'a>,
+        statements: Vec<Document<'a>>,
+    ) -> Document<'a> {
+        let (imports, exports) = imports.into_commonjs_doc();
+        let exports = exports
+            .into_iter()
+            .chain(std::mem::take(&mut self.commonjs_exports))
+            .sorted()
+            .map(|name| (name.to_doc(), None));
+        let exports = docvec!["module.exports = ", wrap_object(exports), ";", line()];
+        let mut document = docvec![type_reference, "\"use strict\";", line(), line()];
+        if !imports.is_empty() {
+            document = docvec![document, imports, line()];
+        }
+        if !statements.is_empty() {
+            document = docvec![document, statements, lines(2)];
+        }
+        docvec![document, exports]
+    }
+
+    /// The start of a definition. ES modules export a public definition where
+    /// it is defined, while CommonJS modules export all of them at the end.
+    fn definition_head(
+        &mut self,
+        keyword: &'static str,
+        name: EcoString,
+        exported: bool,
+    ) -> Document<'a> {
+        match (exported, self.module_format) {
+            (false, _) => keyword.to_doc(),
+            (true, ModuleFormat::Esm) => docvec!["export ", keyword],
+            (true, ModuleFormat::CommonJs) => {
+                self.commonjs_exports.push(name);
+                keyword.to_doc()
+            }
+        }
+    }
+
     fn register_prelude_usage(
         &self,
         imports: &mut Imports<'a>,
@@ -228,9 +431,10 @@ impl<'a> Generator<'a> {
 
             Definition::Function(function) => {
                 // If there's an external JavaScript implementation then it will be imported,
-                // so we don't need to generate a function definition.
+                // so we don't need to generate a function definition, unless its ints have
+                // to be converted.
                 if function.external_javascript.is_some() {
-                    return None;
+                    return self.external_function_wrapper(function).map(Ok);
                 }
 
                 // If the function does not support JavaScript then we don't need to generate
@@ -244,8 +448,13 @@ impl<'a> Generator<'a> {
         }
     }
 
+    /// Each custom type gets a class that all of its constructors' classes
+    /// extend, so a value can be checked to be of that type with
+    /// `instanceof`.
+    ///
     fn custom_type_definition(
         &mut self,
+        name: &'a str,
         constructors: &'a [TypedRecordConstructor],
         publicity: Publicity,
         opaque: bool,
@@ -256,17 +465,25 @@ impl<'a> Generator<'a> {
         }
 
         self.tracker.custom_type_used = true;
-        constructors
-            .iter()
-            .map(|constructor| Ok(self.record_definition(constructor, publicity, opaque)))
+        let exported = !publicity.is_private() && !opaque;
+        let class_name = custom_type_class_name(name);
+        let head = self.definition_head("class ", class_name.clone(), exported);
+        let type_class = docvec![head, class_name.clone(), " extends $CustomType {}"];
+
+        std::iter::once(Ok(type_class))
+            .chain(
+                constructors.iter().map(|constructor| {
+                    Ok(self.record_definition(constructor, &class_name, exported))
+                }),
+            )
             .collect()
     }
 
     fn record_definition(
-        &self,
+        &mut self,
         constructor: &'a TypedRecordConstructor,
-        publicity: Publicity,
-        opaque: bool,
+        type_class_name: &EcoString,
+        exported: bool,
     ) -> Document<'a> {
         fn parameter((i, arg): (usize, &TypedRecordConstructorArg)) -> Document<'_> {
             arg.label
@@ -275,14 +492,16 @@ impl<'a> Generator<'a> {
                 .unwrap_or_else(|| eco_format!("x{i}").to_doc())
         }
 
-        let head = if publicity.is_private() || opaque {
-            "class "
-        } else {
-            "export class "
-        };
-        let head = docvec![head, &constructor.name, " extends $CustomType {"];
+        let head = self.definition_head("class ", constructor.name.clone(), exported);
+        let head = docvec![
+            head,
+            &constructor.name,
+            " extends ",
+            type_class_name.clone(),
+            " {"
+        ];
 
-        if constructor.arguments.is_empty() {
+        if constructor.arguments.is_empty() && !self.freeze_values {
             return head.append("}");
         };
 
@@ -291,8 +510,11 @@ impl<'a> Generator<'a> {
             break_(",", ", "),
         );
 
-        let constructor_body = join(
-            constructor.arguments.iter().enumerate().map(|(i, arg)| {
+        let mut constructor_body = constructor
+            .arguments
+            .iter()
+            .enumerate()
+            .map(|(i, arg)| {
                 let var = parameter((i, arg));
                 match &arg.label {
                     None => docvec!["this[", i, "] = ", var, ";"],
@@ -300,9 +522,13 @@ impl<'a> Generator<'a> {
                         docvec!["this.", maybe_escape_property_doc(name), " = ", var, ";"]
                     }
                 }
-            }),
-            line(),
-        );
+            })
+            .collect_vec();
+        if self.freeze_values {
+            // A constructor could be named `Object`, so the global is used.
+            constructor_body.push("globalThis.Object.freeze(this);".to_doc());
+        }
+        let constructor_body = join(constructor_body, line());
 
         let class_body = docvec![
             line(),
@@ -324,11 +550,12 @@ impl<'a> Generator<'a> {
             .iter()
             .flat_map(|statement| match statement {
                 Definition::CustomType(CustomType {
+                    name,
                     publicity,
                     constructors,
                     opaque,
                     ..
-                }) => self.custom_type_definition(constructors, *publicity, *opaque),
+                }) => self.custom_type_definition(name, constructors, *publicity, *opaque),
 
                 Definition::Function(Function { .. })
                 | Definition::TypeAlias(TypeAlias { .. })
@@ -353,6 +580,22 @@ impl<'a> Generator<'a> {
                     self.register_import(&mut imports, package, module, as_name, unqualified);
                 }
 
+                Definition::Function(
+                    function @ Function {
+                        name: Some((_, name)),
+                        publicity,
+                        external_javascript: Some((module, external_function, _location)),
+                        ..
+                    },
+                ) if self.external_function_needs_conversion(function) => {
+                    self.register_wrapped_external_function(
+                        &mut imports,
+                        name,
+                        module,
+                        external_function,
+                    );
+                }
+
                 Definition::Function(Function {
                     name: Some((_, name)),
                     publicity,
@@ -381,19 +624,21 @@ impl<'a> Generator<'a> {
     fn import_path(&self, package: &'a str, module: &'a str) -> EcoString {
         // TODO: strip shared prefixed between current module and imported
         // module to avoid descending and climbing back out again
+        let extension = self.module_format.extension();
         if package == self.module.type_info.package || package.is_empty() {
             // Same package
             match self.current_module_name_segments_count {
-                1 => eco_format!("./{module}.mjs"),
+                1 => eco_format!("./{module}.{extension}"),
                 _ => {
                     let prefix = "../".repeat(self.current_module_name_segments_count - 1);
-                    eco_format!("{prefix}{module}.mjs")
+                    eco_format!("{prefix}{module}.{extension}")
                 }
             }
         } else {
-            // Different package
+            // Different package. All packages use the module format of the
+            // root package.
             let prefix = "../".repeat(self.current_module_name_segments_count);
-            eco_format!("{prefix}{package}/{module}.mjs")
+            eco_format!("{prefix}{package}/{module}.{extension}")
         }
     }
 
@@ -458,20 +703,161 @@ impl<'a> Generator<'a> {
         imports.register_module(EcoString::from(module), [], [member]);
     }
 
+    /// An external function whose FFI module is written for another
+    /// representation of ints is imported under another name, and wrapped in a
+    /// function that converts the ints of its arguments and return value.
+    fn register_wrapped_external_function(
+        &mut self,
+        imports: &mut Imports<'a>,
+        name: &'a str,
+        module: &'a str,
+        fun: &'a str,
+    ) {
+        let member = Member {
+            name: fun.to_doc(),
+            alias: Some(ffi_function_name(name).to_doc()),
+        };
+        imports.register_module(EcoString::from(module), [], [member]);
+    }
+
+    fn external_function_needs_conversion(&self, function: &TypedFunction) -> bool {
+        self.int_representation != self.ffi_int_representation
+            && function.implementations.supports(Target::JavaScript)
+            && (contains_convertible_ints(&function.return_type)
+                || function
+                    .arguments
+                    .iter()
+                    .any(|arg| contains_convertible_ints(&arg.type_)))
+    }
+
+    fn external_function_wrapper(&mut self, function: &'a TypedFunction) -> Option<Document<'a>> {
+        if !self.external_function_needs_conversion(function) {
+            return None;
+        }
+        let (_, name) = function
+            .name
+            .as_ref()
+            .expect("A module's function must be named");
+
+        let argument_names = fun_arg_names(&function.arguments, false);
+        let arguments = function
+            .arguments
+            .iter()
+            .zip(argument_names.iter().cloned())
+            .map(|(arg, name)| self.convert_ints(&arg.type_, name, self.ffi_int_representation))
+            .collect_vec();
+        let call = docvec![ffi_function_name(name), wrap_args(arguments)];
+        let call = self.convert_ints(&function.return_type, call, self.int_representation);
+
+        let head = self.definition_head(
+            "function ",
+            maybe_escape_identifier_string(name),
+            !function.publicity.is_private(),
+        );
+        Some(docvec![
+            head,
+            maybe_escape_identifier_doc(name.as_str()),
+            wrap_args(argument_names),
+            " {",
+            docvec![line(), "return ", call, ";"].nest(INDENT).group(),
+            line(),
+            "}",
+        ])
+    }
+
+    /// Converts the ints in a value to the given representation. Ints in
+    /// tuples, lists and results are converted too, while other values are
+    /// left as they are.
+    fn convert_ints(
+        &mut self,
+        type_: &Type,
+        value: Document<'a>,
+        to: IntRepresentation,
+    ) -> Document<'a> {
+        if type_.is_int() {
+            return docvec![self.int_conversion_function(to), "(", value, ")"];
+        }
+        match self.int_converter(type_, to) {
+            Some(converter) => docvec!["(", converter, ")(", value, ")"],
+            None => value,
+        }
+    }
+
+    /// A JavaScript function converting the ints in values of a type, if it
+    /// contains any.
+    fn int_converter(&mut self, type_: &Type, to: IntRepresentation) -> Option<Document<'a>> {
+        if !contains_convertible_ints(type_) {
+            return None;
+        }
+        if type_.is_int() {
+            return Some(self.int_conversion_function(to));
+        }
+
+        if let Some(elements) = type_.tuple_types() {
+            let elements = elements.iter().enumerate().map(|(index, element)| {
+                let value = docvec!["tuple[", index, "]"];
+                self.convert_ints(element, value, to)
+            });
+            let elements = elements.collect_vec();
+            return Some(docvec!["(tuple) => [", join(elements, ", ".to_doc()), "]"]);
+        }
+
+        if let Some(element) = type_.list_element_type() {
+            let converter = self.int_converter(&element, to)?;
+            self.tracker.list_used = true;
+            return Some(docvec![
+                "(list) => toList(list.toArray().map(",
+                converter,
+                "))"
+            ]);
+        }
+
+        let (ok, error) = type_.result_types()?;
+        self.tracker.ok_used = true;
+        self.tracker.error_used = true;
+        let ok = self.convert_ints(&ok, "result[0]".to_doc(), to);
+        let error = self.convert_ints(&error, "result[0]".to_doc(), to);
+        Some(docvec![
+            "(result) => result.isOk() ? new Ok(",
+            ok,
+            ") : new Error(",
+            error,
+            ")"
+        ])
+    }
+
+    /// The function converting an int to the given representation. Ints are
+    /// narrowed to numbers by the prelude, which errors for ints a number can't
+    /// represent exactly. The global `BigInt` is used as a constructor could be
+    /// named `BigInt`.
+    fn int_conversion_function(&mut self, to: IntRepresentation) -> Document<'a> {
+        match to {
+            IntRepresentation::Number => {
+                self.tracker.int_narrowing_used = true;
+                "bigIntToNumber".to_doc()
+            }
+            IntRepresentation::BigInt => "globalThis.BigInt".to_doc(),
+        }
+    }
+
     fn module_constant(
         &mut self,
         publicity: Publicity,
         name: &'a str,
         value: &'a TypedConstant,
     ) -> Output<'a> {
-        let head = if publicity.is_private() {
-            "const "
-        } else {
-            "export const "
-        };
+        let head = self.definition_head(
+            "const ",
+            maybe_escape_identifier_string(name),
+            !publicity.is_private(),
+        );
 
-        let document =
-            expression::constant_expression(Context::Constant, &mut self.tracker, value)?;
+        let document = expression::constant_expression(
+            Context::Constant,
+            &mut self.tracker,
+            self.int_representation,
+            value,
+        )?;
 
         Ok(docvec![
             head,
@@ -503,13 +889,8 @@ impl<'a> Generator<'a> {
             argument_names,
             &mut self.tracker,
             self.module_scope.clone(),
+            self.int_representation,
         );
-        let head = if function.publicity.is_private() {
-            "function "
-        } else {
-            "export function "
-        };
-
         let body = match generator.function_body(&function.body, function.arguments.as_slice()) {
             // No error, let's continue!
             Ok(body) => body,
@@ -524,11 +905,18 @@ impl<'a> Generator<'a> {
             // Some other error case which will be returned to the user.
             Err(error) => return Some(Err(error)),
         };
+        let tail_recursion_used = generator.tail_recursion_used;
+
+        let head = self.definition_head(
+            "function ",
+            maybe_escape_identifier_string(name),
+            !function.publicity.is_private(),
+        );
 
         let document = docvec![
             head,
             maybe_escape_identifier_doc(name.as_str()),
-            fun_args(function.arguments.as_slice(), generator.tail_recursion_used),
+            fun_args(function.arguments.as_slice(), tail_recursion_used),
             " {",
             docvec![line(), body].nest(INDENT).group(),
             line(),
@@ -564,6 +952,7 @@ impl<'a> Generator<'a> {
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn module(
     module: &TypedModule,
     line_numbers: &LineNumbers,
@@ -571,14 +960,27 @@ pub fn module(
     src: &EcoString,
     target_support: TargetSupport,
     typescript: TypeScriptDeclarations,
+    module_format: ModuleFormat,
+    int_representation: IntRepresentation,
+    ffi_int_representation: IntRepresentation,
+    freeze_values: bool,
 ) -> Result<String, crate::Error> {
-    let document = Generator::new(line_numbers, module, target_support, typescript)
-        .compile()
-        .map_err(|error| crate::Error::JavaScript {
-            path: path.to_path_buf(),
-            src: src.clone(),
-            error,
-        })?;
+    let document = Generator::new(
+        line_numbers,
+        module,
+        target_support,
+        typescript,
+        module_format,
+        int_representation,
+        ffi_int_representation,
+        freeze_values,
+    )
+    .compile()
+    .map_err(|error| crate::Error::JavaScript {
+        path: path.to_path_buf(),
+        src: src.clone(),
+        error,
+    })?;
     Ok(document.to_pretty_string(80))
 }
 
@@ -586,8 +988,10 @@ pub fn ts_declaration(
     module: &TypedModule,
     path: &Utf8Path,
     src: &EcoString,
+    module_format: ModuleFormat,
+    int_representation: IntRepresentation,
 ) -> Result<String, crate::Error> {
-    let document = typescript::TypeScriptGenerator::new(module)
+    let document = typescript::TypeScriptGenerator::new(module, module_format, int_representation)
         .compile()
         .map_err(|error| crate::Error::JavaScript {
             path: path.to_path_buf(),
@@ -599,7 +1003,18 @@ pub fn ts_declaration(
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Error {
-    Unsupported { feature: String, location: SrcSpan },
+    Unsupported {
+        feature: String,
+        location: SrcSpan,
+    },
+    EsModuleRequiredByCommonJs {
+        module: EcoString,
+        location: SrcSpan,
+    },
+    FfiIntsCannotBeConverted {
+        type_: EcoString,
+        location: SrcSpan,
+    },
 }
 
 impl Error {
@@ -613,20 +1028,72 @@ impl Error {
 }
 
 fn fun_args(args: &'_ [TypedArg], tail_recursion_used: bool) -> Document<'_> {
+    wrap_args(fun_arg_names(args, tail_recursion_used))
+}
+
+fn fun_arg_names(args: &'_ [TypedArg], tail_recursion_used: bool) -> Vec<Document<'_>> {
     let mut discards = 0;
-    wrap_args(args.iter().map(|a| match a.get_variable_name() {
-        None => {
-            let doc = if discards == 0 {
-                "_".to_doc()
-            } else {
-                eco_format!("_{discards}").to_doc()
-            };
-            discards += 1;
-            doc
-        }
-        Some(name) if tail_recursion_used => eco_format!("loop${name}").to_doc(),
-        Some(name) => maybe_escape_identifier_doc(name),
-    }))
+    args.iter()
+        .map(|a| match a.get_variable_name() {
+            None => {
+                let doc = if discards == 0 {
+                    "_".to_doc()
+                } else {
+                    eco_format!("_{discards}").to_doc()
+                };
+                discards += 1;
+                doc
+            }
+            Some(name) if tail_recursion_used => eco_format!("loop${name}").to_doc(),
+            Some(name) => maybe_escape_identifier_doc(name),
+        })
+        .collect()
+}
+
+/// The name an external function is imported as when it is wrapped in a
+/// function of its own name.
+fn ffi_function_name(name: &str) -> EcoString {
+    eco_format!("{name}$ffi")
+}
+
+/// Whether a type is an int, or a tuple, list or result containing ints, which
+/// are the values whose ints are converted for FFI modules.
+fn contains_convertible_ints(type_: &Type) -> bool {
+    if type_.is_int() {
+        return true;
+    }
+    if let Some(elements) = type_.tuple_types() {
+        return elements
+            .iter()
+            .any(|element| contains_convertible_ints(element));
+    }
+    if let Some(element) = type_.list_element_type() {
+        return contains_convertible_ints(&element);
+    }
+    match type_.result_types() {
+        Some((ok, error)) => contains_convertible_ints(&ok) || contains_convertible_ints(&error),
+        None => false,
+    }
+}
+
+/// Whether the ints in values of a type can be converted between
+/// representations, which is only done for ints in tuples, lists and results.
+fn ints_can_be_converted(type_: &Type) -> bool {
+    if type_.is_int() {
+        return true;
+    }
+    if let Some(elements) = type_.tuple_types() {
+        return elements
+            .iter()
+            .all(|element| ints_can_be_converted(element));
+    }
+    if let Some(element) = type_.list_element_type() {
+        return ints_can_be_converted(&element);
+    }
+    match type_.result_types() {
+        Some((ok, error)) => ints_can_be_converted(&ok) && ints_can_be_converted(&error),
+        None => !type_.contains_int(),
+    }
 }
 
 fn wrap_args<'a, I>(args: I) -> Document<'a>
@@ -670,7 +1137,11 @@ fn wrap_object<'a>(
 fn is_usable_js_identifier(word: &str) -> bool {
     !matches!(
         word,
-        // Keywords and reserved words
+        // Keywords and reserved words, including those only reserved in strict
+        // mode and modules, as all generated code is one or the other.
+        // The words reserved only by standards before ES5, such as `int` and
+        // `float`, are not escaped as no runtime reserves them any more, and
+        // escaping them would rename public functions such as `dynamic.int`.
         // https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Lexical_grammar
         "await"
             | "arguments"
@@ -720,8 +1191,17 @@ fn is_usable_js_identifier(word: &str) -> bool {
             | "while"
             | "with"
             | "yield"
-            // `undefined` to avoid any unintentional overriding.
+            // Globals the generated code refers to, which a Gleam name would
+            // otherwise shadow. Gleam names are snake_case, so other globals
+            // such as `globalThis` can't be shadowed by them, while `Number`
+            // or `Symbol` can only be shadowed by constructors, which is why
+            // they are always referred to through `globalThis`.
             | "undefined"
+            // The variables CommonJS modules are given, which generated
+            // CommonJS modules use to import and export definitions.
+            | "require"
+            | "module"
+            | "exports"
             // `then` to avoid a module that defines a `then` function being
             // used as a `thenable` in JavaScript when the module is imported
             // dynamically, which results in unexpected behaviour.
@@ -765,6 +1245,19 @@ fn maybe_escape_identifier_doc(word: &str) -> Document<'_> {
     }
 }
 
+/// The name of the class that the constructors of a custom type extend.
+/// Gleam names cannot contain `$`, so this cannot clash with a constructor or
+/// a module import. The one exception is the prelude's `CustomType`, which is
+/// imported as `$CustomType`.
+///
+fn custom_type_class_name(name: &str) -> EcoString {
+    if name == "CustomType" {
+        "$CustomType$".into()
+    } else {
+        eco_format!("${name}")
+    }
+}
+
 fn maybe_escape_property_doc(label: &str) -> Document<'_> {
     if is_usable_js_property(label) {
         label.to_doc()
@@ -790,6 +1283,7 @@ pub(crate) struct UsageTracker {
     pub string_bit_array_segment_used: bool,
     pub codepoint_bit_array_segment_used: bool,
     pub float_bit_array_segment_used: bool,
+    pub int_narrowing_used: bool,
 }
 
 fn bool(bool: bool) -> Document<'static> {
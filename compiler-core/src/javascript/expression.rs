@@ -48,6 +48,7 @@ pub(crate) struct Generator<'module> {
     // at the top level of the function to use in place of pushing new stack
     // frames.
     pub tail_recursion_used: bool,
+    pub int_representation: IntRepresentation,
 }
 
 impl<'module> Generator<'module> {
@@ -59,6 +60,7 @@ impl<'module> Generator<'module> {
         function_arguments: Vec<Option<&'module EcoString>>,
         tracker: &'module mut UsageTracker,
         mut current_scope_vars: im::HashMap<EcoString, usize>,
+        int_representation: IntRepresentation,
     ) -> Self {
         let mut function_name = Some(function_name);
         for &name in function_arguments.iter().flatten() {
@@ -81,6 +83,7 @@ impl<'module> Generator<'module> {
             current_scope_vars,
             function_position: Position::Tail,
             scope_position: Position::Tail,
+            int_representation,
         }
     }
 
@@ -140,7 +143,7 @@ impl<'module> Generator<'module> {
         let document = match expression {
             TypedExpr::String { value, .. } => Ok(string(value)),
 
-            TypedExpr::Int { value, .. } => Ok(int(value)),
+            TypedExpr::Int { value, .. } => Ok(int(value, self.int_representation)),
             TypedExpr::Float { value, .. } => Ok(float(value)),
 
             TypedExpr::List { elements, tail, .. } => self.not_in_tail_position(|gen| match tail {
@@ -185,6 +188,16 @@ impl<'module> Generator<'module> {
 
             TypedExpr::Block { statements, .. } => self.block(statements),
 
+            // A boolean operator with a self call on the right hand side can
+            // make a tail call, but only once it has been turned into an if
+            // statement as a tail call is not an expression.
+            TypedExpr::BinOp {
+                name: name @ (BinOp::And | BinOp::Or),
+                left,
+                right,
+                ..
+            } if self.is_self_tail_call(right) => return self.boolean_tail_call(name, left, right),
+
             TypedExpr::BinOp {
                 name, left, right, ..
             } => self.bin_op(name, left, right),
@@ -251,7 +264,9 @@ impl<'module> Generator<'module> {
                             Ok(u8_slice(&bytes))
                         }
 
-                        (Some(size_value), _) if size_value == 8.into() => Ok(value),
+                        (Some(size_value), _) if size_value == 8.into() => {
+                            Ok(int_to_number(value, self.int_representation))
+                        }
 
                         (Some(size_value), _) if size_value <= 0.into() => Ok(docvec![]),
 
@@ -357,10 +372,9 @@ impl<'module> Generator<'module> {
                     }
                 }
 
-                (
-                    size_value,
-                    self.not_in_tail_position(|gen| gen.wrap_expression(size))?,
-                )
+                let size = self.not_in_tail_position(|gen| gen.wrap_expression(size))?;
+                let size = bit_array_size(size, size_value.as_ref(), self.int_representation);
+                (size_value, size)
             }
             _ => {
                 let size_value = if segment.type_ == crate::type_::int() {
@@ -451,9 +465,12 @@ impl<'module> Generator<'module> {
     {
         // Save initial state
         let scope_position = self.scope_position;
+        let function_position = self.function_position;
 
-        // Set state for in this iife
+        // Set state for in this iife. Returning from the iife doesn't return
+        // from the enclosing function, so no tail calls can be made in it.
         self.scope_position = Position::Tail;
+        self.function_position = Position::NotTail;
         let current_scope_vars = self.current_scope_vars.clone();
 
         // Generate the expression
@@ -462,6 +479,7 @@ impl<'module> Generator<'module> {
         // Reset
         self.current_scope_vars = current_scope_vars;
         self.scope_position = scope_position;
+        self.function_position = function_position;
 
         // Wrap in iife document
         let doc = immediately_invoked_function_expression_document(result?);
@@ -474,9 +492,12 @@ impl<'module> Generator<'module> {
         constructor: &'a ValueConstructor,
     ) -> Output<'a> {
         match &constructor.variant {
-            ValueConstructorVariant::LocalConstant { literal } => {
-                constant_expression(Context::Function, self.tracker, literal)
-            }
+            ValueConstructorVariant::LocalConstant { literal } => constant_expression(
+                Context::Function,
+                self.tracker,
+                self.int_representation,
+                literal,
+            ),
             ValueConstructorVariant::Record { arity, .. } => {
                 let type_ = constructor.type_.clone();
                 let tracker = &mut self.tracker;
@@ -511,7 +532,12 @@ impl<'module> Generator<'module> {
     }
 
     fn block<'a>(&mut self, statements: &'a Vec1<TypedStatement>) -> Output<'a> {
-        if statements.len() == 1 {
+        // A block in tail position doesn't need to be wrapped in a function
+        // as nothing else can come after it. This also means that any self
+        // tail calls inside it can be turned into iterations of the loop.
+        if self.scope_position.is_tail() {
+            self.statements(statements)
+        } else if statements.len() == 1 {
             match statements.first() {
                 Statement::Expression(expression) => self.child_expression(expression),
 
@@ -931,22 +957,46 @@ impl<'module> Generator<'module> {
     fn div_int<'a>(&mut self, left: &'a TypedExpr, right: &'a TypedExpr) -> Output<'a> {
         let left = self.not_in_tail_position(|gen| gen.child_expression(left))?;
         let right = self.not_in_tail_position(|gen| gen.child_expression(right))?;
+        Ok(self.prelude_div_int_call(left, right))
+    }
+
+    pub(super) fn prelude_div_int_call<'a>(
+        &mut self,
+        left: Document<'a>,
+        right: Document<'a>,
+    ) -> Document<'a> {
         self.tracker.int_division_used = true;
-        Ok(docvec!("divideInt", wrap_args([left, right])))
+        docvec!("divideInt", wrap_args([left, right]))
     }
 
     fn remainder_int<'a>(&mut self, left: &'a TypedExpr, right: &'a TypedExpr) -> Output<'a> {
         let left = self.not_in_tail_position(|gen| gen.child_expression(left))?;
         let right = self.not_in_tail_position(|gen| gen.child_expression(right))?;
+        Ok(self.prelude_remainder_int_call(left, right))
+    }
+
+    pub(super) fn prelude_remainder_int_call<'a>(
+        &mut self,
+        left: Document<'a>,
+        right: Document<'a>,
+    ) -> Document<'a> {
         self.tracker.int_remainder_used = true;
-        Ok(docvec!("remainderInt", wrap_args([left, right])))
+        docvec!("remainderInt", wrap_args([left, right]))
     }
 
     fn div_float<'a>(&mut self, left: &'a TypedExpr, right: &'a TypedExpr) -> Output<'a> {
         let left = self.not_in_tail_position(|gen| gen.child_expression(left))?;
         let right = self.not_in_tail_position(|gen| gen.child_expression(right))?;
+        Ok(self.prelude_div_float_call(left, right))
+    }
+
+    pub(super) fn prelude_div_float_call<'a>(
+        &mut self,
+        left: Document<'a>,
+        right: Document<'a>,
+    ) -> Document<'a> {
         self.tracker.float_division_used = true;
-        Ok(docvec!("divideFloat", wrap_args([left, right])))
+        docvec!("divideFloat", wrap_args([left, right]))
     }
 
     fn equal<'a>(
@@ -987,6 +1037,51 @@ impl<'module> Generator<'module> {
         docvec!(operator, args)
     }
 
+    /// Returns `true` if the expression is a call to the current function
+    /// that can be turned into an iteration of its loop.
+    fn is_self_tail_call(&self, expression: &TypedExpr) -> bool {
+        match expression {
+            TypedExpr::Call { fun, .. } => match fun.as_ref() {
+                TypedExpr::Var { name, .. } => {
+                    self.function_name.as_ref() == Some(name)
+                        && self.function_position.is_tail()
+                        && self.scope_position.is_tail()
+                        && self.current_scope_vars.get(name) == Some(&0)
+                }
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// Renders `left && right` or `left || right` as an if statement, so
+    /// that `right` can be a tail call.
+    fn boolean_tail_call<'a>(
+        &mut self,
+        name: &'a BinOp,
+        left: &'a TypedExpr,
+        right: &'a TypedExpr,
+    ) -> Output<'a> {
+        let condition = self.not_in_tail_position(|gen| gen.wrap_expression(left))?;
+        let call = self.expression(right)?;
+        let (then, otherwise) = match name {
+            BinOp::And => (call, "return false;".to_doc()),
+            _ => ("return true;".to_doc(), call),
+        };
+        Ok(docvec![
+            "if (",
+            condition,
+            ") {",
+            docvec![line(), then].nest(INDENT),
+            line(),
+            "} else {",
+            docvec![line(), otherwise].nest(INDENT),
+            line(),
+            "}",
+        ]
+        .force_break())
+    }
+
     fn print_bin_op<'a>(
         &mut self,
         left: &'a TypedExpr,
@@ -1176,7 +1271,7 @@ impl<'module> Generator<'module> {
     }
 }
 
-pub fn int(value: &str) -> Document<'_> {
+pub fn int(value: &str, int_representation: IntRepresentation) -> Document<'_> {
     let mut out = EcoString::with_capacity(value.len());
 
     if value.starts_with('-') {
@@ -1205,9 +1300,37 @@ pub fn int(value: &str) -> Document<'_> {
     }
     out.push_str(value);
 
+    if int_representation == IntRepresentation::BigInt {
+        out.push('n');
+    }
+
     out.to_doc()
 }
 
+/// Converts an int to a JavaScript number, for the places where one is needed
+/// even when ints are represented as BigInts, such as a byte of a bit array.
+/// The global is used as a constructor could be named `Number`.
+fn int_to_number(int: Document<'_>, int_representation: IntRepresentation) -> Document<'_> {
+    match int_representation {
+        IntRepresentation::Number => int,
+        IntRepresentation::BigInt => docvec!["globalThis.Number(", int, ")"],
+    }
+}
+
+/// The size of a bit array segment is always a JavaScript number. When the
+/// size is known at compile time it is written as a number literal.
+fn bit_array_size<'a>(
+    size: Document<'a>,
+    size_value: Option<&BigInt>,
+    int_representation: IntRepresentation,
+) -> Document<'a> {
+    match (int_representation, size_value) {
+        (IntRepresentation::Number, _) => size,
+        (IntRepresentation::BigInt, Some(size_value)) => eco_format!("{size_value}").to_doc(),
+        (IntRepresentation::BigInt, None) => int_to_number(size, int_representation),
+    }
+}
+
 pub fn float(value: &str) -> Document<'_> {
     let mut out = EcoString::with_capacity(value.len());
 
@@ -1230,21 +1353,22 @@ pub fn float(value: &str) -> Document<'_> {
 pub(crate) fn guard_constant_expression<'a>(
     assignments: &mut Vec<Assignment<'a>>,
     tracker: &mut UsageTracker,
+    int_representation: IntRepresentation,
     expression: &'a TypedConstant,
 ) -> Output<'a> {
     match expression {
         Constant::Tuple { elements, .. } => array(
             elements
                 .iter()
-                .map(|e| guard_constant_expression(assignments, tracker, e)),
+                .map(|e| guard_constant_expression(assignments, tracker, int_representation, e)),
         ),
 
         Constant::List { elements, .. } => {
             tracker.list_used = true;
             list(
-                elements
-                    .iter()
-                    .map(|e| guard_constant_expression(assignments, tracker, e)),
+                elements.iter().map(|e| {
+                    guard_constant_expression(assignments, tracker, int_representation, e)
+                }),
             )
         }
         Constant::Record { type_, name, .. } if type_.is_bool() && name == "True" => {
@@ -1289,7 +1413,9 @@ pub(crate) fn guard_constant_expression<'a>(
 
             let field_values: Vec<_> = args
                 .iter()
-                .map(|arg| guard_constant_expression(assignments, tracker, &arg.value))
+                .map(|arg| {
+                    guard_constant_expression(assignments, tracker, int_representation, &arg.value)
+                })
                 .try_collect()?;
             Ok(construct_record(
                 module.as_ref().map(|(module, _)| module.as_str()),
@@ -1298,9 +1424,15 @@ pub(crate) fn guard_constant_expression<'a>(
             ))
         }
 
-        Constant::BitArray { segments, .. } => bit_array(tracker, segments, |tracker, constant| {
-            guard_constant_expression(assignments, tracker, constant)
-        }),
+        Constant::BitArray { segments, .. } => bit_array(
+            Context::Function,
+            tracker,
+            int_representation,
+            segments,
+            |tracker, constant| {
+                guard_constant_expression(assignments, tracker, int_representation, constant)
+            },
+        ),
 
         Constant::Var { name, .. } => Ok(assignments
             .iter()
@@ -1308,7 +1440,9 @@ pub(crate) fn guard_constant_expression<'a>(
             .map(|assignment| assignment.subject.clone().append(assignment.path.clone()))
             .unwrap_or_else(|| maybe_escape_identifier_doc(name))),
 
-        expression => constant_expression(Context::Function, tracker, expression),
+        expression => {
+            constant_expression(Context::Function, tracker, int_representation, expression)
+        }
     }
 }
 
@@ -1324,19 +1458,29 @@ pub enum Context {
     Function,
 }
 
+/// Annotates a call made in the definition of a constant as "@__PURE__", so
+/// that bundlers can remove the constant when it is not used.
+fn annotate_pure_call(context: Context, call: Document<'_>) -> Document<'_> {
+    match context {
+        Context::Constant => docvec!["/* @__PURE__ */ ", call],
+        Context::Function => call,
+    }
+}
+
 pub(crate) fn constant_expression<'a>(
     context: Context,
     tracker: &mut UsageTracker,
+    int_representation: IntRepresentation,
     expression: &'a TypedConstant,
 ) -> Output<'a> {
     match expression {
-        Constant::Int { value, .. } => Ok(int(value)),
+        Constant::Int { value, .. } => Ok(int(value, int_representation)),
         Constant::Float { value, .. } => Ok(float(value)),
         Constant::String { value, .. } => Ok(string(value)),
         Constant::Tuple { elements, .. } => array(
             elements
                 .iter()
-                .map(|e| constant_expression(context, tracker, e)),
+                .map(|e| constant_expression(context, tracker, int_representation, e)),
         ),
 
         Constant::List { elements, .. } => {
@@ -1344,13 +1488,10 @@ pub(crate) fn constant_expression<'a>(
             let list = list(
                 elements
                     .iter()
-                    .map(|e| constant_expression(context, tracker, e)),
+                    .map(|e| constant_expression(context, tracker, int_representation, e)),
             )?;
 
-            match context {
-                Context::Constant => Ok(docvec!["/* @__PURE__ */ ", list]),
-                Context::Function => Ok(list),
-            }
+            Ok(annotate_pure_call(context, list))
         }
 
         Constant::Record { type_, name, .. } if type_.is_bool() && name == "True" => {
@@ -1395,7 +1536,7 @@ pub(crate) fn constant_expression<'a>(
 
             let field_values: Vec<_> = args
                 .iter()
-                .map(|arg| constant_expression(context, tracker, &arg.value))
+                .map(|arg| constant_expression(context, tracker, int_representation, &arg.value))
                 .try_collect()?;
 
             let constructor = construct_record(
@@ -1403,21 +1544,16 @@ pub(crate) fn constant_expression<'a>(
                 name,
                 field_values,
             );
-            match context {
-                Context::Constant => Ok(docvec!["/* @__PURE__ */ ", constructor]),
-                Context::Function => Ok(constructor),
-            }
+            Ok(annotate_pure_call(context, constructor))
         }
 
-        Constant::BitArray { segments, .. } => {
-            let bit_array = bit_array(tracker, segments, |tracker, expr| {
-                constant_expression(context, tracker, expr)
-            })?;
-            match context {
-                Context::Constant => Ok(docvec!["/* @__PURE__ */ ", bit_array]),
-                Context::Function => Ok(bit_array),
-            }
-        }
+        Constant::BitArray { segments, .. } => bit_array(
+            context,
+            tracker,
+            int_representation,
+            segments,
+            |tracker, expr| constant_expression(context, tracker, int_representation, expr),
+        ),
 
         Constant::Var { name, module, .. } => Ok({
             match module {
@@ -1432,8 +1568,8 @@ pub(crate) fn constant_expression<'a>(
         }),
 
         Constant::StringConcatenation { left, right, .. } => {
-            let left = constant_expression(context, tracker, left)?;
-            let right = constant_expression(context, tracker, right)?;
+            let left = constant_expression(context, tracker, int_representation, left)?;
+            let right = constant_expression(context, tracker, int_representation, right)?;
             Ok(docvec!(left, " + ", right))
         }
 
@@ -1442,7 +1578,9 @@ pub(crate) fn constant_expression<'a>(
 }
 
 fn bit_array<'a>(
+    context: Context,
     tracker: &mut UsageTracker,
+    int_representation: IntRepresentation,
     segments: &'a [TypedConstantBitArraySegment],
     mut constant_expr_fun: impl FnMut(&mut UsageTracker, &'a TypedConstant) -> Output<'a>,
 ) -> Output<'a> {
@@ -1454,8 +1592,12 @@ fn bit_array<'a>(
         let value = constant_expr_fun(tracker, &segment.value)?;
 
         if segment.type_ == crate::type_::int() || segment.type_ == crate::type_::float() {
-            let details =
-                sized_bit_array_segment_details(segment, tracker, &mut constant_expr_fun)?;
+            let details = sized_bit_array_segment_details(
+                segment,
+                tracker,
+                int_representation,
+                &mut constant_expr_fun,
+            )?;
 
             if segment.type_ == crate::type_::int() {
                 match (details.size_value, segment.value.as_ref()) {
@@ -1471,47 +1613,61 @@ fn bit_array<'a>(
                         Ok(u8_slice(&bytes))
                     }
 
-                    (Some(size_value), _) if size_value == 8.into() => Ok(value),
+                    (Some(size_value), _) if size_value == 8.into() => {
+                        Ok(int_to_number(value, int_representation))
+                    }
 
                     (Some(size_value), _) if size_value <= 0.into() => Ok(docvec![]),
 
                     _ => {
                         tracker.sized_integer_segment_used = true;
-                        Ok(docvec![
-                            "sizedInt(",
-                            value,
-                            ", ",
-                            details.size,
-                            ", ",
-                            bool(details.endianness.is_big()),
-                            ")"
-                        ])
+                        Ok(annotate_pure_call(
+                            context,
+                            docvec![
+                                "sizedInt(",
+                                value,
+                                ", ",
+                                details.size,
+                                ", ",
+                                bool(details.endianness.is_big()),
+                                ")"
+                            ],
+                        ))
                     }
                 }
             } else {
                 tracker.float_bit_array_segment_used = true;
-                Ok(docvec![
-                    "sizedFloat(",
-                    value,
-                    ", ",
-                    details.size,
-                    ", ",
-                    bool(details.endianness.is_big()),
-                    ")"
-                ])
+                Ok(annotate_pure_call(
+                    context,
+                    docvec![
+                        "sizedFloat(",
+                        value,
+                        ", ",
+                        details.size,
+                        ", ",
+                        bool(details.endianness.is_big()),
+                        ")"
+                    ],
+                ))
             }
         } else {
             match segment.options.as_slice() {
                 // UTF8 strings
                 [Opt::Utf8 { .. }] => {
                     tracker.string_bit_array_segment_used = true;
-                    Ok(docvec!["stringBits(", value, ")"])
+                    Ok(annotate_pure_call(
+                        context,
+                        docvec!["stringBits(", value, ")"],
+                    ))
                 }
 
                 // UTF8 codepoints
                 [Opt::Utf8Codepoint { .. }] => {
                     tracker.codepoint_bit_array_segment_used = true;
-                    Ok(docvec!["codepointBits(", value, ")"])
+                    Ok(annotate_pure_call(
+                        context,
+                        docvec!["codepointBits(", value, ")"],
+                    ))
                 }
 
                 // Bit strings
@@ -1526,7 +1682,10 @@ fn bit_array<'a>(
         }
     }))?;
 
-    Ok(docvec!["toBitArray(", segments_array, ")"])
+    Ok(annotate_pure_call(
+        context,
+        docvec!["toBitArray(", segments_array, ")"],
+    ))
 }
 
 #[derive(Debug)]
@@ -1541,6 +1700,7 @@ struct SizedBitArraySegmentDetails<'a> {
 fn sized_bit_array_segment_details<'a>(
     segment: &'a TypedConstantBitArraySegment,
     tracker: &mut UsageTracker,
+    int_representation: IntRepresentation,
     constant_expr_fun: &mut impl FnMut(&mut UsageTracker, &'a TypedConstant) -> Output<'a>,
 ) -> Result<SizedBitArraySegmentDetails<'a>, Error> {
     use BitArrayOption as Opt;
@@ -1587,7 +1747,9 @@ fn sized_bit_array_segment_details<'a>(
                 }
             }
 
-            (size_value, constant_expr_fun(tracker, size)?)
+            let size = constant_expr_fun(tracker, size)?;
+            let size = bit_array_size(size, size_value.as_ref(), int_representation);
+            (size_value, size)
         }
         _ => {
             let size_value = if segment.type_ == crate::type_::int() {
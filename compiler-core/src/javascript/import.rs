@@ -73,6 +73,20 @@ impl<'a> Imports<'a> {
         }
     }
 
+    /// Renders the imports as CommonJS `require` calls. A CommonJS module
+    /// exports its values by assigning `module.exports` once its definitions
+    /// have been evaluated, so the names to export are returned separately to
+    /// be rendered after them.
+    pub fn into_commonjs_doc(self) -> (Document<'a>, Vec<EcoString>) {
+        let imports = concat(
+            self.imports
+                .into_values()
+                .sorted_by(|a, b| a.path.cmp(&b.path))
+                .map(Import::into_require_doc),
+        );
+        (imports, self.exports.into_iter().sorted().collect())
+    }
+
     pub fn is_empty(&self) -> bool {
         self.imports.is_empty() && self.exports.is_empty()
     }
@@ -136,6 +150,40 @@ impl<'a> Import<'a> {
             ]
         }
     }
+
+    fn into_require_doc(self) -> Document<'a> {
+        let path = self.path.to_doc();
+        let alias_imports = concat(self.aliases.into_iter().sorted().map(|alias| {
+            docvec![
+                "const ",
+                alias,
+                " = require(\"",
+                path.clone(),
+                r#"");"#,
+                line()
+            ]
+        }));
+        if self.unqualified.is_empty() {
+            alias_imports
+        } else {
+            let members = self.unqualified.into_iter().map(Member::into_require_doc);
+            let members = join(members, break_(",", ", "));
+            let members = docvec![
+                docvec![break_("", " "), members].nest(INDENT),
+                break_(",", " ")
+            ]
+            .group();
+            docvec![
+                alias_imports,
+                "const {",
+                members,
+                "} = require(\"",
+                path,
+                r#"");"#,
+                line()
+            ]
+        }
+    }
 }
 
 #[derive(Debug)]
@@ -151,6 +199,13 @@ impl<'a> Member<'a> {
             Some(alias) => docvec![self.name, " as ", alias],
         }
     }
+
+    fn into_require_doc(self) -> Document<'a> {
+        match self.alias {
+            None => self.name,
+            Some(alias) => docvec![self.name, ": ", alias],
+        }
+    }
 }
 
 #[test]
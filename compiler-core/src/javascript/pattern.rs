@@ -22,6 +22,12 @@ enum Index<'a> {
         endianness: Endianness,
         is_signed: bool,
     },
+    BigIntFromSlice {
+        start: usize,
+        end: usize,
+        endianness: Endianness,
+        is_signed: bool,
+    },
     FloatFromSlice {
         start: usize,
         end: usize,
@@ -113,12 +119,21 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
         endianness: Endianness,
         is_signed: bool,
     ) {
-        self.path.push(Index::IntFromSlice {
-            start,
-            end,
-            endianness,
-            is_signed,
-        });
+        self.path
+            .push(match self.expression_generator.int_representation {
+                IntRepresentation::Number => Index::IntFromSlice {
+                    start,
+                    end,
+                    endianness,
+                    is_signed,
+                },
+                IntRepresentation::BigInt => Index::BigIntFromSlice {
+                    start,
+                    end,
+                    endianness,
+                    is_signed,
+                },
+            });
     }
 
     fn push_float_from_slice(&mut self, start: usize, end: usize, endianness: Endianness) {
@@ -175,6 +190,22 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
                 bool(*is_signed),
                 ")"
             ),
+            Index::BigIntFromSlice {
+                start,
+                end,
+                endianness,
+                is_signed,
+            } => docvec!(
+                ".bigIntFromSlice(",
+                start,
+                ", ",
+                end,
+                ", ",
+                bool(endianness.is_big()),
+                ", ",
+                bool(*is_signed),
+                ")"
+            ),
             Index::FloatFromSlice {
                 start,
                 end,
@@ -331,16 +362,24 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
                 docvec!(left, " * ", right)
             }
 
-            ClauseGuard::DivFloat { left, right, .. } | ClauseGuard::DivInt { left, right, .. } => {
-                let left = self.wrapped_guard(left)?;
-                let right = self.wrapped_guard(right)?;
-                docvec!(left, " / ", right)
+            ClauseGuard::DivFloat { left, right, .. } => {
+                let left = self.guard(left)?;
+                let right = self.guard(right)?;
+                self.expression_generator
+                    .prelude_div_float_call(left, right)
+            }
+
+            ClauseGuard::DivInt { left, right, .. } => {
+                let left = self.guard(left)?;
+                let right = self.guard(right)?;
+                self.expression_generator.prelude_div_int_call(left, right)
             }
 
             ClauseGuard::RemainderInt { left, right, .. } => {
-                let left = self.wrapped_guard(left)?;
-                let right = self.wrapped_guard(right)?;
-                docvec!(left, " % ", right)
+                let left = self.guard(left)?;
+                let right = self.guard(right)?;
+                self.expression_generator
+                    .prelude_remainder_int_call(left, right)
             }
 
             ClauseGuard::Or { left, right, .. } => {
@@ -387,6 +426,7 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
                 return expression::guard_constant_expression(
                     &mut self.assignments,
                     self.expression_generator.tracker,
+                    self.expression_generator.int_representation,
                     constant,
                 )
             }
@@ -415,7 +455,10 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
                 Ok(())
             }
             Pattern::Int { value, .. } => {
-                self.push_equality_check(subject.clone(), expression::int(value));
+                self.push_equality_check(
+                    subject.clone(),
+                    expression::int(value, self.expression_generator.int_representation),
+                );
                 Ok(())
             }
             Pattern::Float { value, .. } => {
@@ -614,7 +657,11 @@ impl<'module_ctx, 'expression_gen, 'a> Generator<'module_ctx, 'expression_gen, '
                                 let end = offset.bytes + increment;
 
                                 if segment.type_ == crate::type_::int() {
-                                    if details.size == 8 && !details.is_signed {
+                                    // A byte can be read directly unless it
+                                    // has to be turned into a BigInt.
+                                    let is_number = self.expression_generator.int_representation
+                                        == IntRepresentation::Number;
+                                    if details.size == 8 && !details.is_signed && is_number {
                                         self.push_byte_at(offset.bytes);
                                     } else {
                                         self.push_int_from_slice(
@@ -1,6 +1,6 @@
 use crate::{
     analyse::TargetSupport,
-    build::{Origin, Target},
+    build::{IntRepresentation, ModuleFormat, Origin, Target},
     config::PackageConfig,
     javascript::*,
     uid::UniqueIdGenerator,
@@ -9,14 +9,17 @@ use crate::{
 use camino::{Utf8Path, Utf8PathBuf};
 
 mod assignments;
+mod big_ints;
 mod bit_arrays;
 mod blocks;
 mod bools;
 mod case;
 mod case_clause_guards;
+mod commonjs;
 mod consts;
 mod custom_types;
 mod externals;
+mod frozen_values;
 mod functions;
 mod generics;
 mod lists;
@@ -84,6 +87,30 @@ macro_rules! assert_js {
             $crate::javascript::tests::compile_js($src, vec![]).expect("compilation failed");
         assert_eq!(($src, output), ($src, $js.to_string()));
     }};
+
+    (options: $options:expr, ($dep_package:expr, $dep_name:expr, $dep_src:expr), $src:expr $(,)?) => {{
+        let compiled = $crate::javascript::tests::compile_js_with_options(
+            $src,
+            vec![($dep_package, $dep_name, $dep_src)],
+            $options,
+        )
+        .expect("compilation failed");
+        let output = format!(
+            "----- SOURCE CODE\n{}\n\n----- COMPILED JAVASCRIPT\n{}",
+            $src, compiled
+        );
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    }};
+
+    (options: $options:expr, $src:expr $(,)?) => {{
+        let compiled = $crate::javascript::tests::compile_js_with_options($src, vec![], $options)
+            .expect("compilation failed");
+        let output = format!(
+            "----- SOURCE CODE\n{}\n\n----- COMPILED JAVASCRIPT\n{}",
+            $src, compiled
+        );
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    }};
 }
 
 #[macro_export]
@@ -93,6 +120,12 @@ macro_rules! assert_js_error {
         let output = format!("----- SOURCE CODE\n{}\n\n----- ERROR\n{}", $src, error);
         insta::assert_snapshot!(insta::internals::AutoName, output, $src);
     }};
+
+    (options: $options:expr, $src:expr $(,)?) => {{
+        let error = $crate::javascript::tests::expect_js_error_with_options($src, vec![], $options);
+        let output = format!("----- SOURCE CODE\n{}\n\n----- ERROR\n{}", $src, error);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    }};
 }
 
 #[macro_export]
@@ -200,7 +233,36 @@ pub fn compile(src: &str, deps: Vec<(&str, &str, &str)>) -> TypedModule {
     .expect("should successfully infer")
 }
 
+/// The options of the JavaScript code generator, for the tests of the ones
+/// that can be configured.
+#[derive(Debug, Clone, Copy)]
+pub struct CodegenOptions {
+    pub module_format: ModuleFormat,
+    pub int_representation: IntRepresentation,
+    pub ffi_int_representation: IntRepresentation,
+    pub freeze_values: bool,
+}
+
+impl Default for CodegenOptions {
+    fn default() -> Self {
+        Self {
+            module_format: ModuleFormat::Esm,
+            int_representation: IntRepresentation::Number,
+            ffi_int_representation: IntRepresentation::Number,
+            freeze_values: false,
+        }
+    }
+}
+
 pub fn compile_js(src: &str, deps: Vec<(&str, &str, &str)>) -> Result<String, crate::Error> {
+    compile_js_with_options(src, deps, CodegenOptions::default())
+}
+
+pub fn compile_js_with_options(
+    src: &str,
+    deps: Vec<(&str, &str, &str)>,
+    options: CodegenOptions,
+) -> Result<String, crate::Error> {
     let ast = compile(src, deps);
     let line_numbers = LineNumbers::new(src);
     module(
@@ -210,16 +272,34 @@ pub fn compile_js(src: &str, deps: Vec<(&str, &str, &str)>) -> Result<String, cr
         &"".into(),
         TargetSupport::Enforced,
         TypeScriptDeclarations::None,
+        options.module_format,
+        options.int_representation,
+        options.ffi_int_representation,
+        options.freeze_values,
     )
 }
 
 pub fn compile_ts(src: &str, deps: Vec<(&str, &str, &str)>) -> Result<String, crate::Error> {
     let ast = compile(src, deps);
-    ts_declaration(&ast, Utf8Path::new(""), &src.into())
+    ts_declaration(
+        &ast,
+        Utf8Path::new(""),
+        &src.into(),
+        ModuleFormat::Esm,
+        IntRepresentation::Number,
+    )
 }
 
 pub fn expect_js_error(src: &str, deps: Vec<(&str, &str, &str)>) -> String {
-    let error = compile_js(src, deps).expect_err("should not compile");
+    expect_js_error_with_options(src, deps, CodegenOptions::default())
+}
+
+pub fn expect_js_error_with_options(
+    src: &str,
+    deps: Vec<(&str, &str, &str)>,
+    options: CodegenOptions,
+) -> String {
+    let error = compile_js_with_options(src, deps, options).expect_err("should not compile");
     println!("er: {error:#?}");
     let better_error = match error {
         crate::Error::JavaScript {
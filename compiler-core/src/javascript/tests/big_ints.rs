@@ -0,0 +1,207 @@
+use crate::{
+    assert_js, assert_js_error,
+    build::{IntRepresentation, ModuleFormat},
+    javascript::tests::CodegenOptions,
+};
+
+const BIG_INTS: CodegenOptions = CodegenOptions {
+    module_format: ModuleFormat::Esm,
+    int_representation: IntRepresentation::BigInt,
+    ffi_int_representation: IntRepresentation::BigInt,
+    freeze_values: false,
+};
+
+/// The options for a dependency, whose FFI modules use numbers for ints.
+const FFI_NUMBERS: CodegenOptions = CodegenOptions {
+    ffi_int_representation: IntRepresentation::Number,
+    ..BIG_INTS
+};
+
+#[test]
+fn int_literals() {
+    assert_js!(
+        options: BIG_INTS,
+        r#"
+pub fn go() {
+  #(1, -1, 0, 007, 1_000_000, 0xFF, 0o17, 0b101, 12345678901234567890)
+}
+"#
+    );
+}
+
+#[test]
+fn int_operators() {
+    assert_js!(
+        options: BIG_INTS,
+        r#"
+pub fn go(x, y) {
+  let a = x + y * 2 - -x
+  let b = x / y
+  let c = x % y
+  a > b && c <= 3
+}
+"#
+    );
+}
+
+#[test]
+fn int_constants() {
+    assert_js!(
+        options: BIG_INTS,
+        r#"
+pub const answer = 42
+
+pub const pair = #(1, [2, 3])
+"#
+    );
+}
+
+#[test]
+fn int_patterns() {
+    assert_js!(
+        options: BIG_INTS,
+        r#"
+pub fn go(x) {
+  case x {
+    0 -> "zero"
+    1 | 2 -> "small"
+    _ if x > 100 -> "big"
+    _ -> "other"
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn bit_array_construction() {
+    assert_js!(
+        options: BIG_INTS,
+        r#"
+pub fn go(x, size) {
+  <<1, x, x:16, x:size(size), 256:64>>
+}
+"#
+    );
+}
+
+#[test]
+fn bit_array_constant() {
+    assert_js!(
+        options: BIG_INTS,
+        r#"
+pub const bits = <<1, 2:16, 3:size(32)>>
+"#
+    );
+}
+
+#[test]
+fn bit_array_patterns() {
+    assert_js!(
+        options: BIG_INTS,
+        r#"
+pub fn go(x) {
+  case x {
+    <<1, a, b:16, c:64-signed-little, 1:64>> -> a + b + c
+    _ -> 0
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn float_literals_are_unchanged() {
+    assert_js!(
+        options: BIG_INTS,
+        r#"
+pub fn go() {
+  1.0 +. 2.5
+}
+"#
+    );
+}
+
+#[test]
+fn constructor_named_number_does_not_shadow_global() {
+    assert_js!(
+        options: BIG_INTS,
+        r#"
+pub type Json {
+  Number(Int)
+}
+
+pub fn main(x) {
+  #(<<x>>, Number(x))
+}
+"#
+    );
+}
+
+#[test]
+fn external_functions_with_ffi_using_numbers() {
+    assert_js!(
+        options: FFI_NUMBERS,
+        r#"
+@external(javascript, "./ffi.mjs", "length")
+pub fn length(string: String) -> Int
+
+@external(javascript, "./ffi.mjs", "slice")
+fn slice(string: String, start: Int, end: Int) -> String
+
+@external(javascript, "./ffi.mjs", "parse")
+pub fn parse(string: String) -> Result(#(Int, String), Nil)
+
+@external(javascript, "./ffi.mjs", "digits")
+pub fn digits(string: String) -> List(Int)
+
+@external(javascript, "./ffi.mjs", "trim")
+pub fn trim(string: String) -> String
+
+pub fn go(string) {
+  slice(trim(string), 0, length(string))
+}
+"#
+    );
+}
+
+#[test]
+fn external_functions_with_ffi_using_numbers_and_ints_in_custom_types() {
+    assert_js_error!(
+        options: FFI_NUMBERS,
+        r#"
+pub type Box(a) {
+  Box(a)
+}
+
+@external(javascript, "./ffi.mjs", "unbox")
+pub fn unbox(box: Box(Int)) -> Int
+"#
+    );
+}
+
+#[test]
+fn external_functions_with_ffi_using_numbers_and_ints_in_functions() {
+    assert_js_error!(
+        options: FFI_NUMBERS,
+        r#"
+@external(javascript, "./ffi.mjs", "each")
+pub fn each(f: fn(Int) -> Nil) -> Nil
+"#
+    );
+}
+
+#[test]
+fn external_functions_with_ffi_using_numbers_and_generic_custom_types() {
+    assert_js!(
+        options: FFI_NUMBERS,
+        r#"
+pub type Box(a) {
+  Box(a)
+}
+
+@external(javascript, "./ffi.mjs", "box")
+pub fn box(value: a) -> Box(a)
+"#
+    );
+}
@@ -531,3 +531,30 @@ fn constructor_function_in_guard() {
     "#,
     );
 }
+
+#[test]
+fn int_division_in_guard_uses_prelude() {
+    assert_js!(
+        r#"pub fn main(x, y) {
+  case x {
+    _ if x / y == 2 -> True
+    _ if x % y == 1 -> True
+    _ -> False
+  }
+}
+"#,
+    );
+}
+
+#[test]
+fn float_division_in_guard_uses_prelude() {
+    assert_js!(
+        r#"pub fn main(x, y) {
+  case x {
+    _ if x /. y >. 2.0 -> True
+    _ -> False
+  }
+}
+"#,
+    );
+}
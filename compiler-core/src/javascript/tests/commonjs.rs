@@ -0,0 +1,139 @@
+use crate::{
+    assert_js, assert_js_error,
+    build::{IntRepresentation, ModuleFormat},
+    javascript::{prelude, tests::CodegenOptions, PRELUDE},
+};
+
+const COMMONJS: CodegenOptions = CodegenOptions {
+    module_format: ModuleFormat::CommonJs,
+    int_representation: IntRepresentation::Number,
+    ffi_int_representation: IntRepresentation::Number,
+    freeze_values: false,
+};
+
+#[test]
+fn public_definitions_are_exported_at_the_end() {
+    assert_js!(
+        options: COMMONJS,
+        r#"
+pub type Wibble {
+  Wibble(Int)
+}
+
+pub opaque type Wobble {
+  Wobble
+}
+
+pub const one = 1
+
+const two = 2
+
+pub fn add(x) {
+  x + one + two
+}
+
+fn private() {
+  Wobble
+}
+"#
+    );
+}
+
+#[test]
+fn imports_are_required() {
+    assert_js!(
+        options: COMMONJS,
+        (
+            "thepackage",
+            "other",
+            "pub fn wibble() { 1 } pub fn wobble() { 2 }"
+        ),
+        r#"
+import other.{wobble as wubble}
+
+pub fn main() {
+  other.wibble() + wubble()
+}
+"#
+    );
+}
+
+#[test]
+fn external_functions_are_required_and_exported() {
+    assert_js!(
+        options: COMMONJS,
+        r#"
+@external(javascript, "./ffi.cjs", "inspect")
+pub fn show(x: anything) -> String
+
+@external(javascript, "./ffi.cjs", "log")
+fn log(x: anything) -> Nil
+"#
+    );
+}
+
+#[test]
+fn prelude_is_required() {
+    assert_js!(
+        options: COMMONJS,
+        r#"
+pub fn main() {
+  Ok([1, 2])
+}
+"#
+    );
+}
+
+#[test]
+fn reserved_word_names_are_exported_escaped() {
+    assert_js!(
+        options: COMMONJS,
+        r#"
+pub fn class() {
+  1
+}
+"#
+    );
+}
+
+#[test]
+fn empty_module() {
+    assert_js!(options: COMMONJS, "");
+}
+
+#[test]
+fn commonjs_variable_names_are_escaped() {
+    assert_js!(
+        options: COMMONJS,
+        r#"
+pub fn require(module) {
+  let exports = module
+  exports
+}
+"#
+    );
+}
+
+#[test]
+fn external_functions_from_es_modules_are_an_error() {
+    assert_js_error!(
+        options: COMMONJS,
+        r#"
+@external(javascript, "./ffi.mjs", "inspect")
+pub fn show(x: anything) -> String
+"#
+    );
+}
+
+#[test]
+fn commonjs_prelude_exports_every_definition() {
+    let exported = PRELUDE.matches("\nexport ").count();
+    let prelude = prelude(ModuleFormat::CommonJs, IntRepresentation::Number, false);
+    assert!(!prelude.contains("\nexport "));
+    let exports = prelude
+        .lines()
+        .find_map(|line| line.strip_prefix("module.exports = { "))
+        .expect("prelude exports");
+    assert_eq!(exports.split(", ").count(), exported);
+    assert!(exports.contains("CustomType, List, prepend"));
+}
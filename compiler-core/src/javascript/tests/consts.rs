@@ -126,3 +126,12 @@ fn literal_nil_does_not_get_constant_annotation() {
 fn constructor_function_in_constant() {
     assert_js!("pub const a = Ok");
 }
+
+#[test]
+fn constant_bit_array_segments_get_pure_annotation() {
+    assert_js!(
+        r#"
+pub const bits = <<1:16, 2.0:float, "a":utf8, 3:size(64)>>
+"#
+    );
+}
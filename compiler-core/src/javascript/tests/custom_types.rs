@@ -767,3 +767,46 @@ pub fn main() {
 "#
     );
 }
+
+#[test]
+fn constructors_extend_a_class_for_their_type() {
+    assert_js!(
+        r#"
+pub type Animal {
+  Cat(name: String)
+  Dog(name: String)
+}
+
+pub opaque type Secret {
+  Secret(Int)
+}
+"#
+    );
+}
+
+#[test]
+fn constructors_extend_a_class_for_their_type_typescript() {
+    assert_ts_def!(
+        r#"
+pub type Animal {
+  Cat(name: String)
+  Dog(name: String)
+}
+
+pub opaque type Secret {
+  Secret(Int)
+}
+"#
+    );
+}
+
+#[test]
+fn class_for_type_named_custom_type_does_not_clash_with_prelude() {
+    assert_js!(
+        r#"
+pub type CustomType {
+  CustomType
+}
+"#
+    );
+}
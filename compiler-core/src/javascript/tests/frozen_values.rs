@@ -0,0 +1,61 @@
+use crate::{
+    assert_js,
+    build::{IntRepresentation, ModuleFormat},
+    javascript::tests::CodegenOptions,
+};
+
+const FROZEN_VALUES: CodegenOptions = CodegenOptions {
+    module_format: ModuleFormat::Esm,
+    int_representation: IntRepresentation::Number,
+    ffi_int_representation: IntRepresentation::Number,
+    freeze_values: true,
+};
+
+#[test]
+fn records_are_frozen() {
+    assert_js!(
+        options: FROZEN_VALUES,
+        r#"
+pub type Cat {
+  Cat(name: String, cuteness: Int)
+}
+"#
+    );
+}
+
+#[test]
+fn records_without_fields_are_frozen() {
+    assert_js!(
+        options: FROZEN_VALUES,
+        r#"
+pub type Direction {
+  North
+  South
+}
+"#
+    );
+}
+
+#[test]
+fn records_with_unlabelled_fields_are_frozen() {
+    assert_js!(
+        options: FROZEN_VALUES,
+        r#"
+pub type Box {
+  Box(Int, Float)
+}
+"#
+    );
+}
+
+#[test]
+fn constructor_named_object_does_not_shadow_global() {
+    assert_js!(
+        options: FROZEN_VALUES,
+        r#"
+pub type Json {
+  Object(List(#(String, Json)))
+}
+"#
+    );
+}
@@ -290,6 +290,49 @@ fn reserved_word_in_function_arguments() {
     );
 }
 
+#[test]
+fn strict_mode_reserved_word_argument() {
+    assert_js!(
+        r#"pub fn main(implements, interface, package, private, protected, public, static) {
+  #(implements, interface, package, private, protected, public, static)
+}
+"#,
+    );
+}
+
+#[test]
+fn future_reserved_word_fn() {
+    assert_js!(
+        r#"pub fn enum(await) {
+  await
+}
+"#,
+    );
+}
+
+// Words reserved only before ES5 are commonly used names, such as
+// `dynamic.int`, so they are not escaped.
+#[test]
+fn words_reserved_before_es5_are_not_escaped() {
+    assert_js!(
+        r#"pub fn int(float, long) {
+  #(float, long)
+}
+"#,
+    );
+}
+
+#[test]
+fn global_name_variable() {
+    assert_js!(
+        r#"pub fn main(undefined) {
+  let then = undefined
+  then
+}
+"#,
+    );
+}
+
 #[test]
 fn let_last() {
     assert_js!(
@@ -44,3 +44,38 @@ pub fn go() { gleam.Nil }
 "#,
     );
 }
+
+// The prelude must be loadable by browsers and bundlers without polyfills.
+#[test]
+fn prelude_does_not_use_node_builtins() {
+    for builtin in ["process.", "Buffer.", "require(", "__dirname"] {
+        assert!(
+            !crate::javascript::PRELUDE.contains(builtin),
+            "the prelude uses `{builtin}`"
+        );
+    }
+}
+
+#[test]
+fn prelude_can_freeze_values() {
+    use crate::{
+        build::{IntRepresentation, ModuleFormat},
+        javascript::prelude,
+    };
+
+    let prelude = |freeze| prelude(ModuleFormat::Esm, IntRepresentation::Number, freeze);
+    assert!(prelude(false).contains("const freezeValues = false;"));
+    assert!(prelude(true).contains("const freezeValues = true;"));
+}
+
+#[test]
+fn prelude_shows_numbers_as_floats_when_ints_are_big_ints() {
+    use crate::{
+        build::{IntRepresentation, ModuleFormat},
+        javascript::prelude,
+    };
+
+    let prelude = |ints| prelude(ModuleFormat::Esm, ints, false);
+    assert!(prelude(IntRepresentation::Number).contains("const intsAreBigInts = false;"));
+    assert!(prelude(IntRepresentation::BigInt).contains("const intsAreBigInts = true;"));
+}
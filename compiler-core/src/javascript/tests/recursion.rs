@@ -59,3 +59,59 @@ pub fn map(map) {
 "#
     );
 }
+
+#[test]
+fn tco_block_in_tail_position() {
+    assert_js!(
+        r#"
+pub fn count(n) {
+  let x = n - 1
+  {
+    case x > 0 {
+      True -> count(x)
+      False -> 0
+    }
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn tco_or() {
+    assert_js!(
+        r#"
+pub fn any(list, predicate) {
+  case list {
+    [] -> False
+    [x, ..rest] -> predicate(x) || any(rest, predicate)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn tco_and() {
+    assert_js!(
+        r#"
+pub fn all(list, predicate) {
+  case list {
+    [] -> True
+    [x, ..rest] -> predicate(x) && all(rest, predicate)
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn not_tco_in_left_of_or() {
+    assert_js!(
+        r#"
+pub fn main(x) {
+  main(x) || x
+}
+"#
+    );
+}
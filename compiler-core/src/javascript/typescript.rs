@@ -12,6 +12,7 @@
 //! <https://www.typescriptlang.org/docs/handbook/declaration-files/introduction.html>
 
 use crate::ast::{AssignName, Publicity};
+use crate::build::{IntRepresentation, ModuleFormat};
 use crate::type_::{is_prelude_module, PRELUDE_MODULE_NAME};
 use crate::{
     ast::{
@@ -178,16 +179,24 @@ pub struct TypeScriptGenerator<'a> {
     aliased_module_names: HashMap<&'a str, &'a str>,
     tracker: UsageTracker,
     current_module_name_segments_count: usize,
+    module_format: ModuleFormat,
+    int_representation: IntRepresentation,
 }
 
 impl<'a> TypeScriptGenerator<'a> {
-    pub fn new(module: &'a TypedModule) -> Self {
+    pub fn new(
+        module: &'a TypedModule,
+        module_format: ModuleFormat,
+        int_representation: IntRepresentation,
+    ) -> Self {
         let current_module_name_segments_count = module.name.split('/').count();
         Self {
             module,
             aliased_module_names: HashMap::new(),
             tracker: UsageTracker::default(),
             current_module_name_segments_count,
+            module_format,
+            int_representation,
         }
     }
 
@@ -348,19 +357,21 @@ impl<'a> TypeScriptGenerator<'a> {
         // DUPE: current_module_name_segments_count
         // TODO: strip shared prefixed between current module and imported
         // module to avoid descending and climbing back out again
+        let extension = self.module_format.declaration_extension();
         if package == self.module.type_info.package || package.is_empty() {
             // Same package
             match self.current_module_name_segments_count {
-                1 => eco_format!("./{module}.d.mts"),
+                1 => eco_format!("./{module}.{extension}"),
                 _ => {
                     let prefix = "../".repeat(self.current_module_name_segments_count - 1);
-                    eco_format!("{prefix}{module}.d.mts")
+                    eco_format!("{prefix}{module}.{extension}")
                 }
             }
         } else {
-            // Different package
+            // Different package. All packages use the module format of the
+            // root package.
             let prefix = "../".repeat(self.current_module_name_segments_count);
-            eco_format!("{prefix}{package}/{module}.d.mts")
+            eco_format!("{prefix}{package}/{module}.{extension}")
         }
     }
 
@@ -428,7 +439,8 @@ impl<'a> TypeScriptGenerator<'a> {
     /// emits a union type to represent the TypeScript type itself. Because in
     /// Gleam constructors can have the same name as the custom type, here we
     /// append a "$" symbol to the emitted TypeScript type to prevent those
-    /// naming classes.
+    /// naming classes. The constructors' classes all extend a class for the
+    /// custom type, named with a "$" prefix.
     ///
     fn custom_type_definition(
         &mut self,
@@ -441,10 +453,24 @@ impl<'a> TypeScriptGenerator<'a> {
         // Constructors for opaque and private types are not exported
         let export_constructors = !opaque && publicity.is_importable();
 
-        let mut definitions: Vec<Output<'_>> = constructors
-            .iter()
-            .map(|constructor| Ok(self.record_definition(constructor, export_constructors)))
-            .collect();
+        let mut definitions: Vec<Output<'_>> = vec![];
+        let class_name = super::custom_type_class_name(name);
+        if !constructors.is_empty() {
+            self.set_prelude_used();
+            definitions.push(Ok(docvec![
+                if export_constructors {
+                    "export ".to_doc()
+                } else {
+                    "declare ".to_doc()
+                },
+                "class ",
+                class_name.clone(),
+                " extends _.CustomType {}"
+            ]));
+        }
+        definitions.extend(constructors.iter().map(|constructor| {
+            Ok(self.record_definition(constructor, &class_name, export_constructors))
+        }));
 
         let definition = if constructors.is_empty() {
             "any".to_doc()
@@ -477,9 +503,9 @@ impl<'a> TypeScriptGenerator<'a> {
     fn record_definition(
         &mut self,
         constructor: &'a TypedRecordConstructor,
+        type_class_name: &EcoString,
         export: bool,
     ) -> Document<'a> {
-        self.set_prelude_used();
         let head = docvec![
             if export {
                 "export ".to_doc()
@@ -491,7 +517,9 @@ impl<'a> TypeScriptGenerator<'a> {
                 super::maybe_escape_identifier_doc(&constructor.name),
                 constructor.arguments.iter().map(|a| &a.type_)
             ),
-            " extends _.CustomType {"
+            " extends ",
+            type_class_name.clone(),
+            " {"
         ];
 
         if constructor.arguments.is_empty() {
@@ -708,7 +736,11 @@ impl<'a> TypeScriptGenerator<'a> {
     ) -> Document<'static> {
         match name {
             "Nil" => "undefined".to_doc(),
-            "Int" | "Float" => "number".to_doc(),
+            "Int" => match self.int_representation {
+                IntRepresentation::Number => "number".to_doc(),
+                IntRepresentation::BigInt => "bigint".to_doc(),
+            },
+            "Float" => "number".to_doc(),
             "UtfCodepoint" => {
                 self.tracker.prelude_used = true;
                 "_.UtfCodepoint".to_doc()
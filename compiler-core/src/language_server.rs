@@ -1,15 +1,22 @@
 mod code_action;
 mod compiler;
 mod completer;
+mod configuration;
 mod edits;
 mod engine;
 mod feedback;
 mod files;
+mod inlay_hints;
 mod messages;
 mod progress;
+mod reference;
+mod rename;
 mod router;
+mod semantic_tokens;
 mod server;
 mod signature_help;
+mod text_sync;
+mod workspace_symbols;
 
 #[cfg(test)]
 mod tests;
@@ -39,6 +46,9 @@ pub trait DownloadDependencies {
     fn download_dependencies(&self, paths: &ProjectPaths) -> Result<Manifest>;
 }
 
+/// The URI scheme of the documents that haven't been saved yet.
+const UNTITLED_SCHEME: &str = "untitled";
+
 pub fn src_span_to_lsp_range(location: SrcSpan, line_numbers: &LineNumbers) -> Range {
     let start = line_numbers.line_and_column_number(location.start);
     let end = line_numbers.line_and_column_number(location.end);
@@ -50,6 +60,13 @@ pub fn src_span_to_lsp_range(location: SrcSpan, line_numbers: &LineNumbers) -> R
 }
 
 fn path(uri: &Url) -> Utf8PathBuf {
+    // A document that hasn't been saved yet, such as `untitled:Untitled-1`,
+    // has no path on disk. It's given a relative path instead, which can't be
+    // part of any project.
+    if uri.scheme() == UNTITLED_SCHEME {
+        return Utf8PathBuf::from(uri.path().trim_start_matches('/'));
+    }
+
     // The to_file_path method is available on these platforms
     #[cfg(any(unix, windows, target_os = "redox", target_os = "wasi"))]
     return Utf8PathBuf::from_path_buf(uri.to_file_path().expect("URL file"))
@@ -58,3 +75,18 @@ fn path(uri: &Url) -> Utf8PathBuf {
     #[cfg(not(any(unix, windows, target_os = "redox", target_os = "wasi")))]
     return Utf8PathBuf::from_path_buf(uri.path().into()).expect("Non Utf8 Path");
 }
+
+/// The URI of a path. This is built from the text of the path rather than with
+/// `Url::from_file_path`, which isn't available on every target the compiler
+/// is built for.
+fn path_to_uri(path: Utf8PathBuf) -> Url {
+    // Documents that haven't been saved yet are given a relative path.
+    if path.is_relative() {
+        let uri = format!("{UNTITLED_SCHEME}:{path}");
+        return Url::parse(&uri).expect("path_to_uri URL parse");
+    }
+
+    let mut file: String = "file://".into();
+    file.push_str(&path.as_os_str().to_string_lossy());
+    Url::parse(&file).expect("path_to_uri URL parse")
+}
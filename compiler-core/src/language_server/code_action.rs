@@ -13,9 +13,9 @@ use crate::{
     parse::extra::ModuleExtra,
     type_::{
         self,
-        error::{ModuleSuggestion, VariableOrigin},
+        error::{ArityFix, ModuleSuggestion, VariableOrigin},
         printer::{Names, Printer},
-        FieldMap, ModuleValueConstructor, Type, TypedCallArg,
+        FieldMap, ModuleInterface, ModuleValueConstructor, Type, TypedCallArg,
     },
     Error, STDLIB_PACKAGE_NAME,
 };
@@ -26,7 +26,10 @@ use itertools::Itertools;
 use lsp_types::{CodeAction, CodeActionKind, CodeActionParams, Position, Range, TextEdit, Url};
 
 use super::{
-    edits::{add_newlines_after_import, get_import_edit, position_of_first_definition_if_import},
+    edits::{
+        add_newlines_after_import, get_import_edit, position_of_first_definition_if_import,
+        sorted_import_edit,
+    },
     engine::{overlaps, within},
     src_span_to_lsp_range,
 };
@@ -922,6 +925,185 @@ fn suggest_imports(
     }
 }
 
+/// Code action to import a value or type that isn't in scope from one of the
+/// modules defining it.
+///
+/// ```gleam
+/// pub fn main() {
+///   Some(1)
+/// }
+/// ```
+///
+/// Becomes:
+///
+/// ```gleam
+/// import gleam/option.{Some}
+///
+/// pub fn main() {
+///   Some(1)
+/// }
+/// ```
+///
+/// If the module is already imported the name is qualified with it instead,
+/// becoming `option.Some(1)`.
+///
+pub fn code_action_import_missing_name(
+    module: &Module,
+    line_numbers: &LineNumbers,
+    params: &CodeActionParams,
+    error: &Option<Error>,
+    importable_modules: &HashMap<EcoString, ModuleInterface>,
+    actions: &mut Vec<CodeAction>,
+) {
+    let uri = &params.text_document.uri;
+    let Some(Error::Type { errors, .. }) = error else {
+        return;
+    };
+
+    for error in errors {
+        let (location, name, layer) = match error {
+            type_::Error::UnknownVariable { location, name, .. } => {
+                (location, name, ast::Layer::Value)
+            }
+            type_::Error::UnknownType { location, name, .. } => (location, name, ast::Layer::Type),
+            _ => continue,
+        };
+        // The location of a type includes its arguments, but it's only the
+        // name that gets qualified.
+        let name_location = SrcSpan::new(location.start, location.start + name.len() as u32);
+        let range = src_span_to_lsp_range(name_location, line_numbers);
+        if !overlaps(params.range, range) {
+            continue;
+        }
+
+        let defining_modules = importable_modules
+            .iter()
+            .filter(|(module_name, _)| **module_name != module.name)
+            // src/ cannot import test/
+            .filter(|(_, interface)| interface.origin.is_src() || !module.origin.is_src())
+            .filter(|(_, interface)| match layer {
+                ast::Layer::Value => interface.get_public_value(name).is_some(),
+                ast::Layer::Type => interface.get_public_type(name).is_some(),
+            })
+            .map(|(module_name, _)| module_name)
+            .sorted();
+
+        for module_name in defining_modules {
+            let existing_import =
+                module
+                    .ast
+                    .definitions
+                    .iter()
+                    .find_map(|definition| match definition {
+                        ast::Definition::Import(import) if import.module == *module_name => {
+                            Some(import)
+                        }
+                        _ => None,
+                    });
+
+            let (title, edit) = match existing_import {
+                // A module imported with a discarded name can't be used
+                // qualified.
+                Some(import) => {
+                    let Some(qualifier) = import.used_name() else {
+                        continue;
+                    };
+                    let qualified = eco_format!("{qualifier}.{name}");
+                    let edit = TextEdit {
+                        range,
+                        new_text: qualified.to_string(),
+                    };
+                    (format!("Qualify as `{qualified}`"), edit)
+                }
+                None => {
+                    let unqualified = match layer {
+                        ast::Layer::Value => name.clone(),
+                        ast::Layer::Type => eco_format!("type {name}"),
+                    };
+                    let import = format!("{module_name}.{{{unqualified}}}");
+                    let edit = sorted_import_edit(module, line_numbers, module_name, &import);
+                    (format!("Import `{name}` from `{module_name}`"), edit)
+                }
+            };
+
+            CodeActionBuilder::new(&title)
+                .kind(CodeActionKind::QUICKFIX)
+                .changes(uri.clone(), vec![edit])
+                .push_to(actions);
+        }
+    }
+}
+
+/// Builder for code action to apply the fixes of an incorrect arity error,
+/// adding the missing arguments or removing the extra ones.
+///
+/// ```gleam
+/// pub fn main() {
+///   wibble(1)
+/// }
+///
+/// fn wibble(a: Int, b: Int) { a + b }
+/// ```
+///
+/// Becomes:
+///
+/// ```gleam
+/// pub fn main() {
+///   wibble(1, todo)
+/// }
+///
+/// fn wibble(a: Int, b: Int) { a + b }
+/// ```
+///
+pub fn code_action_fix_arity(
+    line_numbers: &LineNumbers,
+    params: &CodeActionParams,
+    error: &Option<Error>,
+    actions: &mut Vec<CodeAction>,
+) {
+    let uri = &params.text_document.uri;
+    let Some(Error::Type { errors, .. }) = error else {
+        return;
+    };
+
+    for error in errors {
+        let type_::Error::IncorrectArity {
+            location, fixes, ..
+        } = error
+        else {
+            continue;
+        };
+        if fixes.is_empty() {
+            continue;
+        }
+        let range = src_span_to_lsp_range(*location, line_numbers);
+        if !overlaps(params.range, range) {
+            continue;
+        }
+
+        let title = match fixes.first() {
+            Some(ArityFix::RemoveArguments { .. }) => "Remove extra arguments",
+            Some(ArityFix::InsertArguments { .. }) | None => "Add missing arguments",
+        };
+        let edits = fixes
+            .iter()
+            .map(|fix| {
+                let (location, new_text) = fix.edit();
+                TextEdit {
+                    range: src_span_to_lsp_range(location, line_numbers),
+                    new_text,
+                }
+            })
+            .collect_vec();
+
+        CodeActionBuilder::new(title)
+            .kind(CodeActionKind::QUICKFIX)
+            .changes(uri.clone(), edits)
+            .preferred(true)
+            .push_to(actions);
+    }
+}
+
 pub fn code_action_add_missing_patterns(
     module: &Module,
     line_numbers: &LineNumbers,
@@ -1234,6 +1416,70 @@ impl<'a> AddAnnotations<'a> {
     }
 }
 
+/// Builder for code action to replace the `_` holes in type annotations with
+/// the types the compiler inferred for them.
+///
+/// ```gleam
+/// let x: List(_) = [1, 2]
+/// //          ^ Replaced with `Int`
+/// ```
+///
+pub struct FillInTypeHoles<'a> {
+    module: &'a Module,
+    params: &'a CodeActionParams,
+    edits: TextEdits<'a>,
+}
+
+impl<'a> FillInTypeHoles<'a> {
+    pub fn new(
+        module: &'a Module,
+        line_numbers: &'a LineNumbers,
+        params: &'a CodeActionParams,
+    ) -> Self {
+        Self {
+            module,
+            params,
+            edits: TextEdits::new(line_numbers),
+        }
+    }
+
+    pub fn code_actions(mut self) -> Vec<CodeAction> {
+        // We use the same printer for all the holes so that type variables
+        // get consistent names.
+        let mut printer = Printer::new(&self.module.ast.names);
+
+        for annotation in self.module.ast.type_info.inferred_annotations.iter() {
+            if !annotation.is_hole() {
+                continue;
+            }
+
+            let hole_range = self.edits.src_span_to_lsp_range(annotation.location);
+            if !overlaps(hole_range, self.params.range) {
+                continue;
+            }
+
+            self.edits.replace(
+                annotation.location,
+                printer.print_type(&annotation.type_).into(),
+            );
+        }
+
+        let title = match self.edits.edits.len() {
+            0 => return vec![],
+            1 => "Fill in type hole",
+            _ => "Fill in type holes",
+        };
+
+        let mut action = Vec::with_capacity(1);
+        CodeActionBuilder::new(title)
+            .kind(CodeActionKind::REFACTOR)
+            .changes(self.params.text_document.uri.clone(), self.edits.edits)
+            .preferred(false)
+            .push_to(&mut action);
+        action
+    }
+}
+
 pub struct QualifiedConstructor<'a> {
     import: &'a ast::Import<EcoString>,
     module_aliased: bool,
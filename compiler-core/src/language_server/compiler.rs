@@ -1,13 +1,12 @@
 use debug_ignore::DebugIgnore;
 use ecow::EcoString;
-use itertools::Itertools;
 
 use crate::{
     analyse::TargetSupport,
     build::{self, Mode, Module, NullTelemetry, Outcome, ProjectCompiler},
     config::PackageConfig,
     io::{BeamCompiler, CommandExecutor, FileSystemReader, FileSystemWriter, Stdio},
-    language_server::Locker,
+    language_server::{workspace_symbols::SymbolIndex, Locker},
     line_numbers::LineNumbers,
     manifest::Manifest,
     paths::ProjectPaths,
@@ -29,7 +28,15 @@ pub struct LspProjectCompiler<IO> {
 
     /// Information on compiled modules.
     pub modules: HashMap<EcoString, Module>,
+    /// Information on the compiled modules of path dependencies, which are
+    /// worked on alongside the root package.
+    pub path_dependency_modules: HashMap<EcoString, Module>,
+    /// Hex and Git dependencies are downloaded into this directory, so the
+    /// modules of any others are from path dependencies.
+    packages_directory: Utf8PathBuf,
     pub sources: HashMap<EcoString, ModuleSourceInformation>,
+    /// The definitions of the compiled modules, to be searched by name.
+    pub symbols: SymbolIndex,
 
     /// The storage for the warning emitter.
     pub warnings: Rc<VectorWarningEmitterIO>,
@@ -56,13 +63,21 @@ where
 
         // The build caches do not contain all the information we need in the
         // LSP (e.g. the typed AST) so delete the caches for the top level
-        // package before we run for the first time.
+        // package and any path dependencies before we run for the first time.
         // TODO: remove this once the caches have contain all the information
         {
             let _guard = locker.lock_for_build();
-            let path = paths.build_directory_for_package(Mode::Lsp, target, &name);
-            io.delete_directory(&path)?;
+            let path_dependencies = manifest
+                .packages
+                .iter()
+                .filter(|package| package.is_local())
+                .map(|package| &package.name);
+            for name in std::iter::once(&name).chain(path_dependencies) {
+                let path = paths.build_directory_for_package(Mode::Lsp, target, name);
+                io.delete_directory(&path)?;
+            }
         }
+        let packages_directory = paths.build_packages_directory();
 
         let options = build::Options {
             warnings_as_errors: false,
@@ -72,6 +87,7 @@ where
             compile: build::Compile::All,
             root_target_support: TargetSupport::Enforced,
             no_print_progress: false,
+            doctests: false,
         };
         let mut project_compiler = ProjectCompiler::new(
             config,
@@ -92,7 +108,10 @@ where
             warnings,
             project_compiler,
             modules: HashMap::new(),
+            path_dependency_modules: HashMap::new(),
+            packages_directory,
             sources: HashMap::new(),
+            symbols: SymbolIndex::default(),
         })
     }
 
@@ -159,11 +178,17 @@ where
             Outcome::TotalFailure(error) => (vec![], Some(error)),
         };
 
-        // Record the compiled dependency modules
-        let mut compiled_modules = compiled_dependencies
-            .into_iter()
-            .map(|m| m.input_path)
-            .collect_vec();
+        // Record the compiled dependency modules, keeping the ones from path
+        // dependencies so they can be searched along with the root package.
+        let mut compiled_modules = Vec::with_capacity(compiled_dependencies.len());
+        for module in compiled_dependencies {
+            compiled_modules.push(module.input_path.clone());
+            if !module.input_path.starts_with(&self.packages_directory) {
+                _ = self
+                    .path_dependency_modules
+                    .insert(module.name.clone(), module);
+            }
+        }
 
         // Store the compiled module information
         for module in modules {
@@ -174,6 +199,7 @@ where
             // function and is used to determine what diagnostics to reset.
             compiled_modules.push(module.input_path.clone());
             // Register information for the LS to use
+            self.symbols.update(&module);
             _ = self.sources.insert(module.name.clone(), source);
             _ = self.modules.insert(module.name.clone(), module);
         }
@@ -3,9 +3,9 @@ use std::{collections::HashMap, sync::Arc};
 use ecow::EcoString;
 use itertools::Itertools;
 use lsp_types::{
-    CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionTextEdit,
-    Documentation, MarkupContent, MarkupKind, Position, Range, TextDocumentPositionParams,
-    TextEdit,
+    CompletionItem, CompletionItemKind, CompletionItemLabelDetails, CompletionItemTag,
+    CompletionTextEdit, Documentation, MarkupContent, MarkupKind, Position, Range,
+    TextDocumentPositionParams, TextEdit,
 };
 use strum::IntoEnumIterator;
 
@@ -18,9 +18,9 @@ use crate::{
     io::{BeamCompiler, CommandExecutor, FileSystemReader, FileSystemWriter},
     line_numbers::LineNumbers,
     type_::{
-        self, collapse_links, error::VariableOrigin, pretty::Printer, FieldMap, ModuleInterface,
-        PreludeType, RecordAccessor, Type, TypeConstructor, ValueConstructorVariant,
-        PRELUDE_MODULE_NAME,
+        self, collapse_links, error::VariableOrigin, pretty::Printer, Deprecation, FieldMap,
+        ModuleInterface, PreludeType, RecordAccessor, Type, TypeConstructor,
+        ValueConstructorVariant, PRELUDE_MODULE_NAME,
     },
     Result,
 };
@@ -891,6 +891,7 @@ fn type_completion(
                 TypeCompletionForm::Default => label.clone(),
             },
         })),
+        tags: deprecated_completion_tag(&type_.deprecation),
         ..Default::default()
     }
 }
@@ -940,10 +941,17 @@ fn value_completion(
             range: insert_range,
             new_text: label.clone(),
         })),
+        tags: deprecated_completion_tag(&value.deprecation),
         ..Default::default()
     }
 }
 
+fn deprecated_completion_tag(deprecation: &Deprecation) -> Option<Vec<CompletionItemTag>> {
+    deprecation
+        .is_deprecated()
+        .then(|| vec![CompletionItemTag::DEPRECATED])
+}
+
 fn local_value_completion(
     module_name: &str,
     name: &str,
@@ -0,0 +1,54 @@
+//! The settings of the language server that can be changed from the editor,
+//! found in the `gleam` section of its configuration.
+
+use serde_json::Value as Json;
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct Configuration {
+    pub inlay_hints: InlayHintsConfig,
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+pub struct InlayHintsConfig {
+    /// Show the inferred type of the variables assigned with `let` that have
+    /// no annotation.
+    pub variable_types: bool,
+    /// Show the labels of the arguments given without one in a call.
+    pub parameter_labels: bool,
+}
+
+impl InlayHintsConfig {
+    pub fn any_enabled(&self) -> bool {
+        self.variable_types || self.parameter_labels
+    }
+}
+
+impl Configuration {
+    /// Reads the configuration sent by the editor. Anything that is missing
+    /// or isn't valid is left with its default value.
+    pub fn from_json(json: Json) -> Self {
+        serde_json::from_value(json).unwrap_or_default()
+    }
+}
+
+#[test]
+fn configuration_from_json() {
+    let json = serde_json::json!({
+        "inlayHints": { "variableTypes": true }
+    });
+    assert_eq!(
+        Configuration::from_json(json),
+        Configuration {
+            inlay_hints: InlayHintsConfig {
+                variable_types: true,
+                parameter_labels: false,
+            }
+        }
+    );
+    assert_eq!(
+        Configuration::from_json(Json::Null),
+        Configuration::default()
+    );
+}
@@ -1,8 +1,9 @@
 use ecow::EcoString;
+use itertools::Itertools;
 use lsp_types::{Position, Range, TextEdit};
 
 use crate::{
-    ast::{Definition, Import, TypedDefinition},
+    ast::{Definition, Import, SrcSpan, TypedDefinition},
     build::Module,
     line_numbers::LineNumbers,
 };
@@ -76,3 +77,49 @@ pub fn get_import_edit(
         new_text: ["import ", module_full_name, new_lines].concat(),
     }
 }
+
+/// Adds an import to a module where the formatter would sort it: before the
+/// first import of a module that comes after it alphabetically, or after the
+/// last import if there's none.
+///
+/// * ``module_name`` - The name of the imported module, used for sorting.
+/// * ``import`` - What follows `import` in the new import statement.
+pub fn sorted_import_edit(
+    module: &Module,
+    line_numbers: &LineNumbers,
+    module_name: &str,
+    import: &str,
+) -> TextEdit {
+    let imports = module
+        .ast
+        .definitions
+        .iter()
+        .filter_map(get_import)
+        .sorted_by_key(|import| import.location.start)
+        .collect_vec();
+
+    let (position, new_text) = match (
+        imports
+            .iter()
+            .find(|existing| existing.module.as_str() > module_name),
+        imports.last(),
+    ) {
+        (Some(next), _) => (next.location.start, format!("import {import}\n")),
+        (None, Some(last)) => (last.location.end, format!("\nimport {import}")),
+        (None, None) => {
+            let import_location = Position::default();
+            let newlines =
+                add_newlines_after_import(import_location, false, line_numbers, &module.code);
+            return get_import_edit(import_location, import, &newlines);
+        }
+    };
+
+    let position = src_span_to_lsp_range(SrcSpan::new(position, position), line_numbers).start;
+    TextEdit {
+        range: Range {
+            start: position,
+            end: position,
+        },
+        new_text,
+    }
+}
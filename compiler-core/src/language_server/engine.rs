@@ -1,8 +1,8 @@
 use crate::{
     analyse::name::correct_name_case,
     ast::{
-        CustomType, Definition, ModuleConstant, SrcSpan, TypedArg, TypedExpr, TypedFunction,
-        TypedModule, TypedPattern,
+        CustomType, Definition, Import, ModuleConstant, SrcSpan, TypedArg, TypedExpr,
+        TypedFunction, TypedModule, TypedPattern,
     },
     build::{type_constructor_from_modules, Located, Module, UnqualifiedImport},
     config::PackageConfig,
@@ -26,18 +26,24 @@ use lsp_types::{
     self as lsp, DocumentSymbol, Hover, HoverContents, MarkedString, Position, Range,
     SignatureHelp, SymbolKind, SymbolTag, TextEdit, Url,
 };
-use std::sync::Arc;
+use std::{collections::HashSet, sync::Arc};
 
 use super::{
     code_action::{
         code_action_add_missing_patterns, code_action_convert_qualified_constructor_to_unqualified,
-        code_action_convert_unqualified_constructor_to_qualified, code_action_import_module,
+        code_action_convert_unqualified_constructor_to_qualified, code_action_fix_arity,
+        code_action_import_missing_name, code_action_import_module,
         code_action_inexhaustive_let_to_case, AddAnnotations, CodeActionBuilder, DesugarUse,
-        ExpandFunctionCapture, ExtractVariable, FillInMissingLabelledArgs, GenerateDynamicDecoder,
-        LabelShorthandSyntax, LetAssertToCase, RedundantTupleInCaseSubject, TurnIntoUse,
+        ExpandFunctionCapture, ExtractVariable, FillInMissingLabelledArgs, FillInTypeHoles,
+        GenerateDynamicDecoder, LabelShorthandSyntax, LetAssertToCase, RedundantTupleInCaseSubject,
+        TurnIntoUse,
     },
     completer::Completer,
-    signature_help, src_span_to_lsp_range, DownloadDependencies, MakeLocker,
+    configuration::InlayHintsConfig,
+    inlay_hints, path_to_uri,
+    reference::{self, ReferenceKind},
+    rename::{self, RenameError},
+    semantic_tokens, signature_help, src_span_to_lsp_range, DownloadDependencies, MakeLocker,
 };
 
 #[derive(Debug, PartialEq, Eq)]
@@ -47,6 +53,16 @@ pub struct Response<T> {
     pub compilation: Compilation,
 }
 
+impl<T> Response<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Response<U> {
+        Response {
+            result: self.result.map(f),
+            warnings: self.warnings,
+            compilation: self.compilation,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Eq)]
 pub enum Compilation {
     /// Compilation was attempted and succeeded for these modules.
@@ -75,7 +91,7 @@ pub struct LanguageServerEngine<IO, Reporter> {
 
     /// Used to know if to show the "View on HexDocs" link
     /// when hovering on an imported value
-    hex_deps: std::collections::HashSet<EcoString>,
+    hex_deps: HashSet<EcoString>,
 }
 
 impl<'a, IO, Reporter> LanguageServerEngine<IO, Reporter>
@@ -317,7 +333,16 @@ where
             );
             code_action_fix_names(&lines, &params, &this.error, &mut actions);
             code_action_import_module(module, &lines, &params, &this.error, &mut actions);
+            code_action_import_missing_name(
+                module,
+                &lines,
+                &params,
+                &this.error,
+                this.compiler.project_compiler.get_importable_modules(),
+                &mut actions,
+            );
             code_action_add_missing_patterns(module, &lines, &params, &this.error, &mut actions);
+            code_action_fix_arity(&lines, &params, &this.error, &mut actions);
             code_action_inexhaustive_let_to_case(
                 module,
                 &lines,
@@ -336,6 +361,7 @@ where
             actions.extend(ExtractVariable::new(module, &lines, &params).code_actions());
             GenerateDynamicDecoder::new(module, &lines, &params, &mut actions).code_actions();
             AddAnnotations::new(module, &lines, &params).code_action(&mut actions);
+            actions.extend(FillInTypeHoles::new(module, &lines, &params).code_actions());
             Ok(if actions.is_empty() {
                 None
             } else {
@@ -357,8 +383,10 @@ where
 
             for definition in &module.ast.definitions {
                 match definition {
-                    // Typically, imports aren't considered document symbols.
-                    Definition::Import(_) => {}
+                    Definition::Import(import) => {
+                        let interface = this.compiler.get_module_interface(&import.module);
+                        symbols.push(import_symbol(import, &line_numbers, module, interface));
+                    }
 
                     Definition::Function(function) => {
                         // By default, the function's location ends right after the return type.
@@ -646,6 +674,126 @@ Unused labelled fields:
         )
     }
 
+    pub fn prepare_rename(
+        &mut self,
+        params: lsp::TextDocumentPositionParams,
+    ) -> Response<Option<Range>> {
+        self.respond(|this| {
+            let Some(module) = this.module_for_uri(&params.text_document.uri) else {
+                return Ok(None);
+            };
+            let line_numbers = LineNumbers::new(&module.code);
+            let byte_index =
+                line_numbers.byte_index(params.position.line, params.position.character);
+            Ok(
+                rename::renameable_location(&this.compiler.modules, module, byte_index)
+                    .ok()
+                    .map(|location| src_span_to_lsp_range(location, &line_numbers)),
+            )
+        })
+    }
+
+    pub fn rename(
+        &mut self,
+        params: lsp::RenameParams,
+    ) -> Response<Result<lsp::WorkspaceEdit, RenameError>> {
+        self.respond(|this| {
+            let position = params.text_document_position;
+            let Some(module) = this.module_for_uri(&position.text_document.uri) else {
+                return Ok(Err(RenameError::NothingToRename));
+            };
+            let byte_index = LineNumbers::new(&module.code)
+                .byte_index(position.position.line, position.position.character);
+            Ok(rename::rename(
+                &this.compiler.modules,
+                module,
+                byte_index,
+                &params.new_name,
+            ))
+        })
+    }
+
+    pub fn workspace_symbol(
+        &mut self,
+        params: &lsp::WorkspaceSymbolParams,
+    ) -> Response<Vec<lsp::SymbolInformation>> {
+        self.respond(|this| Ok(this.compiler.symbols.search(&params.query)))
+    }
+
+    pub fn inlay_hints(
+        &mut self,
+        params: lsp::InlayHintParams,
+        config: InlayHintsConfig,
+    ) -> Response<Vec<lsp::InlayHint>> {
+        self.respond(|this| {
+            if !config.any_enabled() {
+                return Ok(vec![]);
+            }
+            let Some(module) = this.module_for_uri(&params.text_document.uri) else {
+                return Ok(vec![]);
+            };
+            Ok(inlay_hints::inlay_hints(module, params.range, config))
+        })
+    }
+
+    pub fn semantic_tokens(
+        &mut self,
+        params: lsp::SemanticTokensParams,
+    ) -> Response<Option<lsp::SemanticTokens>> {
+        self.respond(|this| {
+            let Some(module) = this.module_for_uri(&params.text_document.uri) else {
+                return Ok(None);
+            };
+            let data = semantic_tokens::semantic_tokens(module, |name| {
+                this.compiler.get_module_interface(name)
+            });
+            Ok(Some(lsp::SemanticTokens {
+                result_id: None,
+                data,
+            }))
+        })
+    }
+
+    pub fn find_references(
+        &mut self,
+        params: lsp::ReferenceParams,
+    ) -> Response<Option<Vec<lsp::Location>>> {
+        self.respond(|this| {
+            let position = params.text_document_position;
+            let Some(module) = this.module_for_uri(&position.text_document.uri) else {
+                return Ok(None);
+            };
+            let byte_index = LineNumbers::new(&module.code)
+                .byte_index(position.position.line, position.position.character);
+            let modules = this
+                .compiler
+                .modules
+                .values()
+                .chain(this.compiler.path_dependency_modules.values());
+            let Some(references) = reference::find_references(modules, module, byte_index) else {
+                return Ok(None);
+            };
+
+            let mut locations = vec![];
+            for (module, references) in references {
+                let uri = path_to_uri(module.input_path.clone());
+                let line_numbers = LineNumbers::new(&module.code);
+                locations.extend(
+                    references
+                        .iter()
+                        .filter(|reference| {
+                            params.context.include_declaration || !reference.is_definition()
+                        })
+                        .map(|reference| lsp::Location {
+                            uri: uri.clone(),
+                            range: src_span_to_lsp_range(reference.location, &line_numbers),
+                        }),
+                );
+            }
+            Ok(Some(locations))
+        })
+    }
+
     fn module_node_at_position(
         &self,
         params: &lsp::TextDocumentPositionParams,
@@ -689,6 +837,95 @@ Unused labelled fields:
     }
 }
 
+fn import_symbol(
+    import: &Import<EcoString>,
+    line_numbers: &LineNumbers,
+    module: &Module,
+    interface: Option<&ModuleInterface>,
+) -> DocumentSymbol {
+    let value_kind = |name: &EcoString| match interface
+        .and_then(|interface| interface.values.get(name))
+        .map(|value| &value.variant)
+    {
+        Some(ValueConstructorVariant::ModuleFn { .. }) => SymbolKind::FUNCTION,
+        Some(
+            ValueConstructorVariant::ModuleConstant { .. }
+            | ValueConstructorVariant::LocalConstant { .. },
+        ) => SymbolKind::CONSTANT,
+        Some(ValueConstructorVariant::Record { arity: 0, .. }) => SymbolKind::ENUM_MEMBER,
+        Some(ValueConstructorVariant::Record { .. }) => SymbolKind::CONSTRUCTOR,
+        Some(ValueConstructorVariant::LocalVariable { .. }) | None => SymbolKind::VARIABLE,
+    };
+
+    // List the names imported unqualified as children of the module they're
+    // imported from.
+    let imported = import
+        .unqualified_types
+        .iter()
+        .map(|imported| (imported, SymbolKind::CLASS))
+        .chain(
+            import
+                .unqualified_values
+                .iter()
+                .map(|imported| (imported, value_kind(&imported.name))),
+        )
+        .sorted_by_key(|(imported, _)| imported.location.start)
+        .map(|(imported, kind)| {
+            let range = src_span_to_lsp_range(imported.location, line_numbers);
+
+            // The 'deprecated' field is deprecated, but we have to specify it anyway
+            // to be able to construct the 'DocumentSymbol' type, so
+            // we suppress the warning. We specify 'None' as specifying 'Some'
+            // is what is actually deprecated.
+            #[allow(deprecated)]
+            DocumentSymbol {
+                name: imported.name.to_string(),
+                detail: imported.as_name.as_ref().map(|alias| format!("as {alias}")),
+                kind,
+                tags: None,
+                deprecated: None,
+                range,
+                selection_range: range,
+                children: None,
+            }
+        })
+        .collect_vec();
+
+    // The import's location starts at the `import` keyword, while the
+    // selection range is just the name of the module.
+    let name_start = module
+        .code
+        .get(import.location.start as usize..import.location.end as usize)
+        .and_then(|code| code.find(import.module.as_str()))
+        .map_or(import.location.start, |offset| {
+            import.location.start + offset as u32
+        });
+    let name_location = SrcSpan::new(name_start, name_start + import.module.len() as u32);
+
+    // The 'deprecated' field is deprecated, but we have to specify it anyway
+    // to be able to construct the 'DocumentSymbol' type, so
+    // we suppress the warning. We specify 'None' as specifying 'Some'
+    // is what is actually deprecated.
+    #[allow(deprecated)]
+    DocumentSymbol {
+        name: import.module.to_string(),
+        detail: import
+            .as_name
+            .as_ref()
+            .map(|(name, _)| format!("as {}", name.name())),
+        kind: SymbolKind::MODULE,
+        tags: None,
+        deprecated: None,
+        range: src_span_to_lsp_range(import.location, line_numbers),
+        selection_range: src_span_to_lsp_range(name_location, line_numbers),
+        children: if imported.is_empty() {
+            None
+        } else {
+            Some(imported)
+        },
+    }
+}
+
 fn custom_type_symbol(
     type_: &CustomType<Arc<Type>>,
     line_numbers: &LineNumbers,
@@ -933,7 +1170,7 @@ fn hover_for_expression(
     expression: &TypedExpr,
     line_numbers: LineNumbers,
     module: &Module,
-    hex_deps: &std::collections::HashSet<EcoString>,
+    hex_deps: &HashSet<EcoString>,
 ) -> Hover {
     let documentation = expression.get_documentation().unwrap_or_default();
 
@@ -1058,6 +1295,16 @@ fn code_action_unused_values(
             .preferred(true)
             .push_to(actions);
 
+        let edit = TextEdit {
+            range: src_span_to_lsp_range(SrcSpan::new(start, start), line_numbers),
+            new_text: "let assert Ok(_) = ".into(),
+        };
+
+        CodeActionBuilder::new("Assert unused Result value is `Ok`")
+            .kind(lsp_types::CodeActionKind::QUICKFIX)
+            .changes(uri.clone(), vec![edit])
+            .push_to(actions);
+
         processed_lsp_range.push(hover_range);
     }
 }
@@ -1069,27 +1316,38 @@ fn code_action_unused_imports(
     actions: &mut Vec<CodeAction>,
 ) {
     let uri = &params.text_document.uri;
-    let unused: Vec<&SrcSpan> = module
-        .ast
-        .type_info
-        .warnings
-        .iter()
-        .filter_map(|warning| match warning {
+    let mut unused_modules = vec![];
+    let mut unused_names = vec![];
+    for warning in &module.ast.type_info.warnings {
+        match warning {
             type_::Warning::UnusedImportedModuleAlias { location, .. }
-            | type_::Warning::UnusedImportedModule { location, .. } => Some(location),
-            _ => None,
-        })
-        .collect();
+            | type_::Warning::UnusedImportedModule { location, .. } => {
+                unused_modules.push(location)
+            }
+            type_::Warning::UnusedImportedValue { location, .. }
+            | type_::Warning::UnusedType {
+                location,
+                imported: true,
+                ..
+            }
+            | type_::Warning::UnusedConstructor {
+                location,
+                imported: true,
+                ..
+            } => unused_names.push(*location),
+            _ => (),
+        }
+    }
 
-    if unused.is_empty() {
+    if unused_modules.is_empty() && unused_names.is_empty() {
         return;
     }
 
     let mut hovered = false;
-    let mut edits = Vec::with_capacity(unused.len());
+    let mut edits = Vec::with_capacity(unused_modules.len() + unused_names.len());
 
-    for unused in unused {
-        let SrcSpan { start, end } = *unused;
+    for unused in &unused_modules {
+        let SrcSpan { start, end } = **unused;
 
         // If removing an unused alias or at the beginning of the file, don't backspace
         // Otherwise, adjust the end position by 1 to ensure the entire line is deleted with the import.
@@ -1109,6 +1367,48 @@ fn code_action_unused_imports(
         });
     }
 
+    // The modules used qualified, as in `wibble.wobble`, which are kept when
+    // none of the names they import unqualified are used.
+    let qualified_modules: HashSet<EcoString> = if unused_names.is_empty() {
+        HashSet::new()
+    } else {
+        reference::module_references(module)
+            .into_iter()
+            .filter(|reference| reference.kind == ReferenceKind::Usage)
+            .map(|reference| reference.referent.module().clone())
+            .collect()
+    };
+
+    for import in module
+        .ast
+        .definitions
+        .iter()
+        .filter_map(|definition| match definition {
+            Definition::Import(import) => Some(import),
+            _ => None,
+        })
+    {
+        // An import that is removed entirely has nothing left to tidy up.
+        if unused_modules.contains(&&import.location) {
+            continue;
+        }
+        let is_module_used = qualified_modules.contains(&import.module);
+        let spans = unused_unqualified_imports(import, &unused_names, is_module_used, &module.code);
+        for span in spans {
+            let end = if span == import.location && delete_line(&span, line_numbers) {
+                span.end + 1
+            } else {
+                span.end
+            };
+            let range = src_span_to_lsp_range(SrcSpan::new(span.start, end), line_numbers);
+            hovered = hovered || overlaps(params.range, range);
+            edits.push(TextEdit {
+                range,
+                new_text: "".into(),
+            });
+        }
+    }
+
     // If none of the imports are where the cursor is we do nothing
     if !hovered {
         return;
@@ -1122,6 +1422,63 @@ fn code_action_unused_imports(
         .push_to(actions);
 }
 
+/// The spans of code to delete to remove the unused names from the list of
+/// unqualified imports of an import, along with the commas separating them.
+/// If none of the names are used the whole list is removed, or the whole
+/// import if the module isn't used qualified either.
+fn unused_unqualified_imports(
+    import: &Import<EcoString>,
+    unused: &[SrcSpan],
+    is_module_used: bool,
+    code: &str,
+) -> Vec<SrcSpan> {
+    let names = import
+        .unqualified_values
+        .iter()
+        .chain(&import.unqualified_types)
+        .map(|imported| imported.location)
+        .sorted_by_key(|location| location.start)
+        .collect_vec();
+    let is_unused = |location: &SrcSpan| unused.contains(location);
+
+    let Some(last_used) = names.iter().rposition(|name| !is_unused(name)) else {
+        // `import wibble.{wobble}` becomes `import wibble`.
+        let (Some(first), Some(last)) = (names.first(), names.last()) else {
+            return vec![];
+        };
+        if !is_module_used {
+            return vec![import.location];
+        }
+        let start = code
+            .get(import.location.start as usize..first.start as usize)
+            .and_then(|code| code.rfind('.'))
+            .map_or(first.start, |offset| import.location.start + offset as u32);
+        let end = code
+            .get(last.end as usize..import.location.end as usize)
+            .and_then(|code| code.find('}'))
+            .map_or(last.end, |offset| last.end + offset as u32 + 1);
+        return vec![SrcSpan::new(start, end)];
+    };
+
+    names
+        .iter()
+        .enumerate()
+        .filter(|(_, name)| is_unused(name))
+        .filter_map(|(index, name)| {
+            if index < last_used {
+                // A name is removed along with the comma and space after it...
+                let next = names.get(index + 1)?;
+                Some(SrcSpan::new(name.start, next.start))
+            } else {
+                // ...unless it comes after the last name that's kept, in which
+                // case it's the comma before it that goes.
+                let previous = names.get(index.checked_sub(1)?)?;
+                Some(SrcSpan::new(previous.end, name.end))
+            }
+        })
+        .collect()
+}
+
 struct NameCorrection {
     pub location: SrcSpan,
     pub correction: EcoString,
@@ -1221,7 +1578,7 @@ fn get_hexdocs_link_section(
     module_name: &str,
     name: &str,
     ast: &TypedModule,
-    hex_deps: &std::collections::HashSet<EcoString>,
+    hex_deps: &HashSet<EcoString>,
 ) -> Option<String> {
     let package_name = ast.definitions.iter().find_map(|def| match def {
         Definition::Import(p) if p.module == module_name && hex_deps.contains(&p.package) => {
@@ -40,7 +40,7 @@ impl Feedback {
         }
     }
 
-    fn append_message(&mut self, diagnostic: Diagnostic) {
+    pub fn append_message(&mut self, diagnostic: Diagnostic) {
         self.messages.push(diagnostic);
     }
 }
@@ -138,6 +138,18 @@ impl FeedbackBookKeeper {
         feedback
     }
 
+    /// Erase all the diagnostics that have been sent, as when the project is
+    /// no longer open in the editor.
+    ///
+    pub fn unset_all(&mut self) -> Feedback {
+        let mut feedback = Feedback::none();
+        for path in self.files_with_warnings.drain() {
+            feedback.unset_existing_diagnostics(path);
+        }
+        self.unset_errors(&mut feedback);
+        feedback
+    }
+
     pub fn error(&mut self, error: Error) -> Feedback {
         self.build_with_error(error, Compilation::No, vec![])
     }
@@ -695,6 +707,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unset_all() {
+        let mut book_keeper = FeedbackBookKeeper::default();
+        let file1 = Utf8PathBuf::from("src/file1.gleam");
+        let file2 = Utf8PathBuf::from("src/file2.gleam");
+
+        let warning = Warning::Type {
+            path: file1.clone(),
+            src: "src".into(),
+            warning: type_::Warning::NoFieldsRecordUpdate {
+                location: SrcSpan::new(1, 2),
+            },
+        };
+        let error = Error::Parse {
+            path: file2.clone(),
+            src: "blah".into(),
+            error: ParseError {
+                error: ParseErrorType::ConcatPatternVariableLeftHandSide,
+                location: SrcSpan::new(1, 4),
+            },
+        };
+        let _ = book_keeper.build_with_error(error, Compilation::Yes(vec![]), vec![warning]);
+
+        // All the diagnostics that were sent are erased, and only once.
+
+        assert_eq!(
+            Feedback {
+                diagnostics: HashMap::from([(file1, vec![]), (file2, vec![])]),
+                messages: vec![],
+            },
+            book_keeper.unset_all()
+        );
+        assert_eq!(Feedback::none(), book_keeper.unset_all());
+    }
+
     #[test]
     fn append_feedback_existing_file_blank() {
         let mut feedback = Feedback {
@@ -6,8 +6,8 @@ use debug_ignore::DebugIgnore;
 use crate::{
     error::Error,
     io::{
-        memory::InMemoryFileSystem, BeamCompiler, CommandExecutor, FileSystemReader,
-        FileSystemWriter, ReadDir, Stdio, WrappedReader,
+        memory::InMemoryFileSystem, BeamCompiler, CommandExecutor, ErlangCompilerDiagnostic,
+        FileSystemReader, FileSystemWriter, ReadDir, Stdio, WrappedReader,
     },
     Result,
 };
@@ -179,7 +179,7 @@ where
         _lib: &Utf8Path,
         _modules: &HashSet<Utf8PathBuf>,
         _stdio: Stdio,
-    ) -> Result<(), Error> {
+    ) -> Result<Vec<ErlangCompilerDiagnostic>, Error> {
         panic!("The language server is not permitted to create subprocesses")
     }
 }
@@ -0,0 +1,146 @@
+//! Inlay hints shown by the editor in between the code: the inferred types of
+//! variables without an annotation and the labels of the arguments given
+//! without one.
+
+use std::sync::Arc;
+
+use ecow::EcoString;
+use lsp_types::{InlayHint, InlayHintKind, InlayHintLabel, Position, Range, TextEdit};
+
+use crate::{
+    ast::{self, visit::Visit as _, AssignmentKind, Pattern, SrcSpan, TypedAssignment, TypedExpr},
+    build::Module,
+    line_numbers::LineNumbers,
+    type_::{printer::Printer, FieldMap, ModuleValueConstructor, Type, TypedCallArg},
+};
+
+use super::{configuration::InlayHintsConfig, src_span_to_lsp_range};
+
+/// The enabled inlay hints for the part of a module in the given range.
+pub fn inlay_hints(module: &Module, range: Range, config: InlayHintsConfig) -> Vec<InlayHint> {
+    let mut collector = InlayHintCollector {
+        config,
+        range,
+        line_numbers: LineNumbers::new(&module.code),
+        printer: Printer::new(&module.ast.names),
+        hints: vec![],
+    };
+    collector.visit_typed_module(&module.ast);
+    collector.hints
+}
+
+struct InlayHintCollector<'a> {
+    config: InlayHintsConfig,
+    range: Range,
+    line_numbers: LineNumbers,
+    printer: Printer<'a>,
+    hints: Vec<InlayHint>,
+}
+
+impl InlayHintCollector<'_> {
+    fn position(&self, byte_index: u32) -> Option<Position> {
+        let position =
+            src_span_to_lsp_range(SrcSpan::new(byte_index, byte_index), &self.line_numbers).start;
+        (self.range.start <= position && position <= self.range.end).then_some(position)
+    }
+
+    /// `let wibble = 1` is shown as `let wibble: Int = 1`.
+    fn variable_type(&mut self, location: SrcSpan, type_: &Type) {
+        let Some(position) = self.position(location.end) else {
+            return;
+        };
+        let annotation = format!(": {}", self.printer.print_type(type_));
+        self.hints.push(InlayHint {
+            position,
+            label: InlayHintLabel::String(annotation.clone()),
+            kind: Some(InlayHintKind::TYPE),
+            // Accepting the hint adds it as an annotation.
+            text_edits: Some(vec![TextEdit {
+                range: Range::new(position, position),
+                new_text: annotation,
+            }]),
+            tooltip: None,
+            padding_left: None,
+            padding_right: None,
+            data: None,
+        });
+    }
+
+    /// `wibble(1)` is shown as `wibble(label: 1)`.
+    fn parameter_label(&mut self, location: SrcSpan, label: &EcoString) {
+        let Some(position) = self.position(location.start) else {
+            return;
+        };
+        self.hints.push(InlayHint {
+            position,
+            label: InlayHintLabel::String(format!("{label}:")),
+            kind: Some(InlayHintKind::PARAMETER),
+            text_edits: None,
+            tooltip: None,
+            padding_left: None,
+            padding_right: Some(true),
+            data: None,
+        });
+    }
+}
+
+impl<'ast> ast::visit::Visit<'ast> for InlayHintCollector<'ast> {
+    fn visit_typed_assignment(&mut self, assignment: &'ast TypedAssignment) {
+        let is_generated = matches!(assignment.kind, AssignmentKind::Generated);
+        if self.config.variable_types && assignment.annotation.is_none() && !is_generated {
+            if let Pattern::Variable {
+                location, type_, ..
+            } = &assignment.pattern
+            {
+                self.variable_type(*location, type_);
+            }
+        }
+        ast::visit::visit_typed_assignment(self, assignment);
+    }
+
+    fn visit_typed_expr_call(
+        &mut self,
+        location: &'ast SrcSpan,
+        type_: &'ast Arc<Type>,
+        fun: &'ast TypedExpr,
+        args: &'ast [TypedCallArg],
+    ) {
+        let field_map = match fun {
+            TypedExpr::Var { constructor, .. } => constructor.field_map(),
+            TypedExpr::ModuleSelect { constructor, .. } => match constructor {
+                ModuleValueConstructor::Record { field_map, .. }
+                | ModuleValueConstructor::Fn { field_map, .. } => field_map.as_ref(),
+                ModuleValueConstructor::Constant { .. } => None,
+            },
+            _ => None,
+        };
+
+        if let Some(field_map) = field_map.filter(|_| self.config.parameter_labels) {
+            // The arguments of a typed call are in the same order as the
+            // parameters, so a label's index is the index of its argument.
+            for (index, arg) in args.iter().enumerate() {
+                if arg.label.is_some() || arg.is_implicit() {
+                    continue;
+                }
+                let Some(label) = label_at(field_map, index) else {
+                    continue;
+                };
+                // There's nothing to learn from `wibble(label)`.
+                if matches!(&arg.value, TypedExpr::Var { name, .. } if *name == *label) {
+                    continue;
+                }
+                self.parameter_label(arg.location, label);
+            }
+        }
+
+        ast::visit::visit_typed_expr_call(self, location, type_, fun, args);
+    }
+}
+
+fn label_at(field_map: &FieldMap, index: usize) -> Option<&EcoString> {
+    field_map
+        .fields
+        .iter()
+        .find(|(_, label_index)| **label_index as usize == index)
+        .map(|(label, _)| label)
+}
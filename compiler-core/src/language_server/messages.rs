@@ -5,14 +5,24 @@ use lsp::{
 };
 use lsp_types::{
     self as lsp,
-    notification::{DidChangeTextDocument, DidCloseTextDocument, DidSaveTextDocument},
+    notification::{
+        DidChangeConfiguration, DidChangeTextDocument, DidChangeWorkspaceFolders,
+        DidCloseTextDocument, DidSaveTextDocument,
+    },
     request::{
         CodeActionRequest, Completion, DocumentSymbolRequest, Formatting, HoverRequest,
-        SignatureHelpRequest,
+        InlayHintRequest, PrepareRenameRequest, RangeFormatting, References, Rename,
+        SemanticTokensFullRequest, SignatureHelpRequest, WorkspaceSymbolRequest,
     },
 };
 use std::time::Duration;
 
+use super::configuration::Configuration;
+
+/// The id of the requests sent to the client asking for the configuration of
+/// the language server, so their responses can be told apart.
+pub const CONFIGURATION_REQUEST_ID: &str = "gleam/configuration";
+
 #[derive(Debug)]
 pub enum Message {
     Request(lsp_server::RequestId, Request),
@@ -22,12 +32,19 @@ pub enum Message {
 #[derive(Debug)]
 pub enum Request {
     Format(lsp::DocumentFormattingParams),
+    FormatRange(lsp::DocumentRangeFormattingParams),
     Hover(lsp::HoverParams),
     GoToDefinition(lsp::GotoDefinitionParams),
     Completion(lsp::CompletionParams),
     CodeAction(lsp::CodeActionParams),
     SignatureHelp(lsp::SignatureHelpParams),
     DocumentSymbol(lsp::DocumentSymbolParams),
+    PrepareRename(lsp::TextDocumentPositionParams),
+    Rename(lsp::RenameParams),
+    FindReferences(lsp::ReferenceParams),
+    WorkspaceSymbol(lsp::WorkspaceSymbolParams),
+    InlayHint(lsp::InlayHintParams),
+    SemanticTokens(lsp::SemanticTokensParams),
 }
 
 impl Request {
@@ -38,6 +55,10 @@ impl Request {
                 let params = cast_request::<Formatting>(request);
                 Some(Message::Request(id, Request::Format(params)))
             }
+            "textDocument/rangeFormatting" => {
+                let params = cast_request::<RangeFormatting>(request);
+                Some(Message::Request(id, Request::FormatRange(params)))
+            }
             "textDocument/hover" => {
                 let params = cast_request::<HoverRequest>(request);
                 Some(Message::Request(id, Request::Hover(params)))
@@ -62,6 +83,30 @@ impl Request {
                 let params = cast_request::<DocumentSymbolRequest>(request);
                 Some(Message::Request(id, Request::DocumentSymbol(params)))
             }
+            "textDocument/prepareRename" => {
+                let params = cast_request::<PrepareRenameRequest>(request);
+                Some(Message::Request(id, Request::PrepareRename(params)))
+            }
+            "textDocument/rename" => {
+                let params = cast_request::<Rename>(request);
+                Some(Message::Request(id, Request::Rename(params)))
+            }
+            "textDocument/references" => {
+                let params = cast_request::<References>(request);
+                Some(Message::Request(id, Request::FindReferences(params)))
+            }
+            "workspace/symbol" => {
+                let params = cast_request::<WorkspaceSymbolRequest>(request);
+                Some(Message::Request(id, Request::WorkspaceSymbol(params)))
+            }
+            "textDocument/inlayHint" => {
+                let params = cast_request::<InlayHintRequest>(request);
+                Some(Message::Request(id, Request::InlayHint(params)))
+            }
+            "textDocument/semanticTokens/full" => {
+                let params = cast_request::<SemanticTokensFullRequest>(request);
+                Some(Message::Request(id, Request::SemanticTokens(params)))
+            }
             _ => None,
         }
     }
@@ -71,12 +116,31 @@ impl Request {
 pub enum Notification {
     /// A Gleam file has been modified in memory, and the new text is provided.
     SourceFileChangedInMemory { path: Utf8PathBuf, text: String },
+    /// A Gleam file has been modified in memory, and the ranges that changed
+    /// are provided along with their new text.
+    SourceFileEditedInMemory {
+        path: Utf8PathBuf,
+        changes: Vec<lsp::TextDocumentContentChangeEvent>,
+    },
     /// A Gleam file has been saved or closed in the editor.
     SourceFileMatchesDisc { path: Utf8PathBuf },
     /// gleam.toml has changed.
     ConfigFileChanged { path: Utf8PathBuf },
+    /// Gleam files have been created, changed, or deleted on disc, possibly
+    /// by something other than the editor.
+    SourceFilesChangedOnDisc { paths: Vec<Utf8PathBuf> },
     /// It's time to compile all open projects.
     CompilePlease,
+    /// The configuration of the language server has changed in the editor.
+    /// If the new configuration wasn't sent along it has to be asked for.
+    ConfigurationChanged {
+        configuration: Option<Configuration>,
+    },
+    /// Folders have been added to or removed from the editor's workspace.
+    WorkspaceFoldersChanged {
+        added: Vec<Utf8PathBuf>,
+        removed: Vec<Utf8PathBuf>,
+    },
 }
 
 impl Notification {
@@ -92,9 +156,9 @@ impl Notification {
             }
             "textDocument/didChange" => {
                 let params = cast_notification::<DidChangeTextDocument>(notification);
-                let notification = Notification::SourceFileChangedInMemory {
+                let notification = Notification::SourceFileEditedInMemory {
                     path: super::path(&params.text_document.uri),
-                    text: params.content_changes.into_iter().last()?.text,
+                    changes: params.content_changes,
                 };
                 Some(Message::Notification(notification))
             }
@@ -116,8 +180,46 @@ impl Notification {
 
             "workspace/didChangeWatchedFiles" => {
                 let params = cast_notification::<DidChangeWatchedFiles>(notification);
-                let notification = Notification::ConfigFileChanged {
-                    path: super::path(&params.changes.into_iter().last()?.uri),
+                let paths = params
+                    .changes
+                    .into_iter()
+                    .map(|change| super::path(&change.uri))
+                    .collect::<Vec<_>>();
+                let config_file = paths
+                    .iter()
+                    .find(|path| path.file_name() == Some("gleam.toml"));
+                let notification = match config_file {
+                    Some(path) => Notification::ConfigFileChanged { path: path.clone() },
+                    None if paths.is_empty() => return None,
+                    None => Notification::SourceFilesChangedOnDisc { paths },
+                };
+                Some(Message::Notification(notification))
+            }
+
+            "workspace/didChangeConfiguration" => {
+                let params = cast_notification::<DidChangeConfiguration>(notification);
+                let notification = Notification::ConfigurationChanged {
+                    configuration: params
+                        .settings
+                        .get("gleam")
+                        .cloned()
+                        .map(Configuration::from_json),
+                };
+                Some(Message::Notification(notification))
+            }
+
+            "workspace/didChangeWorkspaceFolders" => {
+                let params = cast_notification::<DidChangeWorkspaceFolders>(notification);
+                let paths = |folders: Vec<lsp::WorkspaceFolder>| {
+                    folders
+                        .into_iter()
+                        .filter(|folder| folder.uri.scheme() == "file")
+                        .map(|folder| super::path(&folder.uri))
+                        .collect()
+                };
+                let notification = Notification::WorkspaceFoldersChanged {
+                    added: paths(params.event.added),
+                    removed: paths(params.event.removed),
                 };
                 Some(Message::Notification(notification))
             }
@@ -206,8 +308,19 @@ impl MessageBuffer {
         Next::MorePlease
     }
 
-    fn response(&mut self, _: lsp_server::Response) -> Next {
-        // We do not use or expect responses from the client currently.
+    fn response(&mut self, response: lsp_server::Response) -> Next {
+        // The only response we use is the one with the configuration, which
+        // holds a value for each of the sections that were asked for.
+        if response.id == CONFIGURATION_REQUEST_ID.to_string().into() {
+            let configuration = match response.result {
+                Some(serde_json::Value::Array(sections)) => sections.into_iter().next(),
+                _ => None,
+            };
+            let notification = Notification::ConfigurationChanged {
+                configuration: Some(Configuration::from_json(configuration.unwrap_or_default())),
+            };
+            self.messages.push(Message::Notification(notification));
+        }
         Next::MorePlease
     }
 
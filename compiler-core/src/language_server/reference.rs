@@ -0,0 +1,813 @@
+//! Finding the places in a module that refer to a name: where it's defined,
+//! imported and used. Renaming and finding references are built on this.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use ecow::EcoString;
+use itertools::Itertools;
+
+use crate::{
+    analyse::Inferred,
+    ast::{
+        visit::{self, Visit},
+        ArgNames, AssignName, CallArg, ClauseGuard, Definition, SrcSpan, TypeAst,
+        TypeAstConstructor, TypeAstFn, TypeAstTuple, TypedArg, TypedAssignment, TypedClause,
+        TypedConstant, TypedDefinition, TypedExpr, TypedFunction, TypedPattern,
+    },
+    build::Module,
+    reference::{self, ValueUsage},
+    type_::{
+        error::VariableOrigin, ModuleValueConstructor, PatternConstructor, Type, TypedCallArg,
+        ValueConstructor, ValueConstructorVariant, PRELUDE_MODULE_NAME,
+    },
+};
+
+/// Something that can be referred to by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Referent {
+    /// A function, constant or record constructor defined at the top level
+    /// of a module.
+    Value { module: EcoString, name: EcoString },
+    /// A custom type or a type alias.
+    Type { module: EcoString, name: EcoString },
+    /// A variable local to a function, told apart from others of the same
+    /// name by where it's defined.
+    LocalVariable {
+        module: EcoString,
+        definition: SrcSpan,
+    },
+}
+
+impl Referent {
+    pub fn module(&self) -> &EcoString {
+        match self {
+            Referent::Value { module, .. }
+            | Referent::Type { module, .. }
+            | Referent::LocalVariable { module, .. } => module,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    pub referent: Referent,
+    /// The location of the name itself.
+    pub location: SrcSpan,
+    /// The name as it's written, which isn't the name of the referent when
+    /// it has been imported with an alias.
+    pub name: EcoString,
+    pub kind: ReferenceKind,
+    /// The index of the top level definition the reference is part of.
+    pub scope: usize,
+}
+
+impl Reference {
+    /// Whether this is where the referent is defined. A variable defined by
+    /// a label shorthand is both defined and labelled there.
+    pub fn is_definition(&self) -> bool {
+        match &self.referent {
+            Referent::LocalVariable { definition, .. } => *definition == self.location,
+            Referent::Value { .. } | Referent::Type { .. } => {
+                self.kind == ReferenceKind::Definition
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceKind {
+    Definition,
+    /// An unqualified import, as in `import wibble.{wobble}`.
+    Import,
+    Usage,
+    /// A variable both defined or used and labelled by a label shorthand, as
+    /// in `wibble(wobble:)`.
+    LabelShorthand,
+}
+
+/// All the references in a module, in the order they're found.
+pub fn module_references(module: &Module) -> Vec<Reference> {
+    let mut collector = ReferenceCollector::new(module);
+    for (scope, definition) in module.ast.definitions.iter().enumerate() {
+        collector.scope = scope;
+        collector.bindings.clear();
+        collector.definition(definition);
+    }
+    collector.references
+}
+
+struct ReferenceCollector<'a> {
+    module: &'a EcoString,
+    code: &'a str,
+    /// The modules imported by the module, by the name they're used with.
+    module_aliases: HashMap<EcoString, EcoString>,
+    /// The values imported unqualified, by the name they're used with.
+    unqualified_values: HashMap<EcoString, (EcoString, EcoString)>,
+    /// The types that can be used unqualified, by the name they're used with.
+    types: HashMap<EcoString, (EcoString, EcoString)>,
+    /// The constants defined in the module.
+    constants: HashSet<EcoString>,
+    scope: usize,
+    /// The local variables defined so far in the current definition, used to
+    /// find the ones clause guards refer to.
+    bindings: Vec<(EcoString, SrcSpan)>,
+    references: Vec<Reference>,
+}
+
+impl<'a> ReferenceCollector<'a> {
+    fn new(module: &'a Module) -> Self {
+        let mut module_aliases = HashMap::new();
+        let mut unqualified_values = HashMap::new();
+        let mut types = HashMap::new();
+        let mut constants = HashSet::new();
+        for definition in &module.ast.definitions {
+            match definition {
+                Definition::Import(import) => {
+                    if let Some(name) = import.used_name() {
+                        let _ = module_aliases.insert(name, import.module.clone());
+                    }
+                    for value in &import.unqualified_values {
+                        let imported = (import.module.clone(), value.name.clone());
+                        let _ = unqualified_values.insert(value.used_name().clone(), imported);
+                    }
+                    for type_ in &import.unqualified_types {
+                        let imported = (import.module.clone(), type_.name.clone());
+                        let _ = types.insert(type_.used_name().clone(), imported);
+                    }
+                }
+                Definition::CustomType(custom_type) => {
+                    let defined = (module.name.clone(), custom_type.name.clone());
+                    let _ = types.insert(custom_type.name.clone(), defined);
+                }
+                Definition::TypeAlias(alias) => {
+                    let defined = (module.name.clone(), alias.alias.clone());
+                    let _ = types.insert(alias.alias.clone(), defined);
+                }
+                Definition::ModuleConstant(constant) => {
+                    let _ = constants.insert(constant.name.clone());
+                }
+                Definition::Function(_) => (),
+            }
+        }
+
+        Self {
+            module: &module.name,
+            code: &module.code,
+            module_aliases,
+            unqualified_values,
+            types,
+            constants,
+            scope: 0,
+            bindings: vec![],
+            references: vec![],
+        }
+    }
+
+    fn push(
+        &mut self,
+        referent: Referent,
+        location: SrcSpan,
+        name: &EcoString,
+        kind: ReferenceKind,
+    ) {
+        self.references.push(Reference {
+            referent,
+            location,
+            name: name.clone(),
+            kind,
+            scope: self.scope,
+        });
+    }
+
+    fn define_variable(&mut self, name: &EcoString, location: SrcSpan, kind: ReferenceKind) {
+        // Variables starting with an underscore are discarded, or generated
+        // by the compiler.
+        if name.starts_with('_') {
+            return;
+        }
+        let referent = Referent::LocalVariable {
+            module: self.module.clone(),
+            definition: location,
+        };
+        self.push(referent, location, name, kind);
+        self.bindings.push((name.clone(), location));
+    }
+
+    fn definition(&mut self, definition: &'a TypedDefinition) {
+        match definition {
+            Definition::Function(function) => self.visit_typed_function(function),
+
+            Definition::ModuleConstant(constant) => {
+                let referent = self.value(&constant.name);
+                self.push(
+                    referent,
+                    constant.name_location,
+                    &constant.name,
+                    ReferenceKind::Definition,
+                );
+                if let Some(annotation) = &constant.annotation {
+                    self.type_ast(annotation);
+                }
+                self.constant(&constant.value);
+            }
+
+            Definition::CustomType(custom_type) => {
+                let referent = self.type_(&custom_type.name);
+                self.push(
+                    referent,
+                    custom_type.name_location,
+                    &custom_type.name,
+                    ReferenceKind::Definition,
+                );
+                for constructor in &custom_type.constructors {
+                    let referent = self.value(&constructor.name);
+                    self.push(
+                        referent,
+                        constructor.name_location,
+                        &constructor.name,
+                        ReferenceKind::Definition,
+                    );
+                    for argument in &constructor.arguments {
+                        self.type_ast(&argument.ast);
+                    }
+                }
+            }
+
+            Definition::TypeAlias(alias) => {
+                let referent = self.type_(&alias.alias);
+                self.push(
+                    referent,
+                    alias.name_location,
+                    &alias.alias,
+                    ReferenceKind::Definition,
+                );
+                self.type_ast(&alias.type_ast);
+            }
+
+            Definition::Import(import) => {
+                for value in &import.unqualified_values {
+                    let referent = Referent::Value {
+                        module: import.module.clone(),
+                        name: value.name.clone(),
+                    };
+                    let location = self.name_location(value.location, &value.name);
+                    self.push(referent, location, &value.name, ReferenceKind::Import);
+                }
+                for type_ in &import.unqualified_types {
+                    let referent = Referent::Type {
+                        module: import.module.clone(),
+                        name: type_.name.clone(),
+                    };
+                    let location = self.name_location(type_.location, &type_.name);
+                    self.push(referent, location, &type_.name, ReferenceKind::Import);
+                }
+            }
+        }
+    }
+
+    /// A value defined at the top level of this module.
+    fn value(&self, name: &EcoString) -> Referent {
+        Referent::Value {
+            module: self.module.clone(),
+            name: name.clone(),
+        }
+    }
+
+    /// A type defined in this module.
+    fn type_(&self, name: &EcoString) -> Referent {
+        Referent::Type {
+            module: self.module.clone(),
+            name: name.clone(),
+        }
+    }
+
+    /// The location of a name within a larger span of code that starts with
+    /// it or with a keyword, as in `type Wibble as Wobble`.
+    fn name_location(&self, location: SrcSpan, name: &str) -> SrcSpan {
+        let start = self
+            .code
+            .get(location.start as usize..location.end as usize)
+            .and_then(|code| code.find(name))
+            .map_or(location.start, |offset| location.start + offset as u32);
+        SrcSpan::new(start, start + name.len() as u32)
+    }
+
+    /// What a name used in an expression refers to, given how it was
+    /// resolved when the module was analysed.
+    fn value_referent(
+        &self,
+        name: &EcoString,
+        variant: &ValueConstructorVariant,
+    ) -> Option<Referent> {
+        match variant {
+            ValueConstructorVariant::LocalVariable { location } => Some(Referent::LocalVariable {
+                module: self.module.clone(),
+                definition: *location,
+            }),
+            ValueConstructorVariant::ModuleConstant { module, .. } => {
+                // Constants don't record their name, which is different from
+                // the one used if they're imported with an alias.
+                let name = match self.unqualified_values.get(name) {
+                    Some((imported_module, imported_name)) if imported_module == module => {
+                        imported_name.clone()
+                    }
+                    _ => name.clone(),
+                };
+                Some(Referent::Value {
+                    module: module.clone(),
+                    name,
+                })
+            }
+            ValueConstructorVariant::ModuleFn { module, name, .. }
+            | ValueConstructorVariant::Record { module, name, .. } => Some(Referent::Value {
+                module: module.clone(),
+                name: name.clone(),
+            }),
+            ValueConstructorVariant::LocalConstant { .. } => None,
+        }
+    }
+
+    fn value_usage(&mut self, usage: ValueUsage<'_>, kind: ReferenceKind) {
+        match usage {
+            ValueUsage::Name {
+                location,
+                name,
+                constructor,
+            } => {
+                if name.starts_with('_') {
+                    return;
+                }
+                if let Some(referent) = self.value_referent(name, &constructor.variant) {
+                    self.push(referent, location, name, kind);
+                }
+            }
+
+            ValueUsage::ModuleSelect {
+                location,
+                module,
+                label,
+                ..
+            } => {
+                let referent = Referent::Value {
+                    module: module.clone(),
+                    name: label.clone(),
+                };
+                self.push(referent, location, label, kind);
+            }
+
+            ValueUsage::Constructor {
+                location,
+                module,
+                name,
+                used_name,
+                ..
+            } => {
+                let referent = Referent::Value {
+                    module: module.clone(),
+                    name: name.clone(),
+                };
+                self.push(referent, location, used_name, kind);
+            }
+        }
+    }
+
+    fn arguments(&mut self, arguments: &'a [TypedArg]) {
+        for argument in arguments {
+            match &argument.names {
+                ArgNames::Named { name, location }
+                | ArgNames::NamedLabelled {
+                    name,
+                    name_location: location,
+                    ..
+                } => self.define_variable(name, *location, ReferenceKind::Definition),
+                ArgNames::Discard { .. } | ArgNames::LabelledDiscard { .. } => (),
+            }
+            if let Some(annotation) = &argument.annotation {
+                self.type_ast(annotation);
+            }
+        }
+    }
+
+    fn type_ast(&mut self, type_ast: &TypeAst) {
+        match type_ast {
+            TypeAst::Constructor(TypeAstConstructor {
+                location,
+                module,
+                name,
+                arguments,
+            }) => {
+                let (module_name, start) = match module {
+                    Some((alias, alias_location)) => (
+                        self.module_aliases.get(alias).cloned(),
+                        // The name follows the module and the dot.
+                        alias_location.end + 1,
+                    ),
+                    None => (None, location.start),
+                };
+                let referent = match (module, module_name) {
+                    (Some(_), Some(module)) => Some(Referent::Type {
+                        module,
+                        name: name.clone(),
+                    }),
+                    (Some(_), None) => None,
+                    (None, _) => Some(match self.types.get(name) {
+                        Some((module, name)) => Referent::Type {
+                            module: module.clone(),
+                            name: name.clone(),
+                        },
+                        None => Referent::Type {
+                            module: PRELUDE_MODULE_NAME.into(),
+                            name: name.clone(),
+                        },
+                    }),
+                };
+                if let Some(referent) = referent {
+                    let location = SrcSpan::new(start, start + name.len() as u32);
+                    self.push(referent, location, name, ReferenceKind::Usage);
+                }
+                for argument in arguments {
+                    self.type_ast(argument);
+                }
+            }
+            TypeAst::Fn(TypeAstFn {
+                arguments, return_, ..
+            }) => {
+                for argument in arguments {
+                    self.type_ast(argument);
+                }
+                self.type_ast(return_);
+            }
+            TypeAst::Tuple(TypeAstTuple { elems, .. }) => {
+                for elem in elems {
+                    self.type_ast(elem);
+                }
+            }
+            TypeAst::Var(_) | TypeAst::Hole(_) => (),
+        }
+    }
+
+    fn constant(&mut self, constant: &TypedConstant) {
+        let mut usages = vec![];
+        reference::constant_usages(constant, &mut |usage| usages.push(usage));
+        for usage in usages {
+            self.value_usage(usage, ReferenceKind::Usage);
+        }
+    }
+
+    /// `span` is where the guard is written in the code. That's needed for
+    /// the constants guards refer to, as they're replaced by their values
+    /// when the module is analysed.
+    fn clause_guard(&mut self, guard: &ClauseGuard<Arc<Type>, EcoString>, span: SrcSpan) {
+        match guard {
+            ClauseGuard::Equals { left, right, .. }
+            | ClauseGuard::NotEquals { left, right, .. }
+            | ClauseGuard::GtInt { left, right, .. }
+            | ClauseGuard::GtEqInt { left, right, .. }
+            | ClauseGuard::LtInt { left, right, .. }
+            | ClauseGuard::LtEqInt { left, right, .. }
+            | ClauseGuard::GtFloat { left, right, .. }
+            | ClauseGuard::GtEqFloat { left, right, .. }
+            | ClauseGuard::LtFloat { left, right, .. }
+            | ClauseGuard::LtEqFloat { left, right, .. }
+            | ClauseGuard::AddInt { left, right, .. }
+            | ClauseGuard::AddFloat { left, right, .. }
+            | ClauseGuard::SubInt { left, right, .. }
+            | ClauseGuard::SubFloat { left, right, .. }
+            | ClauseGuard::MultInt { left, right, .. }
+            | ClauseGuard::MultFloat { left, right, .. }
+            | ClauseGuard::DivInt { left, right, .. }
+            | ClauseGuard::DivFloat { left, right, .. }
+            | ClauseGuard::RemainderInt { left, right, .. }
+            | ClauseGuard::Or { left, right, .. }
+            | ClauseGuard::And { left, right, .. } => {
+                let location = guard.location();
+                let operator = guard.bin_op_name().map_or("", |name| name.name());
+                let search_from = match left.as_ref() {
+                    ClauseGuard::Constant(_) => location.start,
+                    left => left.location().end,
+                };
+                let operator_start = self
+                    .code
+                    .get(search_from as usize..location.end as usize)
+                    .and_then(|code| code.find(operator))
+                    .map_or(search_from, |offset| search_from + offset as u32);
+                let operator_end = operator_start + operator.len() as u32;
+                self.clause_guard(left, SrcSpan::new(location.start, operator_start));
+                self.clause_guard(right, SrcSpan::new(operator_end, location.end));
+            }
+
+            ClauseGuard::Not {
+                location,
+                expression,
+                ..
+            } => self.clause_guard(expression, SrcSpan::new(location.start + 1, location.end)),
+
+            ClauseGuard::TupleIndex {
+                location, tuple, ..
+            } => self.clause_guard(tuple, self.before_last_dot(*location)),
+
+            ClauseGuard::FieldAccess {
+                location,
+                container,
+                ..
+            } => self.clause_guard(container, self.before_last_dot(*location)),
+
+            // Guards don't record where the variables they use are defined,
+            // so it's the most recent definition of a variable by that name.
+            ClauseGuard::Var { location, name, .. } => {
+                let Some((_, definition)) =
+                    self.bindings.iter().rev().find(|(bound, _)| bound == name)
+                else {
+                    return;
+                };
+                let referent = Referent::LocalVariable {
+                    module: self.module.clone(),
+                    definition: *definition,
+                };
+                self.push(referent, *location, name, ReferenceKind::Usage);
+            }
+
+            ClauseGuard::ModuleSelect {
+                location,
+                label,
+                module_name,
+                ..
+            } => {
+                let referent = Referent::Value {
+                    module: module_name.clone(),
+                    name: label.clone(),
+                };
+                // The location of a module select in a guard is that of the
+                // module name, and the label comes after it.
+                let rest = SrcSpan::new(location.end, self.code.len() as u32);
+                let location = self.name_location(rest, label);
+                self.push(referent, location, label, ReferenceKind::Usage);
+            }
+
+            ClauseGuard::Constant(_) => self.guard_constant(span),
+        }
+    }
+
+    fn before_last_dot(&self, location: SrcSpan) -> SrcSpan {
+        let end = self
+            .code
+            .get(location.start as usize..location.end as usize)
+            .and_then(|code| code.rfind('.'))
+            .map_or(location.end, |offset| location.start + offset as u32);
+        SrcSpan::new(location.start, end)
+    }
+
+    /// A constant in a guard that's written as the name of a constant, rather
+    /// than as a literal value.
+    fn guard_constant(&mut self, span: SrcSpan) {
+        let Some(code) = self.code.get(span.start as usize..span.end as usize) else {
+            return;
+        };
+        let trimmed = code.trim_start();
+        // The guard of a clause is given along with the `if` before it and
+        // the arrow after it.
+        let trimmed = trimmed
+            .strip_prefix("if")
+            .filter(|rest| rest.starts_with(char::is_whitespace))
+            .unwrap_or(trimmed)
+            .trim_start();
+        let name = trimmed.trim_end().trim_end_matches("->").trim_end();
+        let is_name = name.starts_with(|char: char| char.is_ascii_lowercase())
+            && name
+                .chars()
+                .all(|char| char.is_ascii_alphanumeric() || char == '_');
+        if !is_name {
+            return;
+        }
+
+        let name = EcoString::from(name);
+        let referent = match self.unqualified_values.get(&name) {
+            Some((module, imported)) => Referent::Value {
+                module: module.clone(),
+                name: imported.clone(),
+            },
+            None if self.constants.contains(&name) => self.value(&name),
+            None => return,
+        };
+        let start = span.start + (code.len() - trimmed.len()) as u32;
+        let location = SrcSpan::new(start, start + name.len() as u32);
+        self.push(referent, location, &name, ReferenceKind::Usage);
+    }
+}
+
+impl<'a> Visit<'a> for ReferenceCollector<'a> {
+    fn visit_typed_function(&mut self, function: &'a TypedFunction) {
+        if let Some((location, name)) = &function.name {
+            let referent = self.value(name);
+            self.push(referent, *location, name, ReferenceKind::Definition);
+        }
+        self.arguments(&function.arguments);
+        if let Some(annotation) = &function.return_annotation {
+            self.type_ast(annotation);
+        }
+        visit::visit_typed_function(self, function);
+    }
+
+    fn visit_typed_expr_var(
+        &mut self,
+        location: &'a SrcSpan,
+        constructor: &'a ValueConstructor,
+        name: &'a EcoString,
+    ) {
+        let usage = ValueUsage::name(*location, name, constructor);
+        self.value_usage(usage, ReferenceKind::Usage);
+    }
+
+    fn visit_typed_expr_module_select(
+        &mut self,
+        location: &'a SrcSpan,
+        type_: &'a Arc<Type>,
+        label: &'a EcoString,
+        module_name: &'a EcoString,
+        _module_alias: &'a EcoString,
+        constructor: &'a ModuleValueConstructor,
+    ) {
+        let usage = ValueUsage::module_select(*location, module_name, label, constructor, type_);
+        self.value_usage(usage, ReferenceKind::Usage);
+    }
+
+    fn visit_typed_expr_fn(
+        &mut self,
+        location: &'a SrcSpan,
+        type_: &'a Arc<Type>,
+        kind: &'a crate::ast::FunctionLiteralKind,
+        args: &'a [TypedArg],
+        body: &'a [crate::ast::TypedStatement],
+        return_annotation: &'a Option<TypeAst>,
+    ) {
+        self.arguments(args);
+        if let Some(annotation) = return_annotation {
+            self.type_ast(annotation);
+        }
+        visit::visit_typed_expr_fn(self, location, type_, kind, args, body, return_annotation);
+    }
+
+    fn visit_typed_assignment(&mut self, assignment: &'a TypedAssignment) {
+        if let Some(annotation) = &assignment.annotation {
+            self.type_ast(annotation);
+        }
+        visit::visit_typed_assignment(self, assignment);
+    }
+
+    fn visit_typed_call_arg(&mut self, arg: &'a TypedCallArg) {
+        match &arg.value {
+            TypedExpr::Var {
+                location,
+                constructor,
+                name,
+            } if arg.uses_label_shorthand() => {
+                let usage = ValueUsage::name(*location, name, constructor);
+                self.value_usage(usage, ReferenceKind::LabelShorthand);
+            }
+            _ => visit::visit_typed_call_arg(self, arg),
+        }
+    }
+
+    fn visit_typed_clause(&mut self, clause: &'a TypedClause) {
+        for pattern in clause.pattern.iter() {
+            self.visit_typed_pattern(pattern);
+        }
+        for patterns in clause.alternative_patterns.iter() {
+            for pattern in patterns {
+                self.visit_typed_pattern(pattern);
+            }
+        }
+        if let Some(guard) = &clause.guard {
+            let patterns_end = clause
+                .alternative_patterns
+                .last()
+                .unwrap_or(&clause.pattern)
+                .last()
+                .map_or(clause.location.start, |pattern| pattern.location().end);
+            let span = SrcSpan::new(patterns_end, clause.then.location().start);
+            self.clause_guard(guard, span);
+        }
+        self.visit_typed_expr(&clause.then);
+    }
+
+    fn visit_typed_pattern_variable(
+        &mut self,
+        location: &'a SrcSpan,
+        name: &'a EcoString,
+        _type_: &'a Arc<Type>,
+        origin: &'a VariableOrigin,
+    ) {
+        let kind = match origin {
+            VariableOrigin::Generated => return,
+            VariableOrigin::LabelShorthand(_) => ReferenceKind::LabelShorthand,
+            VariableOrigin::Variable(_)
+            | VariableOrigin::FunctionArgument(_)
+            | VariableOrigin::AssignmentPattern => ReferenceKind::Definition,
+        };
+        self.define_variable(name, *location, kind);
+    }
+
+    fn visit_typed_pattern_var_usage(
+        &mut self,
+        location: &'a SrcSpan,
+        name: &'a EcoString,
+        constructor: &'a Option<ValueConstructor>,
+        _type_: &'a Arc<Type>,
+    ) {
+        if let Some(constructor) = constructor {
+            let usage = ValueUsage::name(*location, name, constructor);
+            self.value_usage(usage, ReferenceKind::Usage);
+        }
+    }
+
+    fn visit_typed_pattern_assign(
+        &mut self,
+        location: &'a SrcSpan,
+        name: &'a EcoString,
+        pattern: &'a TypedPattern,
+    ) {
+        self.visit_typed_pattern(pattern);
+        self.define_variable(name, *location, ReferenceKind::Definition);
+    }
+
+    fn visit_typed_pattern_constructor(
+        &mut self,
+        location: &'a SrcSpan,
+        name: &'a EcoString,
+        arguments: &'a Vec<CallArg<TypedPattern>>,
+        module: &'a Option<(EcoString, SrcSpan)>,
+        constructor: &'a Inferred<PatternConstructor>,
+        spread: &'a Option<SrcSpan>,
+        type_: &'a Arc<Type>,
+    ) {
+        if let Some(usage) =
+            ValueUsage::pattern_constructor(*location, name, module, constructor, type_)
+        {
+            self.value_usage(usage, ReferenceKind::Usage);
+        }
+        visit::visit_typed_pattern_constructor(
+            self,
+            location,
+            name,
+            arguments,
+            module,
+            constructor,
+            spread,
+            type_,
+        );
+    }
+
+    fn visit_typed_pattern_string_prefix(
+        &mut self,
+        _location: &'a SrcSpan,
+        _left_location: &'a SrcSpan,
+        left_side_assignment: &'a Option<(EcoString, SrcSpan)>,
+        right_location: &'a SrcSpan,
+        _left_side_string: &'a EcoString,
+        right_side_assignment: &'a AssignName,
+    ) {
+        if let Some((name, location)) = left_side_assignment {
+            self.define_variable(name, *location, ReferenceKind::Definition);
+        }
+        if let AssignName::Variable(name) = right_side_assignment {
+            self.define_variable(name, *right_location, ReferenceKind::Definition);
+        }
+    }
+}
+
+/// The reference at a position in a module, if there is one.
+pub fn reference_at(references: &[Reference], byte_index: u32) -> Option<&Reference> {
+    references
+        .iter()
+        .find(|reference| reference.location.contains(byte_index))
+}
+
+/// Every reference to what's named at a position in a module, from the given
+/// modules, grouped by module. Local variables can only be referred to from
+/// the module they're defined in, so only that module is searched for them.
+pub fn find_references<'a>(
+    modules: impl IntoIterator<Item = &'a Module>,
+    module: &'a Module,
+    byte_index: u32,
+) -> Option<Vec<(&'a Module, Vec<Reference>)>> {
+    let references = module_references(module);
+    let referent = reference_at(&references, byte_index)?.referent.clone();
+    let is_referent = |reference: &Reference| reference.referent == referent;
+
+    let found = match referent {
+        Referent::LocalVariable { .. } => {
+            vec![(module, references.into_iter().filter(is_referent).collect())]
+        }
+        Referent::Value { .. } | Referent::Type { .. } => modules
+            .into_iter()
+            .map(|module| {
+                let references = module_references(module);
+                (module, references.into_iter().filter(is_referent).collect())
+            })
+            .filter(|(_, references): &(_, Vec<_>)| !references.is_empty())
+            .sorted_by(|(one, _), (other, _)| one.name.cmp(&other.name))
+            .collect(),
+    };
+    Some(found)
+}
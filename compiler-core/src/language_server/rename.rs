@@ -0,0 +1,325 @@
+//! Renaming a function, constant, type, record constructor or variable
+//! everywhere it's referred to in the package.
+
+use std::collections::HashMap;
+
+use ecow::EcoString;
+use lsp_types::{TextEdit, WorkspaceEdit};
+
+use crate::{
+    ast::{Definition, SrcSpan},
+    build::Module,
+    diagnostic::{Diagnostic, Level},
+    line_numbers::LineNumbers,
+    parse::{lexer, token::Token},
+};
+
+use super::{
+    path_to_uri,
+    reference::{module_references, reference_at, Reference, ReferenceKind, Referent},
+    src_span_to_lsp_range,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RenameError {
+    NothingToRename,
+    /// Dependencies can't be edited, so nothing they define can be renamed.
+    DefinedInDependency {
+        name: EcoString,
+    },
+    InvalidName {
+        name: EcoString,
+        expected: NameKind,
+    },
+    NameTaken {
+        name: EcoString,
+        module: EcoString,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameKind {
+    Lowercase,
+    Uppercase,
+}
+
+impl RenameError {
+    pub fn to_diagnostic(&self) -> Diagnostic {
+        let text = match self {
+            RenameError::NothingToRename => "There is nothing here that can be renamed.".into(),
+            RenameError::DefinedInDependency { name } => {
+                format!("`{name}` is defined in a dependency so it can't be renamed.")
+            }
+            RenameError::InvalidName {
+                name,
+                expected: NameKind::Lowercase,
+            } => format!("`{name}` is not a valid name, it must be written in snake_case."),
+            RenameError::InvalidName {
+                name,
+                expected: NameKind::Uppercase,
+            } => format!("`{name}` is not a valid name, it must be written in PascalCase."),
+            RenameError::NameTaken { name, module } => {
+                format!("The name `{name}` is already in use in the `{module}` module.")
+            }
+        };
+        Diagnostic {
+            title: "Cannot rename".into(),
+            text,
+            level: Level::Error,
+            location: None,
+            hint: None,
+        }
+    }
+}
+
+/// The location of the name at a position that can be renamed.
+pub fn renameable_location(
+    modules: &HashMap<EcoString, Module>,
+    module: &Module,
+    byte_index: u32,
+) -> Result<SrcSpan, RenameError> {
+    let references = module_references(module);
+    let reference = reference_at(&references, byte_index).ok_or(RenameError::NothingToRename)?;
+    if !modules.contains_key(reference.referent.module()) {
+        return Err(RenameError::DefinedInDependency {
+            name: reference.name.clone(),
+        });
+    }
+    Ok(reference.location)
+}
+
+/// The edits that rename the name at a position in a module, along with
+/// every reference to what it names in the other modules of the package.
+pub fn rename(
+    modules: &HashMap<EcoString, Module>,
+    module: &Module,
+    byte_index: u32,
+    new_name: &str,
+) -> Result<WorkspaceEdit, RenameError> {
+    let references = module_references(module);
+    let reference = reference_at(&references, byte_index).ok_or(RenameError::NothingToRename)?;
+    let referent = reference.referent.clone();
+    let old_name = match &referent {
+        Referent::Value { name, .. } | Referent::Type { name, .. } => name.clone(),
+        Referent::LocalVariable { .. } => reference.name.clone(),
+    };
+    let Some(defining_module) = modules.get(referent.module()) else {
+        return Err(RenameError::DefinedInDependency {
+            name: reference.name.clone(),
+        });
+    };
+
+    check_name(&referent, &old_name, new_name)?;
+
+    // Local variables can only be referred to from the module they're
+    // defined in.
+    let package_references: Vec<(&Module, Vec<Reference>)> = match &referent {
+        Referent::LocalVariable { .. } => vec![(module, references.clone())],
+        Referent::Value { .. } | Referent::Type { .. } => modules
+            .values()
+            .map(|module| (module, module_references(module)))
+            .collect(),
+    };
+
+    check_name_is_free(
+        &referent,
+        reference,
+        defining_module,
+        &package_references,
+        new_name,
+    )?;
+
+    let mut changes = HashMap::new();
+    for (module, references) in &package_references {
+        let line_numbers = LineNumbers::new(&module.code);
+        let edits: Vec<TextEdit> = references
+            .iter()
+            // A name imported with an alias is used with the alias, which
+            // stays as it is.
+            .filter(|reference| reference.referent == referent && reference.name == old_name)
+            .map(|reference| rename_edit(module, reference, new_name, &line_numbers))
+            .collect();
+        if edits.is_empty() {
+            continue;
+        }
+        let uri = path_to_uri(module.input_path.clone());
+        let _ = changes.insert(uri, edits);
+    }
+
+    Ok(WorkspaceEdit {
+        changes: Some(changes),
+        document_changes: None,
+        change_annotations: None,
+    })
+}
+
+fn rename_edit(
+    module: &Module,
+    reference: &Reference,
+    new_name: &str,
+    line_numbers: &LineNumbers,
+) -> TextEdit {
+    match reference.kind {
+        // `wibble(wobble:)` becomes `wibble(wobble: new_name)`, keeping the
+        // label as it is.
+        ReferenceKind::LabelShorthand => {
+            let end = reference.location.end;
+            let colon = module
+                .code
+                .get(end as usize..)
+                .and_then(|rest| rest.find(':'))
+                .map_or(end, |offset| end + offset as u32 + 1);
+            TextEdit {
+                range: src_span_to_lsp_range(SrcSpan::new(colon, colon), line_numbers),
+                new_text: format!(" {new_name}"),
+            }
+        }
+        ReferenceKind::Definition | ReferenceKind::Import | ReferenceKind::Usage => TextEdit {
+            range: src_span_to_lsp_range(reference.location, line_numbers),
+            new_text: new_name.into(),
+        },
+    }
+}
+
+/// Checks the new name is the right kind of name for what's being renamed.
+fn check_name(referent: &Referent, old_name: &str, new_name: &str) -> Result<(), RenameError> {
+    let expected = match referent {
+        Referent::Type { .. } => NameKind::Uppercase,
+        Referent::LocalVariable { .. } => NameKind::Lowercase,
+        Referent::Value { .. } if old_name.starts_with(|char: char| char.is_uppercase()) => {
+            NameKind::Uppercase
+        }
+        Referent::Value { .. } => NameKind::Lowercase,
+    };
+    let mut tokens = lexer::make_tokenizer(new_name)
+        .filter(|token| !matches!(token, Ok((_, Token::NewLine | Token::EndOfFile, _))));
+    let is_valid = match (tokens.next(), tokens.next(), expected) {
+        (Some(Ok((_, Token::Name { name }, _))), None, NameKind::Lowercase)
+        | (Some(Ok((_, Token::UpName { name }, _))), None, NameKind::Uppercase) => name == new_name,
+        _ => false,
+    };
+    if is_valid {
+        Ok(())
+    } else {
+        Err(RenameError::InvalidName {
+            name: new_name.into(),
+            expected,
+        })
+    }
+}
+
+/// Checks the new name doesn't clash with another name in any of the places
+/// it would be used.
+fn check_name_is_free(
+    referent: &Referent,
+    reference: &Reference,
+    defining_module: &Module,
+    package_references: &[(&Module, Vec<Reference>)],
+    new_name: &str,
+) -> Result<(), RenameError> {
+    let taken = |module: &Module| RenameError::NameTaken {
+        name: new_name.into(),
+        module: module.name.clone(),
+    };
+    match referent {
+        // The new name mustn't be used for anything else in the definition
+        // the variable is in, or the variable would shadow it or be
+        // shadowed by it.
+        Referent::LocalVariable { .. } => {
+            let (module, references) = package_references.first().expect("module references");
+            let is_taken = references.iter().any(|other| {
+                other.scope == reference.scope
+                    && other.name == new_name
+                    && other.referent != *referent
+            });
+            if is_taken {
+                return Err(taken(module));
+            }
+        }
+
+        Referent::Value { name, .. } | Referent::Type { name, .. } => {
+            let is_type = matches!(referent, Referent::Type { .. });
+            let in_scope = |module: &Module| {
+                if is_type {
+                    type_in_scope(module, new_name)
+                } else {
+                    value_in_scope(module, new_name)
+                }
+            };
+            if in_scope(defining_module) {
+                return Err(taken(defining_module));
+            }
+            // Modules that import the name unqualified use it by its name,
+            // so the new name must be free in them too.
+            for (module, references) in package_references {
+                let imports_it = references.iter().any(|other| {
+                    other.kind == ReferenceKind::Import
+                        && other.referent == *referent
+                        && imported_without_alias(module, name)
+                });
+                if imports_it && in_scope(module) {
+                    return Err(taken(module));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn imported_without_alias(module: &Module, name: &str) -> bool {
+    module
+        .ast
+        .definitions
+        .iter()
+        .any(|definition| match definition {
+            Definition::Import(import) => import
+                .unqualified_values
+                .iter()
+                .chain(&import.unqualified_types)
+                .any(|imported| imported.name == name && imported.as_name.is_none()),
+            _ => false,
+        })
+}
+
+/// Whether a value by the given name is defined or imported unqualified in a
+/// module.
+fn value_in_scope(module: &Module, name: &str) -> bool {
+    module
+        .ast
+        .definitions
+        .iter()
+        .any(|definition| match definition {
+            Definition::Function(function) => function
+                .name
+                .as_ref()
+                .is_some_and(|(_, function_name)| function_name == name),
+            Definition::ModuleConstant(constant) => constant.name == name,
+            Definition::CustomType(custom_type) => custom_type
+                .constructors
+                .iter()
+                .any(|constructor| constructor.name == name),
+            Definition::Import(import) => import
+                .unqualified_values
+                .iter()
+                .any(|imported| imported.used_name() == name),
+            Definition::TypeAlias(_) => false,
+        })
+}
+
+/// Whether a type by the given name is defined or imported unqualified in a
+/// module.
+fn type_in_scope(module: &Module, name: &str) -> bool {
+    module
+        .ast
+        .definitions
+        .iter()
+        .any(|definition| match definition {
+            Definition::CustomType(custom_type) => custom_type.name == name,
+            Definition::TypeAlias(alias) => alias.alias == name,
+            Definition::Import(import) => import
+                .unqualified_types
+                .iter()
+                .any(|imported| imported.used_name() == name),
+            Definition::Function(_) | Definition::ModuleConstant(_) => false,
+        })
+}
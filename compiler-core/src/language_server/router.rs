@@ -16,7 +16,7 @@ use std::{
 
 use camino::{Utf8Path, Utf8PathBuf};
 
-use super::feedback::FeedbackBookKeeper;
+use super::feedback::{Feedback, FeedbackBookKeeper};
 
 /// The language server instance serves a language client, typically a text
 /// editor. The editor could have multiple Gleam projects open at once, so run
@@ -53,6 +53,11 @@ where
         }
     }
 
+    /// The root directories of the projects with a running engine.
+    pub fn project_paths(&self) -> Vec<Utf8PathBuf> {
+        self.engines.keys().cloned().collect()
+    }
+
     pub fn project_path(&self, path: &Utf8Path) -> Option<Utf8PathBuf> {
         find_gleam_project_parent(&self.io, path)
     }
@@ -120,6 +125,28 @@ where
         }
     }
 
+    /// The root directory of the project a folder open in the editor is, or
+    /// is part of. Projects nested inside of the folder aren't searched for,
+    /// as a folder such as a monorepo can hold a great many of them. Each is
+    /// served once one of its modules is opened instead.
+    pub fn project_of_directory(&self, directory: &Utf8Path) -> Option<Utf8PathBuf> {
+        find_gleam_project_parent(&self.io, directory)
+    }
+
+    /// Stops the engines of the projects inside a directory, such as a folder
+    /// that has been closed in the editor, erasing their diagnostics.
+    pub fn delete_engines_in_directory(&mut self, directory: &Utf8Path) -> Feedback {
+        let mut feedback = Feedback::none();
+        self.engines.retain(|path, project| {
+            let in_directory = path.starts_with(directory);
+            if in_directory {
+                feedback.append_feedback(project.feedback.unset_all());
+            }
+            !in_directory
+        });
+        feedback
+    }
+
     fn new_project(
         path: Utf8PathBuf,
         io: FileSystemProxy<IO>,
@@ -156,6 +183,12 @@ fn find_gleam_project_parent<IO>(io: &IO, path: &Utf8Path) -> Option<Utf8PathBuf
 where
     IO: FileSystemReader,
 {
+    // Documents that haven't been saved yet are given a relative path, and
+    // they're not part of a project.
+    if path.is_relative() {
+        return None;
+    }
+
     let is_module = path.extension().map(|x| x == "gleam").unwrap_or(false);
     let mut directory = path.to_path_buf();
 
@@ -262,6 +295,16 @@ mod find_gleam_project_parent_tests {
         );
     }
 
+    #[test]
+    fn unsaved_document() {
+        let io = InMemoryFileSystem::new();
+        io.write(Utf8Path::new("gleam.toml"), "").unwrap();
+        assert_eq!(
+            find_gleam_project_parent(&io, Utf8Path::new("Untitled-1")),
+            None
+        );
+    }
+
     #[test]
     fn nested_projects() {
         let io = InMemoryFileSystem::new();
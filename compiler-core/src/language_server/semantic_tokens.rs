@@ -0,0 +1,333 @@
+//! Semantic tokens, which tell the editor what each name in a module refers
+//! to so it can be coloured more accurately than with a grammar alone.
+
+use ecow::EcoString;
+use lsp_types::{SemanticToken, SemanticTokenModifier, SemanticTokenType, SemanticTokensLegend};
+
+use crate::{
+    ast::{AssignName, Definition, SrcSpan},
+    build::Module,
+    line_numbers::LineNumbers,
+    parse::{lexer, token::Token},
+    type_::{self, ModuleInterface, ValueConstructorVariant},
+};
+
+use super::reference::{module_references, Reference, ReferenceKind, Referent};
+
+/// The token types used, in the order they're given to the editor in the
+/// legend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TokenType {
+    Namespace,
+    Type,
+    Constructor,
+    Function,
+    Variable,
+}
+
+impl TokenType {
+    const ALL: [TokenType; 5] = [
+        TokenType::Namespace,
+        TokenType::Type,
+        TokenType::Constructor,
+        TokenType::Function,
+        TokenType::Variable,
+    ];
+
+    fn lsp_type(self) -> SemanticTokenType {
+        match self {
+            TokenType::Namespace => SemanticTokenType::NAMESPACE,
+            TokenType::Type => SemanticTokenType::TYPE,
+            TokenType::Constructor => SemanticTokenType::ENUM_MEMBER,
+            TokenType::Function => SemanticTokenType::FUNCTION,
+            TokenType::Variable => SemanticTokenType::VARIABLE,
+        }
+    }
+}
+
+/// The token modifiers used, each being a bit of the modifiers of a token
+/// in the order they're given to the editor in the legend.
+const DECLARATION: u32 = 1 << 0;
+const READONLY: u32 = 1 << 1;
+const DEPRECATED: u32 = 1 << 2;
+const UNUSED: u32 = 1 << 3;
+
+pub fn legend() -> SemanticTokensLegend {
+    SemanticTokensLegend {
+        token_types: TokenType::ALL
+            .iter()
+            .map(|type_| type_.lsp_type())
+            .collect(),
+        token_modifiers: vec![
+            SemanticTokenModifier::DECLARATION,
+            SemanticTokenModifier::READONLY,
+            SemanticTokenModifier::DEPRECATED,
+            // Not one of the standard modifiers, but editors can be configured
+            // to show unused names differently with it.
+            SemanticTokenModifier::new("unused"),
+        ],
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct NameToken {
+    location: SrcSpan,
+    type_: TokenType,
+    modifiers: u32,
+}
+
+/// The semantic tokens of a module, encoded relative to each other as the
+/// protocol requires.
+pub fn semantic_tokens<'a>(
+    module: &'a Module,
+    module_interface: impl Fn(&str) -> Option<&'a ModuleInterface>,
+) -> Vec<SemanticToken> {
+    let interface = |name: &str| {
+        if name == module.name {
+            Some(&module.ast.type_info)
+        } else {
+            module_interface(name)
+        }
+    };
+    let unused = UnusedLocations::new(module);
+
+    let mut tokens: Vec<NameToken> = module_references(module)
+        .iter()
+        .map(|reference| {
+            let (type_, mut modifiers) = classify(reference, &interface);
+            if reference.is_definition() {
+                modifiers |= DECLARATION;
+            }
+            if unused.contains(reference) {
+                modifiers |= UNUSED;
+            }
+            NameToken {
+                location: reference.location,
+                type_,
+                modifiers,
+            }
+        })
+        .collect();
+    tokens.extend(module_names(module, &tokens, &unused));
+
+    tokens.sort_by_key(|token| token.location.start);
+    // Tokens can't overlap, so a name found twice is only given once.
+    tokens.dedup_by(|token, previous| token.location.start < previous.location.end);
+
+    encode(&tokens, &LineNumbers::new(&module.code))
+}
+
+fn classify<'a>(
+    reference: &Reference,
+    interface: &impl Fn(&str) -> Option<&'a ModuleInterface>,
+) -> (TokenType, u32) {
+    let deprecated = |deprecation: &type_::Deprecation| {
+        if deprecation.is_deprecated() {
+            DEPRECATED
+        } else {
+            0
+        }
+    };
+    match &reference.referent {
+        Referent::LocalVariable { .. } => (TokenType::Variable, 0),
+
+        Referent::Type { module, name } => {
+            let modifiers = interface(module)
+                .and_then(|interface| interface.types.get(name))
+                .map_or(0, |type_| deprecated(&type_.deprecation));
+            (TokenType::Type, modifiers)
+        }
+
+        Referent::Value { module, name } => {
+            match interface(module).and_then(|interface| interface.values.get(name)) {
+                Some(value) => {
+                    let modifiers = deprecated(&value.deprecation);
+                    match &value.variant {
+                        ValueConstructorVariant::Record { .. } => {
+                            (TokenType::Constructor, modifiers)
+                        }
+                        ValueConstructorVariant::ModuleConstant { .. }
+                        | ValueConstructorVariant::LocalConstant { .. } => {
+                            (TokenType::Variable, modifiers | READONLY)
+                        }
+                        ValueConstructorVariant::ModuleFn { .. }
+                        | ValueConstructorVariant::LocalVariable { .. } => {
+                            (TokenType::Function, modifiers)
+                        }
+                    }
+                }
+                // Only record constructors have uppercase names.
+                None if name.starts_with(|char: char| char.is_uppercase()) => {
+                    (TokenType::Constructor, 0)
+                }
+                None => (TokenType::Function, 0),
+            }
+        }
+    }
+}
+
+/// The names of the imported modules, both in the imports themselves and
+/// where they're used to qualify a name, as in `wibble.wobble`.
+fn module_names(
+    module: &Module,
+    references: &[NameToken],
+    unused: &UnusedLocations,
+) -> Vec<NameToken> {
+    let mut tokens = vec![];
+    let mut aliases: Vec<EcoString> = vec![];
+
+    for definition in &module.ast.definitions {
+        let Definition::Import(import) = definition else {
+            continue;
+        };
+        let modifiers = if unused.contains_import(import.location) {
+            UNUSED
+        } else {
+            0
+        };
+        let import_code = module
+            .code
+            .get(import.location.start as usize..import.location.end as usize)
+            .unwrap_or_default();
+        if let Some(offset) = import_code.find(import.module.as_str()) {
+            let start = import.location.start + offset as u32;
+            tokens.push(NameToken {
+                location: SrcSpan::new(start, start + import.module.len() as u32),
+                type_: TokenType::Namespace,
+                modifiers: modifiers | DECLARATION,
+            });
+        }
+        // The location of an alias includes the `as` before it.
+        if let Some((AssignName::Variable(alias), location)) = &import.as_name {
+            tokens.push(NameToken {
+                location: SrcSpan::new(location.end - alias.len() as u32, location.end),
+                type_: TokenType::Namespace,
+                modifiers: modifiers | DECLARATION,
+            });
+        }
+        aliases.extend(import.used_name());
+    }
+
+    // A name followed by a dot is a module, unless it's a variable whose
+    // field is being accessed.
+    let is_reference = |location: SrcSpan| {
+        references
+            .iter()
+            .any(|reference| reference.location.start == location.start)
+    };
+    let mut tokens_in_code = lexer::make_tokenizer(&module.code)
+        .filter_map(Result::ok)
+        .peekable();
+    while let Some((start, token, end)) = tokens_in_code.next() {
+        let Token::Name { name } = token else {
+            continue;
+        };
+        let location = SrcSpan::new(start, end);
+        let is_qualifier = matches!(tokens_in_code.peek(), Some((_, Token::Dot, _)));
+        if is_qualifier && aliases.contains(&name) && !is_reference(location) {
+            tokens.push(NameToken {
+                location,
+                type_: TokenType::Namespace,
+                modifiers: 0,
+            });
+        }
+    }
+
+    tokens
+}
+
+/// Where the compiler warned about something that is never used.
+struct UnusedLocations {
+    variables: Vec<SrcSpan>,
+    definitions: Vec<SrcSpan>,
+    imports: Vec<SrcSpan>,
+}
+
+impl UnusedLocations {
+    fn new(module: &Module) -> Self {
+        let mut unused = UnusedLocations {
+            variables: vec![],
+            definitions: vec![],
+            imports: vec![],
+        };
+        for warning in &module.ast.type_info.warnings {
+            match warning {
+                type_::Warning::UnusedVariable { location, .. } => unused.variables.push(*location),
+                type_::Warning::UnusedType { location, .. }
+                | type_::Warning::UnusedConstructor { location, .. }
+                | type_::Warning::UnusedImportedValue { location, .. }
+                | type_::Warning::UnusedPrivateModuleConstant { location, .. }
+                | type_::Warning::UnusedPrivateFunction { location, .. } => {
+                    unused.definitions.push(*location)
+                }
+                type_::Warning::UnusedImportedModule { location, .. }
+                | type_::Warning::UnusedImportedModuleAlias { location, .. } => {
+                    unused.imports.push(*location)
+                }
+                _ => (),
+            }
+        }
+        unused
+    }
+
+    /// Whether a reference is where something that is never used is defined
+    /// or imported.
+    fn contains(&self, reference: &Reference) -> bool {
+        let within = |locations: &[SrcSpan]| {
+            locations.iter().any(|location| {
+                location.start <= reference.location.start && reference.location.end <= location.end
+            })
+        };
+        match reference.referent {
+            Referent::LocalVariable { .. } => reference.is_definition() && within(&self.variables),
+            // The location of the warning for a function is its whole head,
+            // so only the definition of the function itself is checked, not
+            // those of its arguments.
+            Referent::Value { .. } | Referent::Type { .. } => {
+                matches!(
+                    reference.kind,
+                    ReferenceKind::Definition | ReferenceKind::Import
+                ) && within(&self.definitions)
+            }
+        }
+    }
+
+    fn contains_import(&self, import: SrcSpan) -> bool {
+        self.imports
+            .iter()
+            .any(|location| import.start <= location.start && location.end <= import.end)
+    }
+}
+
+/// Each token is given by its line and start relative to the token before
+/// it, its length, its type and its modifiers.
+fn encode(tokens: &[NameToken], line_numbers: &LineNumbers) -> Vec<SemanticToken> {
+    let mut previous_line = 0;
+    let mut previous_start = 0;
+    tokens
+        .iter()
+        .map(|token| {
+            let position = line_numbers.line_and_column_number(token.location.start);
+            let line = position.line - 1;
+            let start = position.column - 1;
+            let delta_line = line - previous_line;
+            let delta_start = if delta_line == 0 {
+                start - previous_start
+            } else {
+                start
+            };
+            previous_line = line;
+            previous_start = start;
+            SemanticToken {
+                delta_line,
+                delta_start,
+                length: token.location.end - token.location.start,
+                token_type: TokenType::ALL
+                    .iter()
+                    .position(|type_| *type_ == token.type_)
+                    .unwrap_or_default() as u32,
+                token_modifiers_bitset: token.modifiers,
+            }
+        })
+        .collect()
+}
@@ -1,26 +1,32 @@
 use super::{
-    messages::{Message, MessageBuffer, Next, Notification, Request},
+    configuration::Configuration,
+    messages::{Message, MessageBuffer, Next, Notification, Request, CONFIGURATION_REQUEST_ID},
     progress::ConnectionProgressReporter,
 };
 use crate::{
+    ast::SrcSpan,
+    config::FormatConfig,
     diagnostic::{Diagnostic, Level},
     io::{BeamCompiler, CommandExecutor, FileSystemReader, FileSystemWriter},
     language_server::{
         engine::{self, LanguageServerEngine},
         feedback::{Feedback, FeedbackBookKeeper},
         files::FileSystemProxy,
+        path_to_uri,
         router::Router,
-        src_span_to_lsp_range, DownloadDependencies, MakeLocker,
+        semantic_tokens, src_span_to_lsp_range,
+        text_sync::CompiledSources,
+        DownloadDependencies, MakeLocker,
     },
     line_numbers::LineNumbers,
     Result,
 };
 use camino::{Utf8Path, Utf8PathBuf};
 use debug_ignore::DebugIgnore;
+use ecow::EcoString;
 use itertools::Itertools;
 use lsp_types::{
-    self as lsp, HoverProviderCapability, InitializeParams, Position, PublishDiagnosticsParams,
-    Range, TextEdit, Url,
+    self as lsp, HoverProviderCapability, InitializeParams, PublishDiagnosticsParams, TextEdit,
 };
 use serde_json::Value as Json;
 use std::collections::{HashMap, HashSet};
@@ -29,6 +35,7 @@ use std::collections::{HashMap, HashSet};
 /// delegating the work to the engine.
 ///
 /// - Configuring watching of the `gleam.toml` file.
+/// - Keeping track of the language server configuration set in the editor.
 /// - Decoding requests.
 /// - Encoding responses.
 /// - Sending diagnostics and messages to the client.
@@ -42,7 +49,10 @@ pub struct LanguageServer<'a, IO> {
     outside_of_project_feedback: FeedbackBookKeeper,
     router: Router<IO, ConnectionProgressReporter<'a>>,
     changed_projects: HashSet<Utf8PathBuf>,
+    compiled_sources: CompiledSources,
     io: FileSystemProxy<IO>,
+    configuration: Configuration,
+    workspace_folders: Vec<Utf8PathBuf>,
 }
 
 impl<'a, IO> LanguageServer<'a, IO>
@@ -60,18 +70,25 @@ where
         let reporter = ConnectionProgressReporter::new(connection, &initialise_params);
         let io = FileSystemProxy::new(io);
         let router = Router::new(reporter, io.clone());
+        let workspace_folders = workspace_folders(&initialise_params);
         Ok(Self {
             connection: connection.into(),
             initialise_params,
             changed_projects: HashSet::new(),
+            compiled_sources: CompiledSources::default(),
             outside_of_project_feedback: FeedbackBookKeeper::default(),
             router,
             io,
+            configuration: Configuration::default(),
+            workspace_folders,
         })
     }
 
     pub fn run(&mut self) -> Result<()> {
-        self.start_watching_gleam_toml();
+        self.start_watching_project_files();
+        self.start_watching_configuration();
+        self.request_configuration();
+        self.queue_workspace_projects();
         let mut buffer = MessageBuffer::new();
 
         loop {
@@ -99,12 +116,19 @@ where
     fn handle_request(&mut self, id: lsp_server::RequestId, request: Request) {
         let (payload, feedback) = match request {
             Request::Format(param) => self.format(param),
+            Request::FormatRange(param) => self.format_range(param),
             Request::Hover(param) => self.hover(param),
             Request::GoToDefinition(param) => self.goto_definition(param),
             Request::Completion(param) => self.completion(param),
             Request::CodeAction(param) => self.code_action(param),
             Request::SignatureHelp(param) => self.signature_help(param),
             Request::DocumentSymbol(param) => self.document_symbol(param),
+            Request::PrepareRename(param) => self.prepare_rename(param),
+            Request::Rename(param) => self.rename(param),
+            Request::FindReferences(param) => self.find_references(param),
+            Request::WorkspaceSymbol(param) => self.workspace_symbol(param),
+            Request::InlayHint(param) => self.inlay_hint(param),
+            Request::SemanticTokens(param) => self.semantic_tokens(param),
         };
 
         self.publish_feedback(feedback);
@@ -127,7 +151,17 @@ where
             Notification::SourceFileChangedInMemory { path, text } => {
                 self.cache_file_in_memory(path, text)
             }
+            Notification::SourceFileEditedInMemory { path, changes } => {
+                self.edit_file_in_memory(path, changes)
+            }
             Notification::ConfigFileChanged { path } => self.watched_files_changed(path),
+            Notification::SourceFilesChangedOnDisc { paths } => self.source_files_changed(paths),
+            Notification::ConfigurationChanged { configuration } => {
+                self.configuration_changed(configuration)
+            }
+            Notification::WorkspaceFoldersChanged { added, removed } => {
+                self.workspace_folders_changed(added, removed)
+            }
         };
         self.publish_feedback(feedback);
     }
@@ -163,7 +197,7 @@ where
         }
     }
 
-    fn start_watching_gleam_toml(&mut self) {
+    fn start_watching_project_files(&mut self) {
         let supports_watch_files = self
             .initialise_params
             .capabilities
@@ -174,21 +208,33 @@ where
             .unwrap_or(false);
 
         if !supports_watch_files {
-            tracing::warn!("lsp_client_cannot_watch_gleam_toml");
+            tracing::warn!("lsp_client_cannot_watch_project_files");
             return;
         }
 
         // Register gleam.toml as a watched file so we get a notification when
         // it changes and thus know that we need to rebuild the entire project.
+        // Gleam modules are watched too, so that changes made outside of the
+        // editor are compiled and any new errors are shown.
         let watch_config = lsp::Registration {
-            id: "watch-gleam-toml".into(),
+            id: "watch-project-files".into(),
             method: "workspace/didChangeWatchedFiles".into(),
             register_options: Some(
                 serde_json::value::to_value(lsp::DidChangeWatchedFilesRegistrationOptions {
-                    watchers: vec![lsp::FileSystemWatcher {
-                        glob_pattern: "**/gleam.toml".to_string().into(),
-                        kind: Some(lsp::WatchKind::Change),
-                    }],
+                    watchers: vec![
+                        lsp::FileSystemWatcher {
+                            glob_pattern: "**/gleam.toml".to_string().into(),
+                            kind: Some(lsp::WatchKind::Change),
+                        },
+                        lsp::FileSystemWatcher {
+                            glob_pattern: "**/*.gleam".to_string().into(),
+                            kind: Some(
+                                lsp::WatchKind::Create
+                                    | lsp::WatchKind::Change
+                                    | lsp::WatchKind::Delete,
+                            ),
+                        },
+                    ],
                 })
                 .expect("workspace/didChangeWatchedFiles to json"),
             ),
@@ -207,6 +253,113 @@ where
             .expect("send client/registerCapability");
     }
 
+    fn start_watching_configuration(&mut self) {
+        let supports_watch_configuration = self
+            .initialise_params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|w| w.did_change_configuration)
+            .and_then(|dc| dc.dynamic_registration)
+            .unwrap_or(false);
+
+        if !supports_watch_configuration {
+            return;
+        }
+
+        // Clients only tell us about changes to the configuration if we ask
+        // them to.
+        let watch_configuration = lsp::Registration {
+            id: "watch-configuration".into(),
+            method: "workspace/didChangeConfiguration".into(),
+            register_options: None,
+        };
+        let request = lsp_server::Request {
+            id: 2.into(),
+            method: "client/registerCapability".into(),
+            params: serde_json::value::to_value(lsp::RegistrationParams {
+                registrations: vec![watch_configuration],
+            })
+            .expect("client/registerCapability to json"),
+        };
+        self.connection
+            .sender
+            .send(lsp_server::Message::Request(request))
+            .expect("send client/registerCapability");
+    }
+
+    /// Asks the client for the `gleam` section of its configuration. The
+    /// response is handled as a `ConfigurationChanged` notification.
+    fn request_configuration(&self) {
+        let supports_configuration = self
+            .initialise_params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|w| w.configuration)
+            .unwrap_or(false);
+
+        if !supports_configuration {
+            return;
+        }
+
+        let request = lsp_server::Request {
+            id: CONFIGURATION_REQUEST_ID.to_string().into(),
+            method: "workspace/configuration".into(),
+            params: serde_json::value::to_value(lsp::ConfigurationParams {
+                items: vec![lsp::ConfigurationItem {
+                    scope_uri: None,
+                    section: Some("gleam".into()),
+                }],
+            })
+            .expect("workspace/configuration to json"),
+        };
+        self.connection
+            .sender
+            .send(lsp_server::Message::Request(request))
+            .expect("send workspace/configuration");
+    }
+
+    fn configuration_changed(&mut self, configuration: Option<Configuration>) -> Feedback {
+        let Some(configuration) = configuration else {
+            self.request_configuration();
+            return Feedback::none();
+        };
+        let inlay_hints_changed = configuration.inlay_hints != self.configuration.inlay_hints;
+        self.configuration = configuration;
+        if inlay_hints_changed {
+            self.refresh_inlay_hints();
+        }
+        Feedback::none()
+    }
+
+    /// Asks the client to request the inlay hints again, as the ones it's
+    /// showing are out of date.
+    fn refresh_inlay_hints(&self) {
+        let supports_refresh = self
+            .initialise_params
+            .capabilities
+            .workspace
+            .as_ref()
+            .and_then(|w| w.inlay_hint.as_ref())
+            .and_then(|ih| ih.refresh_support)
+            .unwrap_or(false);
+
+        if !supports_refresh {
+            return;
+        }
+
+        let request = lsp_server::Request {
+            id: "gleam/inlayHintRefresh".to_string().into(),
+            method: "workspace/inlayHint/refresh".into(),
+            params: Json::Null,
+        };
+        self.connection
+            .sender
+            .send(lsp_server::Message::Request(request))
+            .expect("send workspace/inlayHint/refresh");
+    }
+
     fn publish_messages(&self, messages: Vec<Diagnostic>) {
         for message in messages {
             let params = lsp::ShowMessageParams {
@@ -281,21 +434,77 @@ where
             Err(error) => return self.path_error_response(path, error),
         };
 
-        if let Err(error) = crate::format::pretty(&mut new_text, &src, &path) {
-            return self.path_error_response(path, error);
-        }
+        let config = self.format_config(&path);
+
+        // A module with syntax errors is still formatted around them, so that
+        // formatting on save works while it's being edited.
+        let errors = match crate::format::pretty_tolerant(&mut new_text, &src, &path, &config) {
+            Ok(errors) => errors,
+            Err(error) => return self.path_error_response(path, error),
+        };
+        let feedback = match errors.into_iter().next() {
+            Some(error) => self.path_error_response(path, error).1,
+            None => Feedback::default(),
+        };
 
-        let line_count = src.lines().count() as u32;
+        let line_numbers = LineNumbers::new(&src);
+        let edits = crate::format::edits(&src, &new_text)
+            .into_iter()
+            .map(|edit| TextEdit {
+                range: src_span_to_lsp_range(edit.location, &line_numbers),
+                new_text: edit.replacement,
+            })
+            .collect_vec();
+        let json = serde_json::to_value(edits).expect("to JSON value");
+
+        (json, feedback)
+    }
 
-        let edit = TextEdit {
-            range: Range::new(Position::new(0, 0), Position::new(line_count, 0)),
-            new_text,
+    fn format_range(&mut self, params: lsp::DocumentRangeFormattingParams) -> (Json, Feedback) {
+        let path = super::path(&params.text_document.uri);
+
+        let src: EcoString = match self.io.read(&path) {
+            Ok(src) => src.into(),
+            Err(error) => return self.path_error_response(path, error),
         };
-        let json = serde_json::to_value(vec![edit]).expect("to JSON value");
+        let config = self.format_config(&path);
+
+        let line_numbers = LineNumbers::new(&src);
+        let start = line_numbers.byte_index(params.range.start.line, params.range.start.character);
+        let end = line_numbers.byte_index(params.range.end.line, params.range.end.character);
+        let edits =
+            match crate::format::pretty_range(&src, &path, SrcSpan::new(start, end), &config) {
+                Ok(edits) => edits,
+                Err(error) => return self.path_error_response(path, error),
+            };
+
+        let edits = edits
+            .into_iter()
+            .map(|edit| TextEdit {
+                range: src_span_to_lsp_range(edit.location, &line_numbers),
+                new_text: edit.replacement,
+            })
+            .collect_vec();
+        let json = serde_json::to_value(edits).expect("to JSON value");
 
         (json, Feedback::default())
     }
 
+    /// The formatting options of the project a module is in, or the default
+    /// ones if it's not in a project.
+    fn format_config(&mut self, path: &Utf8Path) -> FormatConfig {
+        match self.router.project_for_path(path.to_path_buf()) {
+            Ok(Some(project)) => project
+                .engine
+                .compiler
+                .project_compiler
+                .config
+                .format
+                .clone(),
+            Ok(None) | Err(_) => FormatConfig::default(),
+        }
+    }
+
     fn hover(&mut self, params: lsp::HoverParams) -> (Json, Feedback) {
         let path = super::path(&params.text_document_position_params.text_document.uri);
         self.respond_with_engine(path, |engine| engine.hover(params))
@@ -333,30 +542,187 @@ where
         self.respond_with_engine(path, |engine| engine.document_symbol(params))
     }
 
+    fn inlay_hint(&mut self, params: lsp::InlayHintParams) -> (Json, Feedback) {
+        let path = super::path(&params.text_document.uri);
+        let config = self.configuration.inlay_hints;
+        self.respond_with_engine(path, |engine| engine.inlay_hints(params, config))
+    }
+
+    fn semantic_tokens(&mut self, params: lsp::SemanticTokensParams) -> (Json, Feedback) {
+        let path = super::path(&params.text_document.uri);
+        self.respond_with_engine(path, |engine| engine.semantic_tokens(params))
+    }
+
+    fn prepare_rename(&mut self, params: lsp::TextDocumentPositionParams) -> (Json, Feedback) {
+        let path = super::path(&params.text_document.uri);
+        self.respond_with_engine(path, |engine| engine.prepare_rename(params))
+    }
+
+    fn find_references(&mut self, params: lsp::ReferenceParams) -> (Json, Feedback) {
+        let path = super::path(&params.text_document_position.text_document.uri);
+        self.respond_with_engine(path, |engine| engine.find_references(params))
+    }
+
+    fn workspace_symbol(&mut self, params: lsp::WorkspaceSymbolParams) -> (Json, Feedback) {
+        let mut feedback = Feedback::none();
+
+        // The projects open in the editor are compiled so they can be
+        // searched before any of their modules have been opened.
+        for project in self.workspace_projects() {
+            if !self.router.project_paths().contains(&project) {
+                let (_, compiled) =
+                    self.respond_with_engine(project, |engine| engine.compile_please());
+                feedback.append_feedback(compiled);
+            }
+        }
+
+        let mut symbols = vec![];
+        for project in self.router.project_paths() {
+            let (json, project_feedback) =
+                self.respond_with_engine(project, |engine| engine.workspace_symbol(&params));
+            feedback.append_feedback(project_feedback);
+            if let Json::Array(found) = json {
+                symbols.extend(found);
+            }
+        }
+        (Json::Array(symbols), feedback)
+    }
+
+    /// The root directories of the projects the folders open in the editor
+    /// are, or are part of.
+    fn workspace_projects(&self) -> Vec<Utf8PathBuf> {
+        self.workspace_folders
+            .iter()
+            .filter_map(|folder| self.router.project_of_directory(folder))
+            .unique()
+            .collect()
+    }
+
+    fn workspace_folders_changed(
+        &mut self,
+        added: Vec<Utf8PathBuf>,
+        removed: Vec<Utf8PathBuf>,
+    ) -> Feedback {
+        let mut feedback = Feedback::none();
+        for folder in removed {
+            self.workspace_folders.retain(|open| *open != folder);
+            feedback.append_feedback(self.router.delete_engines_in_directory(&folder));
+        }
+        for folder in added {
+            if !self.workspace_folders.contains(&folder) {
+                self.workspace_folders.push(folder);
+            }
+        }
+
+        // The projects of the new folders, and those that are still open in
+        // another folder after one containing them was closed, are compiled.
+        let served_projects = self.router.project_paths();
+        for project in self.workspace_projects() {
+            if !served_projects.contains(&project) {
+                _ = self.changed_projects.insert(project);
+            }
+        }
+        feedback
+    }
+
+    fn rename(&mut self, params: lsp::RenameParams) -> (Json, Feedback) {
+        let path = super::path(&params.text_document_position.text_document.uri);
+        let mut refusal = None;
+        let (json, mut feedback) = self.respond_with_engine(path, |engine| {
+            engine.rename(params).map(|result| {
+                result
+                    .map_err(|error| refusal = Some(error.to_diagnostic()))
+                    .ok()
+            })
+        });
+        // The reason a rename can't be done is shown to the programmer, as
+        // otherwise it would look like nothing has happened.
+        if let Some(message) = refusal {
+            feedback.append_message(message);
+        }
+        (json, feedback)
+    }
+
+    fn edit_file_in_memory(
+        &mut self,
+        path: Utf8PathBuf,
+        changes: Vec<lsp::TextDocumentContentChangeEvent>,
+    ) -> Feedback {
+        // The editor's copy of the file is either cached in memory or, if it
+        // has no unsaved changes, the same as the one on disc.
+        let text = match self.io.read(&path) {
+            Ok(text) => text,
+            Err(error) => return self.outside_of_project_feedback.error(error),
+        };
+        let edited = super::text_sync::apply_changes(&text, changes);
+        // Changes that leave the text as it was, such as typing a character
+        // and then deleting it, don't need the project to be compiled again.
+        if edited == text {
+            return Feedback::none();
+        }
+        self.cache_file_in_memory(path, edited)
+    }
+
     fn cache_file_in_memory(&mut self, path: Utf8PathBuf, text: String) -> Feedback {
-        self.project_changed(&path);
         if let Err(error) = self.io.write_mem_cache(&path, &text) {
+            self.project_changed(&path);
             return self.outside_of_project_feedback.error(error);
         }
+        self.module_changed(path, &text);
         Feedback::none()
     }
 
     fn discard_in_memory_cache(&mut self, path: Utf8PathBuf) -> Feedback {
-        self.project_changed(&path);
         if let Err(error) = self.io.delete_mem_cache(&path) {
+            self.project_changed(&path);
             return self.outside_of_project_feedback.error(error);
         }
+        // Once saved the module on disc is usually the same as the one that
+        // was compiled, in which case there's nothing to compile.
+        match self.io.read(&path) {
+            Ok(text) => self.module_changed(path, &text),
+            Err(_) => self.project_changed(&path),
+        }
         Feedback::none()
     }
 
     fn watched_files_changed(&mut self, path: Utf8PathBuf) -> Feedback {
+        if let Some(project) = path.parent() {
+            self.compiled_sources.forget_project(project);
+        }
         self.router.delete_engine_for_path(&path);
         Feedback::none()
     }
 
+    fn source_files_changed(&mut self, paths: Vec<Utf8PathBuf>) -> Feedback {
+        // Only the projects already being served are recompiled, as modules of
+        // dependencies in the build directory change when they're downloaded.
+        let served_projects = self.router.project_paths();
+        for path in paths {
+            if let Some(project) = self.router.project_path(&path) {
+                if served_projects.contains(&project) {
+                    _ = self.changed_projects.insert(project);
+                }
+            }
+        }
+        Feedback::none()
+    }
+
+    /// Queues the projects open in the editor to be compiled so that the
+    /// errors and warnings of all their modules are shown, not only those of
+    /// the modules that have been opened. They are compiled with the next
+    /// `CompilePlease`, so the server doesn't wait for them before handling
+    /// the first messages.
+    fn queue_workspace_projects(&mut self) {
+        for project in self.workspace_projects() {
+            _ = self.changed_projects.insert(project);
+        }
+    }
+
     fn compile_please(&mut self) -> Feedback {
         let mut accumulator = Feedback::none();
-        let projects = std::mem::take(&mut self.changed_projects);
+        let mut projects = std::mem::take(&mut self.changed_projects);
+        projects.extend(self.compiled_sources.take_changed_projects());
         for path in projects {
             let (_, feedback) = self.respond_with_engine(path, |e| e.compile_please());
             accumulator.append_feedback(feedback);
@@ -364,6 +730,14 @@ where
         accumulator
     }
 
+    /// Records the new text of a module, so its project is compiled again if
+    /// the text differs from the one that was last compiled.
+    fn module_changed(&mut self, path: Utf8PathBuf, text: &str) {
+        if let Some(project) = self.router.project_path(&path) {
+            self.compiled_sources.module_changed(path, project, text);
+        }
+    }
+
     fn project_changed(&mut self, path: &Utf8Path) {
         let project_path = self.router.project_path(path);
         if let Some(project_path) = project_path {
@@ -372,12 +746,28 @@ where
     }
 }
 
+/// The root directories of the folders open in the editor when it started.
+fn workspace_folders(initialise_params: &InitializeParams) -> Vec<Utf8PathBuf> {
+    #[allow(deprecated)]
+    let root_uri = initialise_params.root_uri.iter();
+    initialise_params
+        .workspace_folders
+        .iter()
+        .flatten()
+        .map(|folder| &folder.uri)
+        .chain(root_uri)
+        .filter(|uri| uri.scheme() == "file")
+        .map(super::path)
+        .unique()
+        .collect()
+}
+
 fn initialisation_handshake(connection: &lsp_server::Connection) -> InitializeParams {
     let server_capabilities = lsp::ServerCapabilities {
         text_document_sync: Some(lsp::TextDocumentSyncCapability::Options(
             lsp::TextDocumentSyncOptions {
                 open_close: Some(true),
-                change: Some(lsp::TextDocumentSyncKind::FULL),
+                change: Some(lsp::TextDocumentSyncKind::INCREMENTAL),
                 will_save: None,
                 will_save_wait_until: None,
                 save: Some(lsp::TextDocumentSyncSaveOptions::SaveOptions(
@@ -408,30 +798,52 @@ fn initialisation_handshake(connection: &lsp_server::Connection) -> InitializePa
         definition_provider: Some(lsp::OneOf::Left(true)),
         type_definition_provider: None,
         implementation_provider: None,
-        references_provider: None,
+        references_provider: Some(lsp::OneOf::Left(true)),
         document_highlight_provider: None,
         document_symbol_provider: Some(lsp::OneOf::Left(true)),
-        workspace_symbol_provider: None,
+        workspace_symbol_provider: Some(lsp::OneOf::Left(true)),
         code_action_provider: Some(lsp::CodeActionProviderCapability::Simple(true)),
         code_lens_provider: None,
         document_formatting_provider: Some(lsp::OneOf::Left(true)),
-        document_range_formatting_provider: None,
+        document_range_formatting_provider: Some(lsp::OneOf::Left(true)),
         document_on_type_formatting_provider: None,
-        rename_provider: None,
+        rename_provider: Some(lsp::OneOf::Right(lsp::RenameOptions {
+            prepare_provider: Some(true),
+            work_done_progress_options: lsp::WorkDoneProgressOptions {
+                work_done_progress: None,
+            },
+        })),
         document_link_provider: None,
         color_provider: None,
         folding_range_provider: None,
         declaration_provider: None,
         execute_command_provider: None,
-        workspace: None,
+        workspace: Some(lsp::WorkspaceServerCapabilities {
+            workspace_folders: Some(lsp::WorkspaceFoldersServerCapabilities {
+                supported: Some(true),
+                change_notifications: Some(lsp::OneOf::Left(true)),
+            }),
+            file_operations: None,
+        }),
         call_hierarchy_provider: None,
-        semantic_tokens_provider: None,
+        semantic_tokens_provider: Some(
+            lsp::SemanticTokensServerCapabilities::SemanticTokensOptions(
+                lsp::SemanticTokensOptions {
+                    work_done_progress_options: lsp::WorkDoneProgressOptions {
+                        work_done_progress: None,
+                    },
+                    legend: semantic_tokens::legend(),
+                    range: None,
+                    full: Some(lsp::SemanticTokensFullOptions::Bool(true)),
+                },
+            ),
+        ),
         moniker_provider: None,
         linked_editing_range_provider: None,
         experimental: None,
         position_encoding: None,
         inline_value_provider: None,
-        inlay_hint_provider: None,
+        inlay_hint_provider: Some(lsp::OneOf::Left(true)),
         diagnostic_provider: None,
     };
     let server_capabilities_json =
@@ -527,9 +939,3 @@ fn diagnostic_to_lsp(diagnostic: Diagnostic) -> Vec<lsp::Diagnostic> {
         None => vec![main],
     }
 }
-
-fn path_to_uri(path: Utf8PathBuf) -> Url {
-    let mut file: String = "file://".into();
-    file.push_str(&path.as_os_str().to_string_lossy());
-    Url::parse(&file).expect("path_to_uri URL parse")
-}
@@ -4,7 +4,12 @@ mod completion;
 mod definition;
 mod document_symbols;
 mod hover;
+mod inlay_hints;
+mod references;
+mod rename;
+mod semantic_tokens;
 mod signature_help;
+mod workspace_symbols;
 
 use std::{
     collections::{HashMap, HashSet},
@@ -227,7 +232,7 @@ impl BeamCompiler for LanguageServerTestIO {
         lib: &Utf8Path,
         modules: &HashSet<Utf8PathBuf>,
         stdio: crate::io::Stdio,
-    ) -> Result<()> {
+    ) -> Result<Vec<crate::io::ErlangCompilerDiagnostic>> {
         panic!(
             "compile_beam({:?}, {:?}, {:?}, {:?}) is not implemented",
             out, lib, modules, stdio
@@ -63,14 +63,19 @@ const CONVERT_TO_CASE: &str = "Convert to case";
 const USE_LABEL_SHORTHAND_SYNTAX: &str = "Use label shorthand syntax";
 const FILL_LABELS: &str = "Fill labels";
 const ASSIGN_UNUSED_RESULT: &str = "Assign unused Result value to `_`";
+const ASSERT_UNUSED_RESULT: &str = "Assert unused Result value is `Ok`";
 const ADD_MISSING_PATTERNS: &str = "Add missing patterns";
 const ADD_ANNOTATION: &str = "Add type annotation";
 const ADD_ANNOTATIONS: &str = "Add type annotations";
+const FILL_IN_TYPE_HOLE: &str = "Fill in type hole";
+const FILL_IN_TYPE_HOLES: &str = "Fill in type holes";
 const DESUGAR_USE_EXPRESSION: &str = "Convert from `use`";
 const CONVERT_TO_USE: &str = "Convert to `use`";
 const EXTRACT_VARIABLE: &str = "Extract variable";
 const EXPAND_FUNCTION_CAPTURE: &str = "Expand function capture";
 const GENERATE_DYNAMIC_DECODER: &str = "Generate dynamic decoder";
+const ADD_MISSING_ARGUMENTS: &str = "Add missing arguments";
+const REMOVE_EXTRA_ARGUMENTS: &str = "Remove extra arguments";
 
 macro_rules! assert_code_action {
     ($title:expr, $code:literal, $range:expr $(,)?) => {
@@ -1144,6 +1149,20 @@ pub fn main() {
     );
 }
 
+#[test]
+fn test_assert_unused_result() {
+    assert_code_action!(
+        ASSERT_UNUSED_RESULT,
+        r#"
+pub fn main() {
+    Ok(0)
+    Nil
+}
+"#,
+        find_position_of("Ok").select_until(find_position_of("(0)")),
+    );
+}
+
 #[test]
 fn test_assign_unused_result_in_block() {
     assert_code_action!(
@@ -1427,6 +1446,119 @@ fn test_no_action_to_import_module_with_constructor_named_same_as_type() {
     );
 }
 
+#[test]
+fn import_missing_value() {
+    let src = "
+pub fn main() {
+  Some(1)
+}
+";
+
+    assert_code_action!(
+        "Import `Some` from `gleam/option`",
+        TestProject::for_source(src)
+            .add_hex_module("gleam/option", "pub type Option(a) { Some(a) None }"),
+        find_position_of("Some").to_selection()
+    );
+}
+
+#[test]
+fn import_missing_type() {
+    let src = "
+pub fn main() {
+  let list: List(Dict(String, Int)) = []
+  list
+}
+";
+
+    assert_code_action!(
+        "Import `Dict` from `gleam/dict`",
+        TestProject::for_source(src).add_hex_module("gleam/dict", "pub type Dict(k, v)"),
+        find_position_of("Dict").to_selection()
+    );
+}
+
+#[test]
+fn import_missing_value_sorted_among_imports() {
+    let src = "
+import gleam/int
+import gleam/string
+
+pub fn main() {
+  int.to_string(is_ok(Ok(1)) |> string.length)
+}
+";
+
+    assert_code_action!(
+        "Import `is_ok` from `gleam/result`",
+        TestProject::for_source(src)
+            .add_hex_module("gleam/int", "pub fn to_string(int: Int) -> String { todo }")
+            .add_hex_module(
+                "gleam/string",
+                "pub fn length(string: Bool) -> Int { todo }"
+            )
+            .add_hex_module("gleam/result", "pub fn is_ok(result) -> Bool { todo }"),
+        find_position_of("is_ok").to_selection()
+    );
+}
+
+#[test]
+fn import_missing_value_after_last_import() {
+    let src = "
+import gleam/int
+
+pub fn main() {
+  int.to_string(wobble())
+}
+";
+
+    assert_code_action!(
+        "Import `wobble` from `wibble`",
+        TestProject::for_source(src)
+            .add_hex_module("gleam/int", "pub fn to_string(int: Int) -> String { todo }")
+            .add_hex_module("wibble", "pub fn wobble() -> Int { 1 }"),
+        find_position_of("wobble").to_selection()
+    );
+}
+
+#[test]
+fn import_missing_value_qualifies_it_when_module_is_imported() {
+    let src = "
+import gleam/option as opt
+
+pub fn main() {
+  case opt.None {
+    Some(x) -> x
+    _ -> 0
+  }
+}
+";
+
+    assert_code_action!(
+        "Qualify as `opt.Some`",
+        TestProject::for_source(src)
+            .add_hex_module("gleam/option", "pub type Option(a) { Some(a) None }"),
+        find_position_of("Some").to_selection()
+    );
+}
+
+#[test]
+fn no_action_to_import_missing_private_value() {
+    let src = "
+pub fn main() {
+  wobble()
+}
+";
+
+    let title = "Import `wobble` from `wibble`";
+
+    assert_no_code_actions!(
+        title,
+        TestProject::for_source(src).add_module("wibble", "fn wobble() { 1 }"),
+        find_position_of("wobble").to_selection()
+    );
+}
+
 #[test]
 fn add_missing_patterns_bool() {
     assert_code_action!(
@@ -1573,6 +1705,63 @@ pub fn add_one(thing) {
     );
 }
 
+#[test]
+fn fill_in_type_hole_in_let() {
+    assert_code_action!(
+        FILL_IN_TYPE_HOLE,
+        r#"
+pub fn main() {
+  let x: List(_) = [1, 2, 3]
+  x
+}
+"#,
+        find_position_of("_").to_selection()
+    );
+}
+
+#[test]
+fn fill_in_type_holes_in_function_signature() {
+    assert_code_action!(
+        FILL_IN_TYPE_HOLES,
+        r#"
+pub fn wibble(a: _, b: #(_, Float)) -> _ {
+  a <> "!" <> b.0
+}
+"#,
+        find_position_of("pub").select_until(find_position_of("{"))
+    );
+}
+
+#[test]
+fn fill_in_generic_type_hole() {
+    assert_code_action!(
+        FILL_IN_TYPE_HOLE,
+        r#"
+pub fn wibble(a: List(_)) -> Int {
+  case a {
+    [] -> 0
+    _ -> 1
+  }
+}
+"#,
+        find_position_of("_").to_selection()
+    );
+}
+
+#[test]
+fn no_code_action_to_fill_in_type_hole_outside_selection() {
+    assert_no_code_actions!(
+        FILL_IN_TYPE_HOLE | FILL_IN_TYPE_HOLES,
+        r#"
+pub fn main() {
+  let x: List(_) = [1, 2, 3]
+  x
+}
+"#,
+        find_position_of("x").nth_occurrence(2).to_selection()
+    );
+}
+
 #[test]
 fn annotate_function_with_annotated_return_type() {
     assert_code_action!(
@@ -3160,23 +3349,23 @@ pub fn main() {
             .select_until(find_position_of("(1)")),
     );
 }
-/* TODO: implement qualified unused location
 #[test]
 fn test_remove_unused_qualified_action() {
-    let code = "
-// test
-import map.{Map, delete}
-";
-    let expected = "
+    let src = "
 // test
-
+import map.{type Map, delete}
 ";
-    assert_eq!(remove_unused_action(code), expected.to_string())
+    assert_code_action!(
+        REMOVE_UNUSED_IMPORTS,
+        TestProject::for_source(src)
+            .add_hex_module("map", "pub type Map { Map }\npub fn delete() {}"),
+        find_position_of("// test").select_until(find_position_of("delete")),
+    );
 }
 
 #[test]
 fn test_remove_unused_qualified_partial_action() {
-    let code = "
+    let src = "
 // test
 import result.{is_ok, is_err}
 
@@ -3184,59 +3373,114 @@ pub fn main() {
   is_ok
 }
 ";
-    let expected = "
+    assert_code_action!(
+        REMOVE_UNUSED_IMPORTS,
+        TestProject::for_source(src)
+            .add_hex_module("result", "pub fn is_ok() {}\npub fn is_err() {}"),
+        find_position_of("is_err").select_until(find_position_of("}")),
+    );
+}
+
+#[test]
+fn test_remove_unused_qualified_partial2_action() {
+    let src = "
 // test
-import result.{is_ok}
+import result.{all, is_ok, is_err}
 
 pub fn main() {
   is_ok
 }
 ";
-    assert_eq!(remove_unused_action(code), expected.to_string())
+    assert_code_action!(
+        REMOVE_UNUSED_IMPORTS,
+        TestProject::for_source(src).add_hex_module(
+            "result",
+            "pub fn all() {}\npub fn is_ok() {}\npub fn is_err() {}"
+        ),
+        find_position_of("all").select_until(find_position_of("is_ok")),
+    );
 }
 
 #[test]
-fn test_remove_unused_qualified_partial2_action() {
-    let code = "
+fn test_remove_unused_qualified_partial3_action() {
+    let src = "
 // test
-import result.{all, is_ok, is_err}
+import result.{all, is_ok, is_err} as res
 
 pub fn main() {
   is_ok
+  res.is_err
 }
 ";
-    let expected = "
-// test
-import result.{ is_ok}
+    assert_code_action!(
+        REMOVE_UNUSED_IMPORTS,
+        TestProject::for_source(src).add_hex_module(
+            "result",
+            "pub fn all() {}\npub fn is_ok() {}\npub fn is_err() {}"
+        ),
+        find_position_of("all").select_until(find_position_of("is_ok")),
+    );
+}
+
+#[test]
+fn test_remove_unused_qualified_list_when_module_is_used() {
+    let src = "
+import option.{type Option, Some, None}
 
 pub fn main() {
-  is_ok
+  option.Some(1)
 }
 ";
-    assert_eq!(remove_unused_action(code), expected.to_string())
+    assert_code_action!(
+        REMOVE_UNUSED_IMPORTS,
+        TestProject::for_source(src)
+            .add_hex_module("option", "pub type Option(a) { Some(a) None }"),
+        find_position_of("Some").to_selection(),
+    );
 }
 
 #[test]
-fn test_remove_unused_qualified_partial3_action() {
-    let code = "
-// test
-import result.{all, is_ok, is_err} as res
+fn test_remove_unused_qualified_multiline_action() {
+    let src = "
+import option.{
+  type Option,
+  None,
+  Some,
+  unwrap,
+}
 
-pub fn main() {
-  is_ok
+pub fn main() -> Option(Int) {
+  None
 }
 ";
-    let expected = "
-// test
-import result.{ is_ok} as res
+    assert_code_action!(
+        REMOVE_UNUSED_IMPORTS,
+        TestProject::for_source(src).add_hex_module(
+            "option",
+            "pub type Option(a) { Some(a) None }\npub fn unwrap() {}"
+        ),
+        find_position_of("Some").to_selection(),
+    );
+}
+
+#[test]
+fn test_remove_unused_imports_and_qualified_names_together() {
+    let src = "
+import list
+import result.{is_ok, is_err}
 
 pub fn main() {
-  is_ok
+  is_err
 }
 ";
-    assert_eq!(remove_unused_action(code), expected.to_string())
+    assert_code_action!(
+        REMOVE_UNUSED_IMPORTS,
+        TestProject::for_source(src)
+            .add_hex_module("list", "")
+            .add_hex_module("result", "pub fn is_ok() {}\npub fn is_err() {}"),
+        find_position_of("is_ok").to_selection(),
+    );
 }
-*/
 
 #[test]
 fn desugar_use_expression_with_no_parens() {
@@ -4149,3 +4393,108 @@ pub type Wibble {
         find_position_of("type").to_selection()
     );
 }
+
+#[test]
+fn add_missing_argument() {
+    assert_code_action!(
+        ADD_MISSING_ARGUMENTS,
+        "
+pub fn main() {
+  wibble(1)
+}
+
+fn wibble(a: Int, b: String) { a }
+",
+        find_position_of("wibble(1)").to_selection()
+    );
+}
+
+#[test]
+fn add_missing_arguments_to_call_without_arguments() {
+    assert_code_action!(
+        ADD_MISSING_ARGUMENTS,
+        "
+pub fn main() {
+  wibble()
+}
+
+fn wibble(a: Int, b: String) { a }
+",
+        find_position_of("wibble()").to_selection()
+    );
+}
+
+#[test]
+fn add_missing_labelled_and_positional_arguments() {
+    assert_code_action!(
+        ADD_MISSING_ARGUMENTS,
+        "
+pub fn main() {
+  wibble(c: 1.0)
+}
+
+fn wibble(a: Int, b b: String, c c: Float) { a }
+",
+        find_position_of("wibble(c").to_selection()
+    );
+}
+
+#[test]
+fn add_missing_fields_to_constructor_pattern() {
+    assert_code_action!(
+        ADD_MISSING_ARGUMENTS,
+        "
+pub type Wibble {
+  Wibble(Int, String)
+}
+
+pub fn main(wibble) {
+  let Wibble = wibble
+}
+",
+        find_position_of("let Wibble").select_until(find_position_of(" = "))
+    );
+}
+
+#[test]
+fn add_missing_element_to_tuple_pattern() {
+    assert_code_action!(
+        ADD_MISSING_ARGUMENTS,
+        "
+pub fn main() {
+  let #(a, b) = #(1, 2.0, \"three\")
+}
+",
+        find_position_of("#(a").to_selection()
+    );
+}
+
+#[test]
+fn remove_extra_arguments() {
+    assert_code_action!(
+        REMOVE_EXTRA_ARGUMENTS,
+        "
+pub fn main() {
+  wibble(1, 2, 3)
+}
+
+fn wibble(a: Int) { a }
+",
+        find_position_of("wibble(1").to_selection()
+    );
+}
+
+#[test]
+fn remove_all_extra_arguments() {
+    assert_code_action!(
+        REMOVE_EXTRA_ARGUMENTS,
+        "
+pub fn main() {
+  wibble(1, 2)
+}
+
+fn wibble() { 1 }
+",
+        find_position_of("wibble(1").to_selection()
+    );
+}
@@ -122,7 +122,6 @@ fn format_completion_results(completions: Vec<CompletionItem>) -> EcoString {
         assert!(command.is_none());
         assert!(commit_characters.is_none());
         assert!(data.is_none());
-        assert!(tags.is_none());
 
         buffer.push_str(&label);
 
@@ -158,6 +157,10 @@ fn format_completion_results(completions: Vec<CompletionItem>) -> EcoString {
             write!(buffer, "\n  docs:   {:?}", m.value).unwrap();
         }
 
+        if let Some(tags) = tags {
+            write!(buffer, "\n  tags:   {tags:?}").unwrap();
+        }
+
         let edit = |buffer: &mut EcoString, e: lsp_types::TextEdit| {
             let a = e.range.start.line;
             let b = e.range.start.character;
@@ -534,6 +537,40 @@ pub opaque type Wibble {
     assert_completion!(TestProject::for_source(code).add_module("dep", dep));
 }
 
+#[test]
+fn deprecated_function_in_dep() {
+    let code = "import dep";
+    let dep = "
+@deprecated(\"Use wobble instead\")
+pub fn wibble() {
+  1
+}
+";
+
+    assert_completion!(TestProject::for_source(code).add_module("dep", dep));
+}
+
+#[test]
+fn deprecated_imported_type() {
+    let dep = "
+@deprecated(\"Use Wobble instead\")
+pub type Wibble = List(String)
+";
+    let code = "import dep
+
+pub fn wibble(
+  _: String,
+) -> Nil {
+  Nil
+}
+";
+
+    assert_completion!(
+        TestProject::for_source(code).add_module("dep", dep),
+        Position::new(3, 0)
+    );
+}
+
 #[test]
 fn private_function_in_dep() {
     let code = "import dep";
@@ -152,3 +152,23 @@ pub const my_const2 = [25]";
 
     assert_debug_snapshot!(doc_symbols(TestProject::for_source(code)))
 }
+
+#[test]
+fn doc_symbols_imports() {
+    let code = "
+import wibble
+import wibble/wobble.{type Wobble, Wobble, wubble, default as fallback} as w
+
+pub fn main() {
+  wibble.main()
+}";
+
+    assert_debug_snapshot!(doc_symbols(
+        TestProject::for_source(code)
+            .add_module("wibble", "pub fn main() { Nil }")
+            .add_module(
+                "wibble/wobble",
+                "pub type Wobble { Wobble }\npub fn wubble() { 1 }\npub const default = 1"
+            )
+    ))
+}
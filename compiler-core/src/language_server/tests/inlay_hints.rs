@@ -0,0 +1,213 @@
+use lsp_types::{InlayHint, InlayHintLabel, InlayHintParams, Position, Range};
+
+use crate::language_server::configuration::InlayHintsConfig;
+
+use super::*;
+
+const ALL_HINTS: InlayHintsConfig = InlayHintsConfig {
+    variable_types: true,
+    parameter_labels: true,
+};
+
+fn inlay_hints(tester: &TestProject<'_>, range: Range, config: InlayHintsConfig) -> Vec<InlayHint> {
+    tester.at(Position::default(), |engine, params, _| {
+        let params = InlayHintParams {
+            work_done_progress_params: Default::default(),
+            text_document: params.text_document,
+            range,
+        };
+        engine.inlay_hints(params, config).result.unwrap()
+    })
+}
+
+/// Shows the hints in the code as the editor would.
+fn show_hints(src: &str, hints: &[InlayHint]) -> String {
+    let mut output = String::new();
+    for (line_number, line) in src.lines().enumerate() {
+        for (column_number, char) in line.chars().enumerate() {
+            let position = Position::new(line_number as u32, column_number as u32);
+            for hint in hints.iter().filter(|hint| hint.position == position) {
+                output.push_str(&hint_text(hint));
+            }
+            output.push(char);
+        }
+        let end_of_line = Position::new(line_number as u32, line.chars().count() as u32);
+        for hint in hints.iter().filter(|hint| hint.position == end_of_line) {
+            output.push_str(&hint_text(hint));
+        }
+        output.push('\n');
+    }
+    output
+}
+
+fn hint_text(hint: &InlayHint) -> String {
+    let InlayHintLabel::String(label) = &hint.label else {
+        panic!("inlay hint labels are strings");
+    };
+    let padding = if hint.padding_right == Some(true) {
+        " "
+    } else {
+        ""
+    };
+    format!("⟨{label}⟩{padding}")
+}
+
+fn whole_module() -> Range {
+    Range::new(Position::new(0, 0), Position::new(u32::MAX, 0))
+}
+
+macro_rules! assert_inlay_hints {
+    ($src:literal $(,)?) => {
+        let project = TestProject::for_source($src);
+        assert_inlay_hints!(project);
+    };
+    ($project:expr $(,)?) => {
+        let project = $project;
+        let hints = inlay_hints(&project, whole_module(), ALL_HINTS);
+        let output = show_hints(project.src, &hints);
+        insta::assert_snapshot!(insta::internals::AutoName, output, project.src);
+    };
+}
+
+#[test]
+fn hints_for_variable_types() {
+    assert_inlay_hints!(
+        "
+pub fn main() {
+  let wibble = 1
+  let wobble = [wibble, 2]
+  let assert [first, ..] = wobble
+  let pair = #(first, \"wubble\")
+  pair
+}
+"
+    );
+}
+
+#[test]
+fn no_hints_for_annotated_variables() {
+    assert_inlay_hints!(
+        "
+pub fn main() {
+  let wibble: Int = 1
+  let wobble: List(_) = [wibble]
+  wobble
+}
+"
+    );
+}
+
+#[test]
+fn hints_for_generic_variable_types() {
+    assert_inlay_hints!(
+        "
+pub fn main(value) {
+  let list = [value]
+  let function = fn(x) { x }
+  #(list, function)
+}
+"
+    );
+}
+
+#[test]
+fn hints_for_parameter_labels() {
+    assert_inlay_hints!(
+        "
+pub fn main() {
+  let total = add(1, to: 2)
+  add(total, 3)
+}
+
+fn add(value: Int, to other: Int) -> Int {
+  value + other
+}
+"
+    );
+}
+
+#[test]
+fn no_hints_for_arguments_named_like_the_label() {
+    assert_inlay_hints!(
+        "
+pub fn main(value) {
+  add(value, 1)
+}
+
+fn add(value: Int, to other: Int) -> Int {
+  value + other
+}
+"
+    );
+}
+
+#[test]
+fn hints_for_record_constructor_labels() {
+    assert_inlay_hints!(
+        "
+pub type Wibble {
+  Wibble(name: String, count: Int)
+}
+
+pub fn main() {
+  Wibble(\"wobble\", 1)
+}
+"
+    );
+}
+
+#[test]
+fn hints_for_labels_of_function_from_other_module() {
+    let src = "
+import wibble
+
+pub fn main() {
+  1 |> wibble.add(2)
+}
+";
+    assert_inlay_hints!(TestProject::for_source(src).add_module(
+        "wibble",
+        "pub fn add(value: Int, to other: Int) -> Int { value + other }"
+    ));
+}
+
+#[test]
+fn hints_only_in_the_requested_range() {
+    let src = "
+pub fn main() {
+  let wibble = 1
+  let wobble = 2
+  wibble + wobble
+}
+";
+    let project = TestProject::for_source(src);
+    let range = Range::new(Position::new(3, 0), Position::new(4, 0));
+    let hints = inlay_hints(&project, range, ALL_HINTS);
+    insta::assert_snapshot!(show_hints(src, &hints));
+}
+
+#[test]
+fn only_enabled_hints_are_shown() {
+    let src = "
+pub fn main() {
+  let wibble = add(1, 2)
+  wibble
+}
+
+fn add(value: Int, to other: Int) -> Int {
+  value + other
+}
+";
+    let project = TestProject::for_source(src);
+    let only_labels = InlayHintsConfig {
+        variable_types: false,
+        parameter_labels: true,
+    };
+    insta::assert_snapshot!(show_hints(
+        src,
+        &inlay_hints(&project, whole_module(), only_labels)
+    ));
+
+    let no_hints = InlayHintsConfig::default();
+    assert!(inlay_hints(&project, whole_module(), no_hints).is_empty());
+}
@@ -0,0 +1,234 @@
+use lsp_types::{Location, Position, Range, ReferenceContext, ReferenceParams};
+
+use super::*;
+
+fn find_references(
+    tester: &TestProject<'_>,
+    position: Position,
+    include_declaration: bool,
+) -> Option<Vec<Location>> {
+    tester.at(position, |engine, param, _| {
+        let params = ReferenceParams {
+            text_document_position: param,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+            context: ReferenceContext {
+                include_declaration,
+            },
+        };
+        engine.find_references(params).result.unwrap()
+    })
+}
+
+/// Underlines all the given ranges in a module.
+fn show_ranges(code: &str, ranges: &[Range]) -> String {
+    let mut buffer = String::new();
+    for (line_number, line) in code.lines().enumerate() {
+        let underline: String = (0..line.chars().count())
+            .map(|column_number| {
+                let position = Position::new(line_number as u32, column_number as u32);
+                let is_referenced = ranges
+                    .iter()
+                    .any(|range| range.start <= position && position < range.end);
+                if is_referenced {
+                    '▔'
+                } else {
+                    ' '
+                }
+            })
+            .collect();
+
+        buffer.push_str(line);
+        if underline.contains('▔') {
+            buffer.push('\n');
+            buffer.push_str(underline.trim_end());
+        }
+        buffer.push('\n');
+    }
+    buffer
+}
+
+fn pretty_references(
+    project: TestProject<'_>,
+    position_finder: PositionFinder,
+    include_declaration: bool,
+) -> String {
+    let position = position_finder.find_position(project.src);
+    let locations = find_references(&project, position, include_declaration).expect("references");
+
+    let mut output = String::new();
+    for (uri, locations) in &locations
+        .into_iter()
+        .chunk_by(|location| location.uri.clone())
+    {
+        let path = uri
+            .path_segments()
+            .expect("a module path")
+            // To make snapshots the same both on windows and unix systems we
+            // need to discard windows' `C:` path segment.
+            .skip_while(|segment| *segment == "C:")
+            .join("/");
+        let ranges = locations.map(|location| location.range).collect_vec();
+        let src = project.src_from_module_url(&uri).expect("a module");
+        output.push_str(&format!("----- {path}\n{}\n", show_ranges(src, &ranges)));
+    }
+    output
+}
+
+macro_rules! assert_references {
+    ($src:literal, $position:expr $(,)?) => {
+        let project = TestProject::for_source($src);
+        assert_references!(project, $position);
+    };
+    ($project:expr, $position:expr $(,)?) => {
+        let output = pretty_references($project, $position, true);
+        insta::assert_snapshot!(insta::internals::AutoName, output);
+    };
+}
+
+#[test]
+fn references_to_local_variable() {
+    assert_references!(
+        "
+pub fn main() {
+  let wibble = 1
+  let wobble = wibble + 1
+  case wobble {
+    _ if wibble > 1 -> wibble
+    _ -> 0
+  }
+}
+",
+        find_position_of("wibble").nth_occurrence(2),
+    );
+}
+
+#[test]
+fn references_to_function_in_root_package() {
+    let src = "
+import wibble.{wobble}
+import wibble as w
+
+pub fn main() {
+  wobble() + w.wobble()
+}
+";
+    assert_references!(
+        TestProject::for_source(src)
+            .add_module("wibble", "pub fn wobble() { 1 }\n\nfn main() { wobble }")
+            .add_module(
+                "wubble",
+                "import wibble.{wobble as w}\n\npub fn main() { w() }"
+            ),
+        find_position_of("wobble()"),
+    );
+}
+
+#[test]
+fn references_from_path_dependencies() {
+    let src = "
+import dep
+
+pub fn main() {
+  dep.wobble()
+}
+";
+    assert_references!(
+        TestProject::for_source(src).add_dep_module(
+            "dep",
+            "pub fn wobble() { 1 }\n\npub fn wibble() { wobble() }"
+        ),
+        find_position_of("wobble"),
+    );
+}
+
+#[test]
+fn references_to_hex_dependency_function() {
+    let src = "
+import hex
+
+pub fn main() {
+  hex.wobble()
+}
+
+pub fn wibble() {
+  hex.wobble
+}
+";
+    assert_references!(
+        TestProject::for_source(src).add_hex_module("hex", "pub fn wobble() { 1 }"),
+        find_position_of("wobble"),
+    );
+}
+
+#[test]
+fn references_to_type() {
+    let src = "
+import wibble.{type Wibble}
+
+pub type Wobble {
+  Wobble(Wibble)
+}
+
+pub fn main(wibble: Wibble) -> wibble.Wibble {
+  wibble
+}
+";
+    assert_references!(
+        TestProject::for_source(src).add_module("wibble", "pub type Wibble {\n  Wibble\n}"),
+        find_position_of("Wibble)"),
+    );
+}
+
+#[test]
+fn references_to_record_constructor() {
+    assert_references!(
+        "
+pub type Wibble {
+  Wibble(Int)
+  Wobble
+}
+
+const default = Wibble(1)
+
+pub fn main(wibble) {
+  case wibble {
+    Wibble(_) -> Wibble(2)
+    Wobble -> default
+  }
+}
+",
+        find_position_of("Wibble(Int)"),
+    );
+}
+
+#[test]
+fn references_without_the_declaration() {
+    let src = "
+pub fn main() {
+  wibble() + wibble()
+}
+
+fn wibble() {
+  1
+}
+";
+    let output = pretty_references(
+        TestProject::for_source(src),
+        find_position_of("wibble"),
+        false,
+    );
+    insta::assert_snapshot!(output);
+}
+
+#[test]
+fn no_references_for_a_keyword() {
+    let src = "
+pub fn main() {
+  1
+}
+";
+    let project = TestProject::for_source(src);
+    let position = find_position_of("fn").find_position(src);
+    assert_eq!(find_references(&project, position, true), None);
+}
@@ -0,0 +1,398 @@
+use lsp_types::{Position, RenameParams, TextDocumentPositionParams, WorkspaceEdit};
+
+use crate::language_server::rename::{NameKind, RenameError};
+
+use super::*;
+
+fn rename(
+    tester: &TestProject<'_>,
+    new_name: &str,
+    position: Position,
+) -> Result<WorkspaceEdit, RenameError> {
+    tester.at(position, |engine, params, _| {
+        let params = RenameParams {
+            text_document_position: params,
+            new_name: new_name.into(),
+            work_done_progress_params: Default::default(),
+        };
+        engine.rename(params).result.unwrap()
+    })
+}
+
+fn prepare_rename(tester: &TestProject<'_>, position: Position) -> Option<lsp_types::Range> {
+    tester.at(position, |engine, params: TextDocumentPositionParams, _| {
+        engine.prepare_rename(params).result.unwrap()
+    })
+}
+
+fn apply_rename(
+    tester: TestProject<'_>,
+    new_name: &str,
+    position_finder: PositionFinder,
+) -> String {
+    let position = position_finder.find_position(tester.src);
+    let changes = rename(&tester, new_name, position)
+        .expect("a rename")
+        .changes
+        .expect("changes");
+
+    let mut output = String::new();
+    for (uri, edits) in changes
+        .into_iter()
+        .sorted_by_key(|(uri, _)| uri.to_string())
+    {
+        let path = uri
+            .path_segments()
+            .expect("a module path")
+            // To make snapshots the same both on windows and unix systems we
+            // need to discard windows' `C:` path segment.
+            .skip_while(|segment| *segment == "C:")
+            .join("/");
+        let src = tester.src_from_module_url(&uri).expect("a renamed module");
+        output.push_str(&format!("----- {path}\n{}\n", apply_code_edit(src, edits)));
+    }
+    output
+}
+
+macro_rules! assert_rename {
+    ($src:literal, $new_name:literal, $position:expr $(,)?) => {
+        let project = TestProject::for_source($src);
+        assert_rename!(project, $new_name, $position);
+    };
+    ($project:expr, $new_name:literal, $position:expr $(,)?) => {
+        let output = apply_rename($project, $new_name, $position);
+        insta::assert_snapshot!(insta::internals::AutoName, output);
+    };
+}
+
+#[test]
+fn rename_local_variable() {
+    assert_rename!(
+        "
+pub fn main() {
+  let wibble = 1
+  let wobble = wibble + 1
+  wibble + wobble
+}
+",
+        "count",
+        find_position_of("wibble").nth_occurrence(2),
+    );
+}
+
+#[test]
+fn rename_function_argument() {
+    assert_rename!(
+        "
+pub fn add(wibble: Int, to wobble: Int) -> Int {
+  wibble + wobble
+}
+",
+        "count",
+        find_position_of("wobble").nth_occurrence(2),
+    );
+}
+
+#[test]
+fn rename_variable_used_with_label_shorthand() {
+    assert_rename!(
+        "
+pub type Wibble {
+  Wibble(label: Int)
+}
+
+pub fn main() {
+  let label = 1
+  let Wibble(label:) = Wibble(label:)
+  label
+}
+",
+        "count",
+        find_position_of("label = 1"),
+    );
+}
+
+#[test]
+fn rename_variable_used_in_clause_guard() {
+    assert_rename!(
+        "
+pub fn main(wibble) {
+  case wibble {
+    wobble if wobble > 1 -> wobble
+    _ -> 0
+  }
+}
+",
+        "count",
+        find_position_of("wobble"),
+    );
+}
+
+#[test]
+fn rename_shadowed_variable() {
+    assert_rename!(
+        "
+pub fn main() {
+  let wibble = 1
+  let wibble = wibble + 1
+  wibble
+}
+",
+        "count",
+        find_position_of("wibble").nth_occurrence(4),
+    );
+}
+
+#[test]
+fn rename_function_in_other_modules() {
+    let src = "
+import wibble.{wobble}
+import wibble as w
+
+pub fn main() {
+  wobble() + w.wobble()
+}
+";
+    assert_rename!(
+        TestProject::for_source(src)
+            .add_module(
+                "wibble",
+                "pub fn wobble() { 1 }\n\npub fn main() { wobble() }"
+            )
+            .add_module(
+                "other",
+                "import wibble.{wobble as w}\n\npub fn main() { w() }"
+            ),
+        "wubble",
+        find_position_of("wobble()"),
+    );
+}
+
+#[test]
+fn rename_function_from_its_definition() {
+    let src = "
+import wibble
+
+pub fn main() {
+  wibble.wobble()
+}
+";
+    assert_rename!(
+        TestProject::for_source(src).add_module("wibble", "pub fn wobble() { 1 }"),
+        "wubble",
+        find_position_of("wobble"),
+    );
+}
+
+#[test]
+fn rename_constant() {
+    assert_rename!(
+        "
+const wibble = 1
+
+const wobble = [wibble, 2]
+
+const enabled = True
+
+pub fn main() {
+  case 1 {
+    x if x == wibble -> wobble
+    x if wibble > x && !enabled -> []
+    _ -> [wibble]
+  }
+}
+",
+        "count",
+        find_position_of("wibble"),
+    );
+}
+
+#[test]
+fn rename_constant_used_in_clause_guard() {
+    let src = "
+import wibble.{enabled}
+
+pub fn main(x) {
+  case x {
+    _ if enabled -> 1
+    _ if !wibble.enabled -> 2
+    _ -> 3
+  }
+}
+";
+    assert_rename!(
+        TestProject::for_source(src).add_module("wibble", "pub const enabled = True"),
+        "active",
+        find_position_of("enabled"),
+    );
+}
+
+#[test]
+fn rename_record_constructor() {
+    let src = "
+import wibble.{type Wibble, Wobble}
+
+const default = Wobble(1)
+
+pub fn main(value: Wibble) {
+  case value {
+    Wobble(x) -> Wobble(x + 1)
+    wibble.Wubble -> default
+  }
+}
+";
+    assert_rename!(
+        TestProject::for_source(src)
+            .add_module("wibble", "pub type Wibble {\n  Wobble(Int)\n  Wubble\n}"),
+        "Wabble",
+        find_position_of("Wobble(x)"),
+    );
+}
+
+#[test]
+fn rename_type() {
+    let src = "
+import wibble.{type Wibble}
+import wibble as w
+
+pub type Pair =
+  #(Wibble, w.Wibble)
+
+pub fn main(value: Wibble) -> List(w.Wibble) {
+  let list: List(Wibble) = [value]
+  list
+}
+";
+    assert_rename!(
+        TestProject::for_source(src).add_module("wibble", "pub type Wibble {\n  Wibble\n}"),
+        "Wobble",
+        find_position_of("Wibble)"),
+    );
+}
+
+#[test]
+fn rename_type_alias() {
+    assert_rename!(
+        "
+pub type Wibble =
+  Int
+
+pub fn main(value: Wibble) -> Wibble {
+  value
+}
+",
+        "Count",
+        find_position_of("Wibble").nth_occurrence(2),
+    );
+}
+
+#[test]
+fn rename_refuses_names_from_dependencies() {
+    let src = "
+import wibble
+
+pub fn main() {
+  wibble.wobble()
+}
+";
+    let project = TestProject::for_source(src).add_hex_module("wibble", "pub fn wobble() { 1 }");
+    let position = find_position_of("wobble").find_position(src);
+    assert_eq!(
+        rename(&project, "wubble", position),
+        Err(RenameError::DefinedInDependency {
+            name: "wobble".into()
+        })
+    );
+    assert_eq!(prepare_rename(&project, position), None);
+}
+
+#[test]
+fn rename_refuses_invalid_names() {
+    let src = "
+pub fn main() {
+  let wibble = 1
+  wibble
+}
+";
+    let project = TestProject::for_source(src);
+    let position = find_position_of("wibble").find_position(src);
+    for name in ["Wibble", "case", "wibble wobble", ""] {
+        assert_eq!(
+            rename(&project, name, position),
+            Err(RenameError::InvalidName {
+                name: name.into(),
+                expected: NameKind::Lowercase
+            })
+        );
+    }
+}
+
+#[test]
+fn rename_refuses_names_already_in_use() {
+    let src = "
+import wibble.{wobble}
+
+pub fn wubble() {
+  wobble()
+}
+";
+    let project = TestProject::for_source(src)
+        .add_module("wibble", "pub fn wobble() { 1 }\n\npub fn wabble() { 2 }");
+    let position = find_position_of("wobble()").find_position(src);
+    assert_eq!(
+        rename(&project, "wabble", position),
+        Err(RenameError::NameTaken {
+            name: "wabble".into(),
+            module: "wibble".into()
+        })
+    );
+    assert_eq!(
+        rename(&project, "wubble", position),
+        Err(RenameError::NameTaken {
+            name: "wubble".into(),
+            module: "app".into()
+        })
+    );
+}
+
+#[test]
+fn rename_refuses_to_shadow_variables() {
+    let src = "
+pub fn main(wobble) {
+  let wibble = 1
+  wibble + wobble
+}
+";
+    let project = TestProject::for_source(src);
+    let position = find_position_of("wibble").find_position(src);
+    assert_eq!(
+        rename(&project, "wobble", position),
+        Err(RenameError::NameTaken {
+            name: "wobble".into(),
+            module: "app".into()
+        })
+    );
+}
+
+#[test]
+fn prepare_rename_gives_the_location_of_the_name() {
+    let src = "
+pub fn main() {
+  let wibble = 1
+  wibble
+}
+";
+    let project = TestProject::for_source(src);
+    let position = find_position_of("wibble")
+        .under_last_char()
+        .find_position(src);
+    assert_eq!(
+        prepare_rename(&project, position),
+        Some(lsp_types::Range::new(
+            Position::new(2, 6),
+            Position::new(2, 12)
+        ))
+    );
+    let position = find_position_of("let").find_position(src);
+    assert_eq!(prepare_rename(&project, position), None);
+}
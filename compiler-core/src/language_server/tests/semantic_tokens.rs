@@ -0,0 +1,144 @@
+use lsp_types::{Position, SemanticTokensParams};
+
+use crate::language_server::semantic_tokens::legend;
+
+use super::*;
+
+/// Lists the tokens of a module, one per line, with the name they cover.
+fn pretty_semantic_tokens(tester: TestProject<'_>) -> String {
+    let tokens = tester.at(Position::default(), |engine, params, _| {
+        let params = SemanticTokensParams {
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+            text_document: params.text_document,
+        };
+        engine
+            .semantic_tokens(params)
+            .result
+            .unwrap()
+            .expect("semantic tokens")
+    });
+
+    let legend = legend();
+    let lines = tester.src.lines().collect_vec();
+    let mut output = String::new();
+    let (mut line, mut start) = (0, 0);
+    for token in tokens.data {
+        if token.delta_line == 0 {
+            start += token.delta_start;
+        } else {
+            line += token.delta_line;
+            start = token.delta_start;
+        }
+        let name = lines
+            .get(line as usize)
+            .and_then(|code| code.get(start as usize..(start + token.length) as usize))
+            .expect("token in the code");
+        let type_ = legend
+            .token_types
+            .get(token.token_type as usize)
+            .expect("token type in the legend")
+            .as_str();
+        let modifiers = legend
+            .token_modifiers
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| token.token_modifiers_bitset & (1 << index) != 0)
+            .map(|(_, modifier)| modifier.as_str())
+            .join(", ");
+        output.push_str(&format!(
+            "{}:{} {name} {type_} [{modifiers}]\n",
+            line + 1,
+            start
+        ));
+    }
+    format!("{}\n\n----- TOKENS\n{output}", tester.src)
+}
+
+#[test]
+fn semantic_tokens_for_definitions_and_usages() {
+    let src = r#"
+pub type Wibble {
+  Wibble(Int)
+  Wobble
+}
+
+const default = Wobble
+
+pub fn main(value: Wibble) -> Wibble {
+  let wubble = fn(x) { x }
+  case value {
+    Wibble(n) -> wubble(Wibble(n))
+    Wobble -> default
+  }
+}
+"#;
+    insta::assert_snapshot!(pretty_semantic_tokens(TestProject::for_source(src)));
+}
+
+#[test]
+fn semantic_tokens_for_modules() {
+    let src = r#"
+import wibble.{type Wibble, wobble}
+import wibble/wubble as w
+
+pub fn main(value: Wibble) -> wibble.Wibble {
+  wobble(value)
+  wibble.wobble(w.count)
+}
+"#;
+    insta::assert_snapshot!(pretty_semantic_tokens(
+        TestProject::for_source(src)
+            .add_module(
+                "wibble",
+                "pub type Wibble { Wibble }\npub fn wobble(x) { x }"
+            )
+            .add_module("wibble/wubble", "pub const count = Nil")
+    ));
+}
+
+#[test]
+fn semantic_tokens_for_field_access_on_variable_named_like_a_module() {
+    let src = r#"
+import wibble
+
+pub type Wobble {
+  Wobble(wibble: Int)
+}
+
+pub fn main(wobble: Wobble) {
+  let wibble = wobble
+  wibble.wibble + wibble.count
+}
+"#;
+    insta::assert_snapshot!(pretty_semantic_tokens(
+        TestProject::for_source(src).add_module("wibble", "pub const count = 1")
+    ));
+}
+
+#[test]
+fn semantic_tokens_for_unused_and_deprecated_names() {
+    let src = r#"
+import wibble.{wobble}
+import wubble
+
+@deprecated("Use something else")
+pub fn old() {
+  1
+}
+
+fn unused(argument) {
+  let unused_variable = old()
+  argument
+}
+
+pub fn main() {
+  old
+}
+"#;
+    insta::assert_snapshot!(pretty_semantic_tokens(
+        TestProject::for_source(src)
+            .add_module("wibble", "pub fn wobble() { 1 }")
+            .add_module("wubble", "pub fn wubble() { 1 }")
+    ));
+}
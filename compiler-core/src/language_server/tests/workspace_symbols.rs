@@ -0,0 +1,83 @@
+use lsp_types::{Position, WorkspaceSymbolParams};
+
+use super::*;
+
+fn workspace_symbols(tester: TestProject<'_>, query: &str) -> String {
+    let symbols = tester.at(Position::default(), |engine, _, _| {
+        let params = WorkspaceSymbolParams {
+            query: query.into(),
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        };
+        engine.workspace_symbol(&params).result.unwrap()
+    });
+
+    symbols
+        .into_iter()
+        .map(|symbol| {
+            let start = symbol.location.range.start;
+            format!(
+                "{} {:?} in {} at {}:{}",
+                symbol.name,
+                symbol.kind,
+                symbol.container_name.unwrap_or_default(),
+                start.line,
+                start.character
+            )
+        })
+        .join("\n")
+}
+
+#[test]
+fn workspace_symbols_from_all_modules() {
+    let src = "
+import wibble
+
+pub fn main() {
+  wibble.wobble()
+}
+
+fn private_wobble() {
+  1
+}
+";
+    let project = TestProject::for_source(src)
+        .add_module(
+            "wibble",
+            "pub fn wobble() { 1 }\n\npub type Wobble {\n  Wobble(Int)\n  Wubble\n}",
+        )
+        .add_test_module("wibble_test", "pub const wobble_count = 1")
+        .add_hex_module("hex", "pub fn wobble() { 1 }");
+
+    insta::assert_snapshot!(workspace_symbols(project, "wob"));
+}
+
+#[test]
+fn workspace_symbols_fuzzy_match() {
+    let src = "
+pub type UserAccount =
+  Int
+
+pub fn update_account() {
+  1
+}
+
+pub fn undo() {
+  1
+}
+";
+    insta::assert_snapshot!(workspace_symbols(TestProject::for_source(src), "uacc"));
+}
+
+#[test]
+fn workspace_symbols_no_match() {
+    let src = "
+pub fn main() {
+  1
+}
+";
+    assert_eq!(
+        workspace_symbols(TestProject::for_source(src), "wibble"),
+        ""
+    );
+}
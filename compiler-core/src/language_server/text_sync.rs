@@ -0,0 +1,222 @@
+//! Incremental synchronisation of documents: rather than sending the whole
+//! text of a module after each keystroke the editor sends only the ranges
+//! that were changed, which are applied to the text the server already has.
+//! The text of each edited module is fingerprinted too, so a project is only
+//! compiled again when one of its modules differs from what was compiled.
+
+use std::collections::{HashMap, HashSet};
+
+use camino::{Utf8Path, Utf8PathBuf};
+use lsp_types::{Position, TextDocumentContentChangeEvent};
+
+use crate::build::SourceFingerprint;
+
+/// Applies the changes sent by the editor to the text of a document, in the
+/// order they were made. A change without a range replaces the whole text.
+pub fn apply_changes(text: &str, changes: Vec<TextDocumentContentChangeEvent>) -> String {
+    let mut text = text.to_string();
+    for change in changes {
+        match change.range {
+            None => text = change.text,
+            Some(range) => {
+                let start = byte_index(&text, range.start);
+                let end = byte_index(&text, range.end).max(start);
+                text.replace_range(start..end, &change.text);
+            }
+        }
+    }
+    text
+}
+
+/// The index of the byte at a position, where the character of the position
+/// is counted in UTF-16 code units as the protocol requires. A position past
+/// the end of a line or of the text is taken to be the end of it.
+fn byte_index(text: &str, position: Position) -> usize {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text.get(line_start..).and_then(|rest| rest.find('\n')) {
+            Some(newline) => line_start += newline + 1,
+            None => return text.len(),
+        }
+    }
+
+    let line = text.get(line_start..).unwrap_or_default();
+    let line = line.split('\n').next().unwrap_or_default();
+    let mut utf16_index = 0;
+    for (index, char) in line.char_indices() {
+        if utf16_index >= position.character as usize {
+            return line_start + index;
+        }
+        utf16_index += char.len_utf16();
+    }
+    line_start + line.len()
+}
+
+/// The fingerprints of the text of the modules edited in the editor, as it was
+/// when their project was last compiled.
+#[derive(Debug, Default)]
+pub struct CompiledSources {
+    compiled: HashMap<Utf8PathBuf, SourceFingerprint>,
+    /// The modules whose text differs from the one that was compiled, with
+    /// the project they belong to and the fingerprint of their new text.
+    changed: HashMap<Utf8PathBuf, (Utf8PathBuf, SourceFingerprint)>,
+}
+
+impl CompiledSources {
+    /// Records the new text of a module. A module whose text is back to the
+    /// one that was compiled, such as after undoing an edit or saving the
+    /// file, doesn't need its project to be compiled again.
+    pub fn module_changed(&mut self, module: Utf8PathBuf, project: Utf8PathBuf, text: &str) {
+        let fingerprint = SourceFingerprint::new(text);
+        if self.compiled.get(&module) == Some(&fingerprint) {
+            _ = self.changed.remove(&module);
+        } else {
+            _ = self.changed.insert(module, (project, fingerprint));
+        }
+    }
+
+    /// The projects with modules that have changed since they were compiled.
+    /// The modules are taken to be compiled from now on.
+    pub fn take_changed_projects(&mut self) -> HashSet<Utf8PathBuf> {
+        self.changed
+            .drain()
+            .map(|(module, (project, fingerprint))| {
+                _ = self.compiled.insert(module, fingerprint);
+                project
+            })
+            .collect()
+    }
+
+    /// Forgets the modules of a project, for when it has to be compiled from
+    /// scratch.
+    pub fn forget_project(&mut self, project: &Utf8Path) {
+        self.compiled
+            .retain(|module, _| !module.starts_with(project));
+    }
+}
+
+#[cfg(test)]
+fn change(
+    (start_line, start_character): (u32, u32),
+    (end_line, end_character): (u32, u32),
+    text: &str,
+) -> TextDocumentContentChangeEvent {
+    TextDocumentContentChangeEvent {
+        range: Some(lsp_types::Range::new(
+            Position::new(start_line, start_character),
+            Position::new(end_line, end_character),
+        )),
+        range_length: None,
+        text: text.into(),
+    }
+}
+
+#[test]
+fn apply_changes_in_order() {
+    let text = "pub fn main() {\n  1\n}\n";
+    let changes = vec![
+        change((1, 2), (1, 3), "wibble"),
+        change((1, 8), (1, 8), " + wobble"),
+        change((0, 0), (0, 4), ""),
+    ];
+    assert_eq!(
+        apply_changes(text, changes),
+        "fn main() {\n  wibble + wobble\n}\n"
+    );
+}
+
+#[test]
+fn apply_changes_over_multiple_lines() {
+    let text = "pub fn main() {\n  1\n  2\n}\n";
+    let changes = vec![change((1, 3), (2, 3), "")];
+    assert_eq!(apply_changes(text, changes), "pub fn main() {\n  1\n}\n");
+}
+
+#[test]
+fn apply_change_without_range() {
+    let changes = vec![TextDocumentContentChangeEvent {
+        range: None,
+        range_length: None,
+        text: "pub const wibble = 1\n".into(),
+    }];
+    assert_eq!(
+        apply_changes("pub fn main() { 1 }", changes),
+        "pub const wibble = 1\n"
+    );
+}
+
+#[test]
+fn apply_changes_after_multibyte_characters() {
+    // `é` is two bytes long but a single UTF-16 code unit, while `🐭` is four
+    // bytes long and two UTF-16 code units.
+    let text = "const wibble = \"é🐭\"\n";
+    let changes = vec![change((0, 19), (0, 20), "!")];
+    assert_eq!(apply_changes(text, changes), "const wibble = \"é🐭!\n");
+}
+
+#[test]
+fn apply_changes_past_the_end() {
+    let text = "const wibble = 1";
+    let changes = vec![change((0, 100), (3, 0), "\n")];
+    assert_eq!(apply_changes(text, changes), "const wibble = 1\n");
+}
+
+#[test]
+fn changed_modules_are_compiled_once() {
+    let mut sources = CompiledSources::default();
+    sources.module_changed(
+        "/app/src/app.gleam".into(),
+        "/app".into(),
+        "pub const x = 1",
+    );
+    assert_eq!(
+        sources.take_changed_projects(),
+        HashSet::from([Utf8PathBuf::from("/app")])
+    );
+    assert!(sources.take_changed_projects().is_empty());
+}
+
+#[test]
+fn modules_changed_back_to_the_compiled_text_are_not_compiled() {
+    let mut sources = CompiledSources::default();
+    sources.module_changed(
+        "/app/src/app.gleam".into(),
+        "/app".into(),
+        "pub const x = 1",
+    );
+    let _ = sources.take_changed_projects();
+
+    sources.module_changed(
+        "/app/src/app.gleam".into(),
+        "/app".into(),
+        "pub const x = 12",
+    );
+    sources.module_changed(
+        "/app/src/app.gleam".into(),
+        "/app".into(),
+        "pub const x = 1",
+    );
+    assert!(sources.take_changed_projects().is_empty());
+}
+
+#[test]
+fn modules_of_forgotten_projects_are_compiled_again() {
+    let mut sources = CompiledSources::default();
+    sources.module_changed(
+        "/app/src/app.gleam".into(),
+        "/app".into(),
+        "pub const x = 1",
+    );
+    let _ = sources.take_changed_projects();
+
+    sources.forget_project(Utf8Path::new("/app"));
+    sources.module_changed(
+        "/app/src/app.gleam".into(),
+        "/app".into(),
+        "pub const x = 1",
+    );
+    assert_eq!(
+        sources.take_changed_projects(),
+        HashSet::from([Utf8PathBuf::from("/app")])
+    );
+}
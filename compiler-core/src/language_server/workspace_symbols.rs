@@ -0,0 +1,156 @@
+//! An index of the definitions in the modules of a project, searched by
+//! name for the workspace symbols request.
+
+use std::collections::HashMap;
+
+use ecow::EcoString;
+use itertools::Itertools;
+use lsp_types::{Location, SymbolInformation, SymbolKind};
+
+use crate::{
+    ast::{Definition, SrcSpan},
+    build::Module,
+    line_numbers::LineNumbers,
+};
+
+use super::{path_to_uri, src_span_to_lsp_range};
+
+/// The most symbols returned for a search, so a short query in a large
+/// project doesn't overwhelm the editor.
+const MAX_RESULTS: usize = 100;
+
+#[derive(Debug, Default)]
+pub struct SymbolIndex {
+    modules: HashMap<EcoString, Vec<IndexedSymbol>>,
+}
+
+#[derive(Debug, Clone)]
+struct IndexedSymbol {
+    name: EcoString,
+    kind: SymbolKind,
+    location: Location,
+}
+
+impl SymbolIndex {
+    /// Indexes the definitions of a module, replacing any that were indexed
+    /// for it before.
+    pub fn update(&mut self, module: &Module) {
+        let uri = path_to_uri(module.input_path.clone());
+        let line_numbers = LineNumbers::new(&module.code);
+        let mut symbols = vec![];
+        let mut push = |name: &EcoString, kind, location: SrcSpan| {
+            symbols.push(IndexedSymbol {
+                name: name.clone(),
+                kind,
+                location: Location {
+                    uri: uri.clone(),
+                    range: src_span_to_lsp_range(location, &line_numbers),
+                },
+            })
+        };
+
+        for definition in &module.ast.definitions {
+            match definition {
+                Definition::Function(function) => {
+                    if let Some((location, name)) = &function.name {
+                        push(name, SymbolKind::FUNCTION, *location);
+                    }
+                }
+                Definition::ModuleConstant(constant) => {
+                    push(&constant.name, SymbolKind::CONSTANT, constant.name_location);
+                }
+                Definition::TypeAlias(alias) => {
+                    push(&alias.alias, SymbolKind::CLASS, alias.name_location);
+                }
+                Definition::CustomType(custom_type) => {
+                    push(
+                        &custom_type.name,
+                        SymbolKind::CLASS,
+                        custom_type.name_location,
+                    );
+                    for constructor in &custom_type.constructors {
+                        let kind = if constructor.arguments.is_empty() {
+                            SymbolKind::ENUM_MEMBER
+                        } else {
+                            SymbolKind::CONSTRUCTOR
+                        };
+                        push(&constructor.name, kind, constructor.name_location);
+                    }
+                }
+                Definition::Import(_) => (),
+            }
+        }
+
+        let _ = self.modules.insert(module.name.clone(), symbols);
+    }
+
+    /// The symbols whose names match a query, best matches first. A name
+    /// matches if it contains all the characters of the query in the same
+    /// order, ignoring case.
+    pub fn search(&self, query: &str) -> Vec<SymbolInformation> {
+        let query = query.to_lowercase();
+        self.modules
+            .iter()
+            .flat_map(|(module, symbols)| symbols.iter().map(move |symbol| (module, symbol)))
+            .filter_map(|(module, symbol)| {
+                let score = match_score(&query, &symbol.name)?;
+                Some((score, module, symbol))
+            })
+            .sorted_by(
+                |(one_score, one_module, one), (other_score, other_module, other)| {
+                    one_score
+                        .cmp(other_score)
+                        .then_with(|| one.name.len().cmp(&other.name.len()))
+                        .then_with(|| one.name.cmp(&other.name))
+                        .then_with(|| one_module.cmp(other_module))
+                },
+            )
+            .take(MAX_RESULTS)
+            .map(|(_, module, symbol)| {
+                // The 'deprecated' field is deprecated, but we have to specify it anyway
+                // to be able to construct the 'SymbolInformation' type, so
+                // we suppress the warning. We specify 'None' as specifying 'Some'
+                // is what is actually deprecated.
+                #[allow(deprecated)]
+                SymbolInformation {
+                    name: symbol.name.to_string(),
+                    kind: symbol.kind,
+                    tags: None,
+                    deprecated: None,
+                    location: symbol.location.clone(),
+                    container_name: Some(module.to_string()),
+                }
+            })
+            .collect()
+    }
+}
+
+/// How well a name matches a lowercase query, lower being better: an exact
+/// match, then a prefix, then the query anywhere in the name, and finally the
+/// characters of the query in order but apart.
+fn match_score(query: &str, name: &str) -> Option<u8> {
+    let name = name.to_lowercase();
+    if name == query {
+        Some(0)
+    } else if name.starts_with(query) {
+        Some(1)
+    } else if name.contains(query) {
+        Some(2)
+    } else {
+        let mut name_chars = name.chars();
+        query
+            .chars()
+            .all(|char| name_chars.any(|name_char| name_char == char))
+            .then_some(3)
+    }
+}
+
+#[test]
+fn match_score_prefers_closer_matches() {
+    assert_eq!(match_score("wibble", "Wibble"), Some(0));
+    assert_eq!(match_score("wib", "wibble_wobble"), Some(1));
+    assert_eq!(match_score("wob", "wibble_wobble"), Some(2));
+    assert_eq!(match_score("wbwb", "wibble_wobble"), Some(3));
+    assert_eq!(match_score("wubble", "wibble_wobble"), None);
+    assert_eq!(match_score("", "wibble"), Some(1));
+}
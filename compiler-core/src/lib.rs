@@ -63,6 +63,7 @@ pub mod bit_array;
 pub mod build;
 pub mod codegen;
 pub mod config;
+pub mod dead_code;
 pub mod dependency;
 pub mod diagnostic;
 pub mod docs;
@@ -94,6 +95,7 @@ mod call_graph;
 mod dep_tree;
 mod exhaustiveness;
 pub(crate) mod graph;
+mod reference;
 
 pub use error::{Error, Result};
 pub use warning::Warning;
@@ -13,8 +13,9 @@ use crate::{
     line_numbers::LineNumbers,
     schema_capnp::{self as schema, *},
     type_::{
-        self, expression::Implementations, AccessorsMap, Deprecation, FieldMap, ModuleInterface,
-        RecordAccessor, Type, TypeConstructor, TypeValueConstructor, TypeValueConstructorField,
+        self, expression::Implementations, AccessorsMap, Deprecation, FieldMap, FunctionReference,
+        InferredAnnotation, InferredAnnotationKind, ModuleInterface, RecordAccessor, Reference,
+        ReferenceKind, Type, TypeConstructor, TypeValueConstructor, TypeValueConstructorField,
         TypeVariantConstructors, ValueConstructor, ValueConstructorVariant,
     },
     uid::UniqueIdGenerator,
@@ -83,6 +84,17 @@ impl ModuleDecoder {
             src_path: self.str(reader.get_src_path()?)?.into(),
             warnings: vec![],
             minimum_required_version: self.version(&reader.get_required_version()?),
+            inferred_annotations: read_vec!(
+                reader.get_inferred_annotations()?,
+                self,
+                inferred_annotation
+            ),
+            function_references: read_vec!(
+                reader.get_function_references()?,
+                self,
+                function_reference
+            ),
+            references: read_vec!(reader.get_references()?, self, reference),
         })
     }
 
@@ -605,6 +617,46 @@ impl ModuleDecoder {
         })
     }
 
+    fn inferred_annotation(
+        &mut self,
+        reader: &inferred_annotation::Reader<'_>,
+    ) -> Result<InferredAnnotation> {
+        Ok(InferredAnnotation {
+            location: self.src_span(&reader.get_location()?)?,
+            type_: self.type_(&reader.get_type()?)?,
+            kind: if reader.get_hole() {
+                InferredAnnotationKind::Hole
+            } else {
+                InferredAnnotationKind::LetBinding
+            },
+        })
+    }
+
+    fn function_reference(
+        &mut self,
+        reader: &function_reference::Reader<'_>,
+    ) -> Result<FunctionReference> {
+        Ok(FunctionReference {
+            function: self.string(reader.get_function()?)?,
+            module: self.string(reader.get_module()?)?,
+            name: self.string(reader.get_name()?)?,
+        })
+    }
+
+    fn reference(&mut self, reader: &reference::Reader<'_>) -> Result<Reference> {
+        Ok(Reference {
+            location: self.src_span(&reader.get_location()?)?,
+            module: self.string(reader.get_module()?)?,
+            name: self.string(reader.get_name()?)?,
+            type_: self.type_(&reader.get_type()?)?,
+            kind: if reader.get_is_type() {
+                ReferenceKind::Type
+            } else {
+                ReferenceKind::Value
+            },
+        })
+    }
+
     fn version(&self, reader: &version::Reader<'_>) -> hexpm::version::Version {
         hexpm::version::Version::new(reader.get_major(), reader.get_minor(), reader.get_patch())
     }
@@ -8,8 +8,8 @@ use crate::{
     schema_capnp::{self as schema, *},
     type_::{
         self, expression::Implementations, AccessorsMap, Deprecation, FieldMap, RecordAccessor,
-        Type, TypeConstructor, TypeValueConstructor, TypeVar, TypeVariantConstructors,
-        ValueConstructor, ValueConstructorVariant,
+        ReferenceKind, Type, TypeConstructor, TypeValueConstructor, TypeVar,
+        TypeVariantConstructors, ValueConstructor, ValueConstructorVariant,
     },
 };
 use std::{collections::HashMap, ops::Deref, sync::Arc};
@@ -48,6 +48,9 @@ impl<'a> ModuleEncoder<'a> {
         self.set_module_types_constructors(&mut module);
         self.set_line_numbers(&mut module);
         self.set_version(&mut module);
+        self.set_inferred_annotations(&mut module);
+        self.set_function_references(&mut module);
+        self.set_references(&mut module);
 
         capnp::serialize_packed::write_message(&mut buffer, &message).expect("capnp encode");
         Ok(buffer)
@@ -175,6 +178,44 @@ impl<'a> ModuleEncoder<'a> {
         version.set_patch(self.data.minimum_required_version.patch);
     }
 
+    fn set_inferred_annotations(&mut self, module: &mut module::Builder<'_>) {
+        let mut builder = module
+            .reborrow()
+            .init_inferred_annotations(self.data.inferred_annotations.len() as u32);
+        for (i, annotation) in self.data.inferred_annotations.iter().enumerate() {
+            let mut inferred = builder.reborrow().get(i as u32);
+            self.build_src_span(inferred.reborrow().init_location(), annotation.location);
+            self.build_type(inferred.reborrow().init_type(), &annotation.type_);
+            inferred.set_hole(annotation.is_hole());
+        }
+    }
+
+    fn set_function_references(&mut self, module: &mut module::Builder<'_>) {
+        let mut builder = module
+            .reborrow()
+            .init_function_references(self.data.function_references.len() as u32);
+        for (i, reference) in self.data.function_references.iter().enumerate() {
+            let mut builder = builder.reborrow().get(i as u32);
+            builder.set_function(&reference.function);
+            builder.set_module(&reference.module);
+            builder.set_name(&reference.name);
+        }
+    }
+
+    fn set_references(&mut self, module: &mut module::Builder<'_>) {
+        let mut builder = module
+            .reborrow()
+            .init_references(self.data.references.len() as u32);
+        for (i, reference) in self.data.references.iter().enumerate() {
+            let mut builder = builder.reborrow().get(i as u32);
+            self.build_src_span(builder.reborrow().init_location(), reference.location);
+            builder.set_module(&reference.module);
+            builder.set_name(&reference.name);
+            self.build_type(builder.reborrow().init_type(), &reference.type_);
+            builder.set_is_type(reference.kind == ReferenceKind::Type);
+        }
+    }
+
     fn build_type_constructor(
         &mut self,
         mut builder: type_constructor::Builder<'_>,
@@ -11,7 +11,8 @@ use crate::{
     build::Origin,
     line_numbers::LineNumbers,
     type_::{
-        self, expression::Implementations, Deprecation, ModuleInterface, Type, TypeConstructor,
+        self, expression::Implementations, Deprecation, FunctionReference, InferredAnnotation,
+        InferredAnnotationKind, ModuleInterface, Reference, ReferenceKind, Type, TypeConstructor,
         TypeValueConstructor, TypeValueConstructorField, TypeVariantConstructors, ValueConstructor,
         ValueConstructorVariant,
     },
@@ -64,6 +65,9 @@ fn constant_module(constant: TypedConstant) -> ModuleInterface {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     }
 }
 
@@ -98,6 +102,9 @@ fn empty_module() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
     assert_eq!(roundtrip(&module), module);
 }
@@ -121,6 +128,9 @@ fn with_line_numbers() {
         ),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
     assert_eq!(roundtrip(&module), module);
 }
@@ -152,6 +162,9 @@ fn module_with_private_type() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
     assert_eq!(roundtrip(&module), module);
 }
@@ -183,6 +196,9 @@ fn module_with_app_type() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
     assert_eq!(roundtrip(&module), module);
 }
@@ -214,6 +230,9 @@ fn module_with_fn_type() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
     assert_eq!(roundtrip(&module), module);
 }
@@ -245,6 +264,9 @@ fn module_with_tuple_type() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
     assert_eq!(roundtrip(&module), module);
 }
@@ -282,6 +304,9 @@ fn module_with_generic_type() {
             line_numbers: LineNumbers::new(""),
             src_path: "some_path".into(),
             minimum_required_version: Version::new(0, 1, 0),
+            inferred_annotations: vec![],
+            function_references: vec![],
+            references: vec![],
         }
     }
 
@@ -319,6 +344,9 @@ fn module_with_type_links() {
             line_numbers: LineNumbers::new(""),
             src_path: "some_path".into(),
             minimum_required_version: Version::new(0, 1, 0),
+            inferred_annotations: vec![],
+            function_references: vec![],
+            references: vec![],
         }
     }
 
@@ -356,6 +384,9 @@ fn module_with_type_constructor_documentation() {
             line_numbers: LineNumbers::new(""),
             src_path: "some_path".into(),
             minimum_required_version: Version::new(0, 1, 0),
+            inferred_annotations: vec![],
+            function_references: vec![],
+            references: vec![],
         }
     }
 
@@ -396,6 +427,9 @@ fn module_with_type_constructor_origin() {
             line_numbers: LineNumbers::new(""),
             src_path: "some_path".into(),
             minimum_required_version: Version::new(0, 1, 0),
+            inferred_annotations: vec![],
+            function_references: vec![],
+            references: vec![],
         }
     }
 
@@ -427,6 +461,9 @@ fn module_type_to_constructors_mapping() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
 
     assert_eq!(roundtrip(&module), module);
@@ -475,6 +512,9 @@ fn module_fn_value() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
     assert_eq!(roundtrip(&module), module);
 }
@@ -524,6 +564,9 @@ fn deprecated_module_fn_value() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
     assert_eq!(roundtrip(&module), module);
 }
@@ -571,6 +614,9 @@ fn private_module_fn_value() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
 
     assert_eq!(roundtrip(&module), module);
@@ -620,6 +666,9 @@ fn module_fn_value_regression() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
 
     assert_eq!(roundtrip(&module), module);
@@ -668,6 +717,9 @@ fn module_fn_value_with_field_map() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
 
     assert_eq!(roundtrip(&module), module);
@@ -711,6 +763,9 @@ fn record_value() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
 
     assert_eq!(roundtrip(&module), module);
@@ -757,6 +812,9 @@ fn record_value_with_field_map() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
 
     assert_eq!(roundtrip(&module), module);
@@ -825,6 +883,9 @@ fn accessors() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
 
     assert_eq!(roundtrip(&module), module);
@@ -1047,6 +1108,9 @@ fn constant_var() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
 
     assert_eq!(roundtrip(&module), module);
@@ -1239,6 +1303,9 @@ fn deprecated_type() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
     assert_eq!(roundtrip(&module), module);
 }
@@ -1286,6 +1353,9 @@ fn module_fn_value_with_external_implementations() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
 
     assert_eq!(roundtrip(&module), module);
@@ -1336,6 +1406,9 @@ fn internal_module_fn() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
 
     assert_eq!(roundtrip(&module), module);
@@ -1389,6 +1462,9 @@ fn internal_annotated_module_fn() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
 
     assert_eq!(roundtrip(&module), module);
@@ -1429,6 +1505,9 @@ fn type_variable_ids_in_constructors_are_shared() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
 
     let expected = HashMap::from([(
@@ -1489,6 +1568,112 @@ fn type_with_inferred_variant() {
         line_numbers: LineNumbers::new(""),
         src_path: "some_path".into(),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
+    };
+    assert_eq!(roundtrip(&module), module);
+}
+
+#[test]
+fn inferred_annotations() {
+    let module = ModuleInterface {
+        warnings: vec![],
+        is_internal: false,
+        package: "some_package".into(),
+        origin: Origin::Src,
+        name: "a/b".into(),
+        types: HashMap::new(),
+        types_value_constructors: HashMap::new(),
+        values: HashMap::new(),
+        accessors: HashMap::new(),
+        line_numbers: LineNumbers::new(""),
+        src_path: "some_path".into(),
+        minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![
+            InferredAnnotation {
+                location: SrcSpan::new(5, 6),
+                type_: type_::list(type_::int()),
+                kind: InferredAnnotationKind::LetBinding,
+            },
+            InferredAnnotation {
+                location: SrcSpan::new(12, 13),
+                type_: type_::string(),
+                kind: InferredAnnotationKind::Hole,
+            },
+        ],
+        function_references: vec![],
+        references: vec![],
+    };
+    assert_eq!(roundtrip(&module), module);
+}
+
+#[test]
+fn function_references() {
+    let module = ModuleInterface {
+        warnings: vec![],
+        is_internal: false,
+        package: "some_package".into(),
+        origin: Origin::Src,
+        name: "a/b".into(),
+        types: HashMap::new(),
+        types_value_constructors: HashMap::new(),
+        values: HashMap::new(),
+        accessors: HashMap::new(),
+        line_numbers: LineNumbers::new(""),
+        src_path: "some_path".into(),
+        minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![
+            FunctionReference {
+                function: "main".into(),
+                module: "a/b".into(),
+                name: "helper".into(),
+            },
+            FunctionReference {
+                function: "main".into(),
+                module: "c".into(),
+                name: "wibble".into(),
+            },
+        ],
+        references: vec![],
+    };
+    assert_eq!(roundtrip(&module), module);
+}
+
+#[test]
+fn references() {
+    let module = ModuleInterface {
+        warnings: vec![],
+        is_internal: false,
+        package: "some_package".into(),
+        origin: Origin::Src,
+        name: "a/b".into(),
+        types: HashMap::new(),
+        types_value_constructors: HashMap::new(),
+        values: HashMap::new(),
+        accessors: HashMap::new(),
+        line_numbers: LineNumbers::new(""),
+        src_path: "some_path".into(),
+        minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![
+            Reference {
+                location: SrcSpan::new(5, 11),
+                module: "c".into(),
+                name: "Wibble".into(),
+                type_: type_::int(),
+                kind: ReferenceKind::Type,
+            },
+            Reference {
+                location: SrcSpan::new(20, 26),
+                module: "c".into(),
+                name: "wobble".into(),
+                type_: type_::fn_(vec![type_::int()], type_::string()),
+                kind: ReferenceKind::Value,
+            },
+        ],
     };
     assert_eq!(roundtrip(&module), module);
 }
@@ -394,6 +394,7 @@ impl ModuleInterface {
                     opaque,
                     deprecation,
                     typed_parameters,
+                    phantom: _,
                     parameters: _,
                     location: _,
                     name_location: _,
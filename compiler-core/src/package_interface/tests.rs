@@ -8,7 +8,10 @@ use hexpm::version::Identifier;
 use crate::{
     analyse::TargetSupport,
     build::{Module, Origin, Package, Target},
-    config::{Docs, ErlangConfig, JavaScriptConfig, PackageConfig, Repository},
+    config::{
+        Docs, ErlangConfig, FormatConfig, JavaScriptConfig, PackageConfig, Repository,
+        WarningsConfig,
+    },
     line_numbers::LineNumbers,
     type_::PRELUDE_MODULE_NAME,
     uid::UniqueIdGenerator,
@@ -159,7 +162,13 @@ fn package_from_module(module: Module) -> Package {
             ),
             licences: vec![],
             description: "description".into(),
-            documentation: Docs { pages: vec![] },
+            documentation: Docs {
+                pages: vec![],
+                doctests: false,
+                logo: None,
+                favicon: None,
+                assets: vec![],
+            },
             dependencies: std::collections::HashMap::new(),
             dev_dependencies: std::collections::HashMap::new(),
             repository: Repository::default(),
@@ -167,6 +176,8 @@ fn package_from_module(module: Module) -> Package {
             erlang: ErlangConfig::default(),
             javascript: JavaScriptConfig::default(),
             target: Target::Erlang,
+            warnings: WarningsConfig::default(),
+            format: FormatConfig::default(),
             internal_modules: Some(vec![GlobBuilder::new("internals/*")
                 .build()
                 .expect("internals glob")]),
@@ -52,7 +52,7 @@
 pub mod error;
 pub mod extra;
 pub mod lexer;
-mod token;
+pub mod token;
 
 use crate::analyse::Inferred;
 use crate::ast::{
@@ -122,6 +122,7 @@ struct Attributes {
     external_erlang: Option<(EcoString, EcoString, SrcSpan)>,
     external_javascript: Option<(EcoString, EcoString, SrcSpan)>,
     internal: InternalAttribute,
+    phantom: Option<SrcSpan>,
 }
 
 impl Attributes {
@@ -354,6 +355,12 @@ where
             }
         }?;
 
+        // The `@phantom` attribute is taken by custom types, if it's still
+        // there then it was used on something else.
+        if let Some(location) = attributes.phantom {
+            return parse_error(ParseErrorType::ExpectedCustomTypeDefinition, location);
+        }
+
         match (def, location) {
             (Some(definition), _) if definition.is_function() => Ok(Some(TargetedDefinition {
                 definition,
@@ -2215,6 +2222,7 @@ where
                             || attributes.external_javascript.is_some()
                             || attributes.target.is_some()
                             || attributes.internal != InternalAttribute::Missing
+                            || attributes.phantom.is_some()
                         {
                             return parse_error(
                                 ParseErrorType::UnknownAttributeRecordVariant,
@@ -2279,6 +2287,7 @@ where
             end_position,
             publicity: self.publicity(public, attributes.internal)?,
             opaque,
+            phantom: attributes.phantom.take().is_some(),
             name,
             name_location,
             parameters,
@@ -3644,6 +3653,7 @@ functions are declared separately from types.";
                 self.parse_deprecated_attribute(start, end, attributes)
             }
             "internal" => self.parse_internal_attribute(start, end, attributes),
+            "phantom" => self.parse_phantom_attribute(start, end, attributes),
             _ => parse_error(ParseErrorType::UnknownAttribute, SrcSpan { start, end }),
         }?;
 
@@ -3715,6 +3725,19 @@ functions are declared separately from types.";
         Ok(end)
     }
 
+    fn parse_phantom_attribute(
+        &mut self,
+        start: u32,
+        end: u32,
+        attributes: &mut Attributes,
+    ) -> Result<u32, ParseError> {
+        if attributes.phantom.is_some() {
+            return parse_error(ParseErrorType::DuplicateAttribute, SrcSpan::new(start, end));
+        }
+        attributes.phantom = Some(SrcSpan::new(start, end));
+        Ok(end)
+    }
+
     fn parse_internal_attribute(
         &mut self,
         start: u32,
@@ -654,6 +654,53 @@ pub fn main() -> Nil {
     );
 }
 
+#[test]
+fn multiple_phantom_attributes() {
+    assert_module_error!(
+        r#"
+@phantom
+@phantom
+pub type Wibble(a) {
+  Wibble
+}
+"#
+    );
+}
+
+#[test]
+fn phantom_attribute_on_function() {
+    assert_module_error!(
+        r#"
+@phantom
+pub fn main() -> Nil {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn phantom_attribute_on_type_alias() {
+    assert_module_error!(
+        r#"
+@phantom
+pub type Wibble(a) = Int
+"#
+    );
+}
+
+#[test]
+fn phantom_attribute_on_type_variant() {
+    assert_module_error!(
+        r#"
+pub type Wibble(a) {
+  @phantom
+  Wibble
+}
+"#
+    );
+}
+
 #[test]
 fn attributes_with_no_definition() {
     assert_module_error!(
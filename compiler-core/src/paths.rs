@@ -82,6 +82,14 @@ impl ProjectPaths {
         self.build_directory().join("erlang-shipment")
     }
 
+    pub fn javascript_browser_directory(&self) -> Utf8PathBuf {
+        self.build_directory().join("javascript-browser")
+    }
+
+    pub fn build_export_escript(&self, package_name: &str) -> Utf8PathBuf {
+        self.build_directory().join("escript").join(package_name)
+    }
+
     pub fn build_documentation_directory(&self, package: &str) -> Utf8PathBuf {
         self.build_directory_for_mode(Mode::Dev)
             .join("docs")
@@ -161,6 +161,11 @@ pub enum Document<'a> {
         kind: BreakKind,
     },
 
+    /// Renders the given number of spaces if the group is broken, and nothing
+    /// otherwise. Used to line up what follows it on the lines of a broken
+    /// group.
+    PaddingIfBroken(isize),
+
     /// Join multiple documents together
     Vec(Vec<Self>),
 
@@ -347,6 +352,11 @@ fn fits(
                 Mode::Unbroken | Mode::ForcedUnbroken => current_width += unbroken.len() as isize,
             },
 
+            Document::PaddingIfBroken(padding) => match mode {
+                Mode::Broken | Mode::ForcedBroken => current_width += padding,
+                Mode::Unbroken | Mode::ForcedUnbroken => (),
+            },
+
             // The `NextBreakFits` can alter the current mode to `ForcedBroken`
             // or `ForcedUnbroken` based on its enabled flag.
             Document::NextBreakFits(doc, enabled) => match enabled {
@@ -480,6 +490,16 @@ fn format(
                 }
             },
 
+            Document::PaddingIfBroken(padding) => match mode {
+                Mode::Broken | Mode::ForcedBroken => {
+                    for _ in 0..*padding {
+                        writer.str_write(" ")?;
+                    }
+                    width += padding;
+                }
+                Mode::Unbroken | Mode::ForcedUnbroken => (),
+            },
+
             // Strings are printed as they are and the current width is
             // increased accordingly.
             Document::EcoString { string, graphemes } => {
@@ -575,6 +595,10 @@ pub fn break_<'a>(broken: &'a str, unbroken: &'a str) -> Document<'a> {
     }
 }
 
+pub fn padding_if_broken<'a>(padding: isize) -> Document<'a> {
+    Document::PaddingIfBroken(padding)
+}
+
 pub fn flex_break<'a>(broken: &'a str, unbroken: &'a str) -> Document<'a> {
     Document::Break {
         broken,
@@ -669,6 +693,7 @@ impl<'a> Document<'a> {
             Str { string, .. } => string.is_empty(),
             // assuming `broken` and `unbroken` are equivalent
             Break { broken, .. } => broken.is_empty(),
+            PaddingIfBroken(padding) => *padding == 0,
             ForceBroken(d) | Nest(_, _, _, d) | Group(d) | NextBreakFits(d, _) => d.is_empty(),
             Vec(docs) => docs.iter().all(|d| d.is_empty()),
         }
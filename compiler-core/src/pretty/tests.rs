@@ -318,3 +318,10 @@ fn set_nesting() {
         doc.set_nesting(0).nest(2).to_pretty_string(1)
     );
 }
+
+#[test]
+fn padding_if_broken_test() {
+    let doc = crate::docvec!["a", padding_if_broken(3), "b", break_("", " "), "c"].group();
+    assert_eq!("ab c", doc.clone().to_pretty_string(80));
+    assert_eq!("a   b\nc", doc.to_pretty_string(2));
+}
@@ -0,0 +1,350 @@
+//! Finding the module values referenced in a module.
+//!
+//! The references found here, together with the type references recorded
+//! when hydrating type annotations, are stored in the module's interface so
+//! that tooling can find and rename the uses of a value or type across
+//! packages without having to analyse their source code again.
+
+#[cfg(test)]
+mod tests;
+
+use std::sync::Arc;
+
+use ecow::EcoString;
+
+use crate::{
+    analyse::Inferred,
+    ast::{
+        visit::{self, Visit},
+        CallArg, Definition, SrcSpan, TypedConstant, TypedDefinition, TypedFunction, TypedPattern,
+    },
+    type_::{
+        ModuleValueConstructor, PatternConstructor, Reference, ReferenceKind, Type,
+        ValueConstructor, ValueConstructorVariant,
+    },
+};
+
+/// A value referred to by name in the code of a module. The location is that
+/// of the name as it's written, excluding any module qualifier.
+///
+/// This is shared by everything that needs to know which values some code
+/// refers to: the references stored in the module's interface, the functions
+/// called by each function, and the references used by the language server.
+///
+#[derive(Debug, Clone, Copy)]
+pub enum ValueUsage<'a> {
+    /// A name resolved to a local variable or a module value when the module
+    /// was analysed, as in `wibble`. In constants this can also be qualified,
+    /// as in `wobble.wibble`.
+    Name {
+        location: SrcSpan,
+        name: &'a EcoString,
+        constructor: &'a ValueConstructor,
+    },
+    /// A value of an imported module, as in `wobble.wibble`.
+    ModuleSelect {
+        location: SrcSpan,
+        module: &'a EcoString,
+        label: &'a EcoString,
+        constructor: &'a ModuleValueConstructor,
+        type_: &'a Arc<Type>,
+    },
+    /// A record constructor used in a pattern or in a constant. `name` is
+    /// the name it has in the module defining it, which is not `used_name`
+    /// when it has been imported with an alias.
+    Constructor {
+        location: SrcSpan,
+        module: &'a EcoString,
+        name: &'a EcoString,
+        used_name: &'a EcoString,
+        type_: &'a Arc<Type>,
+    },
+}
+
+impl<'a> ValueUsage<'a> {
+    pub fn name(location: SrcSpan, name: &'a EcoString, constructor: &'a ValueConstructor) -> Self {
+        ValueUsage::Name {
+            location: SrcSpan::new(location.start, location.start + name.len() as u32),
+            name,
+            constructor,
+        }
+    }
+
+    pub fn module_select(
+        location: SrcSpan,
+        module: &'a EcoString,
+        label: &'a EcoString,
+        constructor: &'a ModuleValueConstructor,
+        type_: &'a Arc<Type>,
+    ) -> Self {
+        ValueUsage::ModuleSelect {
+            location: SrcSpan::new(location.end - label.len() as u32, location.end),
+            module,
+            label,
+            constructor,
+            type_,
+        }
+    }
+
+    pub fn pattern_constructor(
+        location: SrcSpan,
+        used_name: &'a EcoString,
+        module: &'a Option<(EcoString, SrcSpan)>,
+        constructor: &'a Inferred<PatternConstructor>,
+        type_: &'a Arc<Type>,
+    ) -> Option<Self> {
+        let Inferred::Known(constructor) = constructor else {
+            return None;
+        };
+        Some(ValueUsage::Constructor {
+            location: name_location(location, module, used_name),
+            module: &constructor.module,
+            name: &constructor.name,
+            used_name,
+            type_,
+        })
+    }
+}
+
+/// Calls `f` with each value referred to in a function.
+pub fn function_usages<'a>(function: &'a TypedFunction, f: &mut impl FnMut(ValueUsage<'a>)) {
+    UsageVisitor { f }.visit_typed_function(function);
+}
+
+/// Calls `f` with each value referred to in a constant.
+pub fn constant_usages<'a>(constant: &'a TypedConstant, f: &mut impl FnMut(ValueUsage<'a>)) {
+    match constant {
+        TypedConstant::Int { .. }
+        | TypedConstant::Float { .. }
+        | TypedConstant::String { .. }
+        | TypedConstant::Invalid { .. } => (),
+
+        TypedConstant::Tuple { elements, .. } | TypedConstant::List { elements, .. } => {
+            for element in elements {
+                constant_usages(element, f);
+            }
+        }
+
+        TypedConstant::Record {
+            location,
+            module,
+            name,
+            args,
+            tag,
+            type_,
+            ..
+        } => {
+            if let Type::Named {
+                module: type_module,
+                ..
+            } = type_.as_ref()
+            {
+                f(ValueUsage::Constructor {
+                    location: name_location(*location, module, name),
+                    module: type_module,
+                    name: tag,
+                    used_name: name,
+                    type_,
+                });
+            }
+            for arg in args {
+                constant_usages(&arg.value, f);
+            }
+        }
+
+        TypedConstant::BitArray { segments, .. } => {
+            for segment in segments {
+                constant_usages(&segment.value, f);
+            }
+        }
+
+        TypedConstant::StringConcatenation { left, right, .. } => {
+            constant_usages(left, f);
+            constant_usages(right, f);
+        }
+
+        TypedConstant::Var {
+            location,
+            module,
+            name,
+            constructor,
+            ..
+        } => {
+            if let Some(constructor) = constructor {
+                f(ValueUsage::name(
+                    name_location(*location, module, name),
+                    name,
+                    constructor,
+                ));
+            }
+        }
+    }
+}
+
+/// The location of a possibly qualified name, excluding the module qualifier.
+fn name_location(
+    location: SrcSpan,
+    module: &Option<(EcoString, SrcSpan)>,
+    name: &EcoString,
+) -> SrcSpan {
+    let start = match module {
+        Some((_, module_location)) => module_location.end + 1,
+        None => location.start,
+    };
+    SrcSpan::new(start, start + name.len() as u32)
+}
+
+struct UsageVisitor<'f, F> {
+    f: &'f mut F,
+}
+
+impl<'a, F> Visit<'a> for UsageVisitor<'_, F>
+where
+    F: FnMut(ValueUsage<'a>),
+{
+    fn visit_typed_expr_var(
+        &mut self,
+        location: &'a SrcSpan,
+        constructor: &'a ValueConstructor,
+        name: &'a EcoString,
+    ) {
+        (self.f)(ValueUsage::name(*location, name, constructor));
+    }
+
+    fn visit_typed_expr_module_select(
+        &mut self,
+        location: &'a SrcSpan,
+        type_: &'a Arc<Type>,
+        label: &'a EcoString,
+        module_name: &'a EcoString,
+        module_alias: &'a EcoString,
+        constructor: &'a ModuleValueConstructor,
+    ) {
+        (self.f)(ValueUsage::module_select(
+            *location,
+            module_name,
+            label,
+            constructor,
+            type_,
+        ));
+        visit::visit_typed_expr_module_select(
+            self,
+            location,
+            type_,
+            label,
+            module_name,
+            module_alias,
+            constructor,
+        );
+    }
+
+    fn visit_typed_pattern_var_usage(
+        &mut self,
+        location: &'a SrcSpan,
+        name: &'a EcoString,
+        constructor: &'a Option<ValueConstructor>,
+        _type_: &'a Arc<Type>,
+    ) {
+        if let Some(constructor) = constructor {
+            (self.f)(ValueUsage::name(*location, name, constructor));
+        }
+    }
+
+    fn visit_typed_pattern_constructor(
+        &mut self,
+        location: &'a SrcSpan,
+        name: &'a EcoString,
+        arguments: &'a Vec<CallArg<TypedPattern>>,
+        module: &'a Option<(EcoString, SrcSpan)>,
+        constructor: &'a Inferred<PatternConstructor>,
+        spread: &'a Option<SrcSpan>,
+        type_: &'a Arc<Type>,
+    ) {
+        if let Some(usage) =
+            ValueUsage::pattern_constructor(*location, name, module, constructor, type_)
+        {
+            (self.f)(usage);
+        }
+        visit::visit_typed_pattern_constructor(
+            self,
+            location,
+            name,
+            arguments,
+            module,
+            constructor,
+            spread,
+            type_,
+        );
+    }
+}
+
+/// Collects the references to module functions, constants and record
+/// constructors in the given definitions.
+///
+pub fn value_references(definitions: &[TypedDefinition]) -> Vec<Reference> {
+    let mut references = vec![];
+    let mut push = |usage| {
+        if let Some(reference) = value_reference(usage) {
+            references.push(reference);
+        }
+    };
+    for definition in definitions {
+        match definition {
+            Definition::Function(function) => function_usages(function, &mut push),
+            Definition::ModuleConstant(constant) => constant_usages(&constant.value, &mut push),
+            Definition::TypeAlias(_) | Definition::CustomType(_) | Definition::Import(_) => (),
+        }
+    }
+    references
+}
+
+fn value_reference(usage: ValueUsage<'_>) -> Option<Reference> {
+    let (location, module, name, type_) = match usage {
+        ValueUsage::Name {
+            location,
+            name,
+            constructor,
+        } => match &constructor.variant {
+            ValueConstructorVariant::ModuleFn { module, name, .. }
+            | ValueConstructorVariant::Record { module, name, .. } => {
+                (location, module, name, &constructor.type_)
+            }
+            ValueConstructorVariant::ModuleConstant { module, .. } => {
+                (location, module, name, &constructor.type_)
+            }
+            ValueConstructorVariant::LocalVariable { .. }
+            | ValueConstructorVariant::LocalConstant { .. } => return None,
+        },
+
+        ValueUsage::ModuleSelect {
+            location,
+            module,
+            label,
+            constructor,
+            type_,
+        } => {
+            let name = match constructor {
+                ModuleValueConstructor::Fn { name, .. }
+                | ModuleValueConstructor::Record { name, .. } => name,
+                ModuleValueConstructor::Constant { .. } => label,
+            };
+            (location, module, name, type_)
+        }
+
+        ValueUsage::Constructor {
+            location,
+            module,
+            name,
+            type_,
+            ..
+        } => (location, module, name, type_),
+    };
+
+    Some(Reference {
+        location,
+        module: module.clone(),
+        name: name.clone(),
+        type_: type_.clone(),
+        kind: ReferenceKind::Value,
+    })
+}
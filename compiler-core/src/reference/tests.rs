@@ -0,0 +1,123 @@
+use itertools::Itertools;
+
+use crate::type_::{tests::compile_module, ReferenceKind};
+
+/// Compiles the given module, with a `wibble` dependency module, and returns
+/// its references as `kind module.name start-end` strings.
+fn references(src: &str) -> Vec<String> {
+    let dep = r#"
+pub type Wibble {
+  Wibble(Int)
+  Wobble
+}
+
+pub const zero = 0
+
+pub fn wibble(x: Int) -> Wibble {
+  Wibble(x)
+}
+"#;
+    let module = compile_module("main", src, None, vec![("thepackage", "wibble", dep)])
+        .expect("should successfully infer");
+    module
+        .type_info
+        .references
+        .iter()
+        .map(|reference| {
+            let kind = match reference.kind {
+                ReferenceKind::Value => "value",
+                ReferenceKind::Type => "type",
+            };
+            format!(
+                "{kind} {}.{} {}-{}",
+                reference.module, reference.name, reference.location.start, reference.location.end
+            )
+        })
+        .collect_vec()
+}
+
+#[test]
+fn qualified_references() {
+    assert_eq!(
+        references(
+            "import wibble
+pub fn main() -> wibble.Wibble {
+  wibble.wibble(wibble.zero)
+}"
+        ),
+        vec![
+            "type wibble.Wibble 38-44",
+            "value wibble.wibble 56-62",
+            "value wibble.zero 70-74",
+        ]
+    );
+}
+
+#[test]
+fn unqualified_references() {
+    assert_eq!(
+        references(
+            "import wibble.{type Wibble, Wobble, wibble as wobble}
+pub fn main(x: Wibble) {
+  case x {
+    Wobble -> wobble(1)
+    _ -> x
+  }
+}"
+        ),
+        vec![
+            "type wibble.Wibble 69-75",
+            "value wibble.Wobble 94-100",
+            "value wibble.wibble 104-110",
+        ]
+    );
+}
+
+#[test]
+fn references_in_constants() {
+    assert_eq!(
+        references(
+            "import wibble
+pub const value = #(wibble.Wibble(wibble.zero), local)
+const local = 1
+"
+        ),
+        vec![
+            "value wibble.Wibble 41-47",
+            "value wibble.zero 55-59",
+            "value main.local 62-67",
+        ]
+    );
+}
+
+#[test]
+fn references_to_local_definitions() {
+    assert_eq!(
+        references(
+            "pub type Box { Box(Int) }
+pub fn main() {
+  let Box(x) = new()
+  x
+}
+fn new() { Box(1) }"
+        ),
+        vec![
+            "type gleam.Int 19-22",
+            "value main.Box 48-51",
+            "value main.new 57-60",
+            "value main.Box 80-83",
+        ]
+    );
+}
+
+#[test]
+fn references_to_aliased_constructor_in_constant() {
+    assert_eq!(
+        references(
+            "import wibble.{Wibble as Wabble}
+pub const value = Wabble(1)
+"
+        ),
+        vec!["value wibble.Wibble 51-57"]
+    );
+}
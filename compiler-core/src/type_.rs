@@ -138,6 +138,28 @@ impl Type {
         }
     }
 
+    /// Gets the ok and error types of a result. Returns `None` if the type is
+    /// not a result.
+    pub fn result_types(&self) -> Option<(Arc<Type>, Arc<Type>)> {
+        match self.named_type_information()? {
+            (module, name, args) if "Result" == name && is_prelude_module(&module) => {
+                Some((args.first()?.clone(), args.get(1)?.clone()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Gets the type of the elements of a list. Returns `None` if the type is
+    /// not a list.
+    pub fn list_element_type(&self) -> Option<Arc<Type>> {
+        match self.named_type_information()? {
+            (module, name, args) if "List" == name && is_prelude_module(&module) => {
+                args.first().cloned()
+            }
+            _ => None,
+        }
+    }
+
     pub fn is_unbound(&self) -> bool {
         match self {
             Self::Var { type_ } => type_.borrow().is_unbound(),
@@ -167,6 +189,86 @@ impl Type {
         }
     }
 
+    /// If this is a type variable, returns its id.
+    pub fn type_variable_id(&self) -> Option<u64> {
+        match self {
+            Self::Var { type_ } => match type_.borrow().deref() {
+                TypeVar::Link { type_ } => type_.type_variable_id(),
+                TypeVar::Unbound { id } | TypeVar::Generic { id } => Some(*id),
+            },
+            Self::Named { .. } | Self::Fn { .. } | Self::Tuple { .. } => None,
+        }
+    }
+
+    /// Whether the type variable with the given id appears anywhere in this
+    /// type.
+    pub fn contains_type_variable(&self, id: u64) -> bool {
+        match self {
+            Self::Named { args, .. } => args.iter().any(|arg| arg.contains_type_variable(id)),
+            Self::Fn { args, retrn } => {
+                args.iter().any(|arg| arg.contains_type_variable(id))
+                    || retrn.contains_type_variable(id)
+            }
+            Self::Tuple { elems } => elems.iter().any(|elem| elem.contains_type_variable(id)),
+            Self::Var { type_ } => match type_.borrow().deref() {
+                TypeVar::Link { type_ } => type_.contains_type_variable(id),
+                TypeVar::Unbound { id: var_id } | TypeVar::Generic { id: var_id } => *var_id == id,
+            },
+        }
+    }
+
+    /// Whether the `Int` type appears anywhere in this type.
+    pub fn contains_int(&self) -> bool {
+        match self {
+            Self::Named { args, .. } => self.is_int() || args.iter().any(|arg| arg.contains_int()),
+            Self::Fn { args, retrn } => {
+                args.iter().any(|arg| arg.contains_int()) || retrn.contains_int()
+            }
+            Self::Tuple { elems } => elems.iter().any(|elem| elem.contains_int()),
+            Self::Var { type_ } => match type_.borrow().deref() {
+                TypeVar::Link { type_ } => type_.contains_int(),
+                TypeVar::Unbound { .. } | TypeVar::Generic { .. } => false,
+            },
+        }
+    }
+
+    /// The ids of the type variables appearing in this type, in the order
+    /// they first appear.
+    pub fn type_variable_ids(&self) -> Vec<u64> {
+        let mut ids = vec![];
+        self.collect_type_variable_ids(&mut ids);
+        ids
+    }
+
+    fn collect_type_variable_ids(&self, ids: &mut Vec<u64>) {
+        match self {
+            Self::Named { args, .. } => {
+                for arg in args {
+                    arg.collect_type_variable_ids(ids);
+                }
+            }
+            Self::Fn { args, retrn } => {
+                for arg in args {
+                    arg.collect_type_variable_ids(ids);
+                }
+                retrn.collect_type_variable_ids(ids);
+            }
+            Self::Tuple { elems } => {
+                for elem in elems {
+                    elem.collect_type_variable_ids(ids);
+                }
+            }
+            Self::Var { type_ } => match type_.borrow().deref() {
+                TypeVar::Link { type_ } => type_.collect_type_variable_ids(ids),
+                TypeVar::Unbound { id } | TypeVar::Generic { id } => {
+                    if !ids.contains(id) {
+                        ids.push(*id);
+                    }
+                }
+            },
+        }
+    }
+
     pub fn fn_types(&self) -> Option<(Vec<Arc<Self>>, Arc<Self>)> {
         match self {
             Self::Fn { args, retrn, .. } => Some((args.clone(), retrn.clone())),
@@ -737,6 +839,17 @@ pub struct ModuleInterface {
     pub warnings: Vec<Warning>,
     /// The minimum Gleam version needed to use this module.
     pub minimum_required_version: Version,
+    /// The types inferred for the `let` bindings and type annotation holes
+    /// in this module, ordered by location.
+    pub inferred_annotations: Vec<InferredAnnotation>,
+    /// The module functions referenced by each of this module's functions,
+    /// used to find the public functions that are never used in a project.
+    pub function_references: Vec<FunctionReference>,
+    /// The module values and types referenced in this module, with their
+    /// resolved module and location, ordered by location. This is used by
+    /// tooling to find references across packages without having to analyse
+    /// their source code again.
+    pub references: Vec<Reference>,
 }
 
 impl ModuleInterface {
@@ -745,6 +858,63 @@ impl ModuleInterface {
     }
 }
 
+/// A reference to a module function from the body of a function.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FunctionReference {
+    /// The name of the function containing the reference.
+    pub function: EcoString,
+    /// The module defining the referenced function.
+    pub module: EcoString,
+    /// The name of the referenced function.
+    pub name: EcoString,
+}
+
+/// A reference to a module value or type, resolved to the module that
+/// defines it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    /// The location of the referenced name, excluding any module qualifier.
+    pub location: SrcSpan,
+    /// The module defining the referenced value or type.
+    pub module: EcoString,
+    /// The name of the value or type in the module defining it.
+    pub name: EcoString,
+    pub type_: Arc<Type>,
+    pub kind: ReferenceKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceKind {
+    /// A function, constant or record constructor.
+    Value,
+    Type,
+}
+
+/// The type inferred for a place in the source where a type annotation could
+/// be written.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InferredAnnotation {
+    pub location: SrcSpan,
+    pub type_: Arc<Type>,
+    pub kind: InferredAnnotationKind,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InferredAnnotationKind {
+    /// A `_` hole inside a type annotation, such as `List(_)`. The location
+    /// is that of the hole itself.
+    Hole,
+    /// A `let` binding. The location is that of the pattern, whether or not
+    /// it already has an annotation.
+    LetBinding,
+}
+
+impl InferredAnnotation {
+    pub fn is_hole(&self) -> bool {
+        self.kind == InferredAnnotationKind::Hole
+    }
+}
+
 /// Information on the constructors of a custom type.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct TypeVariantConstructors {
@@ -817,6 +987,27 @@ impl ModuleInterface {
         }
     }
 
+    /// If the given name is a constructor of a public opaque type defined in
+    /// this module, returns the name of that type.
+    ///
+    /// The constructors of an opaque type are stored as private values, so
+    /// they can be told apart from regular private records by the publicity
+    /// of the type they construct.
+    pub fn get_opaque_type_constructor(&self, name: &str) -> Option<EcoString> {
+        let value = self.values.get(name)?;
+        if !value.publicity.is_private() || !value.variant.is_record() {
+            return None;
+        }
+        let type_ = value.type_.return_type().unwrap_or(value.type_.clone());
+        let (_, type_name) = type_.named_type_name()?;
+        let type_constructor = self.get_public_type(&type_name)?;
+        if type_constructor.module == self.name {
+            Some(type_name)
+        } else {
+            None
+        }
+    }
+
     pub fn get_public_type(&self, name: &str) -> Option<&TypeConstructor> {
         let type_ = self.types.get(name)?;
         if type_.publicity.is_importable() {
@@ -844,6 +1035,28 @@ impl ModuleInterface {
         })
     }
 
+    /// The names of the public zero arity functions whose names end with
+    /// `_test` and that support the given target, in alphabetical order.
+    pub fn test_function_names(&self, target: Target) -> Vec<EcoString> {
+        self.values
+            .iter()
+            .filter(|(name, value)| {
+                name.ends_with("_test")
+                    && value.publicity.is_importable()
+                    && matches!(
+                        &value.variant,
+                        ValueConstructorVariant::ModuleFn {
+                            arity: 0,
+                            implementations,
+                            ..
+                        } if implementations.supports(target)
+                    )
+            })
+            .map(|(name, _)| name.clone())
+            .sorted()
+            .collect_vec()
+    }
+
     pub fn public_value_names(&self) -> Vec<EcoString> {
         self.values
             .iter()
@@ -1180,14 +1393,17 @@ fn assert_no_labelled_arguments<A>(args: &[CallArg<A>]) -> Result<(), Error> {
 /// could cause naively-implemented type checking to diverge.
 /// While traversing the type tree.
 ///
-fn unify_unbound_type(type_: Arc<Type>, own_id: u64) -> Result<(), UnifyError> {
+/// An error is returned if the type variable does occur in the type, and it
+/// is up to the caller to report which types were being unified.
+///
+fn unify_unbound_type(type_: Arc<Type>, own_id: u64) -> Result<(), ()> {
     if let Type::Var { type_ } = type_.deref() {
         let new_value = match type_.borrow().deref() {
             TypeVar::Link { type_, .. } => return unify_unbound_type(type_.clone(), own_id),
 
             TypeVar::Unbound { id } => {
                 if id == &own_id {
-                    return Err(UnifyError::RecursiveType);
+                    return Err(());
                 } else {
                     Some(TypeVar::Unbound { id: *id })
                 }
@@ -3,13 +3,13 @@ use pubgrub::range::Range;
 use crate::{
     analyse::TargetSupport,
     ast::{Publicity, PIPE_VARIABLE},
-    build::Target,
+    build::{IntRepresentation, Target},
     error::edit_distance,
     uid::UniqueIdGenerator,
 };
 
 use super::*;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 #[derive(Debug)]
 pub struct Environment<'a> {
@@ -63,6 +63,48 @@ pub struct Environment<'a> {
     /// compilation target.
     pub target_support: TargetSupport,
 
+    /// Functions and constants of the current module that are excluded from
+    /// compilation because they are only defined for another target, along
+    /// with the target they are defined for. Used to give a helpful error
+    /// when one of them is referenced.
+    pub values_for_other_targets: HashMap<EcoString, Target>,
+
+    /// Functions of the current module whose body can never finish evaluating
+    /// because it always ends in a `panic`, `todo` or another such call.
+    pub diverging_functions: HashSet<EcoString>,
+
+    /// The targets the package is used on: the one being compiled for and
+    /// the one set in its `gleam.toml`.
+    pub supported_targets: Vec<Target>,
+
+    /// Whether to warn when a local variable shadows another one, as enabled
+    /// in the `[warnings]` section of the package's `gleam.toml`.
+    pub warn_on_shadowing: bool,
+
+    /// The oldest OTP release the package supports, as declared in the
+    /// `[erlang]` section of the package's `gleam.toml`.
+    pub minimum_otp_version: Option<u32>,
+
+    /// How ints are represented on JavaScript, as declared in the
+    /// `[javascript]` section of the package's `gleam.toml`.
+    pub javascript_int_representation: IntRepresentation,
+
+    /// The name of the module constant currently being inferred, if any.
+    /// While inferring a constant, references to private constants are
+    /// recorded as dependencies of that constant rather than as usages, so
+    /// that constants only used by other unused constants can be reported.
+    pub current_constant: Option<EcoString>,
+
+    /// The private constants referenced by each module constant.
+    pub constant_dependencies: HashMap<EcoString, Vec<EcoString>>,
+
+    /// The types inferred for `let` bindings and type annotation holes in the
+    /// current module, so that tooling can insert the annotations.
+    pub inferred_annotations: Vec<InferredAnnotation>,
+
+    /// The types referenced in the current module's type annotations.
+    pub references: Vec<Reference>,
+
     pub names: Names,
 }
 
@@ -114,6 +156,16 @@ impl<'a> Environment<'a> {
             current_module,
             entity_usages: vec![HashMap::new()],
             target_support,
+            values_for_other_targets: HashMap::new(),
+            diverging_functions: HashSet::new(),
+            supported_targets: vec![],
+            warn_on_shadowing: false,
+            minimum_otp_version: None,
+            javascript_int_representation: IntRepresentation::Number,
+            current_constant: None,
+            constant_dependencies: HashMap::new(),
+            inferred_annotations: vec![],
+            references: vec![],
             names,
         }
     }
@@ -149,6 +201,20 @@ pub struct ScopeResetData {
 }
 
 impl Environment<'_> {
+    /// Whether the code of this module could run on JavaScript, either
+    /// because it is being compiled for it or because the package supports
+    /// it.
+    pub fn supports_javascript(&self) -> bool {
+        self.target == Target::JavaScript || self.supported_targets.contains(&Target::JavaScript)
+    }
+
+    /// Whether Int literals could lose precision when this module runs on
+    /// JavaScript, which is not the case when ints are represented as BigInts.
+    pub fn has_unsafe_javascript_ints(&self) -> bool {
+        self.supports_javascript()
+            && self.javascript_int_representation == IntRepresentation::Number
+    }
+
     pub fn in_new_scope<T, E>(
         &mut self,
         problems: &mut Problems,
@@ -230,6 +296,28 @@ impl Environment<'_> {
         );
     }
 
+    /// Emits a warning if shadowing warnings are enabled and defining a local
+    /// variable with the given name would hide another local variable.
+    pub fn warn_if_shadowing(&self, name: &str, location: SrcSpan, problems: &mut Problems) {
+        if !self.warn_on_shadowing {
+            return;
+        }
+        if let Some(ValueConstructor {
+            variant:
+                ValueConstructorVariant::LocalVariable {
+                    location: shadowed_location,
+                },
+            ..
+        }) = self.scope.get(name)
+        {
+            problems.warning(Warning::ShadowedVariable {
+                location,
+                name: name.into(),
+                shadowed_location: *shadowed_location,
+            });
+        }
+    }
+
     /// Insert a constant in the current scope
     pub fn insert_local_constant(
         &mut self,
@@ -458,11 +546,18 @@ impl Environment<'_> {
                 let _ = self.unused_modules.remove(module_name);
                 let _ = self.unused_module_aliases.remove(module_name);
                 module.get_public_value(name).ok_or_else(|| {
-                    UnknownValueConstructorError::ModuleValue {
-                        name: name.clone(),
-                        module_name: module.name.clone(),
-                        value_constructors: module.public_value_names(),
-                        imported_value_as_type: false,
+                    match module.get_opaque_type_constructor(name) {
+                        Some(type_name) => UnknownValueConstructorError::OpaqueTypeConstructor {
+                            name: name.clone(),
+                            module_name: module.name.clone(),
+                            type_name,
+                        },
+                        None => UnknownValueConstructorError::ModuleValue {
+                            name: name.clone(),
+                            module_name: module.name.clone(),
+                            value_constructors: module.public_value_names(),
+                            imported_value_as_type: false,
+                        },
                     }
                 })
             }
@@ -486,7 +581,7 @@ impl Environment<'_> {
             .collect_vec()
     }
 
-    fn type_value_constructor_to_constructor(
+    pub fn type_value_constructor_to_constructor(
         &self,
         module: &EcoString,
         variant: &TypeValueConstructor,
@@ -494,8 +589,9 @@ impl Environment<'_> {
         if *module == self.current_module {
             self.scope.get(&variant.name)
         } else {
-            let (_, module) = self.imported_modules.get(module)?;
-            module.get_public_value(&variant.name)
+            self.importable_modules
+                .get(module)?
+                .get_public_value(&variant.name)
         }
     }
 
@@ -623,6 +719,14 @@ impl Environment<'_> {
             .rev()
             .find_map(|scope| scope.get_mut(&name))
         {
+            if let (EntityKind::PrivateConstant, Some(constant)) = (&kind, &self.current_constant) {
+                self.constant_dependencies
+                    .entry(constant.clone())
+                    .or_default()
+                    .push(name);
+                break;
+            }
+
             *used = true;
 
             match kind {
@@ -638,10 +742,11 @@ impl Environment<'_> {
     /// Converts entities with a usage count of 0 to warnings.
     /// Returns the list of unused imported module location for the removed unused lsp action.
     pub fn convert_unused_to_warnings(&mut self, problems: &mut Problems) {
-        let unused = self
+        let mut unused = self
             .entity_usages
             .pop()
             .expect("Expected a bottom level of entity usages.");
+        self.mark_constants_used_by_used_constants(&mut unused);
         self.handle_unused(unused, problems);
 
         for (name, location) in self.unused_modules.clone().into_iter() {
@@ -662,6 +767,40 @@ impl Environment<'_> {
         }
     }
 
+    /// Private constants referenced by other constants are only considered
+    /// used if the constant referencing them is used itself, or is public.
+    fn mark_constants_used_by_used_constants(
+        &self,
+        usages: &mut HashMap<EcoString, (EntityKind, SrcSpan, bool)>,
+    ) {
+        let is_used =
+            |usages: &HashMap<_, (EntityKind, SrcSpan, bool)>, name| match usages.get(name) {
+                Some((EntityKind::PrivateConstant, _, used)) => *used,
+                // Public constants are not tracked for usage.
+                _ => true,
+            };
+
+        let mut used_constants: Vec<&EcoString> = self
+            .constant_dependencies
+            .keys()
+            .filter(|constant| is_used(usages, *constant))
+            .collect();
+
+        while let Some(constant) = used_constants.pop() {
+            for dependency in self
+                .constant_dependencies
+                .get(constant)
+                .into_iter()
+                .flatten()
+            {
+                if let Some((_, _, used @ false)) = usages.get_mut(dependency) {
+                    *used = true;
+                    used_constants.push(dependency);
+                }
+            }
+        }
+    }
+
     fn handle_unused(
         &mut self,
         unused: HashMap<EcoString, (EntityKind, SrcSpan, bool)>,
@@ -816,7 +955,10 @@ pub fn unify(t1: Arc<Type>, t2: Arc<Type>) -> Result<(), UnifyError> {
             TypeVar::Link { type_ } => Action::Unify(type_.clone()),
 
             TypeVar::Unbound { id } => {
-                unify_unbound_type(t2.clone(), *id)?;
+                unify_unbound_type(t2.clone(), *id).map_err(|()| UnifyError::RecursiveType {
+                    variable: t1.clone(),
+                    type_: t2.clone(),
+                })?;
                 Action::Link
             }
 
@@ -847,6 +989,7 @@ pub fn unify(t1: Arc<Type>, t2: Arc<Type>) -> Result<(), UnifyError> {
                 expected: t1.clone(),
                 given: t2,
                 situation: None,
+                mismatch_path: vec![],
             }),
         };
     }
@@ -870,8 +1013,13 @@ pub fn unify(t1: Arc<Type>, t2: Arc<Type>) -> Result<(), UnifyError> {
                 ..
             },
         ) if m1 == m2 && n1 == n2 && args1.len() == args2.len() => {
-            for (a, b) in args1.iter().zip(args2) {
-                unify_enclosed_type(t1.clone(), t2.clone(), unify(a.clone(), b.clone()))?;
+            for (i, (a, b)) in args1.iter().zip(args2).enumerate() {
+                unify_enclosed_type(
+                    t1.clone(),
+                    t2.clone(),
+                    TypePathStep::TypeArgument(i),
+                    unify(a.clone(), b.clone()),
+                )?;
             }
             Ok(())
         }
@@ -879,8 +1027,13 @@ pub fn unify(t1: Arc<Type>, t2: Arc<Type>) -> Result<(), UnifyError> {
         (Type::Tuple { elems: elems1, .. }, Type::Tuple { elems: elems2, .. })
             if elems1.len() == elems2.len() =>
         {
-            for (a, b) in elems1.iter().zip(elems2) {
-                unify_enclosed_type(t1.clone(), t2.clone(), unify(a.clone(), b.clone()))?;
+            for (i, (a, b)) in elems1.iter().zip(elems2).enumerate() {
+                unify_enclosed_type(
+                    t1.clone(),
+                    t2.clone(),
+                    TypePathStep::TupleElement(i),
+                    unify(a.clone(), b.clone()),
+                )?;
             }
             Ok(())
         }
@@ -903,17 +1056,18 @@ pub fn unify(t1: Arc<Type>, t2: Arc<Type>) -> Result<(), UnifyError> {
 
             for (i, (a, b)) in args1.iter().zip(args2).enumerate() {
                 unify(a.clone(), b.clone())
-                    .map_err(|_| unify_wrong_arguments(&t1, a, &t2, b, i))?;
+                    .map_err(|error| unify_wrong_arguments(&t1, a, &t2, b, i, error))?;
             }
 
             unify(retrn1.clone(), retrn2.clone())
-                .map_err(|_| unify_wrong_returns(&t1, retrn1, &t2, retrn2))
+                .map_err(|error| unify_wrong_returns(&t1, retrn1, &t2, retrn2, error))
         }
 
         _ => Err(UnifyError::CouldNotUnify {
             expected: t1.clone(),
             given: t2.clone(),
             situation: None,
+            mismatch_path: vec![],
         }),
     }
 }
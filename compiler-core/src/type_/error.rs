@@ -1,20 +1,25 @@
 use super::{
     expression::{ArgumentKind, CallKind},
-    FieldAccessUsage,
+    FieldAccessUsage, FieldMap,
 };
 use crate::{
-    ast::{BinOp, Layer, SrcSpan, TodoKind},
+    ast::{BinOp, CallArg, Layer, SrcSpan, TodoKind},
     build::Target,
-    type_::Type,
+    type_::{ModuleInterface, Type},
 };
 
 use camino::Utf8PathBuf;
-use ecow::EcoString;
+use ecow::{eco_format, EcoString};
 use hexpm::version::Version;
+use itertools::Itertools;
 use num_bigint::BigInt;
+use num_traits::{FromPrimitive, ToPrimitive};
 #[cfg(test)]
 use pretty_assertions::assert_eq;
-use std::sync::Arc;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Arc,
+};
 
 /// Errors and warnings discovered when compiling a module.
 ///
@@ -84,14 +89,25 @@ pub struct UnknownType {
 /// Here the error can be extra useful and explain that to access `field` all
 /// variants should have that field at the same position and with the same type.
 ///
-#[derive(Debug, Eq, PartialEq, Clone, Copy)]
+#[derive(Debug, Eq, PartialEq, Clone)]
 pub enum UnknownField {
     /// The field we're trying to access appears in at least a variant, so it
     /// can be useful to explain why it cannot be accessed and how to fix it
     /// (adding it to all variants/making sure it has the same type/making sure
     /// it's in the same position).
     ///
-    AppearsInAVariant,
+    /// We keep track of which variants have the field, so we can list the
+    /// ones that don't and show how to access it with a case expression.
+    ///
+    AppearsInAVariant {
+        variants: Vec<VariantField>,
+        /// The module defining the custom type, used to refer to its variants
+        /// the way they are imported in the current module.
+        module: EcoString,
+        /// Where the value being accessed is, if the error comes from a field
+        /// access.
+        record_location: Option<SrcSpan>,
+    },
 
     /// The field we are trying to access appears in a variant, but we can
     /// infer that the value we are accessing on is never the one that this
@@ -108,6 +124,16 @@ pub enum UnknownField {
     NoFields,
 }
 
+/// A variant of a custom type, and whether it has the field that is being
+/// accessed.
+///
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct VariantField {
+    pub name: EcoString,
+    pub arity: usize,
+    pub has_field: bool,
+}
+
 /// A suggestion for an unknown module
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub enum ModuleSuggestion {
@@ -181,6 +207,23 @@ pub enum Error {
         hint: UnknownTypeHint,
     },
 
+    /// A function or constant is referenced but it is only defined for a
+    /// target other than the one being compiled for.
+    ///
+    /// ```gleam
+    /// @target(javascript)
+    /// fn wibble() { 1 }
+    ///
+    /// pub fn main() { wibble() } // <- error when compiling to Erlang
+    /// ```
+    ///
+    ValueDefinedForOtherTarget {
+        location: SrcSpan,
+        name: EcoString,
+        target: Target,
+        defined_for: Target,
+    },
+
     UnknownModule {
         location: SrcSpan,
         name: EcoString,
@@ -204,6 +247,25 @@ pub enum Error {
         context: ModuleValueUsageContext,
     },
 
+    /// A constructor of an opaque type is used outside of the module that
+    /// defines the type.
+    ///
+    /// ```gleam
+    /// // wibble.gleam
+    /// pub opaque type Wibble { Wibble }
+    ///
+    /// // main.gleam
+    /// import wibble
+    /// pub fn main() { wibble.Wibble }
+    /// ```
+    ///
+    OpaqueTypeConstructor {
+        location: SrcSpan,
+        name: EcoString,
+        module_name: EcoString,
+        type_name: EcoString,
+    },
+
     ModuleAliasUsedAsName {
         location: SrcSpan,
         name: EcoString,
@@ -228,6 +290,9 @@ pub enum Error {
         expected: usize,
         given: usize,
         labels: Vec<EcoString>,
+        /// The changes that would fix the number of arguments, if we could
+        /// work them out.
+        fixes: Vec<ArityFix>,
     },
 
     UnsafeRecordUpdate {
@@ -252,10 +317,25 @@ pub enum Error {
         situation: Option<UnifyErrorSituation>,
         expected: Arc<Type>,
         given: Arc<Type>,
+        /// The path from the expected and given types to the first part of
+        /// them that doesn't match. It's empty if the types themselves don't
+        /// match.
+        mismatch_path: Vec<TypePathStep>,
     },
 
+    /// The clauses of a case expression return different types. Each of the
+    /// clauses is listed with the location and type of the value it returns.
+    CaseClauseMismatch {
+        location: SrcSpan,
+        clauses: Vec<(SrcSpan, Arc<Type>)>,
+    },
+
+    /// A value would need to have an infinite type, as the type variable
+    /// would have to be bound to a type that contains itself.
     RecursiveType {
         location: SrcSpan,
+        variable: Arc<Type>,
+        type_: Arc<Type>,
     },
 
     DuplicateName {
@@ -396,7 +476,8 @@ pub enum Error {
     ///     type ForkBomb = #(ForkBomb, ForkBomb)
     RecursiveTypeAlias {
         location: SrcSpan,
-        cycle: Vec<EcoString>,
+        /// The name and location of each alias in the cycle.
+        cycle: Vec<(EcoString, SrcSpan)>,
     },
 
     /// A function has been given an external implementation but not all the
@@ -436,6 +517,22 @@ pub enum Error {
         name: EcoString,
     },
 
+    /// A function's external implementation has been referenced with an
+    /// arity, `function/2`, that is different from the number of arguments
+    /// the Gleam function takes.
+    ///
+    /// ```gleam
+    /// @external(erlang, "wibble", "wobble/2")
+    /// fn wobble(a: Int) -> Int
+    /// ```
+    ExternalArityMismatch {
+        location: SrcSpan,
+        target: Target,
+        function: EcoString,
+        external_arity: usize,
+        arity: usize,
+    },
+
     /// A case expression is missing one or more patterns to match all possible
     /// values of the type.
     InexhaustiveCaseExpression {
@@ -651,6 +748,10 @@ impl Named {
 pub enum VariableOrigin {
     /// A variable that can be ignored by prefixing with an underscore, `_name`
     Variable(EcoString),
+    /// A function argument that can be ignored by prefixing with an underscore,
+    /// `_name`. This includes the arguments of anonymous functions and the
+    /// variables bound on the left hand side of a `use`
+    FunctionArgument(EcoString),
     /// A variable from label shorthand syntax, which can be ignored with an underscore: `label: _`
     LabelShorthand(EcoString),
     /// A variable from an assignment pattern, which can be ignored by removing `as name`,
@@ -662,7 +763,7 @@ pub enum VariableOrigin {
 impl VariableOrigin {
     pub fn how_to_ignore(&self) -> Option<String> {
         match self {
-            VariableOrigin::Variable(name) => {
+            VariableOrigin::Variable(name) | VariableOrigin::FunctionArgument(name) => {
                 Some(format!("You can ignore it with an underscore: `_{name}`."))
             }
             VariableOrigin::LabelShorthand(label) => Some(format!(
@@ -708,6 +809,14 @@ pub enum Warning {
         name: EcoString,
     },
 
+    /// A custom type declares a type parameter that none of its constructors
+    /// use. Types annotated with `@phantom` are not reported.
+    UnusedTypeParameter {
+        location: SrcSpan,
+        name: EcoString,
+        type_name: EcoString,
+    },
+
     UnusedConstructor {
         location: SrcSpan,
         imported: bool,
@@ -812,6 +921,39 @@ pub enum Warning {
         location: SrcSpan,
     },
 
+    /// This happens when the subject of a case expression is a literal or a
+    /// constant, and one of the patterns can never match its value:
+    ///
+    /// ```gleam
+    /// const debug = False
+    ///
+    /// case debug {
+    ///   True -> io.println("debugging")
+    ///   False -> Nil
+    /// }
+    /// ```
+    ///
+    NeverMatchingPattern {
+        location: SrcSpan,
+    },
+
+    /// This happens when the subject of a case expression is a literal or a
+    /// constant, and one of the patterns always matches its value, so none
+    /// of the clauses after it can ever be reached:
+    ///
+    /// ```gleam
+    /// const debug = False
+    ///
+    /// case debug {
+    ///   False -> Nil
+    ///   True -> io.println("debugging")
+    /// }
+    /// ```
+    ///
+    AlwaysMatchingPattern {
+        location: SrcSpan,
+    },
+
     /// This happens when someone defines an external type (with no
     /// constructors) and marks it as opqaue:
     ///
@@ -864,6 +1006,9 @@ pub enum Warning {
     UnreachableCodeAfterPanic {
         location: SrcSpan,
         panic_position: PanicPosition,
+        /// The expression that never returns, making the code unreachable.
+        diverging: DivergingExpression,
+        diverging_location: SrcSpan,
     },
 
     /// When a function capture is used in a pipe to pipe into the first
@@ -895,11 +1040,89 @@ pub enum Warning {
         feature_kind: FeatureKind,
     },
 
-    /// When targeting JavaScript and an `Int` value is specified that lies
-    /// outside the range `Number.MIN_SAFE_INTEGER` - `Number.MAX_SAFE_INTEGER`.
+    /// When targeting JavaScript, or when the package supports it, and an
+    /// `Int` value is specified that lies outside the range
+    /// `Number.MIN_SAFE_INTEGER` - `Number.MAX_SAFE_INTEGER`.
     ///
     JavaScriptIntUnsafe {
         location: SrcSpan,
+        /// The value the `Int` is rounded to by JavaScript's number type, if
+        /// it can't be represented exactly.
+        javascript_value: Option<EcoString>,
+    },
+
+    /// When a function's Erlang implementation has been given but its
+    /// module name is empty or contains spaces or slashes.
+    ///
+    /// ```gleam
+    /// @external(erlang, "", "wobble")
+    /// pub fn wobble() -> Int
+    /// ```
+    ///
+    InvalidExternalErlangModule {
+        location: SrcSpan,
+        module: EcoString,
+        name: EcoString,
+    },
+
+    /// When a function's Erlang implementation has been given but its
+    /// function name is empty or contains spaces or slashes.
+    ///
+    /// ```gleam
+    /// @external(erlang, "wibble", "wob ble")
+    /// pub fn wobble() -> Int
+    /// ```
+    ///
+    InvalidExternalErlangFunction {
+        location: SrcSpan,
+        function: EcoString,
+        name: EcoString,
+    },
+
+    /// When a public function only has an external implementation for one of
+    /// the targets, while the package is used on both: it is being compiled for
+    /// a target other than the one set in its `gleam.toml`.
+    ///
+    /// ```gleam
+    /// @external(erlang, "wibble", "wobble")
+    /// pub fn wobble() -> Int
+    /// ```
+    ///
+    MissingExternalForSupportedTarget {
+        location: SrcSpan,
+        missing_target: Target,
+    },
+
+    /// When a public function or constant is only defined for a target other
+    /// than the one being compiled for, so the module is missing part of its
+    /// public API on this target.
+    ///
+    /// ```gleam
+    /// @target(javascript)
+    /// pub fn wibble() { 1 } // <- warning when compiling to Erlang
+    /// ```
+    ///
+    PublicValueNotDefinedForTarget {
+        location: SrcSpan,
+        name: EcoString,
+        target: Target,
+        defined_for: Target,
+    },
+
+    /// When a local variable is defined with the same name as another local
+    /// variable that is still in scope. This is only emitted if enabled with
+    /// `shadowing = true` in the `[warnings]` section of `gleam.toml`.
+    ///
+    /// ```gleam
+    /// let x = 1
+    /// let x = x + 1
+    /// //  ^ This shadows the previous `x`
+    /// ```
+    ///
+    ShadowedVariable {
+        location: SrcSpan,
+        name: EcoString,
+        shadowed_location: SrcSpan,
     },
 }
 
@@ -916,6 +1139,7 @@ pub enum FeatureKind {
     RecordAccessVariantInference,
     LetAssertWithMessage,
     VariantWithDeprecatedAnnotation,
+    PhantomAnnotation,
 }
 
 impl FeatureKind {
@@ -941,6 +1165,8 @@ impl FeatureKind {
             FeatureKind::VariantWithDeprecatedAnnotation | FeatureKind::LetAssertWithMessage => {
                 Version::new(1, 7, 0)
             }
+
+            FeatureKind::PhantomAnnotation => Version::new(1, 8, 0),
         }
     }
 }
@@ -958,6 +1184,29 @@ pub enum PanicPosition {
     PreviousExpression,
 }
 
+/// An expression that never returns, making any code that would run after it
+/// unreachable.
+#[derive(Debug, Eq, PartialEq, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub enum DivergingExpression {
+    Panic,
+    /// A `todo` used as a statement. A `todo` used as a function argument is
+    /// most likely a placeholder, so we don't consider it.
+    Todo,
+    /// A call to a function that can only ever panic: a pure Gleam function
+    /// returning a type variable that doesn't appear in any of its arguments,
+    /// as there's no way for it to produce a value of that type.
+    ///
+    /// ```gleam
+    /// pub fn crash(message: String) -> a {
+    ///   panic as message
+    /// }
+    /// ```
+    ///
+    Call,
+    /// A case expression where every clause never returns.
+    Case,
+}
+
 #[derive(Debug, Eq, PartialEq, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub enum TodoOrPanic {
     Todo,
@@ -983,9 +1232,11 @@ impl Error {
             | Error::BitArraySegmentError { location, .. }
             | Error::UnknownVariable { location, .. }
             | Error::UnknownType { location, .. }
+            | Error::ValueDefinedForOtherTarget { location, .. }
             | Error::UnknownModule { location, .. }
             | Error::UnknownModuleType { location, .. }
             | Error::UnknownModuleValue { location, .. }
+            | Error::OpaqueTypeConstructor { location, .. }
             | Error::ModuleAliasUsedAsName { location, .. }
             | Error::NotFn { location, .. }
             | Error::UnknownRecordField { location, .. }
@@ -994,6 +1245,7 @@ impl Error {
             | Error::UnnecessarySpreadOperator { location, .. }
             | Error::IncorrectTypeArity { location, .. }
             | Error::CouldNotUnify { location, .. }
+            | Error::CaseClauseMismatch { location, .. }
             | Error::RecursiveType { location, .. }
             | Error::DuplicateName {
                 location_a: location,
@@ -1026,6 +1278,7 @@ impl Error {
             | Error::UnsupportedExpressionTarget { location, .. }
             | Error::InvalidExternalJavascriptModule { location, .. }
             | Error::InvalidExternalJavascriptFunction { location, .. }
+            | Error::ExternalArityMismatch { location, .. }
             | Error::InexhaustiveCaseExpression { location, .. }
             | Error::InexhaustiveLetAssignment { location, .. }
             | Error::UnusedTypeAliasParameter { location, .. }
@@ -1080,6 +1333,7 @@ impl Warning {
             | Warning::NoFieldsRecordUpdate { location, .. }
             | Warning::AllFieldsRecordUpdate { location, .. }
             | Warning::UnusedType { location, .. }
+            | Warning::UnusedTypeParameter { location, .. }
             | Warning::UnusedConstructor { location, .. }
             | Warning::UnusedImportedValue { location, .. }
             | Warning::UnusedImportedModule { location, .. }
@@ -1095,6 +1349,8 @@ impl Warning {
             | Warning::UnreachableCaseClause { location, .. }
             | Warning::CaseMatchOnLiteralCollection { location, .. }
             | Warning::CaseMatchOnLiteralValue { location, .. }
+            | Warning::NeverMatchingPattern { location, .. }
+            | Warning::AlwaysMatchingPattern { location, .. }
             | Warning::OpaqueExternalType { location, .. }
             | Warning::InternalTypeLeak { location, .. }
             | Warning::RedundantAssertAssignment { location, .. }
@@ -1102,7 +1358,12 @@ impl Warning {
             | Warning::UnreachableCodeAfterPanic { location, .. }
             | Warning::RedundantPipeFunctionCapture { location, .. }
             | Warning::FeatureRequiresHigherGleamVersion { location, .. }
-            | Warning::JavaScriptIntUnsafe { location, .. } => *location,
+            | Warning::JavaScriptIntUnsafe { location, .. }
+            | Warning::InvalidExternalErlangModule { location, .. }
+            | Warning::InvalidExternalErlangFunction { location, .. }
+            | Warning::MissingExternalForSupportedTarget { location, .. }
+            | Warning::PublicValueNotDefinedForTarget { location, .. }
+            | Warning::ShadowedVariable { location, .. } => *location,
         }
     }
 
@@ -1133,6 +1394,12 @@ pub enum UnknownValueConstructorError {
         value_constructors: Vec<EcoString>,
         imported_value_as_type: bool,
     },
+
+    OpaqueTypeConstructor {
+        name: EcoString,
+        module_name: EcoString,
+        type_name: EcoString,
+    },
 }
 
 pub fn convert_get_value_constructor_error(
@@ -1171,9 +1438,249 @@ pub fn convert_get_value_constructor_error(
             type_with_same_name: imported_value_as_type,
             context: ModuleValueUsageContext::ModuleAccess,
         },
+
+        UnknownValueConstructorError::OpaqueTypeConstructor {
+            name,
+            module_name,
+            type_name,
+        } => Error::OpaqueTypeConstructor {
+            location,
+            name,
+            module_name,
+            type_name,
+        },
     }
 }
 
+/// Builds the error for a name that is not one of the public values of an
+/// imported module.
+pub fn unknown_module_value_error(
+    module: &ModuleInterface,
+    name: &EcoString,
+    location: SrcSpan,
+    context: ModuleValueUsageContext,
+) -> Error {
+    match module.get_opaque_type_constructor(name) {
+        Some(type_name) => Error::OpaqueTypeConstructor {
+            location,
+            name: name.clone(),
+            module_name: module.name.clone(),
+            type_name,
+        },
+        None => Error::UnknownModuleValue {
+            location,
+            name: name.clone(),
+            module_name: module.name.clone(),
+            value_constructors: module.public_value_names(),
+            type_with_same_name: module.get_public_type(name).is_some(),
+            context,
+        },
+    }
+}
+
+/// A change to the arguments of a call, or to the fields of a pattern, that
+/// would fix an arity mismatch. These are kept in the error so that they can
+/// both be shown in the error message and applied by the language server.
+///
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub enum ArityFix {
+    /// Some arguments are missing and can be inserted at the given position.
+    ///
+    /// ```gleam
+    /// wibble(1)
+    /// //      ^ Insert `, todo` here
+    /// ```
+    ///
+    InsertArguments {
+        position: u32,
+        prefix: EcoString,
+        arguments: Vec<MissingArgument>,
+        suffix: EcoString,
+        /// What to use as the value of each inserted argument: `todo` in an
+        /// expression and `_` in a pattern.
+        placeholder: EcoString,
+    },
+
+    /// Some arguments are not needed and the code at the given location can
+    /// be removed.
+    ///
+    /// ```gleam
+    /// wibble(1, 2, 3)
+    /// //      ^^^^^^ Remove this
+    /// ```
+    ///
+    RemoveArguments { location: SrcSpan, count: usize },
+}
+
+#[derive(Debug, Eq, PartialEq, Clone)]
+pub struct MissingArgument {
+    pub label: Option<EcoString>,
+    pub type_: Arc<Type>,
+}
+
+impl ArityFix {
+    /// The span of code to replace, and what to replace it with, in order to
+    /// apply this fix.
+    ///
+    pub fn edit(&self) -> (SrcSpan, String) {
+        match self {
+            ArityFix::InsertArguments {
+                position,
+                prefix,
+                arguments,
+                suffix,
+                placeholder,
+            } => {
+                let arguments = arguments
+                    .iter()
+                    .map(|argument| match &argument.label {
+                        Some(label) => format!("{label}: {placeholder}"),
+                        None => placeholder.to_string(),
+                    })
+                    .join(", ");
+                (
+                    SrcSpan::new(*position, *position),
+                    format!("{prefix}{arguments}{suffix}"),
+                )
+            }
+            ArityFix::RemoveArguments { location, .. } => (*location, String::new()),
+        }
+    }
+}
+
+/// Works out how to fix a call, or a pattern, that has the wrong number of
+/// arguments.
+///
+/// `arguments_start` is the position just after the opening parenthesis of
+/// the arguments, or `None` if there are no parentheses at all, in which case
+/// any missing arguments are inserted at `end` wrapped in parentheses.
+///
+/// If there's no obvious fix, because labels are used in an invalid way or
+/// some of the arguments are implicitly added by the compiler, no fix is
+/// returned.
+///
+pub fn arity_fixes<A>(
+    arguments: &[CallArg<A>],
+    parameters: &[Arc<Type>],
+    field_map: Option<&FieldMap>,
+    arguments_start: Option<u32>,
+    end: u32,
+    placeholder: &str,
+) -> Vec<ArityFix> {
+    if arguments.iter().any(|argument| argument.is_implicit()) {
+        return vec![];
+    }
+    let placeholder = EcoString::from(placeholder);
+
+    // If there's too many arguments and they're all positional we can remove
+    // the extra ones from the end.
+    if arguments.len() > parameters.len() {
+        if arguments.iter().any(|argument| argument.label.is_some()) {
+            return vec![];
+        }
+        let start = match parameters.len().checked_sub(1) {
+            Some(index) => arguments.get(index).map(|argument| argument.location.end),
+            None => arguments.first().map(|argument| argument.location.start),
+        };
+        let end = arguments.last().map(|argument| argument.location.end);
+        return match start.zip(end) {
+            Some((start, end)) => vec![ArityFix::RemoveArguments {
+                location: SrcSpan::new(start, end),
+                count: arguments.len() - parameters.len(),
+            }],
+            None => vec![],
+        };
+    }
+
+    let positional = arguments
+        .iter()
+        .take_while(|argument| argument.label.is_none())
+        .count();
+    if arguments
+        .iter()
+        .skip(positional)
+        .any(|argument| argument.label.is_none())
+    {
+        return vec![];
+    }
+
+    let mut supplied: HashSet<usize> = (0..positional).collect();
+    for label in arguments
+        .iter()
+        .filter_map(|argument| argument.label.as_ref())
+    {
+        match field_map.and_then(|field_map| field_map.fields.get(label)) {
+            Some(index) => _ = supplied.insert(*index as usize),
+            None => return vec![],
+        }
+    }
+
+    let labels: HashMap<usize, &EcoString> = field_map
+        .map(|field_map| {
+            field_map
+                .fields
+                .iter()
+                .map(|(label, index)| (*index as usize, label))
+                .collect()
+        })
+        .unwrap_or_default();
+    let (labelled, unlabelled): (Vec<_>, Vec<_>) = parameters
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !supplied.contains(index))
+        .map(|(index, type_)| MissingArgument {
+            label: labels.get(&index).map(|label| (*label).clone()),
+            type_: type_.clone(),
+        })
+        .partition(|argument| argument.label.is_some());
+
+    let Some(arguments_start) = arguments_start else {
+        return vec![ArityFix::InsertArguments {
+            position: end,
+            prefix: "(".into(),
+            arguments: unlabelled.into_iter().chain(labelled).collect(),
+            suffix: ")".into(),
+            placeholder,
+        }];
+    };
+
+    // Positional arguments are inserted right after the last positional
+    // argument, while labelled ones can go at the end of the arguments. If
+    // there's no labelled arguments those two positions are the same.
+    let insert = |arguments_before: &[CallArg<A>], arguments_after: bool, missing| {
+        let (position, prefix) = match arguments_before.last() {
+            Some(argument) => (argument.location.end, ", "),
+            None => (arguments_start, ""),
+        };
+        let suffix = if arguments_after { ", " } else { "" };
+        ArityFix::InsertArguments {
+            position,
+            prefix: prefix.into(),
+            arguments: missing,
+            suffix: suffix.into(),
+            placeholder: placeholder.clone(),
+        }
+    };
+    let positional_arguments = arguments.get(..positional).unwrap_or_default();
+    let has_labelled_arguments = positional < arguments.len();
+
+    let mut fixes = vec![];
+    if has_labelled_arguments {
+        if !unlabelled.is_empty() {
+            fixes.push(insert(positional_arguments, true, unlabelled));
+        }
+        if !labelled.is_empty() {
+            fixes.push(insert(arguments, false, labelled));
+        }
+    } else {
+        let missing = unlabelled.into_iter().chain(labelled).collect_vec();
+        if !missing.is_empty() {
+            fixes.push(insert(arguments, false, missing));
+        }
+    }
+    fixes
+}
+
 #[derive(Debug, Eq, PartialEq, Clone)]
 pub enum UnsafeRecordUpdateReason {
     UnknownVariant {
@@ -1182,6 +1689,9 @@ pub enum UnsafeRecordUpdateReason {
     WrongVariant {
         constructed_variant: EcoString,
         spread_variant: EcoString,
+        /// The fields of the constructed variant that would be copied from
+        /// the spread value, but that its variant doesn't have.
+        missing_fields: Vec<EcoString>,
     },
     IncompatibleFieldTypes {
         constructed_variant: Arc<Type>,
@@ -1281,6 +1791,7 @@ pub fn convert_not_fun_error(
             location: call_location,
             expected,
             given,
+            fixes: vec![],
         },
 
         (CallKind::Function, MatchFunTypeError::NotFn { type_ }) => Error::NotFn {
@@ -1314,10 +1825,12 @@ pub fn flip_unify_error(e: UnifyError) -> UnifyError {
             expected,
             given,
             situation: note,
+            mismatch_path,
         } => UnifyError::CouldNotUnify {
             expected: given,
             given: expected,
             situation: note,
+            mismatch_path,
         },
         other => other,
     }
@@ -1329,12 +1842,14 @@ fn flip_unify_error_test() {
         UnifyError::CouldNotUnify {
             expected: crate::type_::int(),
             given: crate::type_::float(),
-            situation: Some(UnifyErrorSituation::CaseClauseMismatch),
+            situation: Some(UnifyErrorSituation::ListElementMismatch),
+            mismatch_path: vec![TypePathStep::TupleElement(1)],
         },
         flip_unify_error(UnifyError::CouldNotUnify {
             expected: crate::type_::float(),
             given: crate::type_::int(),
-            situation: Some(UnifyErrorSituation::CaseClauseMismatch),
+            situation: Some(UnifyErrorSituation::ListElementMismatch),
+            mismatch_path: vec![TypePathStep::TupleElement(1)],
         })
     );
 }
@@ -1342,16 +1857,21 @@ fn flip_unify_error_test() {
 pub fn unify_enclosed_type(
     e1: Arc<Type>,
     e2: Arc<Type>,
+    step: TypePathStep,
     result: Result<(), UnifyError>,
 ) -> Result<(), UnifyError> {
-    // If types cannot unify, show the type error with the enclosing types, e1 and e2.
+    // If types cannot unify, show the type error with the enclosing types, e1 and e2,
+    // keeping track of where in them the mismatch is.
     match result {
         Err(UnifyError::CouldNotUnify {
-            situation: note, ..
+            situation: note,
+            mismatch_path,
+            ..
         }) => Err(UnifyError::CouldNotUnify {
             expected: e1,
             given: e2,
             situation: note,
+            mismatch_path: std::iter::once(step).chain(mismatch_path).collect(),
         }),
 
         _ => result,
@@ -1364,15 +1884,18 @@ fn unify_enclosed_type_test() {
         Err(UnifyError::CouldNotUnify {
             expected: crate::type_::int(),
             given: crate::type_::float(),
-            situation: Some(UnifyErrorSituation::CaseClauseMismatch)
+            situation: Some(UnifyErrorSituation::ListElementMismatch),
+            mismatch_path: vec![TypePathStep::TypeArgument(0), TypePathStep::TupleElement(1)],
         }),
         unify_enclosed_type(
             crate::type_::int(),
             crate::type_::float(),
+            TypePathStep::TypeArgument(0),
             Err(UnifyError::CouldNotUnify {
                 expected: crate::type_::string(),
                 given: crate::type_::bits(),
-                situation: Some(UnifyErrorSituation::CaseClauseMismatch)
+                situation: Some(UnifyErrorSituation::ListElementMismatch),
+                mismatch_path: vec![TypePathStep::TupleElement(1)],
             })
         )
     );
@@ -1393,6 +1916,7 @@ pub fn unify_wrong_arity(
                 given: arity2,
             },
         }),
+        mismatch_path: vec![],
     }
 }
 
@@ -1402,6 +1926,7 @@ pub fn unify_wrong_arguments(
     given: &Arc<Type>,
     given_arg: &Arc<Type>,
     position: usize,
+    error: UnifyError,
 ) -> UnifyError {
     UnifyError::CouldNotUnify {
         expected: expected.clone(),
@@ -1413,6 +1938,7 @@ pub fn unify_wrong_arguments(
                 position,
             },
         }),
+        mismatch_path: enclosed_mismatch_path(TypePathStep::FnArgument(position), error),
     }
 }
 
@@ -1421,6 +1947,7 @@ pub fn unify_wrong_returns(
     expected_return: &Arc<Type>,
     given: &Arc<Type>,
     given_return: &Arc<Type>,
+    error: UnifyError,
 ) -> UnifyError {
     UnifyError::CouldNotUnify {
         expected: expected.clone(),
@@ -1431,14 +1958,22 @@ pub fn unify_wrong_returns(
                 given: given_return.clone(),
             },
         }),
+        mismatch_path: enclosed_mismatch_path(TypePathStep::FnReturn, error),
+    }
+}
+
+/// The path to the mismatch of an error found while unifying a component of
+/// a type, starting from the enclosing type.
+fn enclosed_mismatch_path(step: TypePathStep, error: UnifyError) -> Vec<TypePathStep> {
+    let mut path = vec![step];
+    if let UnifyError::CouldNotUnify { mismatch_path, .. } = error {
+        path.extend(mismatch_path);
     }
+    path
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum UnifyErrorSituation {
-    /// Clauses in a case expression were found to return different types.
-    CaseClauseMismatch,
-
     /// A function was found to return a value that did not match its return
     /// annotation.
     ReturnAnnotationMismatch,
@@ -1480,10 +2015,6 @@ pub enum FunctionsMismatchReason {
 impl UnifyErrorSituation {
     pub fn description(&self) -> Option<&'static str> {
         match self {
-            Self::CaseClauseMismatch => Some(
-                "This case clause was found to return a different type than the previous
-one, but all case clauses must return the same type.",
-            ),
             Self::ReturnAnnotationMismatch => Some(
                 "The type of this returned value doesn't match the return type
 annotation of this function.",
@@ -1508,12 +2039,30 @@ this list don't match the type of the elements being prepended to it.",
     }
 }
 
+/// A step from a type to one of the types it is made of.
+///
+/// A sequence of steps is used to point to the part of two types that doesn't
+/// match, so that it can be highlighted in a type mismatch error rather than
+/// leaving the programmer to compare the two types themselves.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypePathStep {
+    /// The nth argument of a named type, like the `Int` in `List(Int)`.
+    TypeArgument(usize),
+    /// The nth element of a tuple.
+    TupleElement(usize),
+    /// The nth argument of a function.
+    FnArgument(usize),
+    /// The return type of a function.
+    FnReturn,
+}
+
 #[derive(Debug, PartialEq)]
 pub enum UnifyError {
     CouldNotUnify {
         expected: Arc<Type>,
         given: Arc<Type>,
         situation: Option<UnifyErrorSituation>,
+        mismatch_path: Vec<TypePathStep>,
     },
 
     ExtraVarInAlternativePattern {
@@ -1528,27 +2077,30 @@ pub enum UnifyError {
         name: EcoString,
     },
 
-    RecursiveType,
+    RecursiveType {
+        variable: Arc<Type>,
+        type_: Arc<Type>,
+    },
 }
 
 impl UnifyError {
     pub fn with_unify_error_situation(self, situation: UnifyErrorSituation) -> Self {
         match self {
             Self::CouldNotUnify {
-                expected, given, ..
+                expected,
+                given,
+                mismatch_path,
+                ..
             } => Self::CouldNotUnify {
                 expected,
                 given,
                 situation: Some(situation),
+                mismatch_path,
             },
             other => other,
         }
     }
 
-    pub fn case_clause_mismatch(self) -> Self {
-        self.with_unify_error_situation(UnifyErrorSituation::CaseClauseMismatch)
-    }
-
     pub fn list_element_mismatch(self) -> Self {
         self.with_unify_error_situation(UnifyErrorSituation::ListElementMismatch)
     }
@@ -1571,11 +2123,13 @@ impl UnifyError {
                 expected,
                 given,
                 situation: note,
+                mismatch_path,
             } => Error::CouldNotUnify {
                 location,
                 expected,
                 given,
                 situation: note,
+                mismatch_path,
             },
 
             Self::ExtraVarInAlternativePattern { name } => {
@@ -1588,7 +2142,11 @@ impl UnifyError {
 
             Self::DuplicateVarInPattern { name } => Error::DuplicateVarInPattern { location, name },
 
-            Self::RecursiveType => Error::RecursiveType { location },
+            Self::RecursiveType { variable, type_ } => Error::RecursiveType {
+                location,
+                variable,
+                type_,
+            },
         }
     }
 
@@ -1607,6 +2165,7 @@ impl UnifyError {
                 expected,
                 given: _,
                 situation: _,
+                mismatch_path: _,
             } if !expected.as_ref().is_fun() => Error::UseFnDoesntTakeCallback {
                 location: function_location,
                 actual_type: Some(expected.as_ref().clone()),
@@ -1627,6 +2186,15 @@ impl UnifyError {
                     expected: one.clone(),
                     given: other.clone(),
                     situation: None,
+                    // We're only reporting the return types, so the path has
+                    // to start from those.
+                    mismatch_path: match &self {
+                        Self::CouldNotUnify { mismatch_path, .. } => mismatch_path
+                            .strip_prefix(&[TypePathStep::FnReturn])
+                            .map(|path| path.to_vec())
+                            .unwrap_or_default(),
+                        _ => vec![],
+                    },
                 },
 
                 FunctionsMismatchReason::Arity {
@@ -1670,14 +2238,23 @@ pub fn convert_unify_call_error(e: UnifyError, location: SrcSpan, kind: Argument
     }
 }
 
-/// When targeting JavaScript, adds a warning if the given Int value is outside the range of
-/// safe integers as defined by Number.MIN_SAFE_INTEGER and Number.MAX_SAFE_INTEGER.
+/// When the code could run on JavaScript, adds a warning if the given Int value is outside the
+/// range of safe integers as defined by Number.MIN_SAFE_INTEGER and Number.MAX_SAFE_INTEGER.
 ///
 pub fn check_javascript_int_safety(int_value: &BigInt, location: SrcSpan, problems: &mut Problems) {
     let js_min_safe_integer = -9007199254740991i64;
     let js_max_safe_integer = 9007199254740991i64;
 
     if *int_value < js_min_safe_integer.into() || *int_value > js_max_safe_integer.into() {
-        problems.warning(Warning::JavaScriptIntUnsafe { location });
+        let javascript_value = match int_value.to_f64() {
+            Some(value) if BigInt::from_f64(value).as_ref() == Some(int_value) => None,
+            Some(value) if value.is_finite() => Some(eco_format!("{value:.0}")),
+            Some(value) if value.is_sign_negative() => Some("-Infinity".into()),
+            Some(_) | None => Some("Infinity".into()),
+        };
+        problems.warning(Warning::JavaScriptIntUnsafe {
+            location,
+            javascript_value,
+        });
     }
 }
@@ -207,6 +207,10 @@ pub(crate) struct ExprTyper<'a, 'b> {
     // panic.
     pub(crate) previous_panics: bool,
 
+    // The expression that made `previous_panics` true, so that unreachable
+    // code warnings can point to it.
+    pub(crate) diverging_expression: Option<(SrcSpan, DivergingExpression)>,
+
     // This is used to track if we've already warned for unreachable code.
     // After emitting the first unreachable code warning we never emit another
     // one to avoid flooding with repetitive warnings.
@@ -245,6 +249,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         Self {
             hydrator,
             previous_panics: false,
+            diverging_expression: None,
             already_warned_for_unreachable_code: false,
             environment,
             implementations,
@@ -316,7 +321,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                 int_value,
                 ..
             } => {
-                if self.environment.target == Target::JavaScript
+                if self.environment.has_unsafe_javascript_ints()
                     && !self.current_function_definition.has_javascript_external
                 {
                     check_javascript_int_safety(&int_value, location, self.problems);
@@ -471,6 +476,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             None => None,
         };
         self.previous_panics = true;
+        self.diverging_expression = Some((location, DivergingExpression::Panic));
         Ok(TypedExpr::Panic {
             location,
             type_,
@@ -486,11 +492,16 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         // We don't want to warn twice for unreachable code inside the same
         // block, so we have to keep track if we've already emitted a warning of
         // this kind.
-        if !self.already_warned_for_unreachable_code {
+        if self.already_warned_for_unreachable_code {
+            return;
+        }
+        if let Some((diverging_location, diverging)) = self.diverging_expression {
             self.already_warned_for_unreachable_code = true;
             self.problems.warning(Warning::UnreachableCodeAfterPanic {
                 location,
                 panic_position,
+                diverging,
+                diverging_location,
             })
         }
     }
@@ -605,11 +616,13 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                     if i < count {
                         self.expression_discarded(&expression);
                     }
+                    self.track_todo_statement(&expression);
                     statements.push(Statement::Expression(expression));
                 }
 
                 Statement::Assignment(assignment) => {
                     let assignment = self.infer_assignment(assignment);
+                    self.track_todo_statement(&assignment.value);
                     statements.push(Statement::Assignment(assignment));
                 }
             }
@@ -618,6 +631,20 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         Vec1::try_from_vec(statements).expect("empty sequence")
     }
 
+    /// A `todo` used as a statement (or as the value of one) makes any
+    /// following statement unreachable.
+    fn track_todo_statement(&mut self, expression: &TypedExpr) {
+        if let TypedExpr::Todo {
+            location,
+            kind: TodoKind::Keyword,
+            ..
+        } = expression
+        {
+            self.previous_panics = true;
+            self.diverging_expression = Some((*location, DivergingExpression::Todo));
+        }
+    }
+
     fn infer_use(
         &mut self,
         use_: UntypedUse,
@@ -1168,6 +1195,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                 location: error.location,
             }
         })?;
+        self.check_bit_segment_otp_support(&options)?;
 
         unify(type_.clone(), value.type_())
             .map_err(|e| convert_unify_error(e, value.location()))?;
@@ -1180,6 +1208,23 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         })
     }
 
+    fn check_bit_segment_otp_support<Value: bit_array::GetLiteralValue>(
+        &self,
+        options: &[BitArrayOption<Value>],
+    ) -> Result<(), Error> {
+        match self.environment.minimum_otp_version {
+            Some(minimum_otp_version) if self.environment.target == Target::Erlang => {
+                bit_array::check_otp_support(options, minimum_otp_version).map_err(|error| {
+                    Error::BitArraySegmentError {
+                        error: error.error,
+                        location: error.location,
+                    }
+                })
+            }
+            Some(_) | None => Ok(()),
+        }
+    }
+
     fn infer_binop(
         &mut self,
         name: BinOp,
@@ -1344,6 +1389,18 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             }
         };
 
+        // Record the type of the binding so tooling can annotate it. Generated
+        // assignments cannot be annotated by the programmer.
+        if !kind.is_generated() {
+            self.environment
+                .inferred_annotations
+                .push(InferredAnnotation {
+                    location: pattern_location,
+                    type_: value_typ.clone(),
+                    kind: InferredAnnotationKind::LetBinding,
+                });
+        }
+
         // Check that any type annotation is accurate.
         if let Some(annotation) = &annotation {
             match self
@@ -1437,6 +1494,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
 
         self.previous_panics = false;
         let mut any_subject_panics = false;
+        let mut subject_diverging_expression = None;
         for subject in subjects {
             let subject_location = subject.location();
             let subject = self.in_new_scope(|subject_typer| {
@@ -1451,7 +1509,10 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                 }
             };
 
-            any_subject_panics = any_subject_panics || self.previous_panics;
+            if self.previous_panics && !any_subject_panics {
+                any_subject_panics = true;
+                subject_diverging_expression = self.diverging_expression;
+            }
             subject_types.push(subject.type_());
             typed_subjects.push(subject);
         }
@@ -1460,6 +1521,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         let mut all_patterns_are_discards = true;
         // NOTE: if there are 0 clauses then there are 0 panics
         let mut all_clauses_panic = !clauses.is_empty();
+        let mut mismatched_clause_location = None;
         for clause in clauses {
             has_a_guard = has_a_guard || clause.guard.is_some();
             all_patterns_are_discards =
@@ -1469,19 +1531,40 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             let typed_clause = self.infer_clause(clause, &typed_subjects);
             all_clauses_panic = all_clauses_panic && self.previous_panics;
 
-            if let Err(e) = unify(return_type.clone(), typed_clause.then.type_())
-                .map_err(|e| e.case_clause_mismatch().into_error(typed_clause.location()))
-            {
-                self.problems.error(e);
+            match unify(return_type.clone(), typed_clause.then.type_()) {
+                Ok(()) => (),
+                // All the clauses returning a type different from the one of
+                // the first clause are reported together in a single error,
+                // once we know the types of all of them.
+                Err(UnifyError::CouldNotUnify { .. }) => {
+                    mismatched_clause_location = mismatched_clause_location
+                        .or_else(|| Some(typed_clause.then.last_location()));
+                }
+                Err(e) => self.problems.error(e.into_error(typed_clause.location())),
             }
             typed_clauses.push(typed_clause);
         }
 
+        if let Some(location) = mismatched_clause_location {
+            let clauses = typed_clauses
+                .iter()
+                .map(|clause| (clause.then.last_location(), clause.then.type_()))
+                .collect_vec();
+            self.problems
+                .error(Error::CaseClauseMismatch { location, clauses });
+        }
+
         self.previous_panics = all_clauses_panic || any_subject_panics;
+        if any_subject_panics {
+            self.diverging_expression = subject_diverging_expression;
+        } else if all_clauses_panic {
+            self.diverging_expression = Some((location, DivergingExpression::Case));
+        }
 
         if let Err(e) = self.check_case_exhaustiveness(location, &subject_types, &typed_clauses) {
             self.problems.error(e);
         };
+        self.check_case_static_matches(&typed_subjects, &typed_clauses);
 
         // We track if the case expression is used like an if: that is all its
         // patterns are discarded and there's at least a guard. For example:
@@ -2213,20 +2296,17 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                         .suggest_modules(module_alias, Imported::Value(label.clone())),
                 })?;
 
-            let constructor =
-                module
-                    .get_public_value(&label)
-                    .ok_or_else(|| Error::UnknownModuleValue {
-                        name: label.clone(),
-                        location: SrcSpan {
-                            start: module_location.end,
-                            end: select_location.end,
-                        },
-                        module_name: module.name.clone(),
-                        value_constructors: module.public_value_names(),
-                        type_with_same_name: module.get_public_type(&label).is_some(),
-                        context: ModuleValueUsageContext::ModuleAccess,
-                    })?;
+            let constructor = module.get_public_value(&label).ok_or_else(|| {
+                unknown_module_value_error(
+                    module,
+                    &label,
+                    SrcSpan {
+                        start: module_location.end,
+                        end: select_location.end,
+                    },
+                    ModuleValueUsageContext::ModuleAccess,
+                )
+            })?;
 
             // Emit a warning if the value being used is deprecated.
             if let Deprecation::Deprecated { message } = &constructor.deprecation {
@@ -2307,7 +2387,14 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         }
 
         let unknown_field = |fields| {
-            self.unknown_field_error(fields, record_type.clone(), location, label.clone(), usage)
+            self.unknown_field_error(
+                fields,
+                record_type.clone(),
+                Some(record_location),
+                location,
+                label.clone(),
+                usage,
+            )
         };
         let (accessors_map, variant_accessors) = match collapse_links(record_type.clone()).as_ref()
         {
@@ -2455,8 +2542,13 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         };
 
         // infer the fields of the variant we want to update
-        let variant =
-            self.infer_record_update_variant(&typed_constructor, &value_constructor, &record_var)?;
+        let updated_fields = args.iter().map(|arg| arg.label.clone()).collect_vec();
+        let variant = self.infer_record_update_variant(
+            &typed_constructor,
+            &value_constructor,
+            &record_var,
+            &updated_fields,
+        )?;
 
         let args = self.infer_record_update_args(&variant, &record_var, args, location)?;
 
@@ -2520,6 +2612,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                         Err(self.unknown_field_error(
                             variant.field_names(),
                             record_type.clone(),
+                            None,
                             *location,
                             label.clone(),
                             FieldAccessUsage::RecordUpdate,
@@ -2596,6 +2689,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         constructor: &TypedExpr,
         value_constructor: &'c ValueConstructor,
         record: &TypedExpr,
+        updated_fields: &[EcoString],
     ) -> Result<RecordUpdateVariant<'c>, Error> {
         let record_type = record.type_();
         // The record constructor needs to be a function.
@@ -2665,27 +2759,15 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         // If we know the variant of the value being spread, and it doesn't match the
         // one being constructed, we can tell the user that it's always wrong
         if record_index.is_some() {
-            let Type::Named {
-                module: record_module,
-                name: record_name,
-                inferred_variant: Some(record_index),
-                ..
-            } = record_type.deref()
-            else {
-                panic!("Spread type must be named and with an index")
-            };
-
-            return Err(Error::UnsafeRecordUpdate {
-                location: record.location(),
-                reason: UnsafeRecordUpdateReason::WrongVariant {
-                    constructed_variant: name,
-                    spread_variant: self
-                        .environment
-                        .type_variant_name(record_module, record_name, *record_index)
-                        .expect("Spread type must exist and variant must be valid")
-                        .clone(),
-                },
-            });
+            return Err(self
+                .wrong_variant_record_update_error(
+                    &record_type,
+                    &name,
+                    field_map,
+                    updated_fields,
+                    record.location(),
+                )
+                .expect("Spread type must exist and variant must be valid"));
         }
 
         // If we don't have information about the variant being spread, we tell the user
@@ -2698,10 +2780,69 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         })
     }
 
+    /// Builds the error for a record update spreading a value whose variant is
+    /// known and is not the one being constructed. The error names the fields
+    /// that would need to be copied over from the spread value but that its
+    /// variant doesn't have.
+    ///
+    /// Returns `None` if the variant of the spread value is not known.
+    ///
+    fn wrong_variant_record_update_error(
+        &self,
+        record_type: &Arc<Type>,
+        constructed_variant: &EcoString,
+        field_map: &FieldMap,
+        updated_fields: &[EcoString],
+        location: SrcSpan,
+    ) -> Option<Error> {
+        let Type::Named {
+            module,
+            name,
+            inferred_variant,
+            ..
+        } = collapse_links(record_type.clone()).deref().clone()
+        else {
+            return None;
+        };
+
+        let variants = &self
+            .environment
+            .get_constructors_for_type(&module, &name)
+            .ok()?
+            .variants;
+        let variant = variants.get(inferred_variant? as usize)?;
+
+        let spread_fields = self
+            .environment
+            .type_value_constructor_to_constructor(&module, variant)
+            .and_then(|constructor| constructor.variant.record_field_map())
+            .map(|field_map| field_map.fields.keys().collect_vec())
+            .unwrap_or_default();
+
+        let missing_fields = field_map
+            .fields
+            .iter()
+            .filter(|(label, _)| !updated_fields.contains(label))
+            .filter(|(label, _)| !spread_fields.contains(label))
+            .sorted_by_key(|(_, index)| **index)
+            .map(|(label, _)| label.clone())
+            .collect_vec();
+
+        Some(Error::UnsafeRecordUpdate {
+            location,
+            reason: UnsafeRecordUpdateReason::WrongVariant {
+                constructed_variant: constructed_variant.clone(),
+                spread_variant: variant.name.clone(),
+                missing_fields,
+            },
+        })
+    }
+
     fn unknown_field_error(
         &self,
         fields: Vec<EcoString>,
         record_type: Arc<Type>,
+        record_location: Option<SrcSpan>,
         location: SrcSpan,
         label: EcoString,
         usage: FieldAccessUsage,
@@ -2738,10 +2879,33 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         // If we know the variant, the field must exist on a different
         // variant from the one we have inferred.
         if inferred_variant.is_some() {
-            error(UnknownField::AppearsInAnImpossibleVariant)
-        } else {
-            error(UnknownField::AppearsInAVariant)
+            return error(UnknownField::AppearsInAnImpossibleVariant);
         }
+
+        let variants = self
+            .environment
+            .get_constructors_for_type(module, name)
+            .map(|constructors| {
+                constructors
+                    .variants
+                    .iter()
+                    .map(|variant| VariantField {
+                        name: variant.name.clone(),
+                        arity: variant.parameters.len(),
+                        has_field: self
+                            .environment
+                            .type_value_constructor_to_constructor(module, variant)
+                            .and_then(|constructor| constructor.variant.record_field_map())
+                            .is_some_and(|field_map| field_map.fields.contains_key(&label)),
+                    })
+                    .collect_vec()
+            })
+            .unwrap_or_default();
+        error(UnknownField::AppearsInAVariant {
+            variants,
+            module: module.clone(),
+            record_location,
+        })
     }
 
     fn infer_value_constructor(
@@ -2778,18 +2942,14 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                             .environment
                             .suggest_modules(module_name, Imported::Value(name.clone())),
                     })?;
-                module
-                    .values
-                    .get(name)
-                    .cloned()
-                    .ok_or_else(|| Error::UnknownModuleValue {
-                        location: *location,
-                        module_name: module_name.clone(),
-                        name: name.clone(),
-                        value_constructors: module.public_value_names(),
-                        type_with_same_name: module.get_public_type(name).is_some(),
-                        context: ModuleValueUsageContext::ModuleAccess,
-                    })?
+                module.get_public_value(name).cloned().ok_or_else(|| {
+                    unknown_module_value_error(
+                        module,
+                        name,
+                        *location,
+                        ModuleValueUsageContext::ModuleAccess,
+                    )
+                })?
             }
         };
 
@@ -2832,19 +2992,27 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                 location: *location,
                 name: name.clone(),
             },
-            None => Error::UnknownVariable {
-                location: *location,
-                name: name.clone(),
-                variables: self.environment.local_value_names(),
-                type_with_name_in_scope: self
-                    .environment
-                    .module_types
-                    .keys()
-                    .any(|typ| typ == name),
+            None => match self.environment.values_for_other_targets.get(name) {
+                Some(defined_for) => Error::ValueDefinedForOtherTarget {
+                    location: *location,
+                    name: name.clone(),
+                    target: self.environment.target,
+                    defined_for: *defined_for,
+                },
+                None => self.unknown_variable_error(name, location),
             },
         }
     }
 
+    fn unknown_variable_error(&self, name: &EcoString, location: &SrcSpan) -> Error {
+        Error::UnknownVariable {
+            location: *location,
+            name: name.clone(),
+            variables: self.environment.local_value_names(),
+            type_with_name_in_scope: self.environment.module_types.keys().any(|typ| typ == name),
+        }
+    }
+
     // helper for infer_const to get the value of a constant ignoring annotations
     fn infer_const_value(&mut self, value: UntypedConstant) -> Result<TypedConstant, Error> {
         match value {
@@ -2853,7 +3021,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                 value,
                 int_value,
             } => {
-                if self.environment.target == Target::JavaScript {
+                if self.environment.has_unsafe_javascript_ints() {
                     check_javascript_int_safety(&int_value, location, self.problems);
                 }
 
@@ -3326,6 +3494,31 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         )
     }
 
+    /// The changes that would fix a call to `fun` with the wrong number of
+    /// arguments.
+    fn call_arity_fixes(
+        &mut self,
+        fun: &TypedExpr,
+        args: &[CallArg<UntypedExpr>],
+        location: SrcSpan,
+    ) -> Vec<ArityFix> {
+        let Type::Fn {
+            args: parameters, ..
+        } = collapse_links(fun.type_()).as_ref().clone()
+        else {
+            return vec![];
+        };
+        let field_map = self.get_field_map(fun).ok().flatten();
+        arity_fixes(
+            args,
+            &parameters,
+            field_map,
+            Some(fun.location().end + 1),
+            location.end,
+            "todo",
+        )
+    }
+
     pub fn do_infer_call_with_known_fun(
         &mut self,
         fun: TypedExpr,
@@ -3353,14 +3546,17 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                 given,
                 labels,
                 location,
+                fixes: _,
             } = e
             {
                 labelled_arity_error = true;
+                let fixes = self.call_arity_fixes(&fun, &args, location);
                 self.problems.error(Error::IncorrectArity {
                     expected,
                     given,
                     labels,
                     location,
+                    fixes,
                 });
             } else {
                 self.problems.error(e);
@@ -3390,6 +3586,22 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                             // If the function has labels then arity issues will already
                             // be handled by the field map so we can ignore them here.
                             if !labelled_arity_error {
+                                let converted_error = match converted_error {
+                                    Error::IncorrectArity {
+                                        location,
+                                        expected,
+                                        given,
+                                        labels,
+                                        fixes: _,
+                                    } => Error::IncorrectArity {
+                                        fixes: self.call_arity_fixes(&fun, &args, location),
+                                        location,
+                                        expected,
+                                        given,
+                                        labels,
+                                    },
+                                    error => error,
+                                };
                                 self.problems.error(converted_error);
                                 (arg_types, return_type)
                             } else {
@@ -3544,9 +3756,121 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
             self.warn_for_unreachable_code(fun.location(), PanicPosition::LastFunctionArgument);
         }
 
+        if self.never_returns(&fun) {
+            self.previous_panics = true;
+            self.diverging_expression = Some((location, DivergingExpression::Call));
+        }
+
         (fun, typed_args, return_type)
     }
 
+    /// Whether calling the given function can never return. This is the case
+    /// for pure Gleam functions returning a type variable that doesn't appear
+    /// in any of their arguments: there's no way for them to produce a value
+    /// of that type, so they must either panic or loop forever.
+    ///
+    /// Only the former is reported: a function of the current module must be
+    /// known to always panic, while an imported one must not be recursive.
+    ///
+    fn never_returns(&self, fun: &TypedExpr) -> bool {
+        let (module, name) = match fun {
+            TypedExpr::Var {
+                constructor:
+                    ValueConstructor {
+                        variant: ValueConstructorVariant::ModuleFn { module, name, .. },
+                        ..
+                    },
+                ..
+            } => (module, name),
+            TypedExpr::ModuleSelect {
+                module_name,
+                label,
+                constructor: ModuleValueConstructor::Fn { .. },
+                ..
+            } => (module_name, label),
+            _ => return false,
+        };
+
+        // We need the function's generalised type, rather than the
+        // instantiated one of this call.
+        let value = if *module == self.environment.current_module {
+            self.environment.module_values.get(name)
+        } else {
+            self.environment
+                .importable_modules
+                .get(module)
+                .and_then(|module| module.values.get(name))
+        };
+        let Some(ValueConstructor {
+            variant:
+                ValueConstructorVariant::ModuleFn {
+                    implementations, ..
+                },
+            type_,
+            ..
+        }) = value
+        else {
+            return false;
+        };
+        if !implementations.gleam {
+            return false;
+        }
+
+        let Some((arguments, return_type)) = type_.fn_types() else {
+            return false;
+        };
+        let returns_unconstrained_type = match collapse_links(return_type).as_ref() {
+            Type::Var { type_ } => match *type_.borrow() {
+                TypeVar::Generic { id } => !arguments
+                    .iter()
+                    .any(|argument| argument.contains_type_variable(id)),
+                TypeVar::Unbound { .. } | TypeVar::Link { .. } => false,
+            },
+            _ => false,
+        };
+        if !returns_unconstrained_type {
+            return false;
+        }
+
+        if *module == self.environment.current_module {
+            self.environment.diverging_functions.contains(name)
+        } else {
+            !self.may_recurse(module, name, &mut HashSet::new(), &mut HashSet::new())
+        }
+    }
+
+    /// Whether the given imported function, or any of the functions it calls,
+    /// might call itself.
+    ///
+    fn may_recurse(
+        &self,
+        module: &EcoString,
+        name: &EcoString,
+        visiting: &mut HashSet<(EcoString, EcoString)>,
+        visited: &mut HashSet<(EcoString, EcoString)>,
+    ) -> bool {
+        let function = (module.clone(), name.clone());
+        if visited.contains(&function) {
+            return false;
+        }
+        if !visiting.insert(function.clone()) {
+            return true;
+        }
+        let Some(interface) = self.environment.importable_modules.get(module) else {
+            return false;
+        };
+        let recurses = interface
+            .function_references
+            .iter()
+            .filter(|reference| reference.function == *name)
+            .any(|reference| {
+                self.may_recurse(&reference.module, &reference.name, visiting, visited)
+            });
+        let _ = visiting.remove(&function);
+        let _ = visited.insert(function);
+        recurses
+    }
+
     fn infer_call_argument(
         &mut self,
         value: UntypedExpr,
@@ -3645,6 +3969,11 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                         }
 
                         // Insert a variable for the argument into the environment
+                        body_typer.environment.warn_if_shadowing(
+                            name,
+                            arg.location,
+                            body_typer.problems,
+                        );
                         body_typer
                             .environment
                             .insert_local_variable(name.clone(), arg.location, t);
@@ -3655,7 +3984,7 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
                             body_typer.environment.init_usage(
                                 name.clone(),
                                 EntityKind::Variable {
-                                    origin: VariableOrigin::Variable(name.clone()),
+                                    origin: VariableOrigin::FunctionArgument(name.clone()),
                                 },
                                 arg.location,
                                 body_typer.problems,
@@ -3817,6 +4146,86 @@ impl<'a, 'b> ExprTyper<'a, 'b> {
         Ok(())
     }
 
+    /// Emits warnings for the patterns that can be determined to always or
+    /// never match because the subject they match on is a literal or a
+    /// constant. For example:
+    ///
+    /// ```gleam
+    /// const debug = False
+    ///
+    /// case debug {
+    ///   True -> io.println("debugging")
+    /// //^^^^ This can never match!
+    ///   False -> Nil
+    /// }
+    /// ```
+    ///
+    fn check_case_static_matches(&mut self, subjects: &[TypedExpr], clauses: &[TypedClause]) {
+        use exhaustiveness::{PatternArena, StaticMatch};
+
+        let mut arena = PatternArena::new();
+        // Matching on a literal value already gets its own warning, so there's
+        // no need to also warn about each of its patterns.
+        let values = subjects
+            .iter()
+            .map(|subject| {
+                if subject.is_literal() {
+                    None
+                } else {
+                    arena.register_known_value(subject, self.environment)
+                }
+            })
+            .collect_vec();
+        if values.iter().all(Option::is_none) {
+            return;
+        }
+        let unknown_value = arena.register_unknown_value();
+
+        for (clause_index, clause) in clauses.iter().enumerate() {
+            let is_last_clause = clause_index + 1 == clauses.len();
+            for multi_pattern in
+                std::iter::once(&clause.pattern).chain(&clause.alternative_patterns)
+            {
+                let mut always_matches = true;
+                let mut is_refutable = false;
+                for (pattern, value) in multi_pattern.iter().zip(&values) {
+                    let pattern_id = arena.register(pattern);
+                    let irrefutable =
+                        arena.static_match(pattern_id, unknown_value) == StaticMatch::Always;
+                    let Some(value) = value else {
+                        always_matches = always_matches && irrefutable;
+                        continue;
+                    };
+                    is_refutable = is_refutable || !irrefutable;
+
+                    match arena.static_match(pattern_id, *value) {
+                        StaticMatch::Always => (),
+                        StaticMatch::Unknown => always_matches = false,
+                        StaticMatch::Never => {
+                            self.problems.warning(Warning::NeverMatchingPattern {
+                                location: pattern.location(),
+                            });
+                            always_matches = false;
+                            break;
+                        }
+                    }
+                }
+
+                // A pattern that always matches makes all the following
+                // clauses unreachable, unless it has a guard that might fail.
+                if always_matches && is_refutable && clause.guard.is_none() && !is_last_clause {
+                    let (Some(first), Some(last)) = (multi_pattern.first(), multi_pattern.last())
+                    else {
+                        continue;
+                    };
+                    self.problems.warning(Warning::AlwaysMatchingPattern {
+                        location: first.location().merge(&last.location()),
+                    });
+                }
+            }
+        }
+    }
+
     fn track_feature_usage(&mut self, feature_kind: FeatureKind, location: SrcSpan) {
         let minimum_required_version = feature_kind.required_version();
 
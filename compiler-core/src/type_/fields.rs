@@ -51,6 +51,7 @@ impl FieldMap {
                 location,
                 expected: self.arity as usize,
                 given: args.len(),
+                fixes: vec![],
             });
         }
 
@@ -129,6 +129,7 @@ impl Hydrator {
                     parameters,
                     type_: return_type,
                     deprecation,
+                    module: constructor_module,
                     ..
                 } = environment
                     .get_type_constructor(module, name)
@@ -179,6 +180,27 @@ impl Hydrator {
 
                 let return_type = environment.instantiate(return_type, &mut type_vars, self);
 
+                // Record the reference to the type so that tooling can find it
+                // without analysing the module again. Type aliases are named
+                // as they are written, as they don't exist in the type itself.
+                let referenced_name = match return_type.as_ref() {
+                    Type::Named {
+                        module, name: n, ..
+                    } if *module == constructor_module => n.clone(),
+                    _ => name.clone(),
+                };
+                let name_start = match module {
+                    Some((_, module_location)) => module_location.end + 1,
+                    None => location.start,
+                };
+                environment.references.push(Reference {
+                    location: SrcSpan::new(name_start, name_start + name.len() as u32),
+                    module: constructor_module,
+                    name: referenced_name,
+                    type_: return_type.clone(),
+                    kind: ReferenceKind::Type,
+                });
+
                 // Unify argument types with instantiated parameter types so that the correct types
                 // are inserted into the return type
                 for (parameter, (location, argument)) in
@@ -255,8 +277,14 @@ impl Hydrator {
                 }
             }
 
-            TypeAst::Hole(TypeAstHole { .. }) if self.permit_holes => {
-                Ok(environment.new_unbound_var())
+            TypeAst::Hole(TypeAstHole { location, .. }) if self.permit_holes => {
+                let type_ = environment.new_unbound_var();
+                environment.inferred_annotations.push(InferredAnnotation {
+                    location: *location,
+                    type_: type_.clone(),
+                    kind: InferredAnnotationKind::Hole,
+                });
+                Ok(type_)
             }
 
             TypeAst::Hole(TypeAstHole { location, .. }) => Err(Error::UnexpectedTypeHole {
@@ -84,6 +84,8 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                 let _ = self.initial_pattern_vars.insert(name.into());
                 // And now insert the variable for use in the code that comes
                 // after the pattern.
+                self.environment
+                    .warn_if_shadowing(name, location, self.problems);
                 self.environment
                     .insert_local_variable(name.into(), location, type_);
                 Ok(())
@@ -292,6 +294,16 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                 error: error.error,
                 location: error.location,
             })?;
+        if let Some(minimum_otp_version) = self.environment.minimum_otp_version {
+            if self.environment.target == Target::Erlang {
+                bit_array::check_otp_support(&options, minimum_otp_version).map_err(|error| {
+                    Error::BitArraySegmentError {
+                        error: error.error,
+                        location: error.location,
+                    }
+                })?;
+            }
+        }
 
         let type_ = {
             match value.deref() {
@@ -468,7 +480,7 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
             } => {
                 unify(type_, int()).map_err(|e| convert_unify_error(e, location))?;
 
-                if self.environment.target == Target::JavaScript
+                if self.environment.has_unsafe_javascript_ints()
                     && !self.implementations.uses_javascript_externals
                 {
                     check_javascript_int_safety(&int_value, location, self.problems);
@@ -533,17 +545,35 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                     expected: type_.clone(),
                     situation: None,
                     location,
+                    mismatch_path: vec![],
                 }),
             },
 
             Pattern::Tuple { elems, location } => match collapse_links(type_.clone()).deref() {
                 Type::Tuple { elems: type_elems } => {
                     if elems.len() != type_elems.len() {
+                        let arguments = elems
+                            .iter()
+                            .map(|elem| CallArg {
+                                label: None,
+                                location: elem.location(),
+                                value: (),
+                                implicit: None,
+                            })
+                            .collect_vec();
                         return Err(Error::IncorrectArity {
                             labels: vec![],
                             location,
                             expected: type_elems.len(),
                             given: elems.len(),
+                            fixes: arity_fixes(
+                                &arguments,
+                                type_elems,
+                                None,
+                                Some(location.start + 2),
+                                location.end,
+                                "_",
+                            ),
                         });
                     }
 
@@ -579,6 +609,7 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                         expected: type_,
                         situation: None,
                         location,
+                        mismatch_path: vec![],
                     })
                 }
             },
@@ -696,7 +727,34 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                             }
                         }
 
-                        field_map.reorder(&mut pattern_args, location)?
+                        field_map.reorder(&mut pattern_args, location).map_err(
+                            |error| match error {
+                                Error::IncorrectArity {
+                                    location,
+                                    expected,
+                                    given,
+                                    labels,
+                                    fixes: _,
+                                } => Error::IncorrectArity {
+                                    fixes: match cons.type_.as_ref() {
+                                        Type::Fn { args, .. } => constructor_arity_fixes(
+                                            &pattern_args,
+                                            args,
+                                            Some(field_map),
+                                            &module,
+                                            &name,
+                                            location,
+                                        ),
+                                        _ => vec![],
+                                    },
+                                    location,
+                                    expected,
+                                    given,
+                                    labels,
+                                },
+                                error => error,
+                            },
+                        )?
                     }
 
                     None => {
@@ -818,6 +876,14 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                                 location,
                                 expected: args.len(),
                                 given: pattern_args.len(),
+                                fixes: constructor_arity_fixes(
+                                    &pattern_args,
+                                    args,
+                                    constructor.field_map.as_ref(),
+                                    &module,
+                                    &name,
+                                    location,
+                                ),
                             })
                         }
                     }
@@ -853,6 +919,14 @@ impl<'a, 'b> PatternTyper<'a, 'b> {
                                 location,
                                 expected: 0,
                                 given: pattern_args.len(),
+                                fixes: constructor_arity_fixes(
+                                    &pattern_args,
+                                    &[],
+                                    None,
+                                    &module,
+                                    &name,
+                                    location,
+                                ),
                             })
                         }
                     }
@@ -935,3 +1009,31 @@ fn unify_constructor_variants(into: &mut Type, from: &Type) {
         _ => {}
     }
 }
+
+/// The changes that would fix a constructor pattern with the wrong number of
+/// arguments.
+///
+fn constructor_arity_fixes(
+    arguments: &[CallArg<UntypedPattern>],
+    parameters: &[Arc<Type>],
+    field_map: Option<&FieldMap>,
+    module: &Option<(EcoString, SrcSpan)>,
+    name: &EcoString,
+    location: SrcSpan,
+) -> Vec<ArityFix> {
+    let name_length = match module {
+        Some((module, _)) => module.len() + 1 + name.len(),
+        None => name.len(),
+    } as u32;
+    // A constructor pattern with no arguments might not have any parentheses.
+    let arguments_start =
+        (location.end - location.start > name_length).then_some(location.start + name_length + 1);
+    arity_fixes(
+        arguments,
+        parameters,
+        field_map,
+        arguments_start,
+        location.end,
+        "_",
+    )
+}
@@ -241,6 +241,9 @@ pub fn build_prelude(ids: &UniqueIdGenerator) -> ModuleInterface {
         // prelude doesn't have real line numbers
         line_numbers: LineNumbers::new(""),
         minimum_required_version: Version::new(0, 1, 0),
+        inferred_annotations: vec![],
+        function_references: vec![],
+        references: vec![],
     };
 
     for t in PreludeType::iter() {
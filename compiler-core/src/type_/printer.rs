@@ -1,9 +1,9 @@
 use bimap::BiMap;
 use ecow::EcoString;
 use im::HashMap;
-use std::{collections::HashSet, sync::Arc};
+use std::{collections::HashSet, ops::Range, sync::Arc};
 
-use crate::type_::{collapse_links, Type, TypeVar};
+use crate::type_::{collapse_links, error::TypePathStep, Type, TypeVar};
 
 /// This class keeps track of what names are used for modules in the current
 /// scope, so they can be printed in errors, etc.
@@ -322,6 +322,26 @@ impl<'a> Printer<'a> {
         buffer
     }
 
+    /// Prints a type, also returning the range of the printed string taken by
+    /// the part of the type that the given path leads to.
+    ///
+    pub fn print_type_highlighting(
+        &mut self,
+        type_: &Type,
+        path: &[TypePathStep],
+    ) -> (EcoString, Option<Range<usize>>) {
+        let mut buffer = EcoString::new();
+        let mut highlight = None;
+        self.print_highlighting(
+            type_,
+            &mut buffer,
+            PrintMode::Normal,
+            Some(path),
+            &mut highlight,
+        );
+        (buffer, highlight)
+    }
+
     pub fn print_module(&self, module: &str) -> EcoString {
         if let Some(module) = self.names.imported_modules.get(module) {
             module.clone()
@@ -337,6 +357,25 @@ impl<'a> Printer<'a> {
     }
 
     fn print(&mut self, type_: &Type, buffer: &mut EcoString, print_mode: PrintMode) {
+        self.print_highlighting(type_, buffer, print_mode, None, &mut None)
+    }
+
+    fn print_highlighting(
+        &mut self,
+        type_: &Type,
+        buffer: &mut EcoString,
+        print_mode: PrintMode,
+        path: Option<&[TypePathStep]>,
+        highlight: &mut Option<Range<usize>>,
+    ) {
+        // Once we've reached the end of the path, this is the type to highlight.
+        if let Some([]) = path {
+            let start = buffer.len();
+            self.print(type_, buffer, print_mode);
+            *highlight = Some(start..buffer.len());
+            return;
+        }
+
         match type_ {
             Type::Named {
                 name, args, module, ..
@@ -359,20 +398,36 @@ impl<'a> Printer<'a> {
 
                 if !args.is_empty() {
                     buffer.push('(');
-                    self.print_arguments(args, buffer, print_mode);
+                    let path = match path {
+                        Some([TypePathStep::TypeArgument(index), rest @ ..]) => {
+                            Some((*index, rest))
+                        }
+                        _ => None,
+                    };
+                    self.print_arguments_highlighting(args, buffer, print_mode, path, highlight);
                     buffer.push(')');
                 }
             }
 
             Type::Fn { args, retrn } => {
                 buffer.push_str("fn(");
-                self.print_arguments(args, buffer, print_mode);
+                let args_path = match path {
+                    Some([TypePathStep::FnArgument(index), rest @ ..]) => Some((*index, rest)),
+                    _ => None,
+                };
+                self.print_arguments_highlighting(args, buffer, print_mode, args_path, highlight);
                 buffer.push_str(") -> ");
-                self.print(retrn, buffer, print_mode);
+                let return_path = match path {
+                    Some([TypePathStep::FnReturn, rest @ ..]) => Some(rest),
+                    _ => None,
+                };
+                self.print_highlighting(retrn, buffer, print_mode, return_path, highlight);
             }
 
             Type::Var { type_, .. } => match *type_.borrow() {
-                TypeVar::Link { ref type_, .. } => self.print(type_, buffer, print_mode),
+                TypeVar::Link { ref type_, .. } => {
+                    self.print_highlighting(type_, buffer, print_mode, path, highlight)
+                }
                 TypeVar::Unbound { id, .. } | TypeVar::Generic { id, .. } => {
                     buffer.push_str(&self.type_variable(id))
                 }
@@ -380,20 +435,27 @@ impl<'a> Printer<'a> {
 
             Type::Tuple { elems, .. } => {
                 buffer.push_str("#(");
-                self.print_arguments(elems, buffer, print_mode);
+                let path = match path {
+                    Some([TypePathStep::TupleElement(index), rest @ ..]) => Some((*index, rest)),
+                    _ => None,
+                };
+                self.print_arguments_highlighting(elems, buffer, print_mode, path, highlight);
                 buffer.push(')');
             }
         }
     }
 
-    fn print_arguments(
+    fn print_arguments_highlighting(
         &mut self,
         args: &[Arc<Type>],
         typ_str: &mut EcoString,
         print_mode: PrintMode,
+        path: Option<(usize, &[TypePathStep])>,
+        highlight: &mut Option<Range<usize>>,
     ) {
         for (i, arg) in args.iter().enumerate() {
-            self.print(arg, typ_str, print_mode);
+            let arg_path = path.and_then(|(index, rest)| (index == i).then_some(rest));
+            self.print_highlighting(arg, typ_str, print_mode, arg_path, highlight);
             if i < args.len() - 1 {
                 typ_str.push_str(", ");
             }
@@ -598,6 +660,33 @@ fn test_fn_type() {
     assert_eq!(printer.print_type(&type_), "fn(Int, gleam.String) -> Bool");
 }
 
+#[test]
+fn test_print_type_highlighting() {
+    let mut names = Names::new();
+    names.named_type_in_scope("gleam".into(), "Int".into(), "Int".into());
+    names.named_type_in_scope("gleam".into(), "String".into(), "String".into());
+    names.named_type_in_scope("gleam".into(), "List".into(), "List".into());
+    let mut printer = Printer::new(&names);
+
+    let type_ = crate::type_::list(crate::type_::tuple(vec![
+        crate::type_::int(),
+        crate::type_::fn_(vec![crate::type_::string()], crate::type_::int()),
+    ]));
+    let path = [
+        TypePathStep::TypeArgument(0),
+        TypePathStep::TupleElement(1),
+        TypePathStep::FnReturn,
+    ];
+
+    let (printed, highlight) = printer.print_type_highlighting(&type_, &path);
+    assert_eq!(printed, "List(#(Int, fn(String) -> Int))");
+    assert_eq!(&printed[highlight.unwrap()], "Int");
+    assert_eq!(
+        printer.print_type_highlighting(&type_, &[TypePathStep::FnReturn]),
+        (printed, None)
+    );
+}
+
 #[test]
 fn test_module_alias() {
     let mut names = Names::new();
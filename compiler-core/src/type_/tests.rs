@@ -2,8 +2,8 @@ use super::*;
 use crate::{
     analyse::TargetSupport,
     ast::{TypedModule, TypedStatement, UntypedExpr, UntypedModule},
-    build::{Origin, Outcome, Target},
-    config::PackageConfig,
+    build::{IntRepresentation, Origin, Outcome, Target},
+    config::{PackageConfig, WarningsConfig},
     error::Error,
     type_::{build_prelude, expression::FunctionDefinition, pretty::Printer},
     uid::UniqueIdGenerator,
@@ -103,6 +103,15 @@ macro_rules! assert_module_error {
     };
 }
 
+#[macro_export]
+macro_rules! assert_module_error_with_minimum_otp_version {
+    ($version:expr, $src:expr$(,)?) => {
+        let error = $crate::type_::tests::module_error_with_minimum_otp_version($src, $version);
+        let output = format!("----- SOURCE CODE\n{}\n\n----- ERROR\n{}", $src, error);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
 #[macro_export]
 macro_rules! assert_internal_module_error {
     ($src:expr) => {
@@ -240,6 +249,41 @@ fn get_printed_warnings(
     print_warnings(get_warnings(src, deps, target, gleam_version))
 }
 
+fn get_printed_warnings_with_package_target(src: &str, target: Target) -> String {
+    get_printed_warnings_with_config(
+        src,
+        PackageConfig {
+            target,
+            ..Default::default()
+        },
+    )
+}
+
+fn get_printed_warnings_with_shadowing(src: &str) -> String {
+    get_printed_warnings_with_config(
+        src,
+        PackageConfig {
+            warnings: WarningsConfig { shadowing: true },
+            ..Default::default()
+        },
+    )
+}
+
+fn get_printed_warnings_with_config(src: &str, config: PackageConfig) -> String {
+    let warnings = VectorWarningEmitterIO::default();
+    _ = compile_module_with_config(
+        "test_module",
+        src,
+        Some(Rc::new(warnings.clone())),
+        vec![],
+        Target::Erlang,
+        TargetSupport::NotEnforced,
+        config,
+    )
+    .expect("Compilation should succeed");
+    print_warnings(warnings.take())
+}
+
 fn print_warnings(warnings: Vec<crate::warning::Warning>) -> String {
     let mut nocolor = termcolor::Buffer::no_color();
     for warning in warnings {
@@ -346,6 +390,26 @@ macro_rules! assert_warnings_with_gleam_version {
     };
 }
 
+#[macro_export]
+macro_rules! assert_warnings_with_package_target {
+    ($target:expr, $src:expr$(,)?) => {
+        let warning = $crate::type_::tests::get_printed_warnings_with_package_target($src, $target);
+        assert!(!warning.is_empty());
+        let output = format!("----- SOURCE CODE\n{}\n\n----- WARNING\n{}", $src, warning);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
+#[macro_export]
+macro_rules! assert_warnings_with_shadowing {
+    ($src:expr$(,)?) => {
+        let warning = $crate::type_::tests::get_printed_warnings_with_shadowing($src);
+        assert!(!warning.is_empty());
+        let output = format!("----- SOURCE CODE\n{}\n\n----- WARNING\n{}", $src, warning);
+        insta::assert_snapshot!(insta::internals::AutoName, output, $src);
+    };
+}
+
 #[macro_export]
 macro_rules! assert_no_warnings {
     ($src:expr $(,)?) => {
@@ -478,6 +542,28 @@ pub fn compile_module_with_opts(
     target: Target,
     target_support: TargetSupport,
     gleam_version: Option<Range<Version>>,
+) -> Result<TypedModule, (Vec<crate::type_::Error>, Names)> {
+    let mut config = PackageConfig::default();
+    config.gleam_version = gleam_version;
+    compile_module_with_config(
+        module_name,
+        src,
+        warnings,
+        dep,
+        target,
+        target_support,
+        config,
+    )
+}
+
+pub fn compile_module_with_config(
+    module_name: &str,
+    src: &str,
+    warnings: Option<Rc<dyn WarningEmitterIO>>,
+    dep: Vec<DependencyModule<'_>>,
+    target: Target,
+    target_support: TargetSupport,
+    mut config: PackageConfig,
 ) -> Result<TypedModule, (Vec<crate::type_::Error>, Names)> {
     let ids = UniqueIdGenerator::new();
     let mut modules = im::HashMap::new();
@@ -524,9 +610,7 @@ pub fn compile_module_with_opts(
         .expect("syntax error");
     let mut ast = parsed.module;
     ast.name = module_name.into();
-    let mut config = PackageConfig::default();
     config.name = "thepackage".into();
-    config.gleam_version = gleam_version;
 
     let warnings = TypeWarningEmitter::new("/src/warning/wrn.gleam".into(), src.into(), emitter);
     let inference_result = crate::analyse::ModuleAnalyzerConstructor::<()> {
@@ -576,6 +660,28 @@ pub fn module_error_with_target(
     error.pretty_string()
 }
 
+pub fn module_error_with_minimum_otp_version(src: &str, minimum_otp_version: u32) -> String {
+    let mut config = PackageConfig::default();
+    config.erlang.minimum_otp_version = Some(minimum_otp_version);
+    let (error, names) = compile_module_with_config(
+        "themodule",
+        src,
+        None,
+        vec![],
+        Target::Erlang,
+        TargetSupport::NotEnforced,
+        config,
+    )
+    .expect_err("should infer an error");
+    let error = Error::Type {
+        names,
+        src: src.into(),
+        path: Utf8PathBuf::from("/src/one/two.gleam"),
+        errors: Vec1::try_from_vec(error).expect("should have at least one error"),
+    };
+    error.pretty_string()
+}
+
 pub fn internal_module_error(src: &str, deps: Vec<DependencyModule<'_>>) -> String {
     internal_module_error_with_target(src, deps, Target::Erlang)
 }
@@ -845,6 +951,9 @@ fn infer_module_type_retention_test() {
             line_numbers: LineNumbers::new(""),
             src_path: "".into(),
             minimum_required_version: Version::new(0, 1, 0),
+            inferred_annotations: vec![],
+            function_references: vec![],
+            references: vec![],
         }
     );
 }
@@ -2678,6 +2787,46 @@ fn private_type_from_internal_module_is_not_exposed_as_internal() {
     assert!(module.type_info.get_public_type("Wibble").is_none());
 }
 
+#[test]
+fn inferred_annotations_are_recorded_for_lets_and_holes() {
+    let src = "pub fn main(x: _) {
+  let y: List(_) = [x, 1]
+  let #(a, b) = #(y, 1.0)
+  use z <- wobble(a)
+  b
+}
+
+fn wobble(x, f) { f(x) }";
+    let module = compile_module("test_module", src, None, vec![]).unwrap();
+    let inferred = module
+        .type_info
+        .inferred_annotations
+        .iter()
+        .map(|annotation| {
+            let mut printer = Printer::new();
+            (
+                &src[annotation.location.start as usize..annotation.location.end as usize],
+                annotation.kind,
+                printer.pretty_print(&annotation.type_, 0),
+            )
+        })
+        .collect_vec();
+
+    assert_eq!(
+        inferred,
+        vec![
+            ("_", InferredAnnotationKind::Hole, "Int".into()),
+            ("y", InferredAnnotationKind::LetBinding, "List(Int)".into()),
+            ("_", InferredAnnotationKind::Hole, "Int".into()),
+            (
+                "#(a, b)",
+                InferredAnnotationKind::LetBinding,
+                "#(List(Int), Float)".into()
+            ),
+        ]
+    );
+}
+
 #[test]
 fn assert_suitable_main_function_not_module_function() {
     let value = ValueConstructor {
@@ -3025,3 +3174,29 @@ pub fn main() {
         vec![("main", "fn() -> Int")]
     );
 }
+
+#[test]
+fn test_function_names() {
+    let module = compile_module(
+        "app_test",
+        r#"
+pub fn wobble_test() { Nil }
+pub fn wibble_test() { Nil }
+fn private_test() { Nil }
+pub fn with_argument_test(x) { x }
+pub fn helper() { Nil }
+@external(erlang, "app_ffi", "erlang_only")
+pub fn erlang_only_test() -> Nil
+"#,
+        None,
+        vec![],
+    )
+    .expect("should successfully infer");
+    assert_eq!(
+        module.type_info.test_function_names(Target::JavaScript),
+        vec![
+            EcoString::from("wibble_test"),
+            EcoString::from("wobble_test")
+        ]
+    );
+}
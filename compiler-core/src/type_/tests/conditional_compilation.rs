@@ -1,4 +1,7 @@
-use crate::assert_module_infer;
+use crate::{
+    assert_js_module_error, assert_js_warning, assert_module_error, assert_module_infer,
+    assert_no_warnings, assert_warning,
+};
 
 #[test]
 fn excluded_error() {
@@ -105,3 +108,88 @@ pub fn main() -> Int
         vec![("main", "fn() -> Int")],
     );
 }
+
+#[test]
+fn function_defined_for_other_target() {
+    assert_module_error!(
+        "
+@target(javascript)
+fn wibble() { 1 }
+
+pub fn main() { wibble() }
+"
+    );
+}
+
+#[test]
+fn constant_defined_for_other_target() {
+    assert_js_module_error!(
+        "
+@target(erlang)
+const wibble = 1
+
+pub fn main() { wibble }
+"
+    );
+}
+
+#[test]
+fn function_defined_for_both_targets() {
+    assert_module_infer!(
+        "
+@target(javascript)
+fn wibble() { 1 }
+
+@target(erlang)
+fn wibble() { 2 }
+
+pub fn main() { wibble() }
+",
+        vec![("main", "fn() -> Int")],
+    );
+}
+
+#[test]
+fn public_function_defined_for_other_target() {
+    assert_warning!(
+        "
+@target(javascript)
+pub fn wibble() { 1 }
+"
+    );
+}
+
+#[test]
+fn public_constant_defined_for_other_target() {
+    assert_js_warning!(
+        "
+@target(erlang)
+pub const wibble = 1
+"
+    );
+}
+
+#[test]
+fn public_function_defined_for_each_target() {
+    assert_no_warnings!(
+        "
+@target(javascript)
+pub fn wibble() { 1 }
+
+@target(erlang)
+pub fn wibble() { 2 }
+"
+    );
+}
+
+#[test]
+fn private_function_defined_for_other_target() {
+    assert_no_warnings!(
+        "
+@target(javascript)
+fn wibble() { 1 }
+
+pub fn main() { 1 }
+"
+    );
+}
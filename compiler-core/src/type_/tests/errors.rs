@@ -1,5 +1,6 @@
 use crate::{
-    assert_error, assert_internal_module_error, assert_module_error, assert_module_syntax_error,
+    assert_error, assert_internal_module_error, assert_module_error,
+    assert_module_error_with_minimum_otp_version, assert_module_syntax_error,
     assert_with_module_error,
 };
 
@@ -266,6 +267,25 @@ fn case_clause_mismatch() {
     assert_error!("case 1 { a -> 1 b -> 2.0 }");
 }
 
+#[test]
+fn case_clause_mismatch_reports_all_clauses() {
+    assert_module_error!(
+        r#"
+pub fn main(x) {
+  case x {
+    0 -> 1
+    1 -> "one"
+    2 -> {
+      let y = x
+      y * 2
+    }
+    _ -> 1.0
+  }
+}
+"#
+    );
+}
+
 #[test]
 fn case_subject_pattern_unify() {
     assert_error!("case 1.0 { 1 -> 1 }");
@@ -385,6 +405,16 @@ fn recursive_var() {
     assert_error!("let id = fn(x) { x(x) } 1");
 }
 
+#[test]
+fn recursive_var_in_list() {
+    assert_error!("fn(x) { [x] == x }");
+}
+
+#[test]
+fn recursive_var_in_tuple_without_other_type_variables() {
+    assert_error!("fn(x) { x == #(1, x) }");
+}
+
 #[test]
 fn true_fn() {
     assert_error!("let True(x) = 1");
@@ -608,6 +638,38 @@ pub fn get_title(person: Person) { person.title }"
     );
 }
 
+#[test]
+fn field_not_in_all_variants_of_aliased_module_type() {
+    assert_with_module_error!(
+        (
+            "school/person",
+            "pub type Person {
+    Teacher(name: String, title: String)
+    Student(name: String)
+}"
+        ),
+        "
+import school/person as p
+pub fn get_title(person: p.Person) { person.title }"
+    );
+}
+
+#[test]
+fn field_not_in_all_variants_of_unqualified_imported_type() {
+    assert_with_module_error!(
+        (
+            "school/person",
+            "pub type Person {
+    Teacher(name: String, title: String)
+    Student(name: String)
+}"
+        ),
+        "
+import school/person.{type Person, Teacher}
+pub fn get_title(person: Person) { person.title }"
+    );
+}
+
 #[test]
 fn field_not_in_any_variant() {
     assert_module_error!(
@@ -2630,6 +2692,65 @@ pub fn b_to_a(value: B) {
     );
 }
 
+#[test]
+fn record_update_wrong_variant_missing_fields() {
+    assert_module_error!(
+        r#"
+pub type Pet {
+  Cat(name: String, lives: Int, indoor: Bool)
+  Dog(name: String, good_boy: Bool)
+}
+
+pub fn dog_to_cat(pet: Pet) {
+  case pet {
+    Cat(..) -> pet
+    Dog(..) as dog -> Cat(..dog, name: "Tom")
+  }
+}
+"#
+    );
+}
+
+#[test]
+fn record_update_other_type_is_type_mismatch() {
+    assert_module_error!(
+        r#"
+pub type Cat {
+  Cat(name: String, lives: Int)
+}
+
+pub type Dog {
+  Dog(name: String, good_boy: Bool)
+}
+
+pub fn dog_to_cat(dog: Dog) {
+  Cat(..dog, name: "Tom")
+}
+"#
+    );
+}
+
+#[test]
+fn record_update_other_type_with_inferred_variant_is_type_mismatch() {
+    assert_module_error!(
+        r#"
+pub type Cat {
+  Cat(name: String, lives: Int)
+}
+
+pub type Pet {
+  Dog(name: String)
+  Fish(name: String, lives: Int)
+}
+
+pub fn main() {
+  let pet = Dog("Rex")
+  Cat(..pet, name: "Tom")
+}
+"#
+    );
+}
+
 #[test]
 fn record_update_incompatible_but_linked_generics() {
     assert_module_error!(
@@ -2655,3 +2776,141 @@ todo
 "#
     );
 }
+
+#[test]
+fn type_mismatch_highlights_nested_difference() {
+    assert_module_error!(
+        r#"
+pub fn main() {
+  let x: List(#(Int, String, Float)) = [#(1, "a", 1.0)]
+  let y: List(#(Int, Int, Float)) = x
+  y
+}
+"#
+    );
+}
+
+#[test]
+fn type_mismatch_highlights_difference_in_function_type() {
+    assert_module_error!(
+        r#"
+pub fn main() {
+  let f: #(Int, fn(Int, String) -> Bool) = #(1, fn(_, _) { True })
+  let g: #(Int, fn(Int, Int) -> Bool) = f
+  g
+}
+"#
+    );
+}
+
+#[test]
+fn type_mismatch_highlights_difference_in_function_return_type() {
+    assert_module_error!(
+        r#"
+pub fn main() {
+  let f: List(fn() -> Result(Int, String)) = []
+  let g: List(fn() -> Result(Int, Nil)) = f
+  g
+}
+"#
+    );
+}
+
+#[test]
+fn incorrect_arity_suggests_missing_argument() {
+    assert_module_error!(
+        r#"
+fn wibble(a: Int, b: String) { a }
+
+pub fn main() {
+  wibble(1)
+}
+"#
+    );
+}
+
+#[test]
+fn incorrect_arity_suggests_missing_labelled_arguments() {
+    assert_module_error!(
+        r#"
+fn wibble(a: Int, b b: String, c c: Float) { a }
+
+pub fn main() {
+  wibble(c: 1.0)
+}
+"#
+    );
+}
+
+#[test]
+fn incorrect_arity_suggests_removing_extra_arguments() {
+    assert_module_error!(
+        r#"
+fn wibble(a: Int) { a }
+
+pub fn main() {
+  wibble(1, 2, 3)
+}
+"#
+    );
+}
+
+#[test]
+fn incorrect_arity_suggests_missing_tuple_pattern_element() {
+    assert_module_error!(
+        r#"
+pub fn main() {
+  let #(a, b) = #(1, 2.0, "three")
+  a
+}
+"#
+    );
+}
+
+#[test]
+fn incorrect_arity_suggests_missing_constructor_pattern_fields() {
+    assert_module_error!(
+        r#"
+pub type Wibble {
+  Wibble(Int, String)
+}
+
+pub fn main(wibble) {
+  let Wibble = wibble
+}
+"#
+    );
+}
+
+#[test]
+fn unknown_field_missing_from_imported_variants_suggests_case() {
+    assert_with_module_error!(
+        (
+            "some_mod",
+            "pub type Wibble {
+              Wibble(field: Int)
+              Wobble(other: String)
+              Wubble
+            }"
+        ),
+        "
+import some_mod
+pub fn main(wibble: some_mod.Wibble) {
+  wibble.field
+}
+"
+    );
+}
+
+#[test]
+fn sixteen_bit_float_segment_with_old_minimum_otp_version() {
+    assert_module_error_with_minimum_otp_version!(23, "pub fn main() { <<1.5:16-float>> }");
+}
+
+#[test]
+fn sixteen_bit_float_pattern_with_old_minimum_otp_version() {
+    assert_module_error_with_minimum_otp_version!(
+        23,
+        "pub fn main(x) { let assert <<y:float-size(16)>> = x  y }"
+    );
+}
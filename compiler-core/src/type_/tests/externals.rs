@@ -1,6 +1,7 @@
 use crate::{
     assert_infer_with_module, assert_js_module_error, assert_js_module_infer, assert_module_error,
-    assert_module_infer, assert_with_module_error,
+    assert_module_infer, assert_warning, assert_warnings_with_package_target,
+    assert_with_module_error, build::Target,
 };
 
 // https://github.com/gleam-lang/gleam/issues/2324
@@ -234,3 +235,117 @@ pub const wobble = wobble",
         vec![("wobble", "fn() -> Int")],
     );
 }
+
+#[test]
+fn empty_erlang_module() {
+    assert_warning!(
+        r#"
+@external(erlang, "", "wobble")
+pub fn wobble() -> Int
+"#
+    );
+}
+
+#[test]
+fn erlang_function_with_spaces() {
+    assert_warning!(
+        r#"
+@external(erlang, "wibble", "wob ble")
+pub fn wobble() -> Int
+"#
+    );
+}
+
+#[test]
+fn erlang_external_with_wrong_arity() {
+    assert_module_error!(
+        r#"
+@external(erlang, "wibble", "wobble/2")
+pub fn wobble(a: Int) -> Int
+"#
+    );
+}
+
+#[test]
+fn erlang_external_with_matching_arity() {
+    assert_warning!(
+        r#"
+@external(erlang, "wibble", "wobble/1")
+pub fn wobble(a: Int) -> Int
+"#
+    );
+}
+
+#[test]
+fn javascript_external_with_wrong_arity() {
+    assert_module_error!(
+        r#"
+@external(javascript, "./wibble.mjs", "wobble/0")
+pub fn wobble(a: Int) -> Int
+"#
+    );
+}
+
+#[test]
+fn erlang_module_with_dots_is_valid() {
+    assert_module_infer!(
+        r#"
+@external(erlang, "Elixir.Wibble", "wobble?")
+pub fn wobble() -> Bool
+"#,
+        vec![("wobble", "fn() -> Bool")]
+    );
+}
+
+#[test]
+fn external_for_single_target_when_package_is_used_on_both_targets() {
+    assert_warnings_with_package_target!(
+        Target::JavaScript,
+        r#"
+@external(erlang, "wibble", "wobble")
+pub fn wobble() -> Int
+"#
+    );
+}
+
+#[test]
+fn external_for_single_target_with_gleam_fallback_when_package_is_used_on_both_targets() {
+    let warnings = super::get_printed_warnings_with_package_target(
+        r#"
+@external(erlang, "wibble", "wobble")
+pub fn wobble() -> Int {
+  1
+}
+"#,
+        Target::JavaScript,
+    );
+    assert_eq!(warnings, "");
+}
+
+#[test]
+fn external_for_single_target_when_package_is_used_on_one_target() {
+    let warnings = super::get_printed_warnings_with_package_target(
+        r#"
+@external(erlang, "wibble", "wobble")
+pub fn wobble() -> Int
+"#,
+        Target::Erlang,
+    );
+    assert_eq!(warnings, "");
+}
+
+#[test]
+fn private_external_for_single_target_when_package_is_used_on_both_targets() {
+    let warnings = super::get_printed_warnings_with_package_target(
+        r#"
+@external(erlang, "wibble", "wobble")
+fn wobble() -> Int
+
+pub fn main() {
+  wobble()
+}
+"#,
+        Target::JavaScript,
+    );
+    assert_eq!(warnings, "");
+}
@@ -62,6 +62,59 @@ pub fn main() {
     );
 }
 
+#[test]
+fn pattern_matching_on_opaque_constructor() {
+    assert_with_module_error!(
+        ("one", "pub opaque type Two { Two }"),
+        "import one
+
+pub fn main(x) {
+  case x {
+    one.Two -> 1
+  }
+}",
+    );
+}
+
+#[test]
+fn using_opaque_constructor_in_constant() {
+    assert_with_module_error!(
+        ("one", "pub opaque type Two { Two(Int) }"),
+        "import one
+
+pub const two = one.Two(1)
+",
+    );
+}
+
+#[test]
+fn using_private_constant_in_constant() {
+    assert_with_module_error!(
+        ("one", "const two = 2"),
+        "import one
+
+pub const two = one.two
+",
+    );
+}
+
+#[test]
+fn opaque_type_can_be_referenced() {
+    assert_infer_with_module!(
+        (
+            "one",
+            "pub opaque type Two { Two(Int) }
+pub fn new() { Two(1) }"
+        ),
+        "import one
+
+pub fn main() -> one.Two {
+  one.new()
+}",
+        vec![("main", "fn() -> Two")],
+    );
+}
+
 #[test]
 fn using_private_function() {
     assert_with_module_error!(
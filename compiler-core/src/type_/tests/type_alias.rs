@@ -148,3 +148,18 @@ fn conflict_with_import() {
         "import wibble.{type Wobble} type Wobble = Int",
     );
 }
+
+#[test]
+fn alias_mutual_cycle_through_other_types() {
+    assert_module_error!(
+        r#"
+type Wibble = List(Wobble)
+
+pub type Wabble {
+  Wabble
+}
+
+type Wobble = #(Int, fn(Wibble) -> Wabble)
+"#
+    );
+}
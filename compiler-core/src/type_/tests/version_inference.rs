@@ -31,6 +31,19 @@ pub type Wibble
     assert_eq!(version, Version::new(1, 1, 0));
 }
 
+#[test]
+fn phantom_annotation_requires_v1_8() {
+    let version = infer_version(
+        "
+@phantom
+pub type Wibble(a) {
+  Wibble
+}
+",
+    );
+    assert_eq!(version, Version::new(1, 8, 0));
+}
+
 #[test]
 fn internal_annotation_on_function_requires_v1_1() {
     let version = infer_version(
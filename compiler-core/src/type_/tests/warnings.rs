@@ -2,6 +2,7 @@ use super::*;
 use crate::{
     assert_js_no_warnings, assert_js_warning, assert_no_warnings, assert_warning,
     assert_warnings_with_gleam_version, assert_warnings_with_imports,
+    assert_warnings_with_package_target, assert_warnings_with_shadowing,
 };
 
 #[test]
@@ -82,6 +83,20 @@ pub fn main() {
     );
 }
 
+#[test]
+fn result_discarded_in_use_callback() {
+    assert_warning!(
+        "
+pub fn wibble() { Ok(5) }
+pub fn with(f: fn() -> a) -> a { f() }
+pub fn main() {
+  use <- with
+  wibble()
+  5
+}"
+    );
+}
+
 #[test]
 fn result_discard_warning_test2() {
     // Explicitly discarded Results do not emit warnings
@@ -247,6 +262,57 @@ pub fn a() {
     );
 }
 
+#[test]
+fn unused_type_parameter() {
+    assert_warning!(
+        "
+pub type Wibble(a, b) {
+  Wibble(a)
+  Wobble(List(a))
+}
+"
+    );
+}
+
+#[test]
+fn unused_type_parameters_are_all_reported() {
+    assert_warning!(
+        "
+pub type Wibble(a, b, c) {
+  Wibble(b)
+}
+"
+    );
+}
+
+#[test]
+fn type_parameter_used_in_a_nested_type_is_not_reported() {
+    assert_no_warnings!(
+        "
+pub type Wibble(a, b) {
+  Wibble(List(#(a, fn(Int) -> b)))
+}
+"
+    );
+}
+
+#[test]
+fn phantom_type_parameter_is_not_reported() {
+    assert_no_warnings!(
+        "
+@phantom
+pub type Id(a) {
+  Id(Int)
+}
+"
+    );
+}
+
+#[test]
+fn external_type_parameter_is_not_reported() {
+    assert_no_warnings!("pub type Wibble(a)");
+}
+
 #[test]
 fn unused_private_fn_warnings_test() {
     assert_warning!("fn a() { 1 }");
@@ -272,6 +338,49 @@ fn used_private_const_warnings_test() {
     assert_no_warnings!("pub const a = 1");
 }
 
+#[test]
+fn private_const_only_used_by_unused_const() {
+    assert_warning!(
+        "
+const a = 1
+const b = a
+"
+    );
+}
+
+#[test]
+fn private_consts_only_used_by_unused_const_chain() {
+    assert_warning!(
+        "
+const a = 1
+const b = [a]
+const c = #(b, a)
+"
+    );
+}
+
+#[test]
+fn private_const_used_by_public_const() {
+    assert_no_warnings!(
+        "
+const a = 1
+const b = [a]
+pub const c = b
+"
+    );
+}
+
+#[test]
+fn private_const_used_by_used_private_const() {
+    assert_no_warnings!(
+        "
+const a = 1
+const b = [a]
+pub fn main() { b }
+"
+    );
+}
+
 #[test]
 fn used_private_const_warnings_test2() {
     assert_no_warnings!("const a = 1 pub fn b() { a }");
@@ -1192,6 +1301,127 @@ pub fn main() {
     );
 }
 
+#[test]
+fn pattern_never_matching_constant() {
+    assert_warning!(
+        "
+const debug = False
+
+pub fn main() {
+  case debug {
+    True -> 1
+    False -> 2
+  }
+}"
+    );
+}
+
+#[test]
+fn pattern_always_matching_constant() {
+    assert_warning!(
+        "
+const debug = False
+
+pub fn main() {
+  case debug {
+    False -> 1
+    True -> 2
+  }
+}"
+    );
+}
+
+#[test]
+fn no_never_matching_pattern_warning_for_literal() {
+    assert_warning!(
+        "
+pub fn main() {
+  case 1 {
+    2 -> 1
+    _ -> 2
+  }
+}"
+    );
+}
+
+#[test]
+fn pattern_never_matching_nested_constant() {
+    assert_warning!(
+        "
+pub type Level { Info Debug }
+const config = #(Info, \"prefix\", [1, 2])
+
+pub fn main() {
+  case config {
+    #(Debug, _, _) -> 1
+    #(_, \"pre\" <> _, [_]) -> 2
+    #(_, _, [0x1, ..]) -> 3
+    _ -> 4
+  }
+}"
+    );
+}
+
+#[test]
+fn pattern_never_matching_alternative() {
+    assert_warning!(
+        "
+const size = 3
+
+pub fn main() {
+  case size {
+    1 | 3 -> 1
+    _ -> 2
+  }
+}"
+    );
+}
+
+#[test]
+fn pattern_always_matching_one_of_multiple_subjects() {
+    assert_warning!(
+        "
+const debug = True
+
+pub fn main(x) {
+  case debug, x {
+    True, _ -> 1
+    _, _ -> 2
+  }
+}"
+    );
+}
+
+#[test]
+fn pattern_always_matching_with_guard_is_not_reported() {
+    assert_no_warnings!(
+        "
+const debug = True
+
+pub fn main(x) {
+  case debug {
+    True if x -> 1
+    _ -> 2
+  }
+}"
+    );
+}
+
+#[test]
+fn pattern_on_partially_known_value_only_warns_for_the_tuple() {
+    assert_warning!(
+        "
+const zero = 0
+
+pub fn main(x) {
+  case #(zero, x) {
+    #(0, 1) -> 1
+    _ -> 2
+  }
+}"
+    );
+}
+
 #[test]
 fn opaque_external_type_raises_a_warning() {
     assert_warning!("pub opaque type External");
@@ -1918,6 +2148,153 @@ fn unreachable_code_analysis_treats_anonymous_functions_independently_3() {
     );
 }
 
+#[test]
+fn unreachable_code_after_todo() {
+    assert_warning!(
+        r#"
+        pub fn main() {
+            todo
+            "warning here!"
+        }
+        "#
+    );
+}
+
+#[test]
+fn unreachable_code_after_todo_assignment() {
+    assert_warning!(
+        r#"
+        pub fn main() {
+            let x = todo
+            x + 1
+        }
+        "#
+    );
+}
+
+#[test]
+fn only_todo_warning_for_todo_argument() {
+    assert_warning!(
+        r#"
+        pub fn main() {
+            wibble(todo as "later")
+            Nil
+        }
+
+        fn wibble(x: Int) -> Int { x }
+        "#
+    );
+}
+
+#[test]
+fn unreachable_code_after_function_that_never_returns() {
+    assert_warning!(
+        r#"
+        pub fn main() {
+            crash("oh no")
+            "warning here!"
+        }
+
+        fn crash(message: String) -> a {
+            panic as message
+        }
+        "#
+    );
+}
+
+#[test]
+fn unreachable_code_after_imported_function_that_never_returns() {
+    assert_warnings_with_imports!(
+        ("wibble", "pub fn crash(message: String) -> a { panic as message }");
+        r#"
+        import wibble
+
+        pub fn main() {
+            wibble.crash("oh no")
+            "warning here!"
+        }
+        "#,
+    );
+}
+
+#[test]
+fn no_unreachable_code_after_recursive_function() {
+    assert_no_warnings!(
+        r#"
+        pub fn main() {
+            wibble()
+            "no warning here!"
+        }
+
+        fn wibble() { wibble() }
+        "#
+    );
+}
+
+#[test]
+fn no_unreachable_code_after_imported_recursive_function() {
+    assert_no_warnings!(
+        ("thepackage", "wibble", "pub fn loop() { loop() }"),
+        r#"
+        import wibble
+
+        pub fn main() {
+            wibble.loop()
+            "no warning here!"
+        }
+        "#
+    );
+}
+
+#[test]
+fn no_unreachable_code_after_imported_function_calling_recursive_function() {
+    assert_no_warnings!(
+        (
+            "thepackage",
+            "wibble",
+            "pub fn wibble() { wobble() } fn wobble() { wibble() }"
+        ),
+        r#"
+        import wibble
+
+        pub fn main() {
+            wibble.wibble()
+            "no warning here!"
+        }
+        "#
+    );
+}
+
+#[test]
+fn no_unreachable_code_after_generic_function_that_can_return() {
+    assert_no_warnings!(
+        r#"
+        pub fn main() {
+            identity(1)
+            "no warning here!"
+        }
+
+        fn identity(x: a) -> a { x }
+        "#
+    );
+}
+
+#[test]
+fn no_unreachable_code_after_external_function_returning_a_type_variable() {
+    assert_no_warnings!(
+        r#"
+        pub fn main() {
+            let _ = decode(1)
+            "no warning here!"
+        }
+
+        @external(erlang, "wibble", "decode")
+        @external(javascript, "./wibble.mjs", "decode")
+        fn decode(x: Int) -> a
+        "#
+    );
+}
+
 #[test]
 fn no_warnings_for_matches_used_like_ifs() {
     assert_no_warnings!(
@@ -2630,6 +3007,54 @@ pub fn go() {
     );
 }
 
+#[test]
+fn javascript_unsafe_int_when_package_supports_javascript() {
+    assert_warnings_with_package_target!(
+        Target::JavaScript,
+        r#"
+pub fn go() {
+  9_007_199_254_740_993
+}
+"#
+    );
+}
+
+#[test]
+fn no_javascript_unsafe_int_when_package_only_supports_erlang() {
+    let warnings = get_printed_warnings_with_package_target(
+        r#"
+pub fn go() {
+  9_007_199_254_740_993
+}
+"#,
+        Target::Erlang,
+    );
+    assert_eq!(warnings, "");
+}
+
+#[test]
+fn no_javascript_unsafe_int_when_ints_are_big_ints() {
+    let mut config = PackageConfig {
+        target: Target::JavaScript,
+        ..Default::default()
+    };
+    config.javascript.int_representation = IntRepresentation::BigInt;
+    let warnings = get_printed_warnings_with_config(
+        r#"
+pub const i = 9_007_199_254_740_992
+
+pub fn go(x) {
+  case x {
+    9_007_199_254_740_993 -> 9_007_199_254_740_994
+    _ -> x
+  }
+}
+"#,
+        config,
+    );
+    assert_eq!(warnings, "");
+}
+
 #[test]
 fn javascript_unsafe_int_in_const() {
     assert_js_warning!(r#"pub const i = 9_007_199_254_740_992"#);
@@ -2989,3 +3414,130 @@ pub fn main() {
 "#
     );
 }
+
+#[test]
+fn unused_function_argument() {
+    assert_warning!(
+        r#"
+pub fn main(x: Int) {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn unused_labelled_function_argument() {
+    assert_warning!(
+        r#"
+pub fn main(label x: Int) {
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn unused_anonymous_function_argument() {
+    assert_warning!(
+        r#"
+pub fn main() {
+  fn(x) { Nil }
+}
+"#
+    );
+}
+
+#[test]
+fn unused_use_callback_argument() {
+    assert_warning!(
+        r#"
+fn apply(f: fn(Int) -> a) -> a {
+  f(1)
+}
+
+pub fn main() {
+  use x <- apply
+  Nil
+}
+"#
+    );
+}
+
+#[test]
+fn discarded_function_argument_is_not_unused() {
+    assert_no_warnings!(
+        r#"
+pub fn main(_x: Int) {
+  fn(_y) { Nil }
+}
+"#
+    );
+}
+
+#[test]
+fn shadowed_variable_is_not_reported_by_default() {
+    assert_no_warnings!(
+        "
+pub fn main() {
+  let x = 1
+  let x = x + 1
+  x
+}"
+    );
+}
+
+#[test]
+fn shadowed_variable() {
+    assert_warnings_with_shadowing!(
+        "
+pub fn main() {
+  let x = 1
+  let x = x + 1
+  x
+}"
+    );
+}
+
+#[test]
+fn shadowed_variable_in_pattern() {
+    assert_warnings_with_shadowing!(
+        "
+pub fn main(x) {
+  case x {
+    [x, ..] -> x
+    _ -> 0
+  }
+}"
+    );
+}
+
+#[test]
+fn shadowed_variable_by_anonymous_function_argument() {
+    assert_warnings_with_shadowing!(
+        "
+pub fn main(x) {
+  fn(x) { x + 1 }(x)
+}"
+    );
+}
+
+#[test]
+fn variables_in_different_scopes_are_not_shadowed() {
+    let warnings = get_printed_warnings_with_shadowing(
+        "
+pub fn main(y) {
+  case y {
+    1 -> {
+      let x = 1
+      x
+    }
+    _ -> {
+      let x = 2
+      x
+    }
+  }
+}",
+    );
+    assert_eq!(warnings, "");
+}
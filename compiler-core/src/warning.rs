@@ -2,12 +2,13 @@ use crate::{
     ast::{SrcSpan, TodoKind},
     build::Target,
     diagnostic::{self, Diagnostic, Location},
-    error::wrap,
+    error::{erlang_compiler_diagnostic, wrap},
+    io::ErlangCompilerDiagnostic,
     type_::{
         self,
         error::{
-            FeatureKind, LiteralCollectionKind, PanicPosition, TodoOrPanic,
-            UnreachableCaseClauseReason,
+            DivergingExpression, FeatureKind, LiteralCollectionKind, PanicPosition, TodoOrPanic,
+            UnreachableCaseClauseReason, VariableOrigin,
         },
         pretty::Printer,
     },
@@ -162,6 +163,14 @@ pub enum Warning {
         src: EcoString,
         warning: DeprecatedSyntaxWarning,
     },
+
+    ErlangCompiler {
+        warning: ErlangCompilerDiagnostic,
+    },
+
+    Dialyzer {
+        warning: ErlangCompilerDiagnostic,
+    },
 }
 
 #[derive(Debug, Clone, Eq, PartialEq, Copy)]
@@ -220,6 +229,16 @@ pub enum DeprecatedSyntaxWarning {
 impl Warning {
     pub fn to_diagnostic(&self) -> Diagnostic {
         match self {
+            Warning::ErlangCompiler { warning } => erlang_compiler_diagnostic(
+                warning,
+                "Erlang compiler warning",
+                diagnostic::Level::Warning,
+            ),
+
+            Warning::Dialyzer { warning } => {
+                erlang_compiler_diagnostic(warning, "Dialyzer warning", diagnostic::Level::Warning)
+            }
+
             Warning::InvalidSource { path } => Diagnostic {
                 title: "Invalid module name".into(),
                 text: "\
@@ -411,10 +430,15 @@ A use expression must always be followed by at least one expression.",
 
                 type_::Warning::ImplicitlyDiscardedResult { location } => Diagnostic {
                     title: "Unused result value".into(),
-                    text: "".into(),
-                    hint: Some(
-                        "If you are sure you don't need it you can assign it to `_`.".into(),
+                    text: wrap(
+                        "This expression returns a `Result` but its value is \
+discarded, so any error it produces is silently ignored.",
                     ),
+                    hint: Some(wrap(
+                        "Handle the possible error with a `case` expression, \
+crash if it is an error with `let assert Ok(_) =`, or if you are sure you don't \
+need it assign it to `_` with `let _ =`.",
+                    )),
                     level: diagnostic::Level::Warning,
                     location: Some(Location {
                         path: path.to_path_buf(),
@@ -507,6 +531,29 @@ A use expression must always be followed by at least one expression.",
                     }
                 }
 
+                type_::Warning::UnusedTypeParameter {
+                    location,
+                    name,
+                    type_name,
+                } => Diagnostic {
+                    title: "Unused type parameter".into(),
+                    text: wrap(&format!(
+                        "The type parameter `{name}` is not used by any of the \
+constructors of `{type_name}`."
+                    )),
+                    hint: Some("If this is intentional, annotate the type with `@phantom`.".into()),
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        src: src.clone(),
+                        path: path.to_path_buf(),
+                        label: diagnostic::Label {
+                            text: Some("This type parameter is never used".into()),
+                            span: *location,
+                        },
+                        extra_labels: Vec::new(),
+                    }),
+                },
+
                 type_::Warning::UnusedConstructor {
                     location, imported, ..
                 } => {
@@ -630,21 +677,34 @@ Hint: You can safely remove it.
                     }),
                 },
 
-                type_::Warning::UnusedVariable { location, origin } => Diagnostic {
-                    title: "Unused variable".into(),
-                    text: "".into(),
-                    hint: origin.how_to_ignore(),
-                    level: diagnostic::Level::Warning,
-                    location: Some(Location {
-                        src: src.clone(),
-                        path: path.to_path_buf(),
-                        label: diagnostic::Label {
-                            text: Some("This variable is never used".into()),
-                            span: *location,
-                        },
-                        extra_labels: Vec::new(),
-                    }),
-                },
+                type_::Warning::UnusedVariable { location, origin } => {
+                    let (title, label) = match origin {
+                        VariableOrigin::FunctionArgument(_) => {
+                            ("Unused function argument", "This argument is never used")
+                        }
+                        VariableOrigin::Variable(_)
+                        | VariableOrigin::LabelShorthand(_)
+                        | VariableOrigin::AssignmentPattern
+                        | VariableOrigin::Generated => {
+                            ("Unused variable", "This variable is never used")
+                        }
+                    };
+                    Diagnostic {
+                        title: title.into(),
+                        text: "".into(),
+                        hint: origin.how_to_ignore(),
+                        level: diagnostic::Level::Warning,
+                        location: Some(Location {
+                            src: src.clone(),
+                            path: path.to_path_buf(),
+                            label: diagnostic::Label {
+                                text: Some(label.into()),
+                                span: *location,
+                            },
+                            extra_labels: Vec::new(),
+                        }),
+                    }
+                }
                 type_::Warning::UnnecessaryDoubleIntNegation { location } => Diagnostic {
                     title: "Unnecessary double negation (--) on integer".into(),
                     text: "".into(),
@@ -858,6 +918,45 @@ can already tell which branch is going to match with this value.",
                     }),
                 },
 
+                type_::Warning::NeverMatchingPattern { location } => Diagnostic {
+                    title: "Pattern never matches".into(),
+                    text: wrap(
+                        "The value being matched on is known at compile time \
+and this pattern can never match it, so the code for it will never run.",
+                    ),
+                    hint: None,
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        src: src.clone(),
+                        path: path.to_path_buf(),
+                        label: diagnostic::Label {
+                            text: Some("This pattern never matches".into()),
+                            span: *location,
+                        },
+                        extra_labels: Vec::new(),
+                    }),
+                },
+
+                type_::Warning::AlwaysMatchingPattern { location } => Diagnostic {
+                    title: "Pattern always matches".into(),
+                    text: wrap(
+                        "The value being matched on is known at compile time \
+and this pattern always matches it, so none of the clauses after it will ever \
+be reached.",
+                    ),
+                    hint: None,
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        src: src.clone(),
+                        path: path.to_path_buf(),
+                        label: diagnostic::Label {
+                            text: Some("This pattern always matches".into()),
+                            span: *location,
+                        },
+                        extra_labels: Vec::new(),
+                    }),
+                },
+
                 type_::Warning::OpaqueExternalType { location } => Diagnostic {
                     title: "Opaque external type".into(),
                     text: "This type has no constructors so making it opaque is redundant.".into(),
@@ -1000,18 +1099,34 @@ See: https://tour.gleam.run/advanced-features/{name}/"
                 type_::Warning::UnreachableCodeAfterPanic {
                     location,
                     panic_position: unreachable_code_kind,
+                    diverging,
+                    diverging_location,
                 } => {
-                    let text = match unreachable_code_kind {
-                        PanicPosition::PreviousExpression =>
+                    let text = match (unreachable_code_kind, diverging) {
+                        (PanicPosition::PreviousExpression, DivergingExpression::Panic) =>
                             "This code is unreachable because it comes after a `panic`.",
-                        PanicPosition::PreviousFunctionArgument =>
+                        (PanicPosition::PreviousExpression, DivergingExpression::Todo) =>
+                            "This code is unreachable because it comes after a `todo`.",
+                        (
+                            PanicPosition::PreviousExpression,
+                            DivergingExpression::Call | DivergingExpression::Case,
+                        ) => "This code is unreachable because it comes after an expression \
+that never returns.",
+                        (PanicPosition::PreviousFunctionArgument, _) =>
                             "This argument is unreachable because the previous one always panics. \
 Your code will crash before reaching this point.",
-                        PanicPosition::LastFunctionArgument =>
+                        (PanicPosition::LastFunctionArgument, _) =>
                             "This function call is unreachable because its last argument always panics. \
 Your code will crash before reaching this point.",
                     };
 
+                    let diverging_label = match diverging {
+                        DivergingExpression::Panic => "This will panic",
+                        DivergingExpression::Todo => "This `todo` will crash",
+                        DivergingExpression::Call => "This function call never returns",
+                        DivergingExpression::Case => "Every clause of this case expression panics",
+                    };
+
                     Diagnostic {
                         title: "Unreachable code".into(),
                         text: wrap(text),
@@ -1024,7 +1139,13 @@ Your code will crash before reaching this point.",
                             },
                             path: path.clone(),
                             src: src.clone(),
-                            extra_labels: vec![],
+                            extra_labels: vec![diagnostic::ExtraLabel {
+                                src_info: None,
+                                label: diagnostic::Label {
+                                    text: Some(diverging_label.into()),
+                                    span: *diverging_location,
+                                },
+                            }],
                         }),
                     }
                 }
@@ -1083,6 +1204,7 @@ See: https://tour.gleam.run/functions/pipelines/",
                         FeatureKind::VariantWithDeprecatedAnnotation => {
                             "Deprecating individual custom type variants was"
                         }
+                        FeatureKind::PhantomAnnotation => "The `@phantom` annotation was",
                     };
 
                     Diagnostic {
@@ -1112,16 +1234,25 @@ See: https://tour.gleam.run/functions/pipelines/",
                     }
                 }
 
-                type_::Warning::JavaScriptIntUnsafe { location } => Diagnostic {
+                type_::Warning::JavaScriptIntUnsafe {
+                    location,
+                    javascript_value,
+                } => Diagnostic {
                     title: "Int is outside JavaScript's safe integer range".into(),
-                    text: wrap(
+                    text: wrap(&format!(
                         "This integer value is too large to be represented accurately by \
-JavaScript's number type. To avoid this warning integer values must be in the range \
+JavaScript's number type.{} To avoid this warning integer values must be in the range \
 -(2^53 - 1) - (2^53 - 1).
 
 See JavaScript's Number.MAX_SAFE_INTEGER and Number.MIN_SAFE_INTEGER properties for more \
 information.",
-                    ),
+                        match javascript_value {
+                            Some(value) => format!(
+                                " It will lose precision and become `{value}` on JavaScript."
+                            ),
+                            None => String::new(),
+                        }
+                    )),
                     hint: None,
                     level: diagnostic::Level::Warning,
                     location: Some(Location {
@@ -1134,6 +1265,146 @@ information.",
                         extra_labels: Vec::new(),
                     }),
                 },
+
+                type_::Warning::InvalidExternalErlangModule {
+                    location,
+                    module,
+                    name,
+                } => Diagnostic {
+                    title: "Invalid Erlang module".into(),
+                    text: wrap(&format!(
+                        "The function `{name}` has an external Erlang \
+implementation but the module name `{module}` is not valid."
+                    )),
+                    hint: None,
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        path: path.to_path_buf(),
+                        src: src.clone(),
+                        label: diagnostic::Label {
+                            text: None,
+                            span: *location,
+                        },
+                        extra_labels: Vec::new(),
+                    }),
+                },
+
+                type_::Warning::InvalidExternalErlangFunction {
+                    location,
+                    function,
+                    name,
+                } => Diagnostic {
+                    title: "Invalid Erlang function".into(),
+                    text: wrap(&format!(
+                        "The function `{name}` has an external Erlang \
+implementation but the function name `{function}` is not valid."
+                    )),
+                    hint: None,
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        path: path.to_path_buf(),
+                        src: src.clone(),
+                        label: diagnostic::Label {
+                            text: None,
+                            span: *location,
+                        },
+                        extra_labels: Vec::new(),
+                    }),
+                },
+
+                type_::Warning::MissingExternalForSupportedTarget {
+                    location,
+                    missing_target,
+                } => {
+                    let (target, other_target) = match missing_target {
+                        Target::Erlang => ("Erlang", "JavaScript"),
+                        Target::JavaScript => ("JavaScript", "Erlang"),
+                    };
+                    Diagnostic {
+                        title: format!("Missing {target} implementation"),
+                        text: wrap(&format!(
+                            "This public function only has an external {other_target} \
+implementation, but this package is used on both targets as it is being \
+compiled for a target other than the one set in its `gleam.toml`. It can't be \
+used when compiling to {target}."
+                        )),
+                        hint: Some(wrap(&format!(
+                            "Add an `@external({missing_target}, ...)` attribute \
+or a Gleam implementation for this function."
+                        ))),
+                        level: diagnostic::Level::Warning,
+                        location: Some(Location {
+                            path: path.to_path_buf(),
+                            src: src.clone(),
+                            label: diagnostic::Label {
+                                text: Some(format!("No {target} implementation")),
+                                span: *location,
+                            },
+                            extra_labels: Vec::new(),
+                        }),
+                    }
+                }
+
+                type_::Warning::PublicValueNotDefinedForTarget {
+                    location,
+                    name,
+                    target,
+                    defined_for,
+                } => Diagnostic {
+                    title: "Public value not defined for target".into(),
+                    text: wrap(&format!(
+                        "`{name}` is public but it is only defined for the \
+{defined_for} target, so this module doesn't provide it when compiling for the \
+{target} target."
+                    )),
+                    hint: Some(wrap(&format!(
+                        "Define `{name}` for the {target} target too, or make it \
+private."
+                    ))),
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        path: path.to_path_buf(),
+                        src: src.clone(),
+                        label: diagnostic::Label {
+                            text: Some(format!("Not defined for {target}")),
+                            span: *location,
+                        },
+                        extra_labels: Vec::new(),
+                    }),
+                },
+
+                type_::Warning::ShadowedVariable {
+                    location,
+                    name,
+                    shadowed_location,
+                } => Diagnostic {
+                    title: "Shadowed variable".into(),
+                    text: wrap(&format!(
+                        "A variable named `{name}` is already in scope, so this \
+definition hides it for the rest of the scope."
+                    )),
+                    hint: Some(wrap(
+                        "Give the new variable a different name, or disable this \
+warning by removing `shadowing = true` from the `[warnings]` section of \
+`gleam.toml`.",
+                    )),
+                    level: diagnostic::Level::Warning,
+                    location: Some(Location {
+                        path: path.to_path_buf(),
+                        src: src.clone(),
+                        label: diagnostic::Label {
+                            text: Some(format!("This shadows the previous `{name}`")),
+                            span: *location,
+                        },
+                        extra_labels: vec![diagnostic::ExtraLabel {
+                            src_info: None,
+                            label: diagnostic::Label {
+                                text: Some("Previously defined here".into()),
+                                span: *shadowed_location,
+                            },
+                        }],
+                    }),
+                },
             },
         }
     }
@@ -1,11 +1,15 @@
 -module('{{ application }}@@main').
--export([run/1]).
+-export([run/1, main/1]).
 
 -define(red, "\e[31;1m").
 -define(grey, "\e[90m").
 -define(reset_color, "\e[39m").
 -define(reset_all, "\e[0m").
 
+% Called when the project is run as an escript created by `gleam export escript`.
+main(_Arguments) ->
+    run('{{ application }}').
+
 run(Module) ->
     io:setopts(standard_io, [binary, {encoding, utf8}]),
     io:setopts(standard_error, [{encoding, utf8}]),
@@ -1,5 +1,6 @@
 export class CustomType {
   withFields<K extends keyof this>(fields: { [P in K]: this[P] }): this;
+  toString(): string;
 }
 
 export class List<T> implements Iterable<T> {
@@ -10,6 +11,7 @@ export class List<T> implements Iterable<T> {
   atLeastLength(desired: number): boolean;
   hasLength(desired: number): boolean;
   countLength(): number;
+  toString(): string;
 
   [Symbol.iterator](): Iterator<T>;
 }
@@ -33,6 +35,12 @@ export class BitArray {
     isBigEndian: boolean,
     isSigned: boolean
   ): number;
+  bigIntFromSlice(
+    start: number,
+    end: number,
+    isBigEndian: boolean,
+    isSigned: boolean
+  ): bigint;
   binaryFromSlice(start: number, end: number): BitArray;
   sliceAfter(index: number): BitArray;
 }
@@ -44,7 +52,7 @@ export class UtfCodepoint {
 export function toBitArray(segments: Array<number | Uint8Array>): BitArray;
 
 export function sizedInt(
-  int: number,
+  int: number | bigint,
   size: number,
   isBigEndian: boolean
 ): Uint8Array;
@@ -57,6 +65,14 @@ export function byteArrayToInt(
   isSigned: boolean
 ): number;
 
+export function byteArrayToBigInt(
+  byteArray: Uint8Array,
+  start: number,
+  end: number,
+  isBigEndian: boolean,
+  isSigned: boolean
+): bigint;
+
 export function byteArrayToFloat(
   byteArray: Uint8Array,
   start: number,
@@ -91,8 +107,12 @@ export class Error<T, E> extends Result<T, E> {
 
 export function isEqual(a: any, b: any): boolean;
 
-export function remainderInt(a: number, b: number): number;
+export function remainderInt<T extends number | bigint>(a: T, b: T): T;
 
-export function divideInt(a: number, b: number): number;
+export function divideInt<T extends number | bigint>(a: T, b: T): T;
 
 export function divideFloat(a: number, b: number): number;
+
+export function bigIntToNumber(int: bigint): number;
+
+export function floatToString(float: number): string;
@@ -1,6 +1,16 @@
 // Values marked with @internal are not part of the public API and may change
 // without notice.
 
+// @internal
+// Set to true by the build tool when values are frozen in development builds,
+// so that FFI code mutating them throws.
+const freezeValues = false;
+
+// @internal
+// Set to true by the build tool when ints are represented as BigInts, so that
+// every number is a float.
+const intsAreBigInts = false;
+
 export class CustomType {
   withFields(fields) {
     let properties = Object.keys(this).map((label) =>
@@ -8,6 +18,46 @@ export class CustomType {
     );
     return new this.constructor(...properties);
   }
+
+  toString() {
+    return inspect(this);
+  }
+
+  [Symbol.for("nodejs.util.inspect.custom")]() {
+    return this.toString();
+  }
+}
+
+// Formats a value the way it would be written in Gleam, so that values are
+// readable when printed or shown in a debugger.
+function inspect(value) {
+  if (value === undefined) return "Nil";
+  if (value === true) return "True";
+  if (value === false) return "False";
+  if (typeof value === "string") return JSON.stringify(value);
+  if (typeof value === "bigint") return value.toString();
+  // When ints are represented as numbers they can't be told apart from whole
+  // floats, so those are shown as ints.
+  if (typeof value === "number") {
+    const isInt = !intsAreBigInts && Number.isSafeInteger(value);
+    return isInt ? value.toString() : floatToString(value);
+  }
+  if (typeof value === "function") return "//fn(...) { ... }";
+  if (Array.isArray(value)) return `#(${value.map(inspect).join(", ")})`;
+  if (value instanceof List) return `[${value.toArray().map(inspect).join(", ")}]`;
+  if (value instanceof BitArray) return `<<${value.buffer.join(", ")}>>`;
+  if (value instanceof UtfCodepoint) {
+    return `//utfcodepoint(${String.fromCodePoint(value.value)})`;
+  }
+  if (value instanceof CustomType) {
+    const fields = Object.keys(value).map((label) => {
+      const field = inspect(value[label]);
+      return /^\d+$/.test(label) ? field : `${label}: ${field}`;
+    });
+    const name = value.constructor.name;
+    return fields.length === 0 ? name : `${name}(${fields.join(", ")})`;
+  }
+  return String(value);
 }
 
 export class List {
@@ -27,6 +77,14 @@ export class List {
     return [...this];
   }
 
+  toString() {
+    return inspect(this);
+  }
+
+  [Symbol.for("nodejs.util.inspect.custom")]() {
+    return this.toString();
+  }
+
   // @internal
   atLeastLength(desired) {
     for (let _ of this) {
@@ -54,10 +112,12 @@ export class List {
 }
 
 // @internal
+/* @__NO_SIDE_EFFECTS__ */
 export function prepend(element, tail) {
   return new NonEmpty(element, tail);
 }
 
+/* @__NO_SIDE_EFFECTS__ */
 export function toList(elements, tail) {
   return List.fromArray(elements, tail);
 }
@@ -81,13 +141,19 @@ class ListIterator {
   }
 }
 
-export class Empty extends List {}
+export class Empty extends List {
+  constructor() {
+    super();
+    if (freezeValues) Object.freeze(this);
+  }
+}
 
 export class NonEmpty extends List {
   constructor(head, tail) {
     super();
     this.head = head;
     this.tail = tail;
+    if (freezeValues) Object.freeze(this);
   }
 }
 
@@ -119,6 +185,11 @@ export class BitArray {
     return byteArrayToInt(this.buffer, start, end, isBigEndian, isSigned);
   }
 
+  // @internal
+  bigIntFromSlice(start, end, isBigEndian, isSigned) {
+    return byteArrayToBigInt(this.buffer, start, end, isBigEndian, isSigned);
+  }
+
   // @internal
   binaryFromSlice(start, end) {
     const buffer = new Uint8Array(
@@ -147,6 +218,7 @@ export class UtfCodepoint {
 }
 
 // @internal
+/* @__NO_SIDE_EFFECTS__ */
 export function toBitArray(segments) {
   if (segments.length === 0) {
     return new BitArray(new Uint8Array());
@@ -199,6 +271,7 @@ export function toBitArray(segments) {
 
 // @internal
 // Derived from this answer https://stackoverflow.com/questions/8482309/converting-javascript-integer-to-byte-array-and-back
+/* @__NO_SIDE_EFFECTS__ */
 export function sizedInt(value, size, isBigEndian) {
   if (size <= 0) {
     return new Uint8Array();
@@ -210,7 +283,8 @@ export function sizedInt(value, size, isBigEndian) {
 
   const byteArray = new Uint8Array(size / 8);
 
-  let byteModulus = 256;
+  // Ints represented as BigInts are written using BigInt arithmetic
+  let byteModulus = typeof value === "bigint" ? 256n : 256;
 
   // Convert negative number to two's complement representation
   if (value < 0) {
@@ -218,7 +292,7 @@ export function sizedInt(value, size, isBigEndian) {
 
     // For output sizes larger than 48 bits BigInt is needed in order to
     // maintain accuracy
-    if (size <= 48) {
+    if (size <= 48 && typeof value === "number") {
       valueModulus = 2 ** size;
     } else {
       valueModulus = 1n << BigInt(size);
@@ -280,32 +354,46 @@ export function byteArrayToInt(byteArray, start, end, isBigEndian, isSigned) {
 
     return value;
   } else {
-    let value = 0n;
+    // Convert the result into a JS number. This may cause quantizing/error on
+    // values outside JavaScript's safe integer range.
+    return Number(
+      byteArrayToBigInt(byteArray, start, end, isBigEndian, isSigned)
+    );
+  }
+}
 
-    // Read bytes as an unsigned integer value
-    if (isBigEndian) {
-      for (let i = start; i < end; i++) {
-        value = (value << 8n) + BigInt(byteArray[i]);
-      }
-    } else {
-      for (let i = end - 1; i >= start; i--) {
-        value = (value << 8n) + BigInt(byteArray[i]);
-      }
-    }
+// @internal
+export function byteArrayToBigInt(
+  byteArray,
+  start,
+  end,
+  isBigEndian,
+  isSigned
+) {
+  const byteSize = end - start;
+  let value = 0n;
 
-    // For signed integers, check if the high bit is set and if so then
-    // reinterpret as two's complement
-    if (isSigned) {
-      const highBit = 1n << BigInt(byteSize * 8 - 1);
-      if (value >= highBit) {
-        value -= highBit * 2n;
-      }
+  // Read bytes as an unsigned integer value
+  if (isBigEndian) {
+    for (let i = start; i < end; i++) {
+      value = (value << 8n) + BigInt(byteArray[i]);
+    }
+  } else {
+    for (let i = end - 1; i >= start; i--) {
+      value = (value << 8n) + BigInt(byteArray[i]);
     }
+  }
 
-    // Convert the result into a JS number. This may cause quantizing/error on
-    // values outside JavaScript's safe integer range.
-    return Number(value);
+  // For signed integers, check if the high bit is set and if so then
+  // reinterpret as two's complement
+  if (isSigned) {
+    const highBit = 1n << BigInt(byteSize * 8 - 1);
+    if (value >= highBit) {
+      value -= highBit * 2n;
+    }
   }
+
+  return value;
 }
 
 // @internal
@@ -325,16 +413,19 @@ export function byteArrayToFloat(byteArray, start, end, isBigEndian) {
 }
 
 // @internal
+/* @__NO_SIDE_EFFECTS__ */
 export function stringBits(string) {
   return new TextEncoder().encode(string);
 }
 
 // @internal
+/* @__NO_SIDE_EFFECTS__ */
 export function codepointBits(codepoint) {
   return stringBits(String.fromCodePoint(codepoint.value));
 }
 
 // @internal
+/* @__NO_SIDE_EFFECTS__ */
 export function sizedFloat(float, size, isBigEndian) {
   if (size !== 32 && size !== 64) {
     const msg = `Sized floats must be 32-bit or 64-bit on JavaScript, got size of ${size} bits`;
@@ -365,6 +456,7 @@ export class Ok extends Result {
   constructor(value) {
     super();
     this[0] = value;
+    if (freezeValues) Object.freeze(this);
   }
 
   // @internal
@@ -377,6 +469,7 @@ export class Error extends Result {
   constructor(detail) {
     super();
     this[0] = detail;
+    if (freezeValues) Object.freeze(this);
   }
 
   // @internal
@@ -476,16 +569,26 @@ function structurallyCompatibleObjects(a, b) {
 
 // @internal
 export function remainderInt(a, b) {
-  if (b === 0) {
+  // Ints may be represented as either numbers or BigInts
+  if (typeof b === "bigint") {
+    return b === 0n ? 0n : a % b;
+  } else if (b === 0) {
     return 0;
   } else {
-    return a % b;
+    // Adding zero turns a negative zero into a zero, as Erlang has no -0
+    return (a % b) + 0;
   }
 }
 
 // @internal
 export function divideInt(a, b) {
-  return Math.trunc(divideFloat(a, b));
+  if (typeof b === "bigint") {
+    // BigInt division already truncates towards zero
+    return b === 0n ? 0n : a / b;
+  } else {
+    // Adding zero turns a negative zero into a zero, as Erlang has no -0
+    return Math.trunc(divideFloat(a, b)) + 0;
+  }
 }
 
 // @internal
@@ -497,6 +600,53 @@ export function divideFloat(a, b) {
   }
 }
 
+// @internal
+// Converts an int represented as a BigInt to a number, for FFI code that takes
+// ints as numbers. Ints a number can't represent exactly are an error rather
+// than being silently rounded.
+export function bigIntToNumber(int) {
+  const number = globalThis.Number(int);
+  if (!globalThis.Number.isSafeInteger(number)) {
+    throw new globalThis.RangeError(
+      `The int ${int} is outside of the range FFI code using numbers supports`,
+    );
+  }
+  return number;
+}
+
+// Formats a float the way Erlang prints it, so the same float is shown as the
+// same text on both targets. The shortest digits that round trip are used,
+// there is always a decimal point, and the exponent is only used when it makes
+// the text shorter: `1.5`, `100.0`, `1.0e3`, `1.0e-5`.
+export function floatToString(float) {
+  if (!Number.isFinite(float)) return float.toString();
+  if (float === 0) return Object.is(float, -0) ? "-0.0" : "0.0";
+
+  const sign = float < 0 ? "-" : "";
+  // `toExponential` gives the shortest digits that round trip to the float
+  const [mantissa, exponent] = Math.abs(float).toExponential().split("e");
+  const digits = mantissa.replace(".", "");
+  const place = Number(exponent) + 1;
+  const length = digits.length;
+
+  const exponentText = `${place - 1}`;
+  const exponentCost = exponentText.length + 1 + (length === 1 ? 2 : 1);
+  const scientific = () =>
+    `${digits[0]}.${length === 1 ? "0" : digits.slice(1)}e${exponentText}`;
+
+  if (place <= 0) {
+    return 2 - place <= exponentCost
+      ? `${sign}0.${"0".repeat(-place)}${digits}`
+      : `${sign}${scientific()}`;
+  } else if (place >= length) {
+    return place - length + 2 <= exponentCost
+      ? `${sign}${digits}${"0".repeat(place - length)}.0`
+      : `${sign}${scientific()}`;
+  } else {
+    return `${sign}${digits.slice(0, place)}.${digits.slice(place)}`;
+  }
+}
+
 // @internal
 export function makeError(variant, module, line, fn, message, extra) {
   let error = new globalThis.Error(message);
@@ -7,7 +7,8 @@ use camino::Utf8PathBuf;
 use gleam_core::{
     analyse::TargetSupport,
     build::{
-        Mode, NullTelemetry, PackageCompiler, StaleTracker, Target, TargetCodegenConfiguration,
+        IntRepresentation, Mode, ModuleFormat, NullTelemetry, PackageCompiler, StaleTracker,
+        Target, TargetCodegenConfiguration,
     },
     config::PackageConfig,
     io::{FileSystemReader, FileSystemWriter},
@@ -183,6 +184,10 @@ fn do_compile_package(project: Project, target: Target) -> Result<(), Error> {
         Target::JavaScript => TargetCodegenConfiguration::JavaScript {
             emit_typescript_definitions: false,
             prelude_location: Utf8PathBuf::from("./gleam_prelude.mjs"),
+            module_format: ModuleFormat::Esm,
+            int_representation: IntRepresentation::Number,
+            ffi_int_representation: IntRepresentation::Number,
+            freeze_values: false,
         },
     };
 
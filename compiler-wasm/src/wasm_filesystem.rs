@@ -1,8 +1,8 @@
 use camino::{Utf8Path, Utf8PathBuf};
 use gleam_core::{
     io::{
-        memory::InMemoryFileSystem, BeamCompiler, CommandExecutor, FileSystemReader,
-        FileSystemWriter, ReadDir, Stdio, WrappedReader,
+        memory::InMemoryFileSystem, BeamCompiler, CommandExecutor, ErlangCompilerDiagnostic,
+        FileSystemReader, FileSystemWriter, ReadDir, Stdio, WrappedReader,
     },
     Error, Result,
 };
@@ -39,8 +39,8 @@ impl BeamCompiler for WasmFileSystem {
         _lib: &Utf8Path,
         _modules: &HashSet<Utf8PathBuf>,
         _stdio: Stdio,
-    ) -> Result<(), Error> {
-        Ok(()) // Always succeed.
+    ) -> Result<Vec<ErlangCompilerDiagnostic>, Error> {
+        Ok(vec![]) // Always succeed.
     }
 }
 
@@ -0,0 +1 @@
+exports.log = (value) => console.log(value);
@@ -23,6 +23,17 @@ fn duplicate_module() {
     );
 }
 
+#[rustfmt::skip]
+#[test]
+fn erlang_app_description_escaping() {
+    let output = crate::prepare("./cases/erlang_app_description_escaping");
+    insta::assert_snapshot!(
+        "erlang_app_description_escaping",
+        output,
+        "./cases/erlang_app_description_escaping",
+    );
+}
+
 #[rustfmt::skip]
 #[test]
 fn erlang_app_generation() {
@@ -34,6 +45,28 @@ fn erlang_app_generation() {
     );
 }
 
+#[rustfmt::skip]
+#[test]
+fn erlang_app_start_module_javascript_only() {
+    let output = crate::prepare("./cases/erlang_app_start_module_javascript_only");
+    insta::assert_snapshot!(
+        "erlang_app_start_module_javascript_only",
+        output,
+        "./cases/erlang_app_start_module_javascript_only",
+    );
+}
+
+#[rustfmt::skip]
+#[test]
+fn erlang_app_start_module_without_start() {
+    let output = crate::prepare("./cases/erlang_app_start_module_without_start");
+    insta::assert_snapshot!(
+        "erlang_app_start_module_without_start",
+        output,
+        "./cases/erlang_app_start_module_without_start",
+    );
+}
+
 #[rustfmt::skip]
 #[test]
 fn erlang_bug_752() {
@@ -89,6 +122,17 @@ fn erlang_import_shadowing_prelude() {
     );
 }
 
+#[rustfmt::skip]
+#[test]
+fn erlang_minimum_otp_version() {
+    let output = crate::prepare("./cases/erlang_minimum_otp_version");
+    insta::assert_snapshot!(
+        "erlang_minimum_otp_version",
+        output,
+        "./cases/erlang_minimum_otp_version",
+    );
+}
+
 #[rustfmt::skip]
 #[test]
 fn erlang_nested() {
@@ -188,6 +232,28 @@ fn imported_record_constructors() {
     );
 }
 
+#[rustfmt::skip]
+#[test]
+fn javascript_big_ints() {
+    let output = crate::prepare("./cases/javascript_big_ints");
+    insta::assert_snapshot!(
+        "javascript_big_ints",
+        output,
+        "./cases/javascript_big_ints",
+    );
+}
+
+#[rustfmt::skip]
+#[test]
+fn javascript_commonjs() {
+    let output = crate::prepare("./cases/javascript_commonjs");
+    insta::assert_snapshot!(
+        "javascript_commonjs",
+        output,
+        "./cases/javascript_commonjs",
+    );
+}
+
 #[rustfmt::skip]
 #[test]
 fn javascript_d_ts() {
@@ -221,6 +287,17 @@ fn javascript_import() {
     );
 }
 
+#[rustfmt::skip]
+#[test]
+fn javascript_package_json() {
+    let output = crate::prepare("./cases/javascript_package_json");
+    insta::assert_snapshot!(
+        "javascript_package_json",
+        output,
+        "./cases/javascript_package_json",
+    );
+}
+
 #[rustfmt::skip]
 #[test]
 fn not_overwriting_erlang_module() {
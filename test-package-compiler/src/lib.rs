@@ -31,7 +31,12 @@ pub fn prepare(path: &str) -> String {
         },
         Target::JavaScript => TargetCodegenConfiguration::JavaScript {
             emit_typescript_definitions: config.javascript.typescript_declarations,
-            prelude_location: Utf8PathBuf::from("../prelude.mjs"),
+            prelude_location: Utf8PathBuf::from("../prelude")
+                .with_extension(config.javascript.module_format.extension()),
+            module_format: config.javascript.module_format,
+            int_representation: config.javascript.int_representation,
+            ffi_int_representation: config.javascript.int_representation,
+            freeze_values: config.javascript.freeze_values,
         },
     };
 
@@ -54,7 +59,7 @@ pub fn prepare(path: &str) -> String {
         ids,
         filesystem.clone(),
     );
-    compiler.write_entrypoint = false;
+    compiler.write_entrypoint = true;
     compiler.write_metadata = true;
     compiler.compile_beam_bytecode = false;
     compiler.copy_native_files = false;
@@ -5,10 +5,13 @@ import {
   List,
   Ok,
   UtfCodepoint,
+  bigIntToNumber,
   codepointBits,
   divideFloat,
   divideInt,
+  floatToString,
   isEqual,
+  remainderInt,
   stringBits,
   toBitArray,
   toList,
@@ -414,6 +417,12 @@ assertEqual(
   new BitArray(new Uint8Array([255, 255, 255, 255, 255, 255, 254])).intFromSlice(0, 7, true, false),
   Number(0xFFFFFFFFFFFFFEn),
 );
+assertEqual(new BitArray(new Uint8Array([1, 2, 3])).bigIntFromSlice(0, 1, true, false), 1n);
+assertEqual(new BitArray(new Uint8Array([160, 2, 3])).bigIntFromSlice(0, 2, true, true), -24574n);
+assertEqual(
+  new BitArray(new Uint8Array([255, 255, 255, 255, 255, 255, 255, 254])).bigIntFromSlice(0, 8, true, false),
+  0xFFFFFFFFFFFFFFFEn,
+);
 assertEqual(
   new BitArray(new Uint8Array([63, 240, 0, 0, 0, 0, 0, 0])).floatFromSlice(0, 8, true),
   1.0,
@@ -487,6 +496,16 @@ assertEqual(
   sizedInt(Number.MIN_SAFE_INTEGER, 64, true),
   new Uint8Array([255, 224, 0, 0, 0, 0, 0, 1]),
 );
+assertEqual(sizedInt(1n, 24, true), new Uint8Array([0, 0, 1]));
+assertEqual(sizedInt(-80000n, 16, true), new Uint8Array([199, 128]));
+assertEqual(
+  sizedInt(-1n, 64, false),
+  new Uint8Array([255, 255, 255, 255, 255, 255, 255, 255]),
+);
+assertEqual(
+  sizedInt(0xFFFFFFFFFFFFFFFEn, 64, true),
+  new Uint8Array([255, 255, 255, 255, 255, 255, 255, 254]),
+);
 
 // Result.isOk
 
@@ -537,7 +556,7 @@ assertEqual(divideInt(3, 2), 1);
 assertEqual(divideInt(11, 3), 3);
 assertEqual(divideInt(-1, 0), 0);
 assertEqual(divideInt(-1, 1), -1);
-assertEqual(divideInt(-1, 2), -0);
+assertEqual(divideInt(-1, 2), 0);
 assertEqual(divideInt(-3, 2), -1);
 assertEqual(divideInt(-11, 3), -3);
 assertEqual(divideInt(1, -1), -1);
@@ -548,6 +567,35 @@ assertEqual(divideInt(-1, -1), 1);
 assertEqual(divideInt(-1, -2), 0);
 assertEqual(divideInt(-3, -2), 1);
 assertEqual(divideInt(-11, -3), 3);
+assertEqual(divideInt(1n, 0n), 0n);
+assertEqual(divideInt(11n, 3n), 3n);
+assertEqual(divideInt(-11n, 3n), -3n);
+assertEqual(divideInt(2n ** 64n, 2n), 2n ** 63n);
+
+assertEqual(remainderInt(1, 0), 0);
+assertEqual(remainderInt(11, 3), 2);
+assertEqual(remainderInt(-11, 3), -2);
+assertEqual(remainderInt(1n, 0n), 0n);
+assertEqual(remainderInt(11n, 3n), 2n);
+assertEqual(remainderInt(-11n, 3n), -2n);
+assertEqual(remainderInt(11, -3), 2);
+assertEqual(remainderInt(-11, -3), -2);
+
+// Erlang has no negative zero, so neither does integer division
+assertEqual(Object.is(divideInt(-1, 2), 0), true);
+assertEqual(Object.is(divideInt(1, -2), 0), true);
+assertEqual(Object.is(remainderInt(-4, 2), 0), true);
+assertEqual(Object.is(remainderInt(-4, -2), 0), true);
+
+assertEqual(bigIntToNumber(0n), 0);
+assertEqual(bigIntToNumber(-42n), -42);
+assertEqual(bigIntToNumber(2n ** 53n - 1n), Number.MAX_SAFE_INTEGER);
+assertThrows("Should not round ints larger than a safe integer", () =>
+  bigIntToNumber(2n ** 53n),
+);
+assertThrows("Should not round ints smaller than a safe integer", () =>
+  bigIntToNumber(-(2n ** 64n)),
+);
 
 assertEqual(divideFloat(1.5, 0.0), 0.0);
 assertEqual(divideFloat(1.5, 2.0), 0.75);
@@ -562,6 +610,26 @@ assertEqual(divideFloat(-1.5, -0.0), 0.0);
 assertEqual(divideFloat(-1.5, -2.0), 0.75);
 assertEqual(divideFloat(-1.5, -2.5), 0.6);
 
+// Floats are formatted the same way Erlang prints them
+assertEqual(floatToString(0.0), "0.0");
+assertEqual(floatToString(-0.0), "-0.0");
+assertEqual(floatToString(1.0), "1.0");
+assertEqual(floatToString(1.5), "1.5");
+assertEqual(floatToString(-1.5), "-1.5");
+assertEqual(floatToString(0.1), "0.1");
+assertEqual(floatToString(100.0), "100.0");
+assertEqual(floatToString(1000.0), "1.0e3");
+assertEqual(floatToString(1250.0), "1250.0");
+assertEqual(floatToString(123456789.0), "123456789.0");
+assertEqual(floatToString(0.0001), "0.0001");
+assertEqual(floatToString(0.00001), "1.0e-5");
+assertEqual(floatToString(0.000015), "1.5e-5");
+assertEqual(floatToString(1.0e21), "1.0e21");
+assertEqual(floatToString(-2.5e100), "-2.5e100");
+assertEqual(floatToString(0.1 + 0.2), "0.30000000000000004");
+assertEqual(floatToString(1.7976931348623157e308), "1.7976931348623157e308");
+assertEqual(floatToString(5.0e-324), "5.0e-324");
+
 // Record updates
 
 assertEqual(new Ok(1).withFields({ 0: 2 }), new Ok(2));
@@ -580,6 +648,23 @@ assertEqual(
   new ExampleRecordImpl(6, 5, 4),
 );
 
+// Inspecting custom types
+
+assertEqual(new Ok(1).toString(), "Ok(1)");
+assertEqual(new Error("nope").toString(), 'Error("nope")');
+assertEqual(
+  new ExampleRecordImpl(true, toList([1, 2]), undefined).toString(),
+  "ExampleRecordImpl(True, detail: [1, 2], boop: Nil)",
+);
+assertEqual(
+  new Ok([1.5, new BitArray(new Uint8Array([1, 2])), 10n]).toString(),
+  "Ok(#(1.5, <<1, 2>>, 10))",
+);
+assertEqual(new Ok(new UtfCodepoint(97)).toString(), "Ok(//utfcodepoint(a))");
+assertEqual(new Ok([2.5e-7, 1.0e21]).toString(), "Ok(#(2.5e-7, 1.0e21))");
+assertEqual(toList([new Ok(toList([]))]).toString(), "[Ok([])]");
+assertEqual(`${new Ok(1)}`, "Ok(1)");
+
 // Test BitArray can only be constructed from Uint8Array, not ArrayBuffer
 const bs1 = new BitArray(new Uint8Array(new ArrayBuffer(8)));
 assertThrows("Should only construct BitArray from Uint8Array", () => {